@@ -26,6 +26,7 @@ fn _gen_fqx_cols_and_types(f: &Field) -> (TokenStream, TokenStream) {
         "i64" => quote! { ::fastqx::adt::val::value::FqxValueType::I64 },
         "f32" => quote! { ::fastqx::adt::val::value::FqxValueType::F32 },
         "f64" => quote! { ::fastqx::adt::val::value::FqxValueType::F64 },
+        "Decimal" => quote! { ::fastqx::adt::val::value::FqxValueType::Decimal },
         "String" => quote! { ::fastqx::adt::val::value::FqxValueType::String },
         "Vec<u8>" => quote! { ::fastqx::adt::val::value::FqxValueType::Blob },
         "DateTime<Local>" => quote! { ::fastqx::adt::val::value::FqxValueType::Timestamp },
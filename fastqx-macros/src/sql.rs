@@ -75,6 +75,9 @@ fn _gen_sqlx_column(f: &Field) -> TokenStream {
         "f64" => quote! {
             ::fastqx::sea_query::ColumnDef::new_with_type(::fastqx::sea_query::Alias::new(#fd), ::fastqx::sea_query::ColumnType::Double)
         },
+        "Decimal" => quote! {
+            ::fastqx::sea_query::ColumnDef::new_with_type(::fastqx::sea_query::Alias::new(#fd), ::fastqx::sea_query::ColumnType::Custom(::fastqx::sea_query::SeaRc::new(::fastqx::sea_query::Alias::new("DECIMAL(38, 10)"))))
+        },
         "String" => quote! {
             ::fastqx::sea_query::ColumnDef::new_with_type(::fastqx::sea_query::Alias::new(#fd), ::fastqx::sea_query::ColumnType::String(None))
         },
@@ -127,7 +130,7 @@ fn sqlx_create_table(table_name: &str, named_fields: &NamedFields) -> TokenStrea
 
 fn sqlx_drop_table(table_name: &str) -> TokenStream {
     quote! {
-        ::fastqx::sea_query::Table::drop().table(::fastqx::sea_query::Alias::new(#table_name)).to_owned()
+        ::fastqx::sea_query::Table::drop().table(::fastqx::sea_query::Alias::new(#table_name)).if_exists().to_owned()
     }
 }
 
@@ -164,7 +167,7 @@ fn sqlx_insert(table_name: &str, named_fields: &NamedFields, struct_name: &Ident
             query.values([#(#column_intos),*])?;
         }
 
-        Ok::<_, ::fastqx::anyhow::Error>(query)
+        Ok::<_, ::fastqx::error::FqxError>(query)
     }}
 }
 
@@ -216,6 +219,7 @@ fn _gen_tiberius_column(f: &Field) -> String {
         "i64" => format!("{} {}", fd, "BIGINT"),
         "f32" => format!("{} {}", fd, "FLOAT(24)"),
         "f64" => format!("{} {}", fd, "FLOAT(53)"),
+        "Decimal" => format!("{} {}", fd, "DECIMAL(38, 10)"),
         "String" => format!("{} {}", fd, "VARCHAR(100)"),
         "Vec<u8>" => format!("{} {}", fd, "BINARY"),
         "DateTime<Local>" => format!("{} {}", fd, "DATETIMEOFFSET(7)"),
@@ -260,16 +264,7 @@ fn tiberius_drop_table(table_name: &str) -> TokenStream {
     }
 }
 
-fn tiberius_insert(
-    table_name: &str,
-    named_fields: &NamedFields,
-    struct_name: &Ident,
-) -> TokenStream {
-    let cols = named_fields
-        .iter()
-        .map(|f| f.ident.as_ref().unwrap().to_string())
-        .collect::<Vec<_>>()
-        .join(",");
+fn _tiberius_values(named_fields: &NamedFields, struct_name: &Ident) -> TokenStream {
     let column_names = named_fields
         .iter()
         .map(|n| {
@@ -295,6 +290,7 @@ fn tiberius_insert(
                 "i64" => quote! {  ::fastqx::sources::sql::ToSqlString::to_sql(#n) },
                 "f32" => quote! {  ::fastqx::sources::sql::ToSqlString::to_sql(#n) },
                 "f64" => quote! {  ::fastqx::sources::sql::ToSqlString::to_sql(#n) },
+                "Decimal" => quote! {  ::fastqx::sources::sql::ToSqlString::to_sql(#n) },
                 "String" => quote! { ::fastqx::sources::sql::ToSqlString::to_sql(#n) },
                 "Vec<u8>" => quote! { ::fastqx::sources::sql::ToSqlString::to_sql(#n) },
                 "DateTime<Local>" => quote! { ::fastqx::sources::sql::ToSqlString::to_sql(#n) },
@@ -306,6 +302,29 @@ fn tiberius_insert(
         })
         .collect::<Vec<_>>();
 
+    quote! {{
+        let mut vals = vec![];
+        for #struct_name {#(#column_names),*} in data.into_iter() {
+            let v = vec![#(#column_intos),*].join(",");
+            vals.push(format!("({})", v));
+        }
+
+        vals
+    }}
+}
+
+fn tiberius_insert(
+    table_name: &str,
+    named_fields: &NamedFields,
+    struct_name: &Ident,
+) -> TokenStream {
+    let cols = named_fields
+        .iter()
+        .map(|f| f.ident.as_ref().unwrap().to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let values = _tiberius_values(named_fields, struct_name);
+
     quote! {{
         let mut res = format!("SET IDENTITY_INSERT {} ON; INSERT INTO {}", #table_name, #table_name);
 
@@ -313,11 +332,7 @@ fn tiberius_insert(
         res.push_str(&#cols);
         res.push_str(") VALUES ");
 
-        let mut vals = vec![];
-        for #struct_name {#(#column_names),*} in data.into_iter() {
-            let v = vec![#(#column_intos),*].join(",");
-            vals.push(format!("({})", v));
-        }
+        let vals = #values;
 
         res.push_str(&vals.join(", "));
         res.push(';');
@@ -327,6 +342,28 @@ fn tiberius_insert(
     }}
 }
 
+fn tiberius_upsert(
+    table_name: &str,
+    named_fields: &NamedFields,
+    struct_name: &Ident,
+) -> TokenStream {
+    let cols = named_fields
+        .iter()
+        .map(|f| f.ident.as_ref().unwrap().to_string())
+        .collect::<Vec<_>>();
+    let values = _tiberius_values(named_fields, struct_name);
+
+    quote! {{
+        let vals = #values;
+        let merge = ::fastqx::sources::sql::merge_into(#table_name, &[#(#cols),*], &vals, keys)?;
+
+        format!(
+            "SET IDENTITY_INSERT {} ON; {} SET IDENTITY_INSERT {} OFF",
+            #table_name, merge, #table_name
+        )
+    }}
+}
+
 // ================================================================================================
 // FqxSqlRow
 // sea_query table statements
@@ -345,10 +382,20 @@ pub(crate) fn impl_connector_statement(
     let tiberius_ct = tiberius_create_table(&table_name, named_fields);
     let tiberius_dt = tiberius_drop_table(&table_name);
     let tiberius_is = tiberius_insert(&table_name, named_fields, struct_name);
+    let tiberius_us = tiberius_upsert(&table_name, named_fields, struct_name);
+
+    let column_names = named_fields
+        .iter()
+        .map(|f| f.ident.as_ref().unwrap().to_string())
+        .collect::<Vec<_>>();
 
     quote! {
         impl ::fastqx::sources::sql::ab::FqxSqlRow for #struct_name {
-            fn create_table(driver: &::fastqx::sources::sql::Driver) -> ::fastqx::anyhow::Result<String> {
+            fn table_name() -> &'static str {
+                #table_name
+            }
+
+            fn create_table(driver: &::fastqx::sources::sql::Driver) -> ::fastqx::error::FqxResult<String> {
                 let res = match driver {
                     ::fastqx::sources::sql::Driver::MYSQL => #sqlx_ct.to_string(::fastqx::sea_query::MysqlQueryBuilder),
                     ::fastqx::sources::sql::Driver::POSTGRES => #sqlx_ct.to_string(::fastqx::sea_query::PostgresQueryBuilder),
@@ -358,7 +405,7 @@ pub(crate) fn impl_connector_statement(
                 Ok(res)
             }
 
-            fn drop_table(driver: &::fastqx::sources::sql::Driver) -> ::fastqx::anyhow::Result<String> {
+            fn drop_table(driver: &::fastqx::sources::sql::Driver) -> ::fastqx::error::FqxResult<String> {
                 let res = match driver {
                     ::fastqx::sources::sql::Driver::MYSQL => #sqlx_dt.to_string(::fastqx::sea_query::MysqlQueryBuilder),
                     ::fastqx::sources::sql::Driver::POSTGRES => #sqlx_dt.to_string(::fastqx::sea_query::PostgresQueryBuilder),
@@ -368,7 +415,7 @@ pub(crate) fn impl_connector_statement(
                 Ok(res)
             }
 
-            fn insert<I: IntoIterator<Item = Self>>(driver: &::fastqx::sources::sql::Driver, data: I) -> ::fastqx::anyhow::Result<String> {
+            fn insert<I: IntoIterator<Item = Self>>(driver: &::fastqx::sources::sql::Driver, data: I) -> ::fastqx::error::FqxResult<String> {
                 let res = match driver {
                     ::fastqx::sources::sql::Driver::MYSQL => #sqlx_is?.to_string(::fastqx::sea_query::MysqlQueryBuilder),
                     ::fastqx::sources::sql::Driver::POSTGRES => #sqlx_is?.to_string(::fastqx::sea_query::PostgresQueryBuilder),
@@ -377,6 +424,17 @@ pub(crate) fn impl_connector_statement(
                 };
                 Ok(res)
             }
+
+            fn upsert<I: IntoIterator<Item = Self>>(driver: &::fastqx::sources::sql::Driver, data: I, keys: &[String]) -> ::fastqx::error::FqxResult<String> {
+                let on_conflict = ::fastqx::sources::sql::on_conflict(&[#(#column_names),*], keys)?;
+                let res = match driver {
+                    ::fastqx::sources::sql::Driver::MYSQL => #sqlx_is?.on_conflict(on_conflict).to_string(::fastqx::sea_query::MysqlQueryBuilder),
+                    ::fastqx::sources::sql::Driver::POSTGRES => #sqlx_is?.on_conflict(on_conflict).to_string(::fastqx::sea_query::PostgresQueryBuilder),
+                    ::fastqx::sources::sql::Driver::MSSQL => #tiberius_us,
+                    ::fastqx::sources::sql::Driver::SQLITE => #sqlx_is?.on_conflict(on_conflict).to_string(::fastqx::sea_query::SqliteQueryBuilder),
+                };
+                Ok(res)
+            }
         }
     }
 }
@@ -441,7 +499,7 @@ pub(crate) fn impl_from_row(struct_name: &Ident, named_fields: &NamedFields) ->
         }
 
         impl ::fastqx::sources::sql::FromTiberiusRow for #struct_name {
-            fn from_row(row: ::fastqx::tiberius::Row) -> ::fastqx::anyhow::Result<Self> {
+            fn from_row(row: ::fastqx::tiberius::Row) -> ::fastqx::error::FqxResult<Self> {
                 Ok(Self {
                     #(#tiberius_column_try),*
                 })
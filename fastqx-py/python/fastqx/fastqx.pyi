@@ -22,8 +22,10 @@ from enum import Enum
 
 import pandas as pd
 import datetime as dt
+import decimal
 
 from .sql import FqxSqlConnector
+from .http import FqxHttpConnector
 
 # ================================================================================================
 # General Types
@@ -33,7 +35,9 @@ JsonType = Union[
     None, int, float, str, bool, List[JsonType], Dict[str, JsonType]
 ]
 
-FqxVT = Union[str, float, int, bytes, dt.date, dt.time, dt.datetime, None]
+FqxVT = Union[
+    str, float, int, decimal.Decimal, bytes, dt.date, dt.time, dt.datetime, None
+]
 
 GET_DATA_TYPE = Union[
     int,  # PyIdx::R
@@ -52,7 +56,10 @@ SET_DATA_TYPE = Union[
     FqxRow,  # PyAssign::R
 ]
 
-JOIN_TYPE = Literal["left", "right", "inner", "outer"]
+JOIN_TYPE = Literal[
+    "left", "right", "inner", "outer", "cross", "semi", "left_semi", "anti", "left_anti"
+]
+ASOF_STRATEGY = Literal["backward", "forward", "nearest"]
 
 VT = Literal[
     "bool",
@@ -66,6 +73,7 @@ VT = Literal[
     "i64",
     "f32",
     "f64",
+    "decimal",
     "string",
     "blob",
     "timestamp",
@@ -91,13 +99,14 @@ class FqxValueType(Enum):
     I64 = 9
     F32 = 10
     F64 = 11
-    String = 12
-    Blob = 13
-    Timestamp = 14
-    DateTime = 15
-    Date = 16
-    Time = 17
-    Null = 18
+    Decimal = 12
+    String = 13
+    Blob = 14
+    Timestamp = 15
+    DateTime = 16
+    Date = 17
+    Time = 18
+    Null = 19
 
     #
     def is_float(self) -> bool: ...
@@ -281,11 +290,27 @@ class FqxData:
     def from_csv(cls, path: str, type_hints: List[VT]) -> FqxData: ...
     def to_csv(self, path: str): ...
 
+    # parquet, `compression` e.g. "snappy", "zstd(3)", "gzip(6)", "uncompressed"
+    @classmethod
+    def from_parquet(cls, path: str, columns: Optional[List[str]] = None) -> FqxData: ...
+    def to_parquet(self, path: str, compression: str = "snappy"): ...
+
     # sql
     @classmethod
     def from_sql(cls, sql: str, conn: FqxSqlConnector) -> FqxData: ...
     def to_sql(self, table: str, conn: FqxSqlConnector, mode: FqxSaveMode): ...
 
+    # arrow (PyCapsule interface), any object exposing `__arrow_c_stream__`
+    @classmethod
+    def from_arrow(cls, data: Any) -> FqxData: ...
+    def to_arrow(self) -> Any: ...
+    def to_polars(self) -> Any: ...
+    def __arrow_c_schema__(self) -> object: ...
+    def __arrow_c_array__(
+        self, requested_schema: Optional[object] = None
+    ) -> Tuple[object, object]: ...
+    def __arrow_c_stream__(self, requested_schema: Optional[object] = None) -> object: ...
+
     # ================================================================================================
     # X
     # ================================================================================================
@@ -324,9 +349,57 @@ class FqxData:
     #
     def cum_mean(self) -> List[FqxRow]: ...
 
+    # values moved down by `n` rows, or up when `n` is negative
+    def shift(self, n: int = 1) -> FqxData: ...
+
+    #
+    def diff(self, n: int = 1) -> FqxData: ...
+
+    #
+    def pct_change(self, n: int = 1) -> FqxData: ...
+
+    # method: average/min/max/first/dense
+    def rank(self, method: str = "average") -> FqxData: ...
+
+    #
+    def row_number(self) -> FqxData: ...
+
+    # `agg` over the last `window` rows, e.g. "mean"/"sum"/"min"/"max"/"std"
+    def rolling(self, window: int, agg: str = "mean") -> FqxData: ...
+
+    # one row per non-empty bucket of `every`, e.g. "15min"/"1h"/"1d"/"1w"/"1mo"/"1y" or a
+    # timedelta, with the bucket start in `time_col`; specs as in `FqxGroup.agg`
+    def resample(
+        self,
+        time_col: str,
+        every: Union[str, dt.timedelta],
+        spec: Optional[Dict[str, Union[str, List[str]]]] = None,
+        by: Optional[List[str]] = None,
+        **named: Tuple[str, str],
+    ) -> FqxData: ...
+
+    # one row per row, aggregating the rows of its `by` group within `window` before it
+    def rolling_time(
+        self,
+        time_col: str,
+        window: Union[str, dt.timedelta],
+        spec: Optional[Dict[str, Union[str, List[str]]]] = None,
+        by: Optional[List[str]] = None,
+        **named: Tuple[str, str],
+    ) -> FqxData: ...
+
     #
     def filter(self, fn: Callable[[FqxRow], bool]) -> FqxData: ...
 
+    # evaluated in Rust, without a per-row callback
+    def filter_expr(self, predicate: FqxExpr) -> FqxData: ...
+
+    # appends the column `expr.output_name()`, or replaces it if it already exists
+    def with_column(self, expr: FqxExpr) -> FqxData: ...
+
+    #
+    def select_exprs(self, exprs: List[FqxExpr]) -> FqxData: ...
+
     #
     def reduce(
         self, fn: Callable[[FqxRow, FqxRow], FqxRow]
@@ -338,13 +411,18 @@ class FqxData:
     #
     def sort_by(self, fn: Callable[[FqxRow], bool]) -> FqxData: ...
 
-    # merge
+    # merge: a key named alike on both sides is kept once, other names found on both sides take
+    # `suffixes`; null keys match each other unless `nulls_equal` is False. Rows follow the left
+    # side, each with its matches, then the unmatched right rows of an outer merge; they are not
+    # sorted on the keys. An unknown key raises FqxColumnNotFoundError
     def merge(
         self,
         other: FqxData,
         left_on: List[str],
         right_on: List[str],
         how: JOIN_TYPE,
+        suffixes: Tuple[str, str] = ("_x", "_y"),
+        nulls_equal: bool = True,
     ) -> FqxData: ...
 
     # join
@@ -353,6 +431,23 @@ class FqxData:
         other: FqxData,
         on: List[str],
         how: JOIN_TYPE,
+        suffixes: Tuple[str, str] = ("_x", "_y"),
+        nulls_equal: bool = True,
+    ) -> FqxData: ...
+
+    # merge_asof: each row takes the nearest row of `other` by `strategy`, after an exact match on
+    # `by`; `tolerance` is a number, or a timedelta for time keys. Columns are named as by `merge`
+    def merge_asof(
+        self,
+        other: FqxData,
+        left_on: str,
+        right_on: str,
+        by: Optional[List[str]] = None,
+        left_by: Optional[List[str]] = None,
+        right_by: Optional[List[str]] = None,
+        strategy: ASOF_STRATEGY = "backward",
+        tolerance: Optional[Union[float, dt.timedelta]] = None,
+        suffixes: Tuple[str, str] = ("_x", "_y"),
     ) -> FqxData: ...
 
 #
@@ -360,6 +455,54 @@ def new_fqx_data(
     data: List[List[FqxVT]], columns: Optional[List[str]] = None
 ) -> FqxData: ...
 
+# ================================================================================================
+# FqxExpr
+# ================================================================================================
+
+ExprLike = Union[FqxExpr, FqxVT]
+
+class FqxExpr:
+    def __add__(self, rhs: ExprLike) -> FqxExpr: ...
+    def __radd__(self, lhs: ExprLike) -> FqxExpr: ...
+    def __sub__(self, rhs: ExprLike) -> FqxExpr: ...
+    def __rsub__(self, lhs: ExprLike) -> FqxExpr: ...
+    def __mul__(self, rhs: ExprLike) -> FqxExpr: ...
+    def __rmul__(self, lhs: ExprLike) -> FqxExpr: ...
+    def __truediv__(self, rhs: ExprLike) -> FqxExpr: ...
+    def __rtruediv__(self, lhs: ExprLike) -> FqxExpr: ...
+    # keeps the left operand's type, i.e. truncates on integers
+    def __floordiv__(self, rhs: ExprLike) -> FqxExpr: ...
+    def __rfloordiv__(self, lhs: ExprLike) -> FqxExpr: ...
+    def __mod__(self, rhs: ExprLike) -> FqxExpr: ...
+    def __rmod__(self, lhs: ExprLike) -> FqxExpr: ...
+    def __eq__(self, rhs: ExprLike) -> FqxExpr: ...  # type: ignore[override]
+    def __ne__(self, rhs: ExprLike) -> FqxExpr: ...  # type: ignore[override]
+    def __lt__(self, rhs: ExprLike) -> FqxExpr: ...
+    def __le__(self, rhs: ExprLike) -> FqxExpr: ...
+    def __gt__(self, rhs: ExprLike) -> FqxExpr: ...
+    def __ge__(self, rhs: ExprLike) -> FqxExpr: ...
+    def __and__(self, rhs: ExprLike) -> FqxExpr: ...
+    def __rand__(self, lhs: ExprLike) -> FqxExpr: ...
+    def __or__(self, rhs: ExprLike) -> FqxExpr: ...
+    def __ror__(self, lhs: ExprLike) -> FqxExpr: ...
+    def __invert__(self) -> FqxExpr: ...
+    def is_null(self) -> FqxExpr: ...
+    def is_not_null(self) -> FqxExpr: ...
+    def cast(self, typ: FqxValueType) -> FqxExpr: ...
+    def alias(self, name: str) -> FqxExpr: ...
+    # the alias if any, otherwise the left-most column name
+    def output_name(self) -> str: ...
+
+def col(name: str) -> FqxExpr: ...
+def lit(value: FqxVT) -> FqxExpr: ...
+
+# ================================================================================================
+# sql_local
+# ================================================================================================
+
+# SELECT with WHERE, GROUP BY, ORDER BY, LIMIT and JOIN, over tables named by their keys
+def sql_local(query: str, tables: Dict[str, FqxData]) -> FqxData: ...
+
 # ================================================================================================
 # X
 # ================================================================================================
@@ -425,10 +568,112 @@ class FqxGroup:
     #
     def values(self) -> ValuesView[FqxData]: ...
 
+    # one row per group in the order of the keys, key columns first; nulls are skipped
+
+    # like pandas, e.g. `agg({"qty": "sum"}, avg_price=("price", "mean"), n=("*", "count"))`;
+    # aggregations: sum/min/max/mean/count/median/quantile(q)/n_unique/first/last/std/var
+    def agg(
+        self,
+        spec: Optional[Dict[str, Union[str, List[str]]]] = None,
+        **named: Tuple[str, str],
+    ) -> FqxData: ...
+
+    #
+    def count(self) -> FqxData: ...
+
+    #
+    def n_unique(self) -> FqxData: ...
+
+    #
+    def first(self) -> FqxData: ...
+
+    #
+    def last(self) -> FqxData: ...
+
+    #
+    def median(self) -> FqxData: ...
+
+    # `q` in [0, 1]
+    def quantile(self, q: float) -> FqxData: ...
+
+    #
+    def std(self) -> FqxData: ...
+
+    #
+    def var(self) -> FqxData: ...
+
+    # the first `n` rows of every group
+    def head(self, n: int) -> FqxData: ...
+
+    # the last `n` rows of every group
+    def tail(self, n: int) -> FqxData: ...
+
+    # windows restart for each group
+    def shift(self, n: int = 1) -> FqxGroup: ...
+
+    #
+    def diff(self, n: int = 1) -> FqxGroup: ...
+
+    #
+    def pct_change(self, n: int = 1) -> FqxGroup: ...
+
+    # method: average/min/max/first/dense
+    def rank(self, method: str = "average") -> FqxGroup: ...
+
+    #
+    def row_number(self) -> FqxGroup: ...
+
+    # `agg` over the last `window` rows, e.g. "mean"/"sum"/"min"/"max"/"std"
+    def rolling(self, window: int, agg: str = "mean") -> FqxGroup: ...
+
 # ================================================================================================
 # FqxSaveMode
 # ================================================================================================
 
-class FqxSaveMode(Enum):
-    Override = 1
-    Append = 2
+class FqxSaveMode:
+    Override: FqxSaveMode
+    Append: FqxSaveMode
+    Truncate: FqxSaveMode
+    CreateIfNotExists: FqxSaveMode
+    ErrorIfExists: FqxSaveMode
+
+    @staticmethod
+    def upsert(keys: List[str]) -> FqxSaveMode: ...
+
+# ================================================================================================
+# FqxConnectionRegistry
+# ================================================================================================
+
+# named connections from a `.toml`/`.json` file, `${ENV_VAR}`s are interpolated (`.env` included);
+# connectors are created on first use and cached by name
+class FqxConnectionRegistry:
+    def __init__(self, path: str) -> None: ...
+    @staticmethod
+    def from_toml(s: str) -> FqxConnectionRegistry: ...
+    @staticmethod
+    def from_json(s: str) -> FqxConnectionRegistry: ...
+    def sql_names(self) -> List[str]: ...
+    def http_names(self) -> List[str]: ...
+    def sql(self, name: str) -> FqxSqlConnector: ...
+    def http(self, name: str) -> FqxHttpConnector: ...
+
+# ================================================================================================
+# Exceptions
+# ================================================================================================
+
+# base class of every error raised by fastqx; exceptions raised in Python callbacks pass through
+class FqxError(Exception): ...
+class FqxTypeMismatchError(FqxError): ...
+class FqxShapeMismatchError(FqxError): ...
+class FqxColumnNotFoundError(FqxError): ...
+class FqxOutOfBoundsError(FqxError): ...
+class FqxCastError(FqxError): ...
+class FqxInvalidError(FqxError): ...
+class FqxSqlError(FqxError): ...
+class FqxHttpError(FqxError): ...
+class FqxCsvError(FqxError): ...
+class FqxIoError(FqxError): ...
+class FqxJsonError(FqxError): ...
+class FqxArrowError(FqxError): ...
+class FqxParquetError(FqxError): ...
+class FqxParseError(FqxError): ...
@@ -3,11 +3,11 @@
 # @date:	2023/09/21 23:56:02 Thursday
 # @brief:
 
-from typing import Optional
+from typing import List, Optional
 from dataclasses import dataclass
 from enum import Enum
 
-from .. import FqxData, FqxSaveMode
+from .. import FqxData, FqxSaveMode, FqxVT, FqxValueType
 
 # ================================================================================================
 # FqxConnectorConfig
@@ -22,6 +22,10 @@ class FqxSqlConnectorConfig:
     db: str
     extra: Optional[str]
 
+    # Sqlite only reads `db`, as the database file path (`:memory:` for an in-memory database)
+    @staticmethod
+    def sqlite(path: str) -> FqxSqlConnectorConfig: ...
+
 # ================================================================================================
 # FqxConnectorType
 # ================================================================================================
@@ -34,16 +38,51 @@ class FqxSqlDriver(Enum):
 
     def to_conn_str(self, config: FqxSqlConnectorConfig) -> str: ...
 
+# ================================================================================================
+# FqxSqlColumnInfo
+# ================================================================================================
+
+@dataclass
+class FqxSqlColumnInfo:
+    name: str
+    native_type: str
+    value_type: FqxValueType
+    nullable: bool
+    primary_key: bool
+
 # ================================================================================================
 # FqxSqlConnector
 # ================================================================================================
 
 class FqxSqlConnector(object):
-    def __init__(self, conn_str: str) -> None: ...
+    # timeouts and `retry_backoff` are in seconds, `None` keeps the driver's default;
+    # `connect_retries` extra attempts are made on a network error, doubling the backoff each time
+    # `statement_timeout` is only supported by Postgres and MySql, other drivers raise on it
+    def __init__(
+        self,
+        conn_str: str,
+        *,
+        max_connections: Optional[int] = None,
+        min_connections: Optional[int] = None,
+        acquire_timeout: Optional[float] = None,
+        idle_timeout: Optional[float] = None,
+        statement_timeout: Optional[float] = None,
+        connect_retries: int = 0,
+        retry_backoff: float = 1.0,
+    ) -> None: ...
+    @staticmethod
+    def sqlite_file(path: str, create_if_missing: bool = True) -> FqxSqlConnector: ...
+    # a private in-memory database, backed by a single connection
+    @staticmethod
+    def sqlite_memory() -> FqxSqlConnector: ...
     def conn_str(self) -> str: ...
     def close(self): ...
     def is_close(self) -> bool: ...
-    def execute(self, sql: str): ...
-    def fetch(self, sql: str) -> FqxData: ...
+    # `?` placeholders are bound to `params`, whichever the driver
+    def execute(self, sql: str, params: Optional[List[FqxVT]] = None): ...
+    def fetch(self, sql: str, params: Optional[List[FqxVT]] = None) -> FqxData: ...
+    def list_schemas(self) -> List[str]: ...
+    def list_tables(self) -> List[str]: ...
+    def describe_table(self, table_name: str) -> List[FqxSqlColumnInfo]: ...
     def save(self, data: FqxData, table_name: str, mode: FqxSaveMode): ...
     def uncheck_save(self, data: FqxData, table_name: str, mode: FqxSaveMode): ...
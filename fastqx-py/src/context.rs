@@ -0,0 +1,27 @@
+//! file: context.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 19:06:45 Saturday
+//! brief:
+
+use std::collections::HashMap;
+
+use fastqx::prelude::*;
+use pyo3::prelude::*;
+
+use crate::PyData;
+
+/// Runs `query` against `tables`, each registered under its key.
+#[pyfunction]
+#[pyo3(name = "sql_local")]
+pub fn py_sql_local(
+    py: Python<'_>,
+    query: String,
+    tables: HashMap<String, PyData>,
+) -> PyResult<PyData> {
+    let mut ctx = FqxContext::new();
+    for (name, d) in tables.into_iter() {
+        ctx.register(name, d.inner.borrow(py).clone());
+    }
+
+    Ok(PyData::from(ctx.sql(&query)?))
+}
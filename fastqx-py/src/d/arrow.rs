@@ -0,0 +1,132 @@
+//! file: arrow.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 14:48:30 Saturday
+//! brief:
+//!
+//! Arrow PyCapsule interface: https://arrow.apache.org/docs/format/CDataInterface/PyCapsuleInterface.html
+
+use std::ffi::CString;
+
+use fastqx::arrow::array::{Array, RecordBatchIterator, RecordBatchReader, StructArray};
+use fastqx::arrow::datatypes::SchemaRef;
+use fastqx::arrow::ffi::{to_ffi, FFI_ArrowSchema};
+use fastqx::arrow::ffi_stream::{ArrowArrayStreamReader, FFI_ArrowArrayStream};
+use fastqx::arrow::record_batch::RecordBatch;
+use fastqx::prelude::*;
+use pyo3::prelude::*;
+use pyo3::types::{PyCapsule, PyTuple, PyType};
+
+use crate::PyData;
+
+// ================================================================================================
+// Capsule helpers
+// ================================================================================================
+
+fn capsule<T: 'static + Send>(py: Python<'_>, value: T, name: &str) -> PyResult<PyObject> {
+    let name = CString::new(name).map_err(FqxError::invalid)?;
+
+    // released by its `Drop`, unless the consumer has already moved it out
+    Ok(PyCapsule::new_bound(py, value, Some(name))?.into_py(py))
+}
+
+fn import_stream(ob: &Bound<PyAny>) -> PyResult<(Vec<RecordBatch>, SchemaRef)> {
+    let cap = ob.call_method0("__arrow_c_stream__")?;
+    let cap = cap
+        .downcast::<PyCapsule>()
+        .map_err(|_| FqxError::invalid("`__arrow_c_stream__` must return a PyCapsule"))?;
+
+    match cap.name()? {
+        Some(n) if n.to_bytes() == b"arrow_array_stream" => {}
+        _ => {
+            return Err(
+                FqxError::invalid("invalid PyCapsule name, expected `arrow_array_stream`").into(),
+            )
+        }
+    }
+
+    // the stream is moved out of the capsule, leaving a released one behind
+    let stream = cap.pointer() as *mut FFI_ArrowArrayStream;
+    let reader = unsafe { ArrowArrayStreamReader::from_raw(stream).map_err(FqxError::from)? };
+    let schema = reader.schema();
+    let batches = reader
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(FqxError::from)?;
+
+    Ok((batches, schema))
+}
+
+// ================================================================================================
+// PyData
+// ================================================================================================
+
+#[pymethods]
+impl PyData {
+    fn __arrow_c_schema__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let schema = self.inner.borrow(py).arrow_schema();
+        let schema = FFI_ArrowSchema::try_from(&schema).map_err(FqxError::from)?;
+
+        capsule(py, schema, "arrow_schema")
+    }
+
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_array__(
+        &self,
+        py: Python<'_>,
+        requested_schema: Option<PyObject>,
+    ) -> PyResult<Py<PyTuple>> {
+        // schema negotiation is optional for producers, the requested schema is ignored
+        let _ = requested_schema;
+
+        let batch = self.inner.borrow(py).to_record_batch()?;
+        let data = StructArray::from(batch).into_data();
+        let (array, schema) = to_ffi(&data).map_err(FqxError::from)?;
+
+        let schema = capsule(py, schema, "arrow_schema")?;
+        let array = capsule(py, array, "arrow_array")?;
+
+        Ok(PyTuple::new_bound(py, [schema, array]).unbind())
+    }
+
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__(
+        &self,
+        py: Python<'_>,
+        requested_schema: Option<PyObject>,
+    ) -> PyResult<PyObject> {
+        let _ = requested_schema;
+
+        let batch = self.inner.borrow(py).to_record_batch()?;
+        let schema = batch.schema();
+        let reader = RecordBatchIterator::new(vec![Ok(batch)], schema);
+        let stream = FFI_ArrowArrayStream::new(Box::new(reader));
+
+        capsule(py, stream, "arrow_array_stream")
+    }
+
+    #[classmethod]
+    fn from_arrow(_cls: &Bound<PyType>, data: &Bound<PyAny>) -> PyResult<Self> {
+        let (batches, schema) = import_stream(data)?;
+
+        let mut res = FqxData::try_from_record_batch(&RecordBatch::new_empty(schema))?;
+        for batch in batches.iter() {
+            let d = FqxData::try_from_record_batch(batch)?;
+            res.data_mut().extend(d.data_take());
+        }
+
+        Ok(PyData::from(res))
+    }
+
+    fn to_arrow(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let module = PyModule::import_bound(py, "pyarrow")?;
+        let table = module.getattr("table")?.call1((self.clone(),))?;
+
+        Ok(table.into())
+    }
+
+    fn to_polars(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let module = PyModule::import_bound(py, "polars")?;
+        let df = module.getattr("DataFrame")?.call1((self.clone(),))?;
+
+        Ok(df.into())
+    }
+}
@@ -6,7 +6,7 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
-use anyhow::{anyhow, Result};
+use fastqx::parquet::basic::Compression;
 use fastqx::prelude::*;
 use fastqx::serde_json;
 use pyo3::prelude::*;
@@ -191,7 +191,7 @@ impl PyData {
     #[classmethod]
     fn from_list(_cls: Bound<PyType>, data: Vec<Vec<FqxValue>>) -> PyResult<Self> {
         if data.is_empty() {
-            return Err(anyhow!("data is empty").into());
+            return Err(FqxError::invalid("data is empty").into());
         }
 
         let columns = (0..data.first().unwrap().len())
@@ -321,7 +321,7 @@ impl PyData {
         let type_hints = type_hints
             .into_iter()
             .map(|s| FqxValueType::from_str(&s))
-            .collect::<Result<Vec<_>>>()?;
+            .collect::<FqxResult<Vec<_>>>()?;
         let res = csv_read_rd(path, &type_hints)?;
 
         Ok(PyData::from(res))
@@ -331,9 +331,31 @@ impl PyData {
         Ok(csv_write_rd(&self.inner.borrow(py), path)?)
     }
 
+    #[classmethod]
+    #[pyo3(signature = (path, columns=None))]
+    fn from_parquet(
+        _cls: &Bound<PyType>,
+        path: String,
+        columns: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        let columns = columns
+            .as_ref()
+            .map(|c| c.iter().map(String::as_str).collect::<Vec<_>>());
+        let res = parquet_read_rd(path, columns.as_deref(), None)?;
+
+        Ok(PyData::from(res))
+    }
+
+    #[pyo3(signature = (path, compression="snappy"))]
+    fn to_parquet(&self, py: Python<'_>, path: String, compression: &str) -> PyResult<()> {
+        let compression = Compression::from_str(compression).map_err(FqxError::from)?;
+
+        Ok(parquet_write_rd(&self.inner.borrow(py), path, compression)?)
+    }
+
     #[classmethod]
     fn from_sql(_cls: &Bound<PyType>, sql: String, conn: &PySqlConnector) -> PyResult<Self> {
-        let res = conn.fetch(&sql)?;
+        let res = conn.fetch(&sql, None)?;
 
         Ok(PyData::from(res))
     }
@@ -343,7 +365,7 @@ impl PyData {
         py: Python<'_>,
         table: String,
         conn: &PySqlConnector,
-        mode: SaveMode,
+        mode: PySaveMode,
     ) -> PyResult<()> {
         Ok(conn.save(py, self, &table, mode)?)
     }
@@ -387,11 +409,11 @@ pub struct PyX(Py<FqxData>);
 #[pymethods]
 impl PyX {
     fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
-        Ok(serde_json::to_string_pretty(self.0.borrow(py).data()).map_err(anyhow::Error::msg)?)
+        Ok(serde_json::to_string_pretty(self.0.borrow(py).data()).map_err(FqxError::from)?)
     }
 
     fn __str__(&self, py: Python<'_>) -> PyResult<String> {
-        Ok(serde_json::to_string(self.0.borrow(py).data()).map_err(anyhow::Error::msg)?)
+        Ok(serde_json::to_string(self.0.borrow(py).data()).map_err(FqxError::from)?)
     }
 
     fn __getitem__(&self, py: Python<'_>, idx: PyObject) -> PyResult<Vec<Vec<FqxValue>>> {
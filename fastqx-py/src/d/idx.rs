@@ -3,12 +3,12 @@
 //! date: 2023/10/27 23:54:11 Friday
 //! brief:
 
-use anyhow::{bail, Result};
 use pyo3::prelude::*;
 use pyo3::types::PySlice;
 
 use super::utils::*;
 use fastqx::adt::{FqxD, FqxData, FqxRow, FqxValue, RowProps};
+use fastqx::error::{FqxError, FqxResult};
 
 // ================================================================================================
 // PyIdx & PyIdxD
@@ -67,7 +67,7 @@ impl<'a> PyIdx<'a> {
         }
     }
 
-    pub fn slice_mut(self, py: Python<'_>, d: &mut FqxData, asn: PyAssign) -> Result<()> {
+    pub fn slice_mut(self, py: Python<'_>, d: &mut FqxData, asn: PyAssign) -> FqxResult<()> {
         let (row_slice, col_slice, val) = match (self, asn) {
             (PyIdx::R(r), PyAssign::D1(d1)) => {
                 let row_slice = _isize2slice(r, py);
@@ -124,7 +124,10 @@ impl<'a> PyIdx<'a> {
                 (row_slice, col_slice, val)
             }
             (i, a) => {
-                bail!("mismatch assignment, idx: {:?}, asn: {:?}", i, a);
+                return Err(FqxError::invalid(format!(
+                    "mismatch assignment, idx: {:?}, asn: {:?}",
+                    i, a
+                )));
             }
         };
 
@@ -3,6 +3,7 @@
 //! date: 2023/11/09 21:14:26 Thursday
 //! brief:
 
+pub mod arrow;
 pub mod data;
 pub mod idx;
 pub mod ops;
@@ -5,11 +5,13 @@
 
 use std::collections::HashMap;
 
-use anyhow::{anyhow, Result};
+use fastqx::chrono;
 use fastqx::prelude::*;
 use fastqx::serde_json;
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 
+use crate::expr::PyExpr;
 use crate::PyData;
 
 #[pymethods]
@@ -27,7 +29,7 @@ impl PyData {
                 let ans = lambda.call1((r,))?.to_object(py);
                 Ok(ans)
             })
-            .collect::<Result<Vec<_>>>()?;
+            .collect::<PyResult<Vec<_>>>()?;
 
         Ok(res)
     }
@@ -70,6 +72,85 @@ impl PyData {
         self.inner.borrow(py).cum_mean()
     }
 
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // window
+
+    #[pyo3(signature = (n=1))]
+    fn shift(&self, py: Python<'_>, n: isize) -> Self {
+        Self::from(self.inner.borrow(py).shift(n))
+    }
+
+    #[pyo3(signature = (n=1))]
+    fn diff(&self, py: Python<'_>, n: isize) -> Self {
+        Self::from(self.inner.borrow(py).diff(n))
+    }
+
+    #[pyo3(signature = (n=1))]
+    fn pct_change(&self, py: Python<'_>, n: isize) -> Self {
+        Self::from(self.inner.borrow(py).pct_change(n))
+    }
+
+    #[pyo3(signature = (method="average"))]
+    fn rank(&self, py: Python<'_>, method: &str) -> PyResult<Self> {
+        Ok(Self::from(self.inner.borrow(py).rank(method.parse()?)))
+    }
+
+    fn row_number(&self, py: Python<'_>) -> Self {
+        Self::from(self.inner.borrow(py).row_number())
+    }
+
+    #[pyo3(signature = (window, agg="mean"))]
+    fn rolling(&self, py: Python<'_>, window: usize, agg: &str) -> PyResult<Self> {
+        Ok(Self::from(
+            self.inner.borrow(py).rolling(window, agg.parse()?),
+        ))
+    }
+
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // resample
+
+    #[pyo3(signature = (time_col, every, spec=None, by=None, **named))]
+    fn resample(
+        &self,
+        py: Python<'_>,
+        time_col: &str,
+        every: Bound<'_, PyAny>,
+        spec: Option<Bound<'_, PyDict>>,
+        by: Option<Vec<String>>,
+        named: Option<Bound<'_, PyDict>>,
+    ) -> PyResult<Self> {
+        let specs = _agg_specs(spec.as_ref(), named.as_ref())?;
+        let res = self.inner.borrow(py).resample_specs(
+            &by.unwrap_or_default(),
+            time_col,
+            _every(&every)?,
+            &specs,
+        )?;
+
+        Ok(Self::from(res))
+    }
+
+    #[pyo3(signature = (time_col, window, spec=None, by=None, **named))]
+    fn rolling_time(
+        &self,
+        py: Python<'_>,
+        time_col: &str,
+        window: Bound<'_, PyAny>,
+        spec: Option<Bound<'_, PyDict>>,
+        by: Option<Vec<String>>,
+        named: Option<Bound<'_, PyDict>>,
+    ) -> PyResult<Self> {
+        let specs = _agg_specs(spec.as_ref(), named.as_ref())?;
+        let res = self.inner.borrow(py).rolling_time_specs(
+            &by.unwrap_or_default(),
+            time_col,
+            _every(&window)?,
+            &specs,
+        )?;
+
+        Ok(Self::from(res))
+    }
+
     ///////////////////////////////////////////////////////////////////////////////////////////////////
     // filter
 
@@ -91,6 +172,32 @@ impl PyData {
         Ok(Self::from(res))
     }
 
+    ///////////////////////////////////////////////////////////////////////////////////////////////////
+    // expr
+
+    fn filter_expr(&self, py: Python<'_>, predicate: PyExpr) -> PyResult<Self> {
+        let res = self
+            .inner
+            .borrow(py)
+            .clone()
+            .filter_expr(&predicate.inner)?;
+
+        Ok(Self::from(res))
+    }
+
+    fn with_column(&self, py: Python<'_>, expr: PyExpr) -> PyResult<Self> {
+        let res = self.inner.borrow(py).clone().with_column(&expr.inner)?;
+
+        Ok(Self::from(res))
+    }
+
+    fn select_exprs(&self, py: Python<'_>, exprs: Vec<PyExpr>) -> PyResult<Self> {
+        let exprs = exprs.into_iter().map(|e| e.inner).collect::<Vec<_>>();
+        let res = self.inner.borrow(py).clone().select_exprs(&exprs)?;
+
+        Ok(Self::from(res))
+    }
+
     ///////////////////////////////////////////////////////////////////////////////////////////////////
     // reduce
 
@@ -123,7 +230,7 @@ impl PyData {
             .map(|(k, v)| (PyGroupKey(k), PyData::from(v)))
             .collect::<HashMap<_, PyData>>();
 
-        PyGroup(res)
+        PyGroup(res, keys)
     }
 
     ///////////////////////////////////////////////////////////////////////////////////////////////////
@@ -147,6 +254,8 @@ impl PyData {
     ///////////////////////////////////////////////////////////////////////////////////////////////////
     // merge
 
+    #[pyo3(signature = (other, left_on, right_on, how, suffixes=None, nulls_equal=true))]
+    #[allow(clippy::too_many_arguments)]
     fn merge(
         &self,
         py: Python<'_>,
@@ -154,20 +263,23 @@ impl PyData {
         left_on: Vec<String>,
         right_on: Vec<String>,
         how: String,
+        suffixes: Option<(String, String)>,
+        nulls_equal: bool,
     ) -> PyResult<Self> {
-        let how = match &how[..] {
-            "left" => FqxJoinType::Left,
-            "right" => FqxJoinType::Right,
-            "outer" => FqxJoinType::Outer,
-            "inner" => FqxJoinType::Inner,
-            _ => return Err(anyhow!("how: left/right/outer/inner").into()),
+        let how = how.parse::<FqxJoinType>()?;
+        let options = FqxJoinOptions {
+            suffixes: suffixes
+                .as_ref()
+                .map_or(DEFAULT_SUFFIXES, |(l, r)| (l.as_str(), r.as_str())),
+            nulls_equal,
         };
-        let res = self.inner.borrow(py).clone().merge(
+        let res = self.inner.borrow(py).clone().merge_with(
             other.inner.borrow(py).clone(),
             &left_on,
             &right_on,
             how,
-        );
+            &options,
+        )?;
 
         Ok(Self::from(res))
     }
@@ -175,24 +287,85 @@ impl PyData {
     ///////////////////////////////////////////////////////////////////////////////////////////////////
     // join
 
-    fn join(&self, py: Python<'_>, other: PyData, on: Vec<String>, how: String) -> PyResult<Self> {
-        let how = match &how[..] {
-            "left" => FqxJoinType::Left,
-            "right" => FqxJoinType::Right,
-            "outer" => FqxJoinType::Outer,
-            "inner" => FqxJoinType::Inner,
-            _ => return Err(anyhow!("how: left/right/outer/inner").into()),
+    #[pyo3(signature = (other, on, how, suffixes=None, nulls_equal=true))]
+    fn join(
+        &self,
+        py: Python<'_>,
+        other: PyData,
+        on: Vec<String>,
+        how: String,
+        suffixes: Option<(String, String)>,
+        nulls_equal: bool,
+    ) -> PyResult<Self> {
+        let how = how.parse::<FqxJoinType>()?;
+        let options = FqxJoinOptions {
+            suffixes: suffixes
+                .as_ref()
+                .map_or(DEFAULT_SUFFIXES, |(l, r)| (l.as_str(), r.as_str())),
+            nulls_equal,
         };
-        let res = self
-            .inner
-            .borrow(py)
-            .clone()
-            .join(other.inner.borrow(py).clone(), &on, how);
+        let res = self.inner.borrow(py).clone().join_with(
+            other.inner.borrow(py).clone(),
+            &on,
+            how,
+            &options,
+        )?;
+
+        Ok(Self::from(res))
+    }
+
+    #[pyo3(signature = (
+        other,
+        left_on,
+        right_on,
+        by=None,
+        left_by=None,
+        right_by=None,
+        strategy="backward",
+        tolerance=None,
+        suffixes=None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn merge_asof(
+        &self,
+        py: Python<'_>,
+        other: PyData,
+        left_on: &str,
+        right_on: &str,
+        by: Option<Vec<String>>,
+        left_by: Option<Vec<String>>,
+        right_by: Option<Vec<String>>,
+        strategy: &str,
+        tolerance: Option<Bound<'_, PyAny>>,
+        suffixes: Option<(String, String)>,
+    ) -> PyResult<Self> {
+        let default = AsofOptions::default();
+        let options = AsofOptions {
+            left_by: left_by.or_else(|| by.clone()).unwrap_or_default(),
+            right_by: right_by.or(by).unwrap_or_default(),
+            strategy: strategy.parse()?,
+            tolerance: tolerance.as_ref().map(_tolerance).transpose()?,
+            suffixes: suffixes.unwrap_or(default.suffixes),
+        };
+        let res = self.inner.borrow(py).clone().merge_asof(
+            other.inner.borrow(py).clone(),
+            left_on,
+            right_on,
+            &options,
+        )?;
 
         Ok(Self::from(res))
     }
 }
 
+/// Either a number, or a `datetime.timedelta` taken in seconds.
+fn _tolerance(tolerance: &Bound<'_, PyAny>) -> PyResult<f64> {
+    match tolerance.extract::<f64>() {
+        Ok(t) => Ok(t),
+        Err(_) => tolerance.call_method0("total_seconds")?.extract::<f64>(),
+    }
+}
+
 // ================================================================================================
 // PyGroupKey & PyGroup
 // ================================================================================================
@@ -213,11 +386,11 @@ impl PyGroupKey {
     }
 
     fn __repr__(&self) -> PyResult<String> {
-        Ok(serde_json::to_string_pretty(&self.0).map_err(anyhow::Error::msg)?)
+        Ok(serde_json::to_string_pretty(&self.0).map_err(FqxError::from)?)
     }
 
     fn __str__(&self) -> PyResult<String> {
-        Ok(serde_json::to_string(&self.0).map_err(anyhow::Error::msg)?)
+        Ok(serde_json::to_string(&self.0).map_err(FqxError::from)?)
     }
 
     fn __getitem__(&self, idx: isize) -> FqxValue {
@@ -241,11 +414,81 @@ impl PyGroupKey {
 
 ///////////////////////////////////////////////////////////////////////////////////////////////////
 
-// TODO: group ops
-
+/// Groups and the names of their key columns.
 #[pyclass]
 #[pyo3(name = "FqxGroup")]
-pub struct PyGroup(HashMap<PyGroupKey, PyData>);
+pub struct PyGroup(HashMap<PyGroupKey, PyData>, Vec<String>);
+
+impl PyGroup {
+    fn to_fqx_group(&self, py: Python<'_>) -> FqxGroup<FqxData> {
+        let groups = self
+            .0
+            .iter()
+            .map(|(k, v)| (k.0.clone(), v.inner.borrow(py).clone()))
+            .collect();
+
+        FqxGroup::from_hashmap(groups, self.1.clone())
+    }
+}
+
+impl From<FqxGroup<FqxData>> for PyGroup {
+    fn from(g: FqxGroup<FqxData>) -> Self {
+        let keys = g.key_names().to_vec();
+        let res = g
+            .to_hashmap()
+            .into_iter()
+            .map(|(k, v)| (PyGroupKey(k), PyData::from(v)))
+            .collect();
+
+        PyGroup(res, keys)
+    }
+}
+
+/// `{"price": "mean"}` names the output after its column, `{"price": ["mean", "max"]}` after both,
+/// i.e. `price_mean` and `price_max`. Rows counted by `{"*": "count"}` are named `count`. `named`
+/// specs are `alias=(column, agg)`, and follow.
+fn _agg_specs(
+    spec: Option<&Bound<'_, PyDict>>,
+    named: Option<&Bound<'_, PyDict>>,
+) -> PyResult<Vec<AggSpec>> {
+    let mut res = vec![];
+    for (k, v) in spec.iter().flat_map(|s| s.iter()) {
+        let column = k.extract::<String>()?;
+        let (aggs, single) = match v.extract::<String>() {
+            Ok(a) => (vec![a], true),
+            Err(_) => (v.extract::<Vec<String>>()?, false),
+        };
+        for a in aggs {
+            let alias = match (column.as_str(), single) {
+                ("*", _) => a.clone(),
+                (c, true) => c.to_string(),
+                (c, false) => format!("{c}_{a}"),
+            };
+            res.push(AggSpec::new(column.clone(), a.parse()?, alias));
+        }
+    }
+    for (k, v) in named.iter().flat_map(|n| n.iter()) {
+        let (column, agg) = v.extract::<(String, String)>()?;
+        res.push(AggSpec::new(column, agg.parse()?, k.extract::<String>()?));
+    }
+
+    Ok(res)
+}
+
+/// Either a string like `"1d"`, or a `datetime.timedelta`.
+fn _every(every: &Bound<'_, PyAny>) -> PyResult<Every> {
+    if let Ok(s) = every.extract::<String>() {
+        return Ok(s.parse()?);
+    }
+    let secs = every.call_method0("total_seconds")?.extract::<f64>()?;
+    let res = Every::from(chrono::Duration::milliseconds(
+        (secs * 1000.0).round() as i64
+    ));
+    match res.is_valid() {
+        true => Ok(res),
+        false => Err(FqxError::invalid(format!("wrong interval: {secs}s")).into()),
+    }
+}
 
 #[pymethods]
 impl PyGroup {
@@ -299,7 +542,88 @@ impl PyGroup {
     ///////////////////////////////////////////////////////////////////////////////////////////////////
     // ops
 
-    // TODO
+    /// Like pandas, e.g. `agg({"qty": "sum"}, avg_price=("price", "mean"), n=("*", "count"))`
+    #[pyo3(signature = (spec=None, **named))]
+    fn agg(
+        &self,
+        py: Python<'_>,
+        spec: Option<Bound<'_, PyDict>>,
+        named: Option<Bound<'_, PyDict>>,
+    ) -> PyResult<PyData> {
+        let specs = _agg_specs(spec.as_ref(), named.as_ref())?;
+        Ok(PyData::from(self.to_fqx_group(py).agg_specs(&specs)?))
+    }
+
+    fn count(&self, py: Python<'_>) -> PyData {
+        PyData::from(self.to_fqx_group(py).count())
+    }
+
+    fn n_unique(&self, py: Python<'_>) -> PyData {
+        PyData::from(self.to_fqx_group(py).n_unique())
+    }
+
+    fn first(&self, py: Python<'_>) -> PyData {
+        PyData::from(self.to_fqx_group(py).first())
+    }
+
+    fn last(&self, py: Python<'_>) -> PyData {
+        PyData::from(self.to_fqx_group(py).last())
+    }
+
+    fn median(&self, py: Python<'_>) -> PyData {
+        PyData::from(self.to_fqx_group(py).median())
+    }
+
+    fn quantile(&self, py: Python<'_>, q: f64) -> PyResult<PyData> {
+        Ok(PyData::from(self.to_fqx_group(py).quantile(q)?))
+    }
+
+    fn std(&self, py: Python<'_>) -> PyData {
+        PyData::from(self.to_fqx_group(py).std())
+    }
+
+    fn var(&self, py: Python<'_>) -> PyData {
+        PyData::from(self.to_fqx_group(py).var())
+    }
+
+    fn head(&self, py: Python<'_>, n: usize) -> PyData {
+        PyData::from(self.to_fqx_group(py).head(n))
+    }
+
+    fn tail(&self, py: Python<'_>, n: usize) -> PyData {
+        PyData::from(self.to_fqx_group(py).tail(n))
+    }
+
+    #[pyo3(signature = (n=1))]
+    fn shift(&self, py: Python<'_>, n: isize) -> Self {
+        Self::from(self.to_fqx_group(py).shift(n))
+    }
+
+    #[pyo3(signature = (n=1))]
+    fn diff(&self, py: Python<'_>, n: isize) -> Self {
+        Self::from(self.to_fqx_group(py).diff(n))
+    }
+
+    #[pyo3(signature = (n=1))]
+    fn pct_change(&self, py: Python<'_>, n: isize) -> Self {
+        Self::from(self.to_fqx_group(py).pct_change(n))
+    }
+
+    #[pyo3(signature = (method="average"))]
+    fn rank(&self, py: Python<'_>, method: &str) -> PyResult<Self> {
+        Ok(Self::from(self.to_fqx_group(py).rank(method.parse()?)))
+    }
+
+    fn row_number(&self, py: Python<'_>) -> Self {
+        Self::from(self.to_fqx_group(py).row_number())
+    }
+
+    #[pyo3(signature = (window, agg="mean"))]
+    fn rolling(&self, py: Python<'_>, window: usize, agg: &str) -> PyResult<Self> {
+        Ok(Self::from(
+            self.to_fqx_group(py).rolling(window, agg.parse()?),
+        ))
+    }
 }
 
 ///////////////////////////////////////////////////////////////////////////////////////////////////
@@ -3,13 +3,13 @@
 //! date: 2023/10/11 13:31:46 Wednesday
 //! brief:
 
-use anyhow::{anyhow, Result};
 use pyo3::{
     types::{PyAnyMethods, PySlice},
     Bound,
 };
 
 use fastqx::adt::{FqxD, FqxData, FqxRow, FqxValue, RowProps};
+use fastqx::error::{FqxError, FqxResult};
 
 // ================================================================================================
 // helpers
@@ -118,8 +118,8 @@ pub(crate) fn slice_fqx(
 fn _slice_mut_op(
     row_len: isize,
     row_slice: Bound<PySlice>,
-    mut f: impl FnMut(usize, usize) -> Result<()>,
-) -> Result<()> {
+    mut f: impl FnMut(usize, usize) -> FqxResult<()>,
+) -> FqxResult<()> {
     let (start, stop, step, mut i) = de_slice(row_len, row_slice);
     let mut val_i = 0;
 
@@ -145,14 +145,15 @@ pub(crate) fn slice_vec_mut<I, O>(
     row_len: isize,
     row_slice: Bound<PySlice>,
     mut val: Vec<O>,
-) -> Result<()>
+) -> FqxResult<()>
 where
     I: std::ops::IndexMut<usize, Output = O>,
 {
     let f = |vi, i| {
+        let len = val.len();
         let v = val
             .get_mut(vi)
-            .ok_or(anyhow!("slice vec out of boundary"))?;
+            .ok_or(FqxError::OutOfBounds { index: vi, len })?;
         std::mem::swap(&mut input[i], v);
 
         Ok(())
@@ -165,12 +166,15 @@ pub(crate) fn slice_fqx_mut(
     row_slice: Bound<PySlice>,
     col_slice: Bound<PySlice>,
     mut val: Vec<Vec<FqxValue>>,
-) -> Result<()> {
+) -> FqxResult<()> {
     let (row_len, col_len) = d.shape();
     let (row_len, col_len) = (row_len as isize, col_len as isize);
 
     let f = |vi, i| {
-        let dest = val.get_mut(vi).ok_or(anyhow!("out of boundary"))?;
+        let len = val.len();
+        let dest = val
+            .get_mut(vi)
+            .ok_or(FqxError::OutOfBounds { index: vi, len })?;
         let v = std::mem::replace(dest, vec![]);
         slice_vec_mut(&mut d.data_mut()[i], col_len, col_slice.clone(), v)?;
 
@@ -0,0 +1,171 @@
+//! file: expr.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 14:58:12 Saturday
+//! brief:
+
+use fastqx::prelude::*;
+use pyo3::prelude::*;
+use pyo3::pyclass::CompareOp;
+
+// ================================================================================================
+// PyExpr
+// ================================================================================================
+
+#[pyclass]
+#[pyo3(name = "FqxExpr")]
+#[derive(Clone)]
+pub struct PyExpr {
+    pub(crate) inner: Expr,
+}
+
+impl From<Expr> for PyExpr {
+    fn from(inner: Expr) -> Self {
+        Self { inner }
+    }
+}
+
+/// A Python operand: either an expression or a plain value taken as a literal.
+#[derive(FromPyObject)]
+pub enum PyExprLike {
+    Expr(PyExpr),
+    Lit(FqxValue),
+}
+
+impl From<PyExprLike> for Expr {
+    fn from(value: PyExprLike) -> Self {
+        match value {
+            PyExprLike::Expr(e) => e.inner,
+            PyExprLike::Lit(v) => Expr::Literal(v),
+        }
+    }
+}
+
+#[pyfunction]
+#[pyo3(name = "col")]
+pub fn py_col(name: String) -> PyExpr {
+    col(name).into()
+}
+
+#[pyfunction]
+#[pyo3(name = "lit")]
+pub fn py_lit(value: FqxValue) -> PyExpr {
+    lit(value).into()
+}
+
+macro_rules! binary {
+    ($s:expr, $op:ident, $rhs:expr) => {
+        PyExpr::from($s.inner.clone().binary(BinaryOp::$op, $rhs.into()))
+    };
+    (rev $s:expr, $op:ident, $lhs:expr) => {
+        PyExpr::from(Expr::from($lhs).binary(BinaryOp::$op, $s.inner.clone()))
+    };
+}
+
+#[pymethods]
+impl PyExpr {
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.inner)
+    }
+
+    fn __add__(&self, rhs: PyExprLike) -> Self {
+        binary!(self, Add, rhs)
+    }
+
+    fn __radd__(&self, lhs: PyExprLike) -> Self {
+        binary!(rev self, Add, lhs)
+    }
+
+    fn __sub__(&self, rhs: PyExprLike) -> Self {
+        binary!(self, Sub, rhs)
+    }
+
+    fn __rsub__(&self, lhs: PyExprLike) -> Self {
+        binary!(rev self, Sub, lhs)
+    }
+
+    fn __mul__(&self, rhs: PyExprLike) -> Self {
+        binary!(self, Mul, rhs)
+    }
+
+    fn __rmul__(&self, lhs: PyExprLike) -> Self {
+        binary!(rev self, Mul, lhs)
+    }
+
+    // arithmetic keeps the left operand's type, which is made a float to match Python's `/`
+    fn __truediv__(&self, rhs: PyExprLike) -> Self {
+        let lhs = self.inner.clone().cast(FqxValueType::F64);
+        lhs.binary(BinaryOp::Div, rhs.into()).into()
+    }
+
+    fn __rtruediv__(&self, lhs: PyExprLike) -> Self {
+        let lhs = Expr::from(lhs).cast(FqxValueType::F64);
+        lhs.binary(BinaryOp::Div, self.inner.clone()).into()
+    }
+
+    fn __floordiv__(&self, rhs: PyExprLike) -> Self {
+        binary!(self, FloorDiv, rhs)
+    }
+
+    fn __rfloordiv__(&self, lhs: PyExprLike) -> Self {
+        binary!(rev self, FloorDiv, lhs)
+    }
+
+    fn __mod__(&self, rhs: PyExprLike) -> Self {
+        binary!(self, Rem, rhs)
+    }
+
+    fn __rmod__(&self, lhs: PyExprLike) -> Self {
+        binary!(rev self, Rem, lhs)
+    }
+
+    fn __richcmp__(&self, rhs: PyExprLike, op: CompareOp) -> Self {
+        match op {
+            CompareOp::Lt => binary!(self, Lt, rhs),
+            CompareOp::Le => binary!(self, Le, rhs),
+            CompareOp::Eq => binary!(self, Eq, rhs),
+            CompareOp::Ne => binary!(self, Neq, rhs),
+            CompareOp::Gt => binary!(self, Gt, rhs),
+            CompareOp::Ge => binary!(self, Ge, rhs),
+        }
+    }
+
+    fn __and__(&self, rhs: PyExprLike) -> Self {
+        binary!(self, And, rhs)
+    }
+
+    fn __rand__(&self, lhs: PyExprLike) -> Self {
+        binary!(rev self, And, lhs)
+    }
+
+    fn __or__(&self, rhs: PyExprLike) -> Self {
+        binary!(self, Or, rhs)
+    }
+
+    fn __ror__(&self, lhs: PyExprLike) -> Self {
+        binary!(rev self, Or, lhs)
+    }
+
+    fn __invert__(&self) -> Self {
+        (!self.inner.clone()).into()
+    }
+
+    fn is_null(&self) -> Self {
+        self.inner.clone().is_null().into()
+    }
+
+    fn is_not_null(&self) -> Self {
+        self.inner.clone().is_not_null().into()
+    }
+
+    fn cast(&self, typ: FqxValueType) -> Self {
+        self.inner.clone().cast(typ).into()
+    }
+
+    fn alias(&self, name: String) -> Self {
+        self.inner.clone().alias(name).into()
+    }
+
+    fn output_name(&self) -> String {
+        self.inner.output_name()
+    }
+}
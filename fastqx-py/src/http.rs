@@ -3,6 +3,8 @@
 //! date: 2023/10/02 19:01:05 Monday
 //! brief:
 
+use std::sync::Arc;
+
 use fastqx::prelude::*;
 use fastqx::serde_json::Value;
 use pyo3::prelude::*;
@@ -19,7 +21,14 @@ use crate::PyData;
 #[pyo3(name = "FqxHttpConnector", subclass)]
 pub struct PyHttpConnector {
     inner: HttpConnector,
-    runtime: Runtime,
+    runtime: Arc<Runtime>,
+}
+
+impl PyHttpConnector {
+    /// Connections kept alive by the client of `inner` are bound to `runtime`.
+    pub(crate) fn from_inner(inner: HttpConnector, runtime: Arc<Runtime>) -> Self {
+        PyHttpConnector { inner, runtime }
+    }
 }
 
 #[pymethods]
@@ -30,7 +39,7 @@ impl PyHttpConnector {
 
         let inner = HttpConnector::new(url, auth)?;
 
-        Ok(PyHttpConnector { inner, runtime })
+        Ok(PyHttpConnector::from_inner(inner, Arc::new(runtime)))
     }
 
     fn url(&self) -> &str {
@@ -41,7 +50,7 @@ impl PyHttpConnector {
         let res = slf.runtime.block_on(async {
             let json = slf.inner.dyn_get(subpath).await?;
 
-            Ok::<_, anyhow::Error>(pythonize(py, &json)?)
+            Ok::<_, PyErr>(pythonize(py, &json)?)
         })?;
 
         Ok(res)
@@ -57,7 +66,7 @@ impl PyHttpConnector {
         let res = slf.runtime.block_on(async {
             let json = slf.inner.dyn_post(subpath, &req).await?;
 
-            Ok::<_, anyhow::Error>(pythonize(py, &json)?)
+            Ok::<_, PyErr>(pythonize(py, &json)?)
         })?;
 
         Ok(res)
@@ -73,7 +82,7 @@ impl PyHttpConnector {
         let res = slf.runtime.block_on(async {
             let json = slf.inner.dyn_put(subpath, &req).await?;
 
-            Ok::<_, anyhow::Error>(pythonize(py, &json)?)
+            Ok::<_, PyErr>(pythonize(py, &json)?)
         })?;
 
         Ok(res)
@@ -83,7 +92,7 @@ impl PyHttpConnector {
         let res = slf.runtime.block_on(async {
             let json = slf.inner.dyn_delete(subpath).await?;
 
-            Ok::<_, anyhow::Error>(pythonize(py, &json)?)
+            Ok::<_, PyErr>(pythonize(py, &json)?)
         })?;
 
         Ok(res)
@@ -99,7 +108,7 @@ impl PyHttpConnector {
         let res = slf.runtime.block_on(async {
             let json = slf.inner.dyn_patch(subpath, &req).await?;
 
-            Ok::<_, anyhow::Error>(pythonize(py, &json)?)
+            Ok::<_, PyErr>(pythonize(py, &json)?)
         })?;
 
         Ok(res)
@@ -115,7 +124,7 @@ impl PyHttpConnector {
         let data = slf.runtime.block_on(async {
             let res = FqxData::curl(&slf.inner, subpath, method, payload).await?;
 
-            Ok::<_, anyhow::Error>(res)
+            Ok::<_, FqxError>(res)
         })?;
 
         Ok(PyData::from(data))
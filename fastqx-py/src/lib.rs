@@ -3,10 +3,13 @@
 //! date: 2023/09/09 14:56:36 Saturday
 //! brief:
 
+pub mod context;
 pub mod csv;
 pub mod d;
+pub mod expr;
 pub mod http;
 pub mod py;
+pub mod registry;
 pub mod sql;
 
 pub use d::*;
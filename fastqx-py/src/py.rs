@@ -9,8 +9,11 @@ use fastqx::prelude::*;
 use fastqx::sources::sql::ConnectorConfig;
 use pyo3::prelude::*;
 
+use crate::context::py_sql_local;
 use crate::csv::{fqx_data_from_csv, fqx_data_to_csv};
+use crate::expr::{py_col, py_lit, PyExpr};
 use crate::http::PyHttpConnector;
+use crate::registry::PyConnectionRegistry;
 use crate::sql::PySqlConnector;
 use crate::{new_fqx_data, PyData, PyGroup, PyGroupKey};
 
@@ -22,6 +25,7 @@ fn module_sql(py: Python<'_>) -> PyResult<Bound<PyModule>> {
     let m = PyModule::new_bound(py, "fastqx.sql")?;
     m.add_class::<Driver>()?;
     m.add_class::<ConnectorConfig>()?;
+    m.add_class::<ColumnInfo>()?;
     m.add_class::<PySqlConnector>()?;
 
     Ok(m)
@@ -51,6 +55,44 @@ fn module_http(py: Python<'_>) -> PyResult<Bound<PyModule>> {
     Ok(m)
 }
 
+// ================================================================================================
+// Exceptions
+// ================================================================================================
+
+fn add_exceptions(py: Python<'_>, m: &Bound<PyModule>) -> PyResult<()> {
+    use fastqx::error::exceptions::*;
+
+    m.add("FqxError", py.get_type_bound::<FqxError>())?;
+    m.add(
+        "FqxTypeMismatchError",
+        py.get_type_bound::<FqxTypeMismatchError>(),
+    )?;
+    m.add(
+        "FqxShapeMismatchError",
+        py.get_type_bound::<FqxShapeMismatchError>(),
+    )?;
+    m.add(
+        "FqxColumnNotFoundError",
+        py.get_type_bound::<FqxColumnNotFoundError>(),
+    )?;
+    m.add(
+        "FqxOutOfBoundsError",
+        py.get_type_bound::<FqxOutOfBoundsError>(),
+    )?;
+    m.add("FqxCastError", py.get_type_bound::<FqxCastError>())?;
+    m.add("FqxInvalidError", py.get_type_bound::<FqxInvalidError>())?;
+    m.add("FqxSqlError", py.get_type_bound::<FqxSqlError>())?;
+    m.add("FqxHttpError", py.get_type_bound::<FqxHttpError>())?;
+    m.add("FqxCsvError", py.get_type_bound::<FqxCsvError>())?;
+    m.add("FqxIoError", py.get_type_bound::<FqxIoError>())?;
+    m.add("FqxJsonError", py.get_type_bound::<FqxJsonError>())?;
+    m.add("FqxArrowError", py.get_type_bound::<FqxArrowError>())?;
+    m.add("FqxParquetError", py.get_type_bound::<FqxParquetError>())?;
+    m.add("FqxParseError", py.get_type_bound::<FqxParseError>())?;
+
+    Ok(())
+}
+
 // ================================================================================================
 // PyModule
 // ================================================================================================
@@ -59,13 +101,21 @@ fn module_http(py: Python<'_>) -> PyResult<Bound<PyModule>> {
 #[pyo3(name = "fastqx")]
 fn py_fastqx(py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     // main
-    m.add_class::<SaveMode>()?;
+    m.add_class::<PySaveMode>()?;
     m.add_class::<FqxValueType>()?;
     m.add_class::<FqxRow>()?;
     m.add_class::<PyData>()?;
     m.add_class::<PyGroup>()?;
     m.add_class::<PyGroupKey>()?;
     m.add_wrapped(wrap_pyfunction!(new_fqx_data))?;
+    m.add_class::<PyConnectionRegistry>()?;
+    m.add_class::<PyExpr>()?;
+    m.add_wrapped(wrap_pyfunction!(py_col))?;
+    m.add_wrapped(wrap_pyfunction!(py_lit))?;
+    m.add_wrapped(wrap_pyfunction!(py_sql_local))?;
+
+    // exceptions
+    add_exceptions(py, m)?;
 
     // submodule: sql
     let sql = module_sql(py)?;
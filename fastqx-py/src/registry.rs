@@ -0,0 +1,71 @@
+//! file: registry.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 11:48:20 Saturday
+//! brief:
+
+use std::sync::Arc;
+
+use fastqx::prelude::*;
+use pyo3::prelude::*;
+use tokio::runtime::Runtime;
+
+use crate::http::PyHttpConnector;
+use crate::sql::PySqlConnector;
+
+// ================================================================================================
+// Classes & Functions exported to Py
+// ================================================================================================
+
+/// Connectors handed out share the registry's runtime, which their pools are bound to.
+#[pyclass]
+#[pyo3(name = "FqxConnectionRegistry")]
+pub struct PyConnectionRegistry {
+    inner: ConnectionRegistry,
+    runtime: Arc<Runtime>,
+}
+
+impl PyConnectionRegistry {
+    fn from_inner(inner: ConnectionRegistry) -> PyResult<Self> {
+        let runtime = Arc::new(Runtime::new()?);
+
+        Ok(PyConnectionRegistry { inner, runtime })
+    }
+}
+
+#[pymethods]
+impl PyConnectionRegistry {
+    #[new]
+    fn new(path: &str) -> PyResult<Self> {
+        Self::from_inner(ConnectionRegistry::from_file(path)?)
+    }
+
+    #[staticmethod]
+    fn from_toml(s: &str) -> PyResult<Self> {
+        Self::from_inner(ConnectionRegistry::from_toml_str(s)?)
+    }
+
+    #[staticmethod]
+    fn from_json(s: &str) -> PyResult<Self> {
+        Self::from_inner(ConnectionRegistry::from_json_str(s)?)
+    }
+
+    fn sql_names(&self) -> Vec<&str> {
+        self.inner.sql_names()
+    }
+
+    fn http_names(&self) -> Vec<&str> {
+        self.inner.http_names()
+    }
+
+    fn sql(&self, name: &str) -> PyResult<PySqlConnector> {
+        let inner = self.runtime.block_on(self.inner.sql(name))?;
+
+        Ok(PySqlConnector::from_inner(inner, self.runtime.clone()))
+    }
+
+    fn http(&self, name: &str) -> PyResult<PyHttpConnector> {
+        let inner = self.inner.http(name)?;
+
+        Ok(PyHttpConnector::from_inner(inner, self.runtime.clone()))
+    }
+}
@@ -3,6 +3,9 @@
 //! date: 2023/11/12 12:39:49 Sunday
 //! brief:
 
+use std::sync::Arc;
+use std::time::Duration;
+
 use fastqx::prelude::*;
 use pyo3::prelude::*;
 use tokio::runtime::Runtime;
@@ -17,20 +20,74 @@ use crate::PyData;
 #[pyo3(name = "FqxSqlConnector", subclass)]
 pub struct PySqlConnector {
     inner: SqlConnector,
-    runtime: Runtime,
+    runtime: Arc<Runtime>,
+}
+
+impl PySqlConnector {
+    /// `runtime` must be the one `inner` was created on, since the sqlx pool is bound to it.
+    pub(crate) fn from_inner(inner: SqlConnector, runtime: Arc<Runtime>) -> Self {
+        PySqlConnector { inner, runtime }
+    }
 }
 
 #[pymethods]
 impl PySqlConnector {
+    /// Timeouts and backoff are in seconds.
     #[new]
-    fn new(conn_str: &str) -> PyResult<Self> {
+    #[pyo3(signature = (
+        conn_str,
+        *,
+        max_connections=None,
+        min_connections=None,
+        acquire_timeout=None,
+        idle_timeout=None,
+        statement_timeout=None,
+        connect_retries=0,
+        retry_backoff=1.0
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        conn_str: &str,
+        max_connections: Option<u32>,
+        min_connections: Option<u32>,
+        acquire_timeout: Option<f64>,
+        idle_timeout: Option<f64>,
+        statement_timeout: Option<f64>,
+        connect_retries: u32,
+        retry_backoff: f64,
+    ) -> PyResult<Self> {
+        let secs = |s: f64| Duration::try_from_secs_f64(s).map_err(FqxError::invalid);
+        let options = ConnectorOptions {
+            max_connections,
+            min_connections,
+            acquire_timeout: acquire_timeout.map(secs).transpose()?,
+            idle_timeout: idle_timeout.map(secs).transpose()?,
+            statement_timeout: statement_timeout.map(secs).transpose()?,
+            connect_retries,
+            retry_backoff: secs(retry_backoff)?,
+        };
+
         let runtime = Runtime::new()?;
+        let inner = runtime.block_on(SqlConnector::new_with_options(conn_str, &options))?;
 
-        let inner = runtime.block_on(async {
-            Ok::<_, anyhow::Error>(SqlConnector::new_by_str(conn_str).await?)
-        })?;
+        Ok(PySqlConnector::from_inner(inner, Arc::new(runtime)))
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (path, create_if_missing=true))]
+    fn sqlite_file(path: &str, create_if_missing: bool) -> PyResult<Self> {
+        let runtime = Runtime::new()?;
+        let inner = runtime.block_on(SqlConnector::sqlite_file(path, create_if_missing))?;
 
-        Ok(PySqlConnector { inner, runtime })
+        Ok(PySqlConnector::from_inner(inner, Arc::new(runtime)))
+    }
+
+    #[staticmethod]
+    fn sqlite_memory() -> PyResult<Self> {
+        let runtime = Runtime::new()?;
+        let inner = runtime.block_on(SqlConnector::sqlite_memory())?;
+
+        Ok(PySqlConnector::from_inner(inner, Arc::new(runtime)))
     }
 
     fn conn_str(&self) -> &str {
@@ -41,7 +98,7 @@ impl PySqlConnector {
         let res = self.runtime.block_on(async {
             self.inner.close().await?;
 
-            Ok::<_, anyhow::Error>(())
+            Ok::<_, FqxError>(())
         });
         res?;
 
@@ -52,43 +109,66 @@ impl PySqlConnector {
         self.inner.is_close()
     }
 
-    fn execute(&self, sql: &str) -> PyResult<()> {
+    #[pyo3(signature = (sql, params=None))]
+    fn execute(&self, sql: &str, params: Option<Vec<FqxValue>>) -> PyResult<()> {
         let conn = self.inner.clone();
 
-        let rt = tokio::runtime::Runtime::new()?;
-        rt.block_on(async move {
-            conn.execute(sql).await?;
+        // pooled connections are returned by a task spawned onto the current runtime, a temporary
+        // runtime would drop them (and with them, an in-memory Sqlite database)
+        self.runtime.block_on(async move {
+            match params {
+                Some(p) => conn.execute_with(sql, &p).await?,
+                None => conn.execute(sql).await?,
+            };
 
-            Ok::<_, anyhow::Error>(())
+            Ok::<_, FqxError>(())
         })?;
 
         Ok(())
     }
 
-    pub fn fetch(&self, sql: &str) -> PyResult<PyData> {
+    #[pyo3(signature = (sql, params=None))]
+    pub fn fetch(&self, sql: &str, params: Option<Vec<FqxValue>>) -> PyResult<PyData> {
         let res = self.runtime.block_on(async {
-            let d = self.inner.dyn_fetch(sql).await?;
+            let d = match params {
+                Some(p) => self.inner.dyn_fetch_with(sql, &p).await?,
+                None => self.inner.dyn_fetch(sql).await?,
+            };
 
-            Ok::<_, anyhow::Error>(d)
+            Ok::<_, FqxError>(d)
         })?;
 
         Ok(PyData::from(res))
     }
 
+    fn list_schemas(&self) -> PyResult<Vec<String>> {
+        Ok(self.runtime.block_on(self.inner.list_schemas())?)
+    }
+
+    fn list_tables(&self) -> PyResult<Vec<String>> {
+        Ok(self.runtime.block_on(self.inner.list_tables())?)
+    }
+
+    fn describe_table(&self, table_name: &str) -> PyResult<Vec<ColumnInfo>> {
+        Ok(self
+            .runtime
+            .block_on(self.inner.describe_table(table_name))?)
+    }
+
     pub fn save(
         &self,
         py: Python<'_>,
         data: &PyData,
         table_name: &str,
-        mode: SaveMode,
+        mode: PySaveMode,
     ) -> PyResult<()> {
         let conn = self.inner.clone();
 
         self.runtime.block_on(async move {
-            conn.dyn_save(data.inner.borrow(py).clone(), table_name, mode, true)
+            conn.dyn_save(data.inner.borrow(py).clone(), table_name, mode.into(), true)
                 .await?;
 
-            Ok::<_, anyhow::Error>(())
+            Ok::<_, FqxError>(())
         })?;
 
         Ok(())
@@ -99,15 +179,20 @@ impl PySqlConnector {
         py: Python<'_>,
         data: &PyData,
         table_name: &str,
-        mode: SaveMode,
+        mode: PySaveMode,
     ) -> PyResult<()> {
         let conn = self.inner.clone();
 
         self.runtime.block_on(async move {
-            conn.dyn_save(data.inner.borrow(py).clone(), table_name, mode, false)
-                .await?;
-
-            Ok::<_, anyhow::Error>(())
+            conn.dyn_save(
+                data.inner.borrow(py).clone(),
+                table_name,
+                mode.into(),
+                false,
+            )
+            .await?;
+
+            Ok::<_, FqxError>(())
         })?;
 
         Ok(())
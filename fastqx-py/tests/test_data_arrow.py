@@ -0,0 +1,49 @@
+# @file:	test_data_arrow.py
+# @author:	Jacob Xie
+# @date:	2026/10/17 15:20:41 Saturday
+# @brief:
+
+import datetime as dt
+
+from fastqx import FqxData, FqxValueType, new_fqx_data
+
+data = new_fqx_data(
+    [[1, "x", 2.3, dt.date(2024, 1, 2)], [2, None, None, dt.date(2024, 1, 3)]],
+    ["c1", "c2", "c3", "c4"],
+)
+rows = [[1, "x", 2.3, dt.date(2024, 1, 2)], [2, None, None, dt.date(2024, 1, 3)]]
+
+# round trip through the Arrow PyCapsule interface
+data2 = FqxData.from_arrow(data)
+print(data2)
+assert data2.columns == ["c1", "c2", "c3", "c4"]
+assert data2.types == [
+    FqxValueType.I64,
+    FqxValueType.String,
+    FqxValueType.F64,
+    FqxValueType.Date,
+]
+assert data2.types == data.types
+assert data2.to_list() == rows
+
+# hand over to pyarrow / polars, if installed
+try:
+    import pyarrow as pa
+
+    table = data.to_arrow()
+    assert table.schema.names == ["c1", "c2", "c3", "c4"]
+    assert table.schema.types == [pa.int64(), pa.string(), pa.float64(), pa.date32()]
+    assert [list(r.values()) for r in table.to_pylist()] == rows
+    assert FqxData.from_arrow(table).to_list() == rows
+except ImportError:
+    pass
+
+try:
+    import polars as pl
+
+    df = data.to_polars()
+    assert df.columns == ["c1", "c2", "c3", "c4"]
+    assert df.dtypes == [pl.Int64, pl.String, pl.Float64, pl.Date]
+    assert [list(r) for r in df.rows()] == rows
+except ImportError:
+    pass
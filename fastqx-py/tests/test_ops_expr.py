@@ -0,0 +1,56 @@
+# @file:	test_ops_expr.py
+# @author:	Jacob Xie
+# @date:	2026/10/17 21:12:40 Saturday
+# @brief:
+
+from fastqx import new_fqx_data, col, lit, FqxValueType, FqxTypeMismatchError
+
+data = new_fqx_data(
+    columns=["c1", "c2", "c3"],
+    data=[[1, "x", 2.5], [2, "y", 3.0], [3, "z", None], [4, "a", 1.5]],
+)
+
+# filter_expr: a null predicate does not hold
+res = data.filter_expr((col("c1") > 1) & (col("c3") < 3.5))
+assert res.to_list() == [[2, "y", 3.0], [4, "a", 1.5]]
+
+# with_column: appends, then replaces
+res = data.with_column((col("c1") * 2).alias("double"))
+assert res.columns == ["c1", "c2", "c3", "double"]
+assert [r[3] for r in res.to_list()] == [2, 4, 6, 8]
+
+res = res.with_column(col("double").cast(FqxValueType.String).alias("double"))
+assert res.to_list()[0][3] == "2"
+
+# `/` is a true division, `//` keeps the integer type
+res = data.select_exprs(
+    [
+        (col("c1") / 2).alias("div"),
+        (col("c1") // 2).alias("floordiv"),
+        (10 / col("c1")).alias("rdiv"),
+        (col("c1") % 2 == 0).alias("even"),
+    ]
+)
+assert res.to_list()[0] == [0.5, 0, 10.0, False]
+assert res.to_list()[3] == [2.0, 2, 2.5, True]
+
+# `//` rounds towards negative infinity, also on floats
+res = data.select_exprs([(col("c3") // 2).alias("f"), (-7 // col("c1")).alias("r")])
+assert res.to_list()[0] == [1.0, -7]
+assert res.to_list()[1] == [1.0, -4]
+assert res.to_list()[3] == [0.0, -2]
+
+# integer division by zero, also through a float literal cast to int, is null
+res = data.select_exprs([(col("c1") // 0).alias("a"), (col("c1") % 0.5).alias("b")])
+assert res.to_list()[0] == [None, None]
+
+# null propagates through arithmetic
+res = data.select_exprs([col("c3") + lit(1)])
+assert res.to_list()[2] == [None]
+
+# arithmetic on strings is a type mismatch
+try:
+    data.select_exprs([col("c2") + 1])
+    assert False
+except FqxTypeMismatchError:
+    pass
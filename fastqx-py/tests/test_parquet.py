@@ -0,0 +1,16 @@
+# @file:	test_parquet.py
+# @author:	Jacob Xie
+# @date:	2026/10/17 16:12:40 Saturday
+# @brief:
+
+from fastqx import FqxData
+
+original = FqxData.from_list([[1, "a", 1.5], [2, None, None], [3, "c", 3.5]])
+original.to_parquet("temp.parquet", "zstd(3)")
+
+data = FqxData.from_parquet("temp.parquet")
+print(data)
+print(data.types)
+
+data = FqxData.from_parquet("temp.parquet", ["col_2", "col_0"])
+print(data.to_str_pretty())
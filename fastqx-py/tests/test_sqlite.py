@@ -0,0 +1,37 @@
+# @file:	test_sqlite.py
+# @author:	Jacob Xie
+# @date:	2026/10/17 21:02:15 Saturday
+# @brief:
+
+from fastqx import new_fqx_data, FqxSaveMode, FqxData
+from fastqx.sql import FqxSqlConnector
+
+# no server required
+connector = FqxSqlConnector.sqlite_memory()
+
+data = new_fqx_data(
+    columns=["c1", "c2", "c3"],
+    # vector of row
+    data=[[1, "x", 2.3], [2, "y", 3.1], [3, "z", None]],
+)
+
+###################################################################################################
+# method #1: using `connector`
+
+print("save table...")
+connector.save(data, "tmp_table2", FqxSaveMode.Override)
+print("save complete")
+
+res = connector.fetch("select * from tmp_table2")
+
+print("res: \n", res.to_str_pretty())
+
+###################################################################################################
+# method #2: using `.to_sql` & `.from_sql`
+
+data.to_sql("tmp_table3", connector, FqxSaveMode.Override)
+
+res = FqxData.from_sql("select * from tmp_table3", connector)
+
+print("res: \n", res.to_str_pretty())
+print("tables: ", connector.list_tables())
@@ -7,21 +7,34 @@ use std::collections::HashSet;
 use std::fmt::Debug;
 use std::hash::Hash;
 
-use anyhow::{bail, Result};
-
 use crate::adt::ab::s::{F, R, RF, RI, RT, RTI, S, VS};
-use crate::adt::{FqxData, FqxValueType, RowProps, SeqAppend, SeqSlice};
+use crate::adt::{FqxData, FqxValue, FqxValueType, RowProps, SeqAppend, SeqSlice};
+use crate::error::{FqxError, FqxResult};
 
 ///////////////////////////////////////////////////////////////////////////////////////////////////
 
 macro_rules! guard {
     ($s:expr, $r:expr) => {
         if !$s.check_row_validation(&$r) {
-            bail!("row mismatch")
+            return Err(_row_mismatch($s.columns(), $s.types(), $r.values()));
         }
     };
 }
 
+fn _row_mismatch(columns: &[String], types: &[FqxValueType], values: &[FqxValue]) -> FqxError {
+    if columns.len() != values.len() {
+        return FqxError::shape_mismatch("row length", columns.len(), values.len());
+    }
+
+    columns
+        .iter()
+        .zip(types)
+        .zip(values)
+        .find(|((_, t), v)| !v.is_null() && !v.is_type(t))
+        .map(|((c, t), v)| FqxError::type_mismatch(t.clone(), v.into()).at_column(c))
+        .unwrap_or_else(|| FqxError::invalid("row mismatch"))
+}
+
 // ================================================================================================
 // FqxR
 // ================================================================================================
@@ -45,7 +58,7 @@ pub trait FqxD: Sized {
 
     fn columns_mut(&mut self) -> &mut [String];
 
-    fn set_columns(&mut self, cols: Self::ColumnsT) -> Result<()>;
+    fn set_columns(&mut self, cols: Self::ColumnsT) -> FqxResult<()>;
 
     fn columns_take(self) -> Self::ColumnsT;
 
@@ -53,7 +66,7 @@ pub trait FqxD: Sized {
 
     fn types_mut(&mut self) -> &mut [FqxValueType];
 
-    fn set_types(&mut self, types: Self::TypesT) -> Result<()>;
+    fn set_types(&mut self, types: Self::TypesT) -> FqxResult<()>;
 
     fn types_take(self) -> Self::TypesT;
 
@@ -61,7 +74,7 @@ pub trait FqxD: Sized {
 
     fn data_mut(&mut self) -> &mut Vec<Self::RowT>;
 
-    fn set_data(&mut self, data: Vec<Self::RowT>) -> Result<()>;
+    fn set_data(&mut self, data: Vec<Self::RowT>) -> FqxResult<()>;
 
     fn data_take(self) -> Vec<Self::RowT>;
 
@@ -89,7 +102,7 @@ pub trait FqxD: Sized {
         (self.height(), self.width())
     }
 
-    fn push(&mut self, row: Self::RowT) -> Result<()> {
+    fn push(&mut self, row: Self::RowT) -> FqxResult<()> {
         guard!(self, row);
 
         self.data_mut().push(row);
@@ -97,7 +110,7 @@ pub trait FqxD: Sized {
         Ok(())
     }
 
-    fn extend<I>(&mut self, rows: I) -> Result<()>
+    fn extend<I>(&mut self, rows: I) -> FqxResult<()>
     where
         I: IntoIterator<Item = Self::RowT>,
         for<'a> &'a I: IntoIterator<Item = &'a Self::RowT>,
@@ -111,7 +124,7 @@ pub trait FqxD: Sized {
         Ok(())
     }
 
-    fn insert(&mut self, idx: usize, row: Self::RowT) -> Result<()> {
+    fn insert(&mut self, idx: usize, row: Self::RowT) -> FqxResult<()> {
         guard!(self, row);
 
         if idx > self.height() {
@@ -0,0 +1,129 @@
+//! file: bitmap.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 10:12:33 Saturday
+//! brief:
+
+// ================================================================================================
+// FqxBitmap
+//
+// A packed bitmap, used as the validity mask of a column: `true` stands for a valid value and
+// `false` for a null.
+// ================================================================================================
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FqxBitmap {
+    bits: Vec<u64>,
+    len: usize,
+}
+
+impl FqxBitmap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            bits: Vec::with_capacity(capacity.div_ceil(64)),
+            len: 0,
+        }
+    }
+
+    pub fn new_filled(len: usize, value: bool) -> Self {
+        let fill = if value { u64::MAX } else { 0 };
+        let mut bits = vec![fill; len.div_ceil(64)];
+
+        // keep the unused tail bits cleared, so that `count_ones` stays correct
+        if value && len % 64 != 0 {
+            if let Some(last) = bits.last_mut() {
+                *last = (1u64 << (len % 64)) - 1;
+            }
+        }
+
+        Self { bits, len }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push(&mut self, value: bool) {
+        if self.len % 64 == 0 {
+            self.bits.push(0);
+        }
+        if value {
+            self.bits[self.len / 64] |= 1 << (self.len % 64);
+        }
+        self.len += 1;
+    }
+
+    pub fn get(&self, idx: usize) -> Option<bool> {
+        if idx >= self.len {
+            return None;
+        }
+
+        Some(self.bits[idx / 64] & (1 << (idx % 64)) != 0)
+    }
+
+    pub fn set(&mut self, idx: usize, value: bool) {
+        if idx >= self.len {
+            return;
+        }
+
+        if value {
+            self.bits[idx / 64] |= 1 << (idx % 64);
+        } else {
+            self.bits[idx / 64] &= !(1 << (idx % 64));
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    pub fn count_zeros(&self) -> usize {
+        self.len - self.count_ones()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(|i| self.bits[i / 64] & (1 << (i % 64)) != 0)
+    }
+}
+
+impl FromIterator<bool> for FqxBitmap {
+    fn from_iter<T: IntoIterator<Item = bool>>(iter: T) -> Self {
+        let mut res = Self::new();
+        iter.into_iter().for_each(|b| res.push(b));
+        res
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_bitmap {
+    use super::*;
+
+    #[test]
+    fn bitmap_success() {
+        let mut bm = FqxBitmap::new_filled(70, true);
+        bm.set(3, false);
+        bm.push(false);
+        bm.push(true);
+
+        assert_eq!(bm.len(), 72);
+        assert_eq!(bm.get(3), Some(false));
+        assert_eq!(bm.get(70), Some(false));
+        assert_eq!(bm.get(71), Some(true));
+        assert_eq!(bm.get(72), None);
+        assert_eq!(bm.count_zeros(), 2);
+
+        let bm2 = bm.iter().collect::<FqxBitmap>();
+        assert_eq!(bm, bm2);
+    }
+}
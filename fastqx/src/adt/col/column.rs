@@ -0,0 +1,513 @@
+//! file: column.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 10:26:08 Saturday
+//! brief:
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use rust_decimal::Decimal;
+
+use super::bitmap::FqxBitmap;
+use crate::adt::{FqxValue, FqxValueType};
+use crate::error::{FqxError, FqxResult};
+
+// ================================================================================================
+// FqxColumnValues
+//
+// Typed storage of a column. Null slots are filled by the type's default value, and are tracked
+// by the validity bitmap of `FqxColumn`.
+// ================================================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FqxColumnValues {
+    Bool(Vec<bool>),
+    U8(Vec<u8>),
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+    U64(Vec<u64>),
+    I8(Vec<i8>),
+    I16(Vec<i16>),
+    I32(Vec<i32>),
+    I64(Vec<i64>),
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+    Decimal(Vec<Decimal>),
+    String(Vec<String>),
+    Blob(Vec<Vec<u8>>),
+    Timestamp(Vec<DateTime<Local>>),
+    DateTime(Vec<NaiveDateTime>),
+    Date(Vec<NaiveDate>),
+    Time(Vec<NaiveTime>),
+    Null(usize),
+}
+
+// apply the same expression to every typed vector, `Null` excluded
+macro_rules! dispatch {
+    ($s:expr, $v:ident => $e:expr, $n:ident => $ne:expr) => {
+        match $s {
+            FqxColumnValues::Bool($v) => $e,
+            FqxColumnValues::U8($v) => $e,
+            FqxColumnValues::U16($v) => $e,
+            FqxColumnValues::U32($v) => $e,
+            FqxColumnValues::U64($v) => $e,
+            FqxColumnValues::I8($v) => $e,
+            FqxColumnValues::I16($v) => $e,
+            FqxColumnValues::I32($v) => $e,
+            FqxColumnValues::I64($v) => $e,
+            FqxColumnValues::F32($v) => $e,
+            FqxColumnValues::F64($v) => $e,
+            FqxColumnValues::Decimal($v) => $e,
+            FqxColumnValues::String($v) => $e,
+            FqxColumnValues::Blob($v) => $e,
+            FqxColumnValues::Timestamp($v) => $e,
+            FqxColumnValues::DateTime($v) => $e,
+            FqxColumnValues::Date($v) => $e,
+            FqxColumnValues::Time($v) => $e,
+            FqxColumnValues::Null($n) => $ne,
+        }
+    };
+}
+
+impl FqxColumnValues {
+    pub fn with_capacity(typ: &FqxValueType, capacity: usize) -> Self {
+        match typ {
+            FqxValueType::Bool => Self::Bool(Vec::with_capacity(capacity)),
+            FqxValueType::U8 => Self::U8(Vec::with_capacity(capacity)),
+            FqxValueType::U16 => Self::U16(Vec::with_capacity(capacity)),
+            FqxValueType::U32 => Self::U32(Vec::with_capacity(capacity)),
+            FqxValueType::U64 => Self::U64(Vec::with_capacity(capacity)),
+            FqxValueType::I8 => Self::I8(Vec::with_capacity(capacity)),
+            FqxValueType::I16 => Self::I16(Vec::with_capacity(capacity)),
+            FqxValueType::I32 => Self::I32(Vec::with_capacity(capacity)),
+            FqxValueType::I64 => Self::I64(Vec::with_capacity(capacity)),
+            FqxValueType::F32 => Self::F32(Vec::with_capacity(capacity)),
+            FqxValueType::F64 => Self::F64(Vec::with_capacity(capacity)),
+            FqxValueType::Decimal => Self::Decimal(Vec::with_capacity(capacity)),
+            FqxValueType::String => Self::String(Vec::with_capacity(capacity)),
+            FqxValueType::Blob => Self::Blob(Vec::with_capacity(capacity)),
+            FqxValueType::Timestamp => Self::Timestamp(Vec::with_capacity(capacity)),
+            FqxValueType::DateTime => Self::DateTime(Vec::with_capacity(capacity)),
+            FqxValueType::Date => Self::Date(Vec::with_capacity(capacity)),
+            FqxValueType::Time => Self::Time(Vec::with_capacity(capacity)),
+            FqxValueType::Null => Self::Null(0),
+        }
+    }
+
+    pub fn typ(&self) -> FqxValueType {
+        match self {
+            FqxColumnValues::Bool(_) => FqxValueType::Bool,
+            FqxColumnValues::U8(_) => FqxValueType::U8,
+            FqxColumnValues::U16(_) => FqxValueType::U16,
+            FqxColumnValues::U32(_) => FqxValueType::U32,
+            FqxColumnValues::U64(_) => FqxValueType::U64,
+            FqxColumnValues::I8(_) => FqxValueType::I8,
+            FqxColumnValues::I16(_) => FqxValueType::I16,
+            FqxColumnValues::I32(_) => FqxValueType::I32,
+            FqxColumnValues::I64(_) => FqxValueType::I64,
+            FqxColumnValues::F32(_) => FqxValueType::F32,
+            FqxColumnValues::F64(_) => FqxValueType::F64,
+            FqxColumnValues::Decimal(_) => FqxValueType::Decimal,
+            FqxColumnValues::String(_) => FqxValueType::String,
+            FqxColumnValues::Blob(_) => FqxValueType::Blob,
+            FqxColumnValues::Timestamp(_) => FqxValueType::Timestamp,
+            FqxColumnValues::DateTime(_) => FqxValueType::DateTime,
+            FqxColumnValues::Date(_) => FqxValueType::Date,
+            FqxColumnValues::Time(_) => FqxValueType::Time,
+            FqxColumnValues::Null(_) => FqxValueType::Null,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        dispatch!(self, v => v.len(), n => *n)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push_default(&mut self) {
+        dispatch!(self, v => v.push(Default::default()), n => *n += 1)
+    }
+
+    fn get(&self, idx: usize) -> FqxValue {
+        match self {
+            FqxColumnValues::Bool(v) => FqxValue::Bool(v[idx]),
+            FqxColumnValues::U8(v) => FqxValue::U8(v[idx]),
+            FqxColumnValues::U16(v) => FqxValue::U16(v[idx]),
+            FqxColumnValues::U32(v) => FqxValue::U32(v[idx]),
+            FqxColumnValues::U64(v) => FqxValue::U64(v[idx]),
+            FqxColumnValues::I8(v) => FqxValue::I8(v[idx]),
+            FqxColumnValues::I16(v) => FqxValue::I16(v[idx]),
+            FqxColumnValues::I32(v) => FqxValue::I32(v[idx]),
+            FqxColumnValues::I64(v) => FqxValue::I64(v[idx]),
+            FqxColumnValues::F32(v) => FqxValue::F32(v[idx]),
+            FqxColumnValues::F64(v) => FqxValue::F64(v[idx]),
+            FqxColumnValues::Decimal(v) => FqxValue::Decimal(v[idx]),
+            FqxColumnValues::String(v) => FqxValue::String(v[idx].clone()),
+            FqxColumnValues::Blob(v) => FqxValue::Blob(v[idx].clone()),
+            FqxColumnValues::Timestamp(v) => FqxValue::Timestamp(v[idx]),
+            FqxColumnValues::DateTime(v) => FqxValue::DateTime(v[idx]),
+            FqxColumnValues::Date(v) => FqxValue::Date(v[idx]),
+            FqxColumnValues::Time(v) => FqxValue::Time(v[idx]),
+            FqxColumnValues::Null(_) => FqxValue::Null,
+        }
+    }
+}
+
+// ================================================================================================
+// FqxColumn
+// ================================================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FqxColumn {
+    values: FqxColumnValues,
+    validity: FqxBitmap,
+}
+
+impl FqxColumn {
+    pub fn new_empty(typ: &FqxValueType) -> Self {
+        Self::with_capacity(typ, 0)
+    }
+
+    pub fn with_capacity(typ: &FqxValueType, capacity: usize) -> Self {
+        Self {
+            values: FqxColumnValues::with_capacity(typ, capacity),
+            validity: FqxBitmap::with_capacity(capacity),
+        }
+    }
+
+    pub fn from_values<I>(typ: &FqxValueType, values: I) -> FqxResult<Self>
+    where
+        I: IntoIterator<Item = FqxValue>,
+    {
+        let iter = values.into_iter();
+        let mut res = Self::with_capacity(typ, iter.size_hint().0);
+        for (idx, v) in iter.enumerate() {
+            res.push(v).map_err(|e| e.at_row(idx))?;
+        }
+        Ok(res)
+    }
+
+    pub fn typ(&self) -> FqxValueType {
+        self.values.typ()
+    }
+
+    pub fn values(&self) -> &FqxColumnValues {
+        &self.values
+    }
+
+    pub fn validity(&self) -> &FqxBitmap {
+        &self.validity
+    }
+
+    pub fn len(&self) -> usize {
+        self.validity.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.validity.is_empty()
+    }
+
+    pub fn null_count(&self) -> usize {
+        self.validity.count_zeros()
+    }
+
+    pub fn is_null(&self, idx: usize) -> bool {
+        !self.validity.get(idx).unwrap_or(false)
+    }
+
+    pub fn push_null(&mut self) {
+        self.values.push_default();
+        self.validity.push(false);
+    }
+
+    /// Push a value into the column, casting it into the column's type if needed.
+    pub fn push(&mut self, value: FqxValue) -> FqxResult<()> {
+        if value.is_null() {
+            self.push_null();
+            return Ok(());
+        }
+
+        let (expected, actual) = (self.typ(), FqxValueType::from(&value));
+        let value = value
+            .try_cast(&expected)
+            .map_err(|_| FqxError::type_mismatch(expected.clone(), actual.clone()))?;
+
+        match (&mut self.values, value) {
+            (FqxColumnValues::Bool(c), FqxValue::Bool(v)) => c.push(v),
+            (FqxColumnValues::U8(c), FqxValue::U8(v)) => c.push(v),
+            (FqxColumnValues::U16(c), FqxValue::U16(v)) => c.push(v),
+            (FqxColumnValues::U32(c), FqxValue::U32(v)) => c.push(v),
+            (FqxColumnValues::U64(c), FqxValue::U64(v)) => c.push(v),
+            (FqxColumnValues::I8(c), FqxValue::I8(v)) => c.push(v),
+            (FqxColumnValues::I16(c), FqxValue::I16(v)) => c.push(v),
+            (FqxColumnValues::I32(c), FqxValue::I32(v)) => c.push(v),
+            (FqxColumnValues::I64(c), FqxValue::I64(v)) => c.push(v),
+            (FqxColumnValues::F32(c), FqxValue::F32(v)) => c.push(v),
+            (FqxColumnValues::F64(c), FqxValue::F64(v)) => c.push(v),
+            (FqxColumnValues::Decimal(c), FqxValue::Decimal(v)) => c.push(v),
+            (FqxColumnValues::String(c), FqxValue::String(v)) => c.push(v),
+            (FqxColumnValues::Blob(c), FqxValue::Blob(v)) => c.push(v),
+            (FqxColumnValues::Timestamp(c), FqxValue::Timestamp(v)) => c.push(v),
+            (FqxColumnValues::DateTime(c), FqxValue::DateTime(v)) => c.push(v),
+            (FqxColumnValues::Date(c), FqxValue::Date(v)) => c.push(v),
+            (FqxColumnValues::Time(c), FqxValue::Time(v)) => c.push(v),
+            _ => return Err(FqxError::type_mismatch(expected, actual)),
+        }
+
+        self.validity.push(true);
+
+        Ok(())
+    }
+
+    pub fn get(&self, idx: usize) -> Option<FqxValue> {
+        match self.validity.get(idx)? {
+            true => Some(self.values.get(idx)),
+            false => Some(FqxValue::Null),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = FqxValue> + '_ {
+        self.validity.iter().enumerate().map(|(i, b)| match b {
+            true => self.values.get(i),
+            false => FqxValue::Null,
+        })
+    }
+}
+
+// ================================================================================================
+// Aggregation kernels
+//
+// Work on the typed vectors directly, and null slots are skipped. `FqxValue::Null` is returned
+// when there is no valid value, when the operation doesn't apply to the column's type, or when an
+// integer or decimal sum overflows.
+// ================================================================================================
+
+trait _Sum: Copy {
+    fn sum_with(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_sum {
+    ($($t:ty),*) => {
+        $(
+            impl _Sum for $t {
+                fn sum_with(self, rhs: Self) -> Option<Self> {
+                    self.checked_add(rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_sum!(u8, u16, u32, u64, i8, i16, i32, i64, Decimal);
+
+impl _Sum for f32 {
+    fn sum_with(self, rhs: Self) -> Option<Self> {
+        Some(self + rhs)
+    }
+}
+
+impl _Sum for f64 {
+    fn sum_with(self, rhs: Self) -> Option<Self> {
+        Some(self + rhs)
+    }
+}
+
+fn _sum<'a, T: _Sum + 'a, I: Iterator<Item = &'a T>>(mut iter: I) -> Option<T> {
+    let ini = *iter.next()?;
+    iter.try_fold(ini, |acc, e| acc.sum_with(*e))
+}
+
+fn _valid<'a, T>(v: &'a [T], validity: &'a FqxBitmap) -> impl Iterator<Item = &'a T> + 'a {
+    v.iter()
+        .zip(validity.iter())
+        .filter_map(|(e, b)| if b { Some(e) } else { None })
+}
+
+fn _min<'a, T: PartialOrd + 'a, I: Iterator<Item = &'a T>>(iter: I) -> Option<&'a T> {
+    iter.fold(None, |acc, e| match acc {
+        Some(a) if a <= e => Some(a),
+        _ => Some(e),
+    })
+}
+
+fn _max<'a, T: PartialOrd + 'a, I: Iterator<Item = &'a T>>(iter: I) -> Option<&'a T> {
+    iter.fold(None, |acc, e| match acc {
+        Some(a) if a >= e => Some(a),
+        _ => Some(e),
+    })
+}
+
+macro_rules! numeric_kernel {
+    ($s:expr, $v:ident => $e:expr) => {
+        match &$s.values {
+            FqxColumnValues::U8($v) => $e.map(FqxValue::U8),
+            FqxColumnValues::U16($v) => $e.map(FqxValue::U16),
+            FqxColumnValues::U32($v) => $e.map(FqxValue::U32),
+            FqxColumnValues::U64($v) => $e.map(FqxValue::U64),
+            FqxColumnValues::I8($v) => $e.map(FqxValue::I8),
+            FqxColumnValues::I16($v) => $e.map(FqxValue::I16),
+            FqxColumnValues::I32($v) => $e.map(FqxValue::I32),
+            FqxColumnValues::I64($v) => $e.map(FqxValue::I64),
+            FqxColumnValues::F32($v) => $e.map(FqxValue::F32),
+            FqxColumnValues::F64($v) => $e.map(FqxValue::F64),
+            FqxColumnValues::Decimal($v) => $e.map(FqxValue::Decimal),
+            _ => None,
+        }
+        .unwrap_or(FqxValue::Null)
+    };
+}
+
+macro_rules! ordering_kernel {
+    ($s:expr, $f:ident) => {
+        match &$s.values {
+            FqxColumnValues::Bool(v) => $f(_valid(v, &$s.validity)).cloned().map(FqxValue::Bool),
+            FqxColumnValues::U8(v) => $f(_valid(v, &$s.validity)).cloned().map(FqxValue::U8),
+            FqxColumnValues::U16(v) => $f(_valid(v, &$s.validity)).cloned().map(FqxValue::U16),
+            FqxColumnValues::U32(v) => $f(_valid(v, &$s.validity)).cloned().map(FqxValue::U32),
+            FqxColumnValues::U64(v) => $f(_valid(v, &$s.validity)).cloned().map(FqxValue::U64),
+            FqxColumnValues::I8(v) => $f(_valid(v, &$s.validity)).cloned().map(FqxValue::I8),
+            FqxColumnValues::I16(v) => $f(_valid(v, &$s.validity)).cloned().map(FqxValue::I16),
+            FqxColumnValues::I32(v) => $f(_valid(v, &$s.validity)).cloned().map(FqxValue::I32),
+            FqxColumnValues::I64(v) => $f(_valid(v, &$s.validity)).cloned().map(FqxValue::I64),
+            FqxColumnValues::F32(v) => $f(_valid(v, &$s.validity)).cloned().map(FqxValue::F32),
+            FqxColumnValues::F64(v) => $f(_valid(v, &$s.validity)).cloned().map(FqxValue::F64),
+            FqxColumnValues::Decimal(v) => {
+                $f(_valid(v, &$s.validity)).cloned().map(FqxValue::Decimal)
+            }
+            FqxColumnValues::String(v) => {
+                $f(_valid(v, &$s.validity)).cloned().map(FqxValue::String)
+            }
+            FqxColumnValues::Blob(v) => $f(_valid(v, &$s.validity)).cloned().map(FqxValue::Blob),
+            FqxColumnValues::Timestamp(v) => $f(_valid(v, &$s.validity))
+                .cloned()
+                .map(FqxValue::Timestamp),
+            FqxColumnValues::DateTime(v) => {
+                $f(_valid(v, &$s.validity)).cloned().map(FqxValue::DateTime)
+            }
+            FqxColumnValues::Date(v) => $f(_valid(v, &$s.validity)).cloned().map(FqxValue::Date),
+            FqxColumnValues::Time(v) => $f(_valid(v, &$s.validity)).cloned().map(FqxValue::Time),
+            FqxColumnValues::Null(_) => None,
+        }
+        .unwrap_or(FqxValue::Null)
+    };
+}
+
+impl FqxColumn {
+    pub fn sum(&self) -> FqxValue {
+        numeric_kernel!(self, v => _sum(_valid(v, &self.validity)))
+    }
+
+    pub fn min(&self) -> FqxValue {
+        ordering_kernel!(self, _min)
+    }
+
+    pub fn max(&self) -> FqxValue {
+        ordering_kernel!(self, _max)
+    }
+
+    /// Mean of the valid values, as `F64`; a `Decimal` column keeps its precision.
+    pub fn mean(&self) -> FqxValue {
+        let count = self.len() - self.null_count();
+        if count == 0 {
+            return FqxValue::Null;
+        }
+
+        match &self.values {
+            FqxColumnValues::Decimal(v) => _sum(_valid(v, &self.validity))
+                .and_then(|s| s.checked_div(Decimal::from(count)))
+                .map_or(FqxValue::Null, FqxValue::Decimal),
+            _ => match self.sum().try_cast(&FqxValueType::F64) {
+                Ok(FqxValue::F64(s)) => FqxValue::F64(s / count as f64),
+                _ => FqxValue::Null,
+            },
+        }
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_column {
+    use super::*;
+
+    #[test]
+    fn column_push_and_agg_success() {
+        let col = FqxColumn::from_values(
+            &FqxValueType::I64,
+            vec![
+                FqxValue::I64(3),
+                FqxValue::Null,
+                FqxValue::I32(-1),
+                FqxValue::Null,
+                FqxValue::I64(10),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(col.len(), 5);
+        assert_eq!(col.null_count(), 2);
+        assert_eq!(col.get(2), Some(FqxValue::I64(-1)));
+        assert_eq!(col.get(3), Some(FqxValue::Null));
+        assert_eq!(col.get(5), None);
+
+        assert_eq!(col.sum(), FqxValue::I64(12));
+        assert_eq!(col.min(), FqxValue::I64(-1));
+        assert_eq!(col.max(), FqxValue::I64(10));
+        assert_eq!(col.mean(), FqxValue::F64(4.0));
+
+        let col = FqxColumn::from_values(&FqxValueType::String, vec![FqxValue::Null]).unwrap();
+        assert_eq!(col.sum(), FqxValue::Null);
+        assert_eq!(col.max(), FqxValue::Null);
+    }
+
+    #[test]
+    fn column_agg_overflow_success() {
+        let col = FqxColumn::from_values(
+            &FqxValueType::I8,
+            vec![FqxValue::I8(100), FqxValue::I8(100)],
+        )
+        .unwrap();
+        assert_eq!(col.sum(), FqxValue::Null);
+        assert_eq!(col.mean(), FqxValue::Null);
+
+        let col = FqxColumn::from_values(
+            &FqxValueType::Decimal,
+            vec![
+                FqxValue::Decimal(Decimal::MAX),
+                FqxValue::Decimal(Decimal::ONE),
+            ],
+        )
+        .unwrap();
+        assert_eq!(col.sum(), FqxValue::Null);
+        assert_eq!(col.mean(), FqxValue::Null);
+    }
+
+    #[test]
+    fn column_push_fail() {
+        let mut col = FqxColumn::new_empty(&FqxValueType::I64);
+        col.push(FqxValue::I64(1)).unwrap();
+
+        let res = col.push(FqxValue::String("x".to_string()));
+        assert!(matches!(
+            res,
+            Err(FqxError::TypeMismatch {
+                expected: FqxValueType::I64,
+                actual: FqxValueType::String,
+                ..
+            })
+        ));
+        // nothing is pushed on failure
+        assert_eq!(col.len(), 1);
+
+        let res = FqxColumn::from_values(
+            &FqxValueType::I64,
+            vec![FqxValue::I64(1), FqxValue::Null, FqxValue::Bool(true)],
+        );
+        assert!(matches!(
+            res,
+            Err(FqxError::TypeMismatch { row: Some(2), .. })
+        ));
+    }
+}
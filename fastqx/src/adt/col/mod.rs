@@ -0,0 +1,10 @@
+//! file: mod.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 10:10:41 Saturday
+//! brief:
+
+pub mod bitmap;
+pub mod column;
+
+pub use bitmap::*;
+pub use column::*;
@@ -0,0 +1,508 @@
+//! file: arrow.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 14:05:52 Saturday
+//! brief:
+
+use std::sync::Arc;
+
+use arrow::array::*;
+use arrow::datatypes::*;
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use rust_decimal::Decimal;
+
+use crate::adt::{FqxData, FqxRow, FqxValue, FqxValueType};
+use crate::error::{FqxError, FqxResult};
+
+// ================================================================================================
+// Const
+// ================================================================================================
+
+// same as `DECIMAL_COLUMN_DEF`, values with a larger scale are rounded
+const DECIMAL_PRECISION: u8 = 38;
+pub(crate) const DECIMAL_SCALE: u32 = 10;
+
+// instants are stored in UTC, which keeps the column tz-aware for any consumer
+const TIMESTAMP_TZ: &str = "UTC";
+
+// ================================================================================================
+// FqxValueType <-> DataType
+// ================================================================================================
+
+impl From<&FqxValueType> for DataType {
+    fn from(value: &FqxValueType) -> Self {
+        match value {
+            FqxValueType::Bool => DataType::Boolean,
+            FqxValueType::U8 => DataType::UInt8,
+            FqxValueType::U16 => DataType::UInt16,
+            FqxValueType::U32 => DataType::UInt32,
+            FqxValueType::U64 => DataType::UInt64,
+            FqxValueType::I8 => DataType::Int8,
+            FqxValueType::I16 => DataType::Int16,
+            FqxValueType::I32 => DataType::Int32,
+            FqxValueType::I64 => DataType::Int64,
+            FqxValueType::F32 => DataType::Float32,
+            FqxValueType::F64 => DataType::Float64,
+            FqxValueType::Decimal => DataType::Decimal128(DECIMAL_PRECISION, DECIMAL_SCALE as i8),
+            FqxValueType::String => DataType::Utf8,
+            FqxValueType::Blob => DataType::Binary,
+            FqxValueType::Timestamp => {
+                DataType::Timestamp(TimeUnit::Microsecond, Some(TIMESTAMP_TZ.into()))
+            }
+            FqxValueType::DateTime => DataType::Timestamp(TimeUnit::Microsecond, None),
+            FqxValueType::Date => DataType::Date32,
+            FqxValueType::Time => DataType::Time64(TimeUnit::Microsecond),
+            FqxValueType::Null => DataType::Null,
+        }
+    }
+}
+
+impl TryFrom<&DataType> for FqxValueType {
+    type Error = FqxError;
+
+    fn try_from(value: &DataType) -> std::result::Result<Self, Self::Error> {
+        let res = match value {
+            DataType::Null => FqxValueType::Null,
+            DataType::Boolean => FqxValueType::Bool,
+            DataType::UInt8 => FqxValueType::U8,
+            DataType::UInt16 => FqxValueType::U16,
+            DataType::UInt32 => FqxValueType::U32,
+            DataType::UInt64 => FqxValueType::U64,
+            DataType::Int8 => FqxValueType::I8,
+            DataType::Int16 => FqxValueType::I16,
+            DataType::Int32 => FqxValueType::I32,
+            DataType::Int64 => FqxValueType::I64,
+            DataType::Float32 => FqxValueType::F32,
+            DataType::Float64 => FqxValueType::F64,
+            DataType::Decimal128(_, _) => FqxValueType::Decimal,
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => FqxValueType::String,
+            DataType::Binary | DataType::LargeBinary => FqxValueType::Blob,
+            DataType::Timestamp(_, Some(_)) => FqxValueType::Timestamp,
+            DataType::Timestamp(_, None) => FqxValueType::DateTime,
+            DataType::Date32 | DataType::Date64 => FqxValueType::Date,
+            DataType::Time32(_) | DataType::Time64(_) => FqxValueType::Time,
+            t => return Err(FqxError::invalid(format!("unsupported arrow type: {t}"))),
+        };
+
+        Ok(res)
+    }
+}
+
+// ================================================================================================
+// FqxData -> RecordBatch
+// ================================================================================================
+
+fn _get<T>(row: &FqxRow, idx: usize) -> FqxResult<Option<T>>
+where
+    T: TryFrom<FqxValue, Error = FqxError>,
+{
+    match row.0.get(idx) {
+        None | Some(FqxValue::Null) => Ok(None),
+        Some(v) => T::try_from(v.clone()).map(Some),
+    }
+}
+
+fn _get_map<T, R, F>(rows: &[FqxRow], idx: usize, f: F) -> FqxResult<Vec<Option<R>>>
+where
+    T: TryFrom<FqxValue, Error = FqxError>,
+    F: Fn(T) -> R,
+{
+    rows.iter()
+        .map(|r| _get::<T>(r, idx).map(|o| o.map(&f)))
+        .collect()
+}
+
+fn _time_to_micros(t: NaiveTime) -> i64 {
+    t.num_seconds_from_midnight() as i64 * 1_000_000 + (t.nanosecond() / 1_000) as i64
+}
+
+/// The mantissa of `d` at `DECIMAL_SCALE`. `rescale` keeps a lower scale when the value doesn't
+/// fit in 96 bits at that scale, which is a cast failure here.
+pub(crate) fn decimal_to_i128(mut d: Decimal) -> FqxResult<i128> {
+    d.rescale(DECIMAL_SCALE);
+    if d.scale() != DECIMAL_SCALE {
+        return Err(FqxError::cast(FqxValueType::Decimal, FqxValueType::Decimal));
+    }
+
+    Ok(d.mantissa())
+}
+
+macro_rules! to_array {
+    ($rows:expr, $idx:expr, $t:ty, $arr:ty) => {
+        Arc::new(<$arr>::from(_get_map::<$t, _, _>($rows, $idx, |v| v)?)) as ArrayRef
+    };
+    ($rows:expr, $idx:expr, $t:ty, $arr:ty, $f:expr) => {
+        Arc::new(<$arr>::from(_get_map::<$t, _, _>($rows, $idx, $f)?)) as ArrayRef
+    };
+}
+
+fn _to_array(rows: &[FqxRow], idx: usize, typ: &FqxValueType) -> FqxResult<ArrayRef> {
+    let arr = match typ {
+        FqxValueType::Bool => to_array!(rows, idx, bool, BooleanArray),
+        FqxValueType::U8 => to_array!(rows, idx, u8, UInt8Array),
+        FqxValueType::U16 => to_array!(rows, idx, u16, UInt16Array),
+        FqxValueType::U32 => to_array!(rows, idx, u32, UInt32Array),
+        FqxValueType::U64 => to_array!(rows, idx, u64, UInt64Array),
+        FqxValueType::I8 => to_array!(rows, idx, i8, Int8Array),
+        FqxValueType::I16 => to_array!(rows, idx, i16, Int16Array),
+        FqxValueType::I32 => to_array!(rows, idx, i32, Int32Array),
+        FqxValueType::I64 => to_array!(rows, idx, i64, Int64Array),
+        FqxValueType::F32 => to_array!(rows, idx, f32, Float32Array),
+        FqxValueType::F64 => to_array!(rows, idx, f64, Float64Array),
+        FqxValueType::Decimal => {
+            let values = rows
+                .iter()
+                .map(|r| _get::<Decimal>(r, idx)?.map(decimal_to_i128).transpose())
+                .collect::<FqxResult<Vec<_>>>()?;
+            Arc::new(
+                Decimal128Array::from(values)
+                    .with_precision_and_scale(DECIMAL_PRECISION, DECIMAL_SCALE as i8)?,
+            )
+        }
+        FqxValueType::String => to_array!(rows, idx, String, StringArray),
+        FqxValueType::Blob => Arc::new(BinaryArray::from_iter(_get_map::<Vec<u8>, _, _>(
+            rows,
+            idx,
+            |v| v,
+        )?)),
+        FqxValueType::Timestamp => Arc::new(
+            TimestampMicrosecondArray::from(_get_map(rows, idx, |v: DateTime<Local>| {
+                v.timestamp_micros()
+            })?)
+            .with_timezone(TIMESTAMP_TZ),
+        ),
+        FqxValueType::DateTime => {
+            to_array!(rows, idx, NaiveDateTime, TimestampMicrosecondArray, |v| v
+                .and_utc()
+                .timestamp_micros())
+        }
+        FqxValueType::Date => {
+            to_array!(
+                rows,
+                idx,
+                NaiveDate,
+                Date32Array,
+                Date32Type::from_naive_date
+            )
+        }
+        FqxValueType::Time => {
+            to_array!(
+                rows,
+                idx,
+                NaiveTime,
+                Time64MicrosecondArray,
+                _time_to_micros
+            )
+        }
+        FqxValueType::Null => Arc::new(NullArray::new(rows.len())),
+    };
+
+    Ok(arr)
+}
+
+pub(crate) fn record_batch_from_rows(
+    schema: SchemaRef,
+    types: &[FqxValueType],
+    rows: &[FqxRow],
+) -> FqxResult<RecordBatch> {
+    let arrays = types
+        .iter()
+        .enumerate()
+        .map(|(idx, t)| _to_array(rows, idx, t))
+        .collect::<FqxResult<Vec<_>>>()?;
+    let options = RecordBatchOptions::new().with_row_count(Some(rows.len()));
+
+    Ok(RecordBatch::try_new_with_options(schema, arrays, &options)?)
+}
+
+// ================================================================================================
+// RecordBatch -> FqxData
+// ================================================================================================
+
+macro_rules! from_primitive {
+    ($arr:expr, $t:ty, $v:ident) => {{
+        let a = $arr.as_primitive::<$t>();
+        a.iter()
+            .map(|v| v.map_or(FqxValue::Null, FqxValue::$v))
+            .collect()
+    }};
+}
+
+macro_rules! from_temporal {
+    ($arr:expr, $t:ty, $f:ident, $m:expr) => {{
+        let a = $arr.as_primitive::<$t>();
+        (0..a.len())
+            .map(|i| match a.is_null(i) {
+                true => FqxValue::Null,
+                false => a.$f(i).map($m).unwrap_or(FqxValue::Null),
+            })
+            .collect()
+    }};
+}
+
+fn _local(v: NaiveDateTime) -> FqxValue {
+    FqxValue::Timestamp(v.and_utc().with_timezone(&Local))
+}
+
+fn _from_array(arr: &ArrayRef) -> FqxResult<Vec<FqxValue>> {
+    let res = match arr.data_type() {
+        DataType::Null => vec![FqxValue::Null; arr.len()],
+        DataType::Boolean => arr
+            .as_boolean()
+            .iter()
+            .map(|v| v.map_or(FqxValue::Null, FqxValue::Bool))
+            .collect(),
+        DataType::UInt8 => from_primitive!(arr, UInt8Type, U8),
+        DataType::UInt16 => from_primitive!(arr, UInt16Type, U16),
+        DataType::UInt32 => from_primitive!(arr, UInt32Type, U32),
+        DataType::UInt64 => from_primitive!(arr, UInt64Type, U64),
+        DataType::Int8 => from_primitive!(arr, Int8Type, I8),
+        DataType::Int16 => from_primitive!(arr, Int16Type, I16),
+        DataType::Int32 => from_primitive!(arr, Int32Type, I32),
+        DataType::Int64 => from_primitive!(arr, Int64Type, I64),
+        DataType::Float32 => from_primitive!(arr, Float32Type, F32),
+        DataType::Float64 => from_primitive!(arr, Float64Type, F64),
+        DataType::Decimal128(_, s) => {
+            if *s < 0 {
+                return Err(FqxError::invalid(format!("unsupported decimal scale: {s}")));
+            }
+            arr.as_primitive::<Decimal128Type>()
+                .iter()
+                .map(|v| match v {
+                    Some(v) => Ok(FqxValue::Decimal(Decimal::try_from_i128_with_scale(
+                        v, *s as u32,
+                    )?)),
+                    None => Ok(FqxValue::Null),
+                })
+                .collect::<FqxResult<Vec<_>>>()?
+        }
+        DataType::Utf8 => arr
+            .as_string::<i32>()
+            .iter()
+            .map(|v| v.map_or(FqxValue::Null, |s| FqxValue::String(s.to_string())))
+            .collect(),
+        DataType::LargeUtf8 => arr
+            .as_string::<i64>()
+            .iter()
+            .map(|v| v.map_or(FqxValue::Null, |s| FqxValue::String(s.to_string())))
+            .collect(),
+        DataType::Utf8View => arr
+            .as_string_view()
+            .iter()
+            .map(|v| v.map_or(FqxValue::Null, |s| FqxValue::String(s.to_string())))
+            .collect(),
+        DataType::Binary => arr
+            .as_binary::<i32>()
+            .iter()
+            .map(|v| v.map_or(FqxValue::Null, |b| FqxValue::Blob(b.to_vec())))
+            .collect(),
+        DataType::LargeBinary => arr
+            .as_binary::<i64>()
+            .iter()
+            .map(|v| v.map_or(FqxValue::Null, |b| FqxValue::Blob(b.to_vec())))
+            .collect(),
+        DataType::Timestamp(u, Some(_)) => match u {
+            TimeUnit::Second => from_temporal!(arr, TimestampSecondType, value_as_datetime, _local),
+            TimeUnit::Millisecond => {
+                from_temporal!(arr, TimestampMillisecondType, value_as_datetime, _local)
+            }
+            TimeUnit::Microsecond => {
+                from_temporal!(arr, TimestampMicrosecondType, value_as_datetime, _local)
+            }
+            TimeUnit::Nanosecond => {
+                from_temporal!(arr, TimestampNanosecondType, value_as_datetime, _local)
+            }
+        },
+        DataType::Timestamp(u, None) => match u {
+            TimeUnit::Second => {
+                from_temporal!(
+                    arr,
+                    TimestampSecondType,
+                    value_as_datetime,
+                    FqxValue::DateTime
+                )
+            }
+            TimeUnit::Millisecond => from_temporal!(
+                arr,
+                TimestampMillisecondType,
+                value_as_datetime,
+                FqxValue::DateTime
+            ),
+            TimeUnit::Microsecond => from_temporal!(
+                arr,
+                TimestampMicrosecondType,
+                value_as_datetime,
+                FqxValue::DateTime
+            ),
+            TimeUnit::Nanosecond => from_temporal!(
+                arr,
+                TimestampNanosecondType,
+                value_as_datetime,
+                FqxValue::DateTime
+            ),
+        },
+        DataType::Date32 => from_temporal!(arr, Date32Type, value_as_date, FqxValue::Date),
+        DataType::Date64 => from_temporal!(arr, Date64Type, value_as_date, FqxValue::Date),
+        DataType::Time32(TimeUnit::Second) => {
+            from_temporal!(arr, Time32SecondType, value_as_time, FqxValue::Time)
+        }
+        DataType::Time32(TimeUnit::Millisecond) => {
+            from_temporal!(arr, Time32MillisecondType, value_as_time, FqxValue::Time)
+        }
+        DataType::Time64(TimeUnit::Microsecond) => {
+            from_temporal!(arr, Time64MicrosecondType, value_as_time, FqxValue::Time)
+        }
+        DataType::Time64(TimeUnit::Nanosecond) => {
+            from_temporal!(arr, Time64NanosecondType, value_as_time, FqxValue::Time)
+        }
+        t => return Err(FqxError::invalid(format!("unsupported arrow type: {t}"))),
+    };
+
+    Ok(res)
+}
+
+// ================================================================================================
+// Impl FqxData
+// ================================================================================================
+
+impl FqxData {
+    pub fn arrow_schema(&self) -> Schema {
+        let fields = self
+            .columns
+            .iter()
+            .zip(self.types.iter())
+            .map(|(c, t)| Field::new(c, DataType::from(t), true))
+            .collect::<Vec<_>>();
+
+        Schema::new(fields)
+    }
+
+    pub fn to_record_batch(&self) -> FqxResult<RecordBatch> {
+        record_batch_from_rows(Arc::new(self.arrow_schema()), &self.types, &self.data)
+    }
+
+    pub fn try_from_record_batch(batch: &RecordBatch) -> FqxResult<Self> {
+        let schema = batch.schema();
+
+        let mut columns = vec![];
+        let mut types = vec![];
+        for f in schema.fields().iter() {
+            columns.push(f.name().to_string());
+            types.push(FqxValueType::try_from(f.data_type())?);
+        }
+
+        let mut data = vec![Vec::with_capacity(columns.len()); batch.num_rows()];
+        for arr in batch.columns().iter() {
+            for (row, v) in data.iter_mut().zip(_from_array(arr)?) {
+                row.push(v);
+            }
+        }
+
+        Ok(FqxData {
+            columns,
+            types,
+            data: data.into_iter().map(FqxRow).collect(),
+        })
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_arrow {
+    use super::*;
+    use crate::fqx;
+    use crate::ops::mock::data::D1;
+
+    #[test]
+    fn record_batch_round_trip_success() {
+        let batch = D1.to_record_batch().unwrap();
+        println!("{:?}", batch);
+        assert_eq!(batch.num_rows(), 9);
+        assert_eq!(batch.column(0).null_count(), 1);
+
+        let d = FqxData::try_from_record_batch(&batch).unwrap();
+        assert_eq!(d.types, D1.types);
+        assert_eq!(d.data, D1.data);
+    }
+
+    #[test]
+    fn record_batch_all_types_success() {
+        let ts = DateTime::from_timestamp(1_700_000_000, 123_000)
+            .unwrap()
+            .with_timezone(&Local);
+        let dt = NaiveDate::from_ymd_opt(2023, 12, 1)
+            .unwrap()
+            .and_hms_micro_opt(8, 30, 0, 500)
+            .unwrap();
+
+        let row = vec![
+            fqx!(true),
+            FqxValue::U8(1),
+            FqxValue::U16(2),
+            FqxValue::U32(3),
+            FqxValue::U64(4),
+            FqxValue::I8(-1),
+            FqxValue::I16(-2),
+            FqxValue::I32(-3),
+            FqxValue::I64(-4),
+            FqxValue::F32(1.5),
+            FqxValue::F64(2.5),
+            FqxValue::Decimal(Decimal::new(123456, 3)),
+            fqx!("x"),
+            FqxValue::Blob(vec![0, 1, 2]),
+            FqxValue::Timestamp(ts),
+            FqxValue::DateTime(dt),
+            FqxValue::Date(dt.date()),
+            FqxValue::Time(dt.time()),
+            FqxValue::Null,
+        ];
+        let types = row.iter().map(FqxValueType::from).collect::<Vec<_>>();
+        let columns = (0..row.len()).map(|i| format!("c{i}")).collect::<Vec<_>>();
+        let nulls = vec![FqxValue::Null; row.len()];
+        let d = FqxData::new(columns, types, vec![row, nulls]).unwrap();
+
+        let batch = d.to_record_batch().unwrap();
+        println!("{:?}", batch.schema());
+        assert_eq!(
+            batch.schema().field(14).data_type(),
+            &DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+        );
+        assert_eq!(batch.schema().field(13).data_type(), &DataType::Binary);
+        assert_eq!(batch.schema().field(18).data_type(), &DataType::Null);
+
+        let r = FqxData::try_from_record_batch(&batch).unwrap();
+        assert_eq!(r.types, d.types);
+        assert_eq!(r.data, d.data);
+    }
+
+    #[test]
+    fn record_batch_large_decimal_success() {
+        // the largest integer part kept at scale 10 is about 7.9e18
+        let large = Decimal::from(7_000_000_000_000_000_000u64) + Decimal::new(12345, 4);
+        let d = FqxData::new(
+            ["d"],
+            [FqxValueType::Decimal],
+            vec![
+                vec![FqxValue::Decimal(large)],
+                vec![FqxValue::Decimal(-large)],
+            ],
+        )
+        .unwrap();
+
+        let batch = d.to_record_batch().unwrap();
+        let r = FqxData::try_from_record_batch(&batch).unwrap();
+        assert_eq!(r.data, d.data);
+
+        let too_large = Decimal::from(9_000_000_000_000_000_000u64);
+        let d = FqxData::new(
+            ["d"],
+            [FqxValueType::Decimal],
+            vec![vec![FqxValue::Decimal(too_large)]],
+        )
+        .unwrap();
+        assert!(matches!(d.to_record_batch(), Err(FqxError::Cast { .. })));
+    }
+}
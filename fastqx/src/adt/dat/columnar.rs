@@ -0,0 +1,468 @@
+//! file: columnar.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 10:48:19 Saturday
+//! brief:
+
+use once_cell::sync::OnceCell;
+
+use crate::adt::{FqxColumn, FqxD, FqxData, FqxRow, FqxValue, FqxValueType, RowProps};
+use crate::error::{FqxError, FqxResult};
+
+// ================================================================================================
+// FqxColumnar
+//
+// Column-major storage: one typed vector plus a validity bitmap per column. The `col_*`
+// aggregations below work on the typed vectors. Row-wise access (required by `FqxD`, and
+// therefore by all the generic ops such as `OpAgg` or `OpCumAgg`) goes through a row view which is
+// materialized on demand; those ops gain nothing over `FqxData`.
+//
+// At least one of `cols` and `rows` is always filled. A shared row access (`data`, `iter`) keeps
+// both, holding the data twice until `compact`. Any mutable row access drops the columns, which
+// are rebuilt lazily by the next column-wise call.
+// ================================================================================================
+
+#[derive(Debug, Clone)]
+pub struct FqxColumnar {
+    pub(crate) columns: Vec<String>,
+    pub(crate) types: Vec<FqxValueType>,
+    cols: OnceCell<Vec<FqxColumn>>,
+    rows: OnceCell<Vec<FqxRow>>,
+}
+
+fn _rows_to_cols(
+    columns: &[String],
+    types: &[FqxValueType],
+    rows: &[FqxRow],
+) -> FqxResult<Vec<FqxColumn>> {
+    let mut cols = types
+        .iter()
+        .map(|t| FqxColumn::with_capacity(t, rows.len()))
+        .collect::<Vec<_>>();
+
+    for (idx, row) in rows.iter().enumerate() {
+        for (i, col) in cols.iter_mut().enumerate() {
+            col.push(row.0.get(i).cloned().unwrap_or(FqxValue::Null))
+                .map_err(|e| e.at_column(&columns[i]).at_row(idx))?;
+        }
+    }
+
+    Ok(cols)
+}
+
+fn _cols_to_rows(cols: &[FqxColumn]) -> Vec<FqxRow> {
+    let height = cols.first().map(FqxColumn::len).unwrap_or(0);
+    let mut rows = vec![Vec::with_capacity(cols.len()); height];
+
+    for col in cols.iter() {
+        for (row, v) in rows.iter_mut().zip(col.iter()) {
+            row.push(v);
+        }
+    }
+
+    rows.into_iter().map(FqxRow).collect()
+}
+
+impl FqxColumnar {
+    pub fn new_empty<I, S, J, T>(columns: I, types: J) -> FqxResult<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+        J: IntoIterator<Item = T>,
+        FqxValueType: From<T>,
+    {
+        let columns = columns
+            .into_iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        let types = types
+            .into_iter()
+            .map(FqxValueType::from)
+            .collect::<Vec<_>>();
+
+        let c_l = columns.len();
+        let t_l = types.len();
+        if c_l != t_l {
+            return Err(FqxError::shape_mismatch("types", c_l, t_l));
+        }
+
+        let cols = types.iter().map(FqxColumn::new_empty).collect();
+
+        Ok(Self {
+            columns,
+            types,
+            cols: OnceCell::with_value(cols),
+            rows: OnceCell::new(),
+        })
+    }
+
+    pub fn from_columns<I, S>(columns: I, cols: Vec<FqxColumn>) -> FqxResult<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        let columns = columns
+            .into_iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+
+        let c_l = columns.len();
+        let v_l = cols.len();
+        if c_l != v_l {
+            return Err(FqxError::shape_mismatch("values", c_l, v_l));
+        }
+        if let Some(h) = cols.first().map(FqxColumn::len) {
+            if let Some((idx, c)) = cols.iter().enumerate().find(|(_, c)| c.len() != h) {
+                let e = FqxError::shape_mismatch("height", h, c.len());
+                return Err(e.at_column(&columns[idx]));
+            }
+        }
+
+        let types = cols.iter().map(FqxColumn::typ).collect();
+
+        Ok(Self {
+            columns,
+            types,
+            cols: OnceCell::with_value(cols),
+            rows: OnceCell::new(),
+        })
+    }
+
+    /// Builds the columns from the row view if needed, which fails on a value that doesn't fit its
+    /// column's type.
+    pub fn cols(&self) -> FqxResult<&[FqxColumn]> {
+        let cols = self.cols.get_or_try_init(|| {
+            _rows_to_cols(&self.columns, &self.types, self.rows.get().unwrap())
+        })?;
+
+        Ok(cols)
+    }
+
+    pub fn column(&self, name: &str) -> FqxResult<&FqxColumn> {
+        let idx = self
+            .columns
+            .iter()
+            .position(|c| c == name)
+            .ok_or_else(|| FqxError::ColumnNotFound(name.to_string()))?;
+
+        Ok(&self.cols()?[idx])
+    }
+
+    /// Build the columns if needed and release the row view.
+    pub fn compact(&mut self) -> FqxResult<()> {
+        self.cols()?;
+        self.rows.take();
+
+        Ok(())
+    }
+
+    fn ensure_rows(&mut self) {
+        let cols = &self.cols;
+        self.rows.get_or_init(|| _cols_to_rows(cols.get().unwrap()));
+        self.cols.take();
+    }
+
+    fn rows_mut(&mut self) -> &mut Vec<FqxRow> {
+        self.ensure_rows();
+        self.rows.get_mut().unwrap()
+    }
+
+    fn into_parts(self) -> (Vec<String>, Vec<FqxValueType>, Vec<FqxRow>) {
+        let rows = match self.rows.into_inner() {
+            Some(rows) => rows,
+            None => _cols_to_rows(&self.cols.into_inner().unwrap_or_default()),
+        };
+
+        (self.columns, self.types, rows)
+    }
+
+    ///////////////////////////////////////////////////////////////////////////////////////////////
+    // column-wise aggregations
+
+    pub fn col_sum(&self) -> FqxResult<FqxRow> {
+        Ok(FqxRow(self.cols()?.iter().map(FqxColumn::sum).collect()))
+    }
+
+    pub fn col_min(&self) -> FqxResult<FqxRow> {
+        Ok(FqxRow(self.cols()?.iter().map(FqxColumn::min).collect()))
+    }
+
+    pub fn col_max(&self) -> FqxResult<FqxRow> {
+        Ok(FqxRow(self.cols()?.iter().map(FqxColumn::max).collect()))
+    }
+
+    pub fn col_mean(&self) -> FqxResult<FqxRow> {
+        Ok(FqxRow(self.cols()?.iter().map(FqxColumn::mean).collect()))
+    }
+}
+
+// ================================================================================================
+// Conversion
+// ================================================================================================
+
+impl From<FqxData> for FqxColumnar {
+    fn from(d: FqxData) -> Self {
+        Self {
+            columns: d.columns,
+            types: d.types,
+            cols: OnceCell::new(),
+            rows: OnceCell::with_value(d.data),
+        }
+    }
+}
+
+impl From<FqxColumnar> for FqxData {
+    fn from(d: FqxColumnar) -> Self {
+        let (columns, types, data) = d.into_parts();
+
+        FqxData {
+            columns,
+            types,
+            data,
+        }
+    }
+}
+
+// ================================================================================================
+// impl FqxD
+// ================================================================================================
+
+impl FqxD for FqxColumnar {
+    type ColumnsT = Vec<String>;
+
+    type TypesT = Vec<FqxValueType>;
+
+    type RowT = FqxRow;
+
+    fn cst(c: Self::ColumnsT, t: Self::TypesT, d: Vec<Self::RowT>) -> Self {
+        FqxColumnar {
+            columns: c,
+            types: t,
+            cols: OnceCell::new(),
+            rows: OnceCell::with_value(d),
+        }
+    }
+
+    fn dcst(self) -> (Self::ColumnsT, Self::TypesT, Vec<Self::RowT>) {
+        self.into_parts()
+    }
+
+    fn columns_(&self) -> &Self::ColumnsT {
+        &self.columns
+    }
+
+    fn types_(&self) -> &Self::TypesT {
+        &self.types
+    }
+
+    fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    fn columns_mut(&mut self) -> &mut [String] {
+        &mut self.columns
+    }
+
+    fn set_columns(&mut self, cols: Self::ColumnsT) -> FqxResult<()> {
+        if self.width() != cols.len() {
+            return Err(FqxError::shape_mismatch(
+                "columns",
+                self.width(),
+                cols.len(),
+            ));
+        }
+
+        self.columns = cols;
+
+        Ok(())
+    }
+
+    fn columns_take(self) -> Vec<String> {
+        self.columns
+    }
+
+    fn types(&self) -> &[FqxValueType] {
+        &self.types
+    }
+
+    fn types_mut(&mut self) -> &mut [FqxValueType] {
+        // typed columns cannot follow a type change, keep the rows only
+        self.ensure_rows();
+        &mut self.types
+    }
+
+    fn set_types(&mut self, types: Self::TypesT) -> FqxResult<()> {
+        if self.width() != types.len() {
+            return Err(FqxError::shape_mismatch("types", self.width(), types.len()));
+        }
+
+        self.ensure_rows();
+        self.types = types;
+
+        Ok(())
+    }
+
+    fn types_take(self) -> Vec<FqxValueType> {
+        self.types
+    }
+
+    fn data(&self) -> &[Self::RowT] {
+        self.rows
+            .get_or_init(|| _cols_to_rows(self.cols.get().unwrap()))
+    }
+
+    fn data_mut(&mut self) -> &mut Vec<Self::RowT> {
+        self.rows_mut()
+    }
+
+    fn set_data(&mut self, data: Vec<Self::RowT>) -> FqxResult<()> {
+        let width = self.width();
+
+        let mut _data = vec![];
+        for (idx, row) in data.into_iter().enumerate() {
+            let mut count = 0;
+
+            for (d, t) in (&row).into_iter().zip(self.types().iter()) {
+                if !d.eq(t) {
+                    let e = FqxError::type_mismatch(t.clone(), d.into());
+                    return Err(e.at_column(&self.columns()[count]).at_row(idx));
+                }
+                count += 1;
+            }
+
+            if width != count {
+                return Err(FqxError::shape_mismatch("row length", width, count).at_row(idx));
+            }
+
+            _data.push(row);
+        }
+
+        *self.data_mut() = _data;
+
+        Ok(())
+    }
+
+    fn data_take(self) -> Vec<Self::RowT> {
+        self.into_parts().2
+    }
+
+    fn check_row_validation(&self, row: &Self::RowT) -> bool {
+        if self.width() != row.len() {
+            return false;
+        }
+
+        for (v, t) in row.into_iter().zip(self.types()) {
+            if v.is_null() {
+                continue;
+            }
+            if !v.is_type(t) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn iter_owned(self) -> std::vec::IntoIter<Self::RowT> {
+        self.into_parts().2.into_iter()
+    }
+
+    fn iter(&self) -> std::slice::Iter<'_, Self::RowT> {
+        self.data().iter()
+    }
+
+    fn iter_mut(&mut self) -> std::slice::IterMut<'_, Self::RowT> {
+        self.rows_mut().iter_mut()
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_columnar {
+    use super::*;
+    use crate::fqx;
+    use crate::ops::mock::data::{D1, D2};
+    use crate::ops::{OpAgg, OpFilter, OpGroup, OpSort};
+
+    fn columnar(d: &FqxData) -> FqxColumnar {
+        let mut c = FqxColumnar::from(d.clone());
+        c.compact().unwrap();
+        c
+    }
+
+    #[test]
+    fn columnar_ops_success() {
+        let c = columnar(&D2);
+
+        assert_eq!(c.sum(), D2.sum());
+        assert_eq!(c.max(), D2.max());
+        assert_eq!(c.min(), D2.min());
+
+        let f = columnar(&D2).filter(|r| r[0] > fqx!(5));
+        assert_eq!(f.data(), D2.clone().filter(|r| r[0] > fqx!(5)).data());
+
+        let s = columnar(&D2).sorted_by(|p, c| p[2] > c[2]);
+        assert_eq!(s.data(), D2.clone().sorted_by(|p, c| p[2] > c[2]).data());
+
+        let g = columnar(&D2).group_by_fn_(|r| vec![r[0].clone() % fqx!(2)]);
+        assert_eq!(g.0.len(), 2);
+        println!("{:?}", g);
+    }
+
+    #[test]
+    fn columnar_col_agg_success() {
+        let c = columnar(&D1);
+
+        let sum = c.col_sum().unwrap();
+        println!("{:?}", sum);
+        assert_eq!(sum[0], fqx!(42));
+        assert_eq!(sum[1], FqxValue::Null);
+
+        assert_eq!(c.col_min().unwrap()[1], fqx!("A"));
+        assert_eq!(c.col_max().unwrap()[2], fqx!(9.9));
+        assert_eq!(c.col_mean().unwrap()[0], fqx!(5.25));
+        assert_eq!(c.column("col_0").unwrap().null_count(), 1);
+        assert!(matches!(
+            c.column("col_9"),
+            Err(FqxError::ColumnNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn columnar_col_agg_fail() {
+        // the row view is not type checked, so the columns are built from a wrong value
+        let mut c = FqxColumnar::from(D1.clone());
+        c.data_mut()[1][0] = fqx!("x");
+
+        let res = c.col_sum();
+        assert!(matches!(
+            res,
+            Err(FqxError::TypeMismatch { column: Some(ref n), row: Some(1), .. }) if n == "col_0"
+        ));
+    }
+
+    #[test]
+    fn columnar_conversion_success() {
+        let mut c = columnar(&D1);
+        c.push(FqxRow(vec![fqx!(10), fqx!("J"), fqx!(10.1)]))
+            .unwrap();
+        assert_eq!(c.height(), 10);
+        assert_eq!(c.col_sum().unwrap()[0], fqx!(52));
+
+        let d = FqxData::from(c);
+        assert_eq!(d.data()[..9], D1.data()[..]);
+
+        let c = FqxColumnar::from_columns(
+            ["a", "b"],
+            vec![
+                FqxColumn::from_values(&FqxValueType::I64, vec![fqx!(1), fqx!()]).unwrap(),
+                FqxColumn::from_values(&FqxValueType::String, vec![fqx!("x"), fqx!("y")]).unwrap(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(c.types(), [FqxValueType::I64, FqxValueType::String]);
+        assert_eq!(c.data()[0], FqxRow(vec![FqxValue::I64(1), fqx!("x")]));
+    }
+}
@@ -5,13 +5,14 @@
 
 use std::collections::HashMap;
 
-use anyhow::{anyhow, bail, Result};
 use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime};
 use itertools::Itertools;
 use pyo3::prelude::*;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 use crate::adt::{FqxD, FqxRow, FqxValue, FqxValueType, RowProps};
+use crate::error::{FqxError, FqxResult};
 
 // ================================================================================================
 // FqxData
@@ -20,14 +21,54 @@ use crate::adt::{FqxD, FqxRow, FqxValue, FqxValueType, RowProps};
 #[pyclass]
 #[pyo3(name = "FqxInner")]
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(try_from = "FqxDataRepr")]
 pub struct FqxData {
     pub(crate) columns: Vec<String>,
     pub(crate) types: Vec<FqxValueType>,
     pub(crate) data: Vec<FqxRow>,
 }
 
+// decimals are serialized as strings, which `FqxValue` alone cannot tell apart from plain strings;
+// they are restored here by the column types
+#[derive(Deserialize)]
+struct FqxDataRepr {
+    columns: Vec<String>,
+    types: Vec<FqxValueType>,
+    data: Vec<FqxRow>,
+}
+
+impl TryFrom<FqxDataRepr> for FqxData {
+    type Error = FqxError;
+
+    fn try_from(value: FqxDataRepr) -> FqxResult<Self> {
+        let FqxDataRepr {
+            columns,
+            types,
+            mut data,
+        } = value;
+
+        let decimals = types
+            .iter()
+            .positions(|t| t == &FqxValueType::Decimal)
+            .collect_vec();
+        for (idx, row) in data.iter_mut().enumerate() {
+            for &i in decimals.iter() {
+                if let Some(v) = row.0.get_mut(i).filter(|v| !v.is_null()) {
+                    *v = FqxValue::Decimal(Decimal::try_from(&*v).map_err(|e| e.at_row(idx))?);
+                }
+            }
+        }
+
+        Ok(Self {
+            columns,
+            types,
+            data,
+        })
+    }
+}
+
 impl FqxData {
-    pub fn new<I, S, J, T, R>(columns: I, types: J, data: Vec<R>) -> Result<Self>
+    pub fn new<I, S, J, T, R>(columns: I, types: J, data: Vec<R>) -> FqxResult<Self>
     where
         I: IntoIterator<Item = S>,
         S: ToString,
@@ -47,7 +88,7 @@ impl FqxData {
         let c_l = columns.len();
         let t_l = types.len();
         if c_l != t_l {
-            bail!(format!("columns len: {c_l}, types len: {t_l}"));
+            return Err(FqxError::shape_mismatch("types", c_l, t_l));
         }
 
         let mut d = vec![];
@@ -56,7 +97,7 @@ impl FqxData {
             let row = row.into();
             let r_l = row.len();
             if c_l != r_l {
-                bail!(format!("columns len: {c_l}, row[{idx}] len: {r_l}"));
+                return Err(FqxError::shape_mismatch("row length", c_l, r_l).at_row(idx));
             }
 
             d.push(row);
@@ -69,7 +110,7 @@ impl FqxData {
         })
     }
 
-    pub fn new_empty<I, S, J, T>(columns: I, types: J) -> Result<Self>
+    pub fn new_empty<I, S, J, T>(columns: I, types: J) -> FqxResult<Self>
     where
         I: IntoIterator<Item = S>,
         S: ToString,
@@ -88,7 +129,7 @@ impl FqxData {
         let c_l = columns.len();
         let t_l = types.len();
         if c_l != t_l {
-            bail!(format!("columns len: {c_l}, types len: {t_l}"));
+            return Err(FqxError::shape_mismatch("types", c_l, t_l));
         }
 
         Ok(Self {
@@ -98,7 +139,7 @@ impl FqxData {
         })
     }
 
-    pub fn new_by_data(data: Vec<Vec<FqxValue>>) -> Result<Self> {
+    pub fn new_by_data(data: Vec<Vec<FqxValue>>) -> FqxResult<Self> {
         Self::try_from(data)
     }
 
@@ -112,7 +153,7 @@ impl FqxData {
 
     ///////////////////////////////////////////////////////////////////////////////////////////////////
 
-    pub fn type_coercion(&mut self) -> Result<()> {
+    pub fn type_coercion(&mut self) -> FqxResult<()> {
         let types = &self.types;
 
         for row in self.data.iter_mut() {
@@ -154,6 +195,9 @@ impl FqxData {
                     FqxValueType::F64 => {
                         *e = FqxValue::F64(f64::try_from(e.clone())?);
                     }
+                    FqxValueType::Decimal => {
+                        *e = FqxValue::Decimal(Decimal::try_from(e.clone())?);
+                    }
                     FqxValueType::String => {
                         *e = FqxValue::String(String::try_from(e.clone())?);
                     }
@@ -178,9 +222,12 @@ impl FqxData {
         Ok(())
     }
 
-    pub fn cast(&mut self, idx: usize, typ: &FqxValueType) -> Result<()> {
+    pub fn cast(&mut self, idx: usize, typ: &FqxValueType) -> FqxResult<()> {
         if idx >= self.width() {
-            bail!(format!("idx: {idx} out of boundary {}", self.width()))
+            return Err(FqxError::OutOfBounds {
+                index: idx,
+                len: self.width(),
+            });
         }
         for r in self.iter_mut() {
             r.uncheck_cast(idx, typ)?;
@@ -193,9 +240,11 @@ impl FqxData {
 
     ///////////////////////////////////////////////////////////////////////////////////////////////////
 
-    pub fn from_hashmaps(objs: Vec<HashMap<String, FqxValue>>) -> Result<Self> {
+    pub fn from_hashmaps(objs: Vec<HashMap<String, FqxValue>>) -> FqxResult<Self> {
         let mut peek = objs.into_iter().peekable();
-        let first = peek.peek().ok_or_else(|| anyhow!("objects is empty"))?;
+        let first = peek
+            .peek()
+            .ok_or_else(|| FqxError::invalid("objects is empty"))?;
 
         let mut columns = vec![];
         let mut types = vec![];
@@ -237,16 +286,16 @@ impl FqxData {
         res
     }
 
-    pub fn from_string(s: &str) -> Result<Self> {
-        Ok(serde_json::from_str::<Self>(s).map_err(anyhow::Error::msg)?)
+    pub fn from_string(s: &str) -> FqxResult<Self> {
+        Ok(serde_json::from_str::<Self>(s)?)
     }
 
-    pub fn to_string(&self) -> Result<String> {
-        Ok(serde_json::to_string(self).map_err(anyhow::Error::msg)?)
+    pub fn to_string(&self) -> FqxResult<String> {
+        Ok(serde_json::to_string(self)?)
     }
 
-    pub fn to_pretty_string(&self) -> Result<String> {
-        Ok(serde_json::to_string_pretty(self).map_err(anyhow::Error::msg)?)
+    pub fn to_pretty_string(&self) -> FqxResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
     }
 
     ///////////////////////////////////////////////////////////////////////////////////////////////////
@@ -255,34 +304,37 @@ impl FqxData {
 ///////////////////////////////////////////////////////////////////////////////////////////////////
 
 impl TryFrom<Vec<Vec<FqxValue>>> for FqxData {
-    type Error = anyhow::Error;
+    type Error = FqxError;
 
-    fn try_from(value: Vec<Vec<FqxValue>>) -> Result<Self> {
+    fn try_from(value: Vec<Vec<FqxValue>>) -> FqxResult<Self> {
         let mut types = None::<Vec<FqxValueType>>;
         let mut data = vec![];
 
-        for row in value.into_iter() {
+        for (idx, row) in value.into_iter().enumerate() {
             let cur_types = row.iter().map(FqxValueType::from).collect_vec();
             match types {
                 Some(t) => {
                     if t.len() != cur_types.len() {
-                        bail!("row lengths not equal");
+                        let e = FqxError::shape_mismatch("row length", t.len(), cur_types.len());
+                        return Err(e.at_row(idx));
                     }
                     let t = t
                         .into_iter()
                         .zip(cur_types.into_iter())
-                        .map(|(p, c)| match (&p, &c) {
+                        .enumerate()
+                        .map(|(i, (p, c))| match (&p, &c) {
                             // update previous `null` type
                             (FqxValueType::Null, _) => Ok(c),
                             // ignore current `null` type
                             (_, FqxValueType::Null) => Ok(p),
                             // type mismatch -> err
                             (t1, t2) if t1 != t2 => {
-                                bail!("type mismatch");
+                                let e = FqxError::type_mismatch(t1.clone(), t2.clone());
+                                Err(e.at_column(format!("col_{i}")).at_row(idx))
                             }
                             _ => Ok(p),
                         })
-                        .collect::<Result<Vec<_>>>()?;
+                        .collect::<FqxResult<Vec<_>>>()?;
                     types = Some(t);
                     data.push(FqxRow(row));
                 }
@@ -346,9 +398,13 @@ impl FqxD for FqxData {
         &mut self.columns
     }
 
-    fn set_columns(&mut self, cols: Self::ColumnsT) -> Result<()> {
+    fn set_columns(&mut self, cols: Self::ColumnsT) -> FqxResult<()> {
         if self.width() != cols.len() {
-            bail!("length mismatch")
+            return Err(FqxError::shape_mismatch(
+                "columns",
+                self.width(),
+                cols.len(),
+            ));
         }
 
         self.columns = cols;
@@ -368,9 +424,9 @@ impl FqxD for FqxData {
         &mut self.types
     }
 
-    fn set_types(&mut self, types: Self::TypesT) -> Result<()> {
+    fn set_types(&mut self, types: Self::TypesT) -> FqxResult<()> {
         if self.width() != types.len() {
-            bail!("length mismatch")
+            return Err(FqxError::shape_mismatch("types", self.width(), types.len()));
         }
 
         self.types = types;
@@ -390,22 +446,23 @@ impl FqxD for FqxData {
         &mut self.data
     }
 
-    fn set_data(&mut self, data: Vec<Self::RowT>) -> Result<()> {
+    fn set_data(&mut self, data: Vec<Self::RowT>) -> FqxResult<()> {
         let width = self.width();
 
         let mut _data = vec![];
-        for row in data.into_iter() {
+        for (idx, row) in data.into_iter().enumerate() {
             let mut count = 0;
 
             for (d, t) in (&row).into_iter().zip(self.types().iter()) {
                 if !d.eq(t) {
-                    bail!("type mismatch")
+                    let e = FqxError::type_mismatch(t.clone(), d.into());
+                    return Err(e.at_column(&self.columns()[count]).at_row(idx));
                 }
                 count += 1;
             }
 
             if width != count {
-                bail!("length mismatch")
+                return Err(FqxError::shape_mismatch("row length", width, count).at_row(idx));
             }
 
             _data.push(row);
@@ -469,6 +526,26 @@ mod test_data {
         println!("{:?}", serde_json::to_string(&foo));
     }
 
+    #[test]
+    fn serde_decimal_success() {
+        let d = FqxData::new(
+            ["d", "s"],
+            [FqxValueType::Decimal, FqxValueType::String],
+            vec![
+                vec![FqxValue::Decimal(Decimal::new(1050, 2)), fqx!("1.5")],
+                vec![fqx!(), fqx!("x")],
+            ],
+        )
+        .unwrap();
+
+        let s = d.to_string().unwrap();
+        let res = FqxData::from_string(&s).unwrap();
+        assert_eq!(res.data[0].0, d.data[0].0);
+        assert!(matches!(res.data[0][0], FqxValue::Decimal(v) if v.to_string() == "10.50"));
+        assert!(matches!(res.data[0][1], FqxValue::String(_)));
+        assert!(res.data[1][0].is_null());
+    }
+
     #[test]
     fn new_by_data_success() {
         let d = FqxData::new_by_data(vec![
@@ -5,10 +5,9 @@
 
 use std::borrow::Cow;
 
-use anyhow::{bail, Result};
-
 use crate::adt::util::{slice_cow, takes_cow};
 use crate::adt::{FqxD, FqxData, FqxRow, FqxRowCow, FqxValueType, FromTo, RowProps, SeqSlice};
+use crate::error::{FqxError, FqxResult};
 
 // ================================================================================================
 // FqxDataR
@@ -134,9 +133,13 @@ impl<'a> FqxD for FqxDataCow<'a> {
         self.columns.to_mut()
     }
 
-    fn set_columns(&mut self, cols: Self::ColumnsT) -> Result<()> {
+    fn set_columns(&mut self, cols: Self::ColumnsT) -> FqxResult<()> {
         if self.width() != cols.len() {
-            bail!("length mismatch")
+            return Err(FqxError::shape_mismatch(
+                "columns",
+                self.width(),
+                cols.len(),
+            ));
         }
 
         self.columns = cols;
@@ -156,9 +159,9 @@ impl<'a> FqxD for FqxDataCow<'a> {
         self.types.to_mut()
     }
 
-    fn set_types(&mut self, types: Self::TypesT) -> Result<()> {
+    fn set_types(&mut self, types: Self::TypesT) -> FqxResult<()> {
         if self.width() != types.len() {
-            bail!("length mismatch")
+            return Err(FqxError::shape_mismatch("types", self.width(), types.len()));
         }
 
         self.types = types;
@@ -178,22 +181,23 @@ impl<'a> FqxD for FqxDataCow<'a> {
         &mut self.data
     }
 
-    fn set_data(&mut self, data: Vec<Self::RowT>) -> Result<()> {
+    fn set_data(&mut self, data: Vec<Self::RowT>) -> FqxResult<()> {
         let width = self.width();
 
         let mut _data = vec![];
-        for row in data.into_iter() {
+        for (idx, row) in data.into_iter().enumerate() {
             let mut count = 0;
 
             for (d, t) in row.0.iter().zip(self.types().iter()) {
                 if !d.eq(t) {
-                    bail!("type mismatch")
+                    let e = FqxError::type_mismatch(t.clone(), d.into());
+                    return Err(e.at_column(&self.columns()[count]).at_row(idx));
                 }
                 count += 1;
             }
 
             if width != count {
-                bail!("length mismatch")
+                return Err(FqxError::shape_mismatch("row length", width, count).at_row(idx));
             }
 
             _data.push(row);
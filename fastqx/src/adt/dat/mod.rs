@@ -3,8 +3,11 @@
 //! date: 2023/12/11 23:08:51 Monday
 //! brief:
 
+pub mod arrow;
+pub mod columnar;
 pub mod data;
 pub mod datacow;
 
+pub use columnar::*;
 pub use data::*;
 pub use datacow::*;
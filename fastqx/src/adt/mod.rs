@@ -4,6 +4,7 @@
 //! brief:
 
 pub mod ab;
+pub mod col;
 pub mod dat;
 pub mod macros;
 pub mod row;
@@ -11,9 +12,13 @@ pub mod util;
 pub mod val;
 
 pub use ab::*;
+pub use col::bitmap::*;
+pub use col::column::*;
+pub use dat::columnar::*;
 pub use dat::data::*;
 pub use dat::datacow::*;
 pub use row::row::*;
 pub use row::rowcow::*;
 pub use val::cvt::TryCast;
+pub use val::key::*;
 pub use val::value::*;
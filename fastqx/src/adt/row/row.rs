@@ -10,7 +10,6 @@ use std::ops::{
 };
 use std::str::FromStr;
 
-use anyhow::{bail, Result};
 use itertools::{EitherOrBoth, Itertools};
 use pyo3::prelude::*;
 use ref_cast::RefCast;
@@ -18,6 +17,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::adt::util::{slice_vec, takes_vec};
 use crate::adt::{FqxValue, FqxValueType, FromTo, RowProps, SeqSlice};
+use crate::error::{FqxError, FqxResult};
 use crate::ops::utils::{_get_row_max, _get_row_min};
 
 // ================================================================================================
@@ -27,7 +27,10 @@ use crate::ops::utils::{_get_row_max, _get_row_min};
 macro_rules! guard {
     ($s:expr, $i:expr) => {
         if $i >= $s.len() {
-            bail!(format!("idx: {} out of boundary {}", $i, $s.len()));
+            return Err(FqxError::OutOfBounds {
+                index: $i,
+                len: $s.len(),
+            });
         }
     };
 }
@@ -48,29 +51,29 @@ impl FqxRow {
         &self.0
     }
 
-    pub fn uncheck_cast(&mut self, idx: usize, typ: &FqxValueType) -> Result<()> {
+    pub fn uncheck_cast(&mut self, idx: usize, typ: &FqxValueType) -> FqxResult<()> {
         self[idx].try_cast_mut(typ)?;
         Ok(())
     }
 
-    pub fn cast(&mut self, idx: usize, typ: &FqxValueType) -> Result<()> {
+    pub fn cast(&mut self, idx: usize, typ: &FqxValueType) -> FqxResult<()> {
         guard!(self, idx);
 
         self.uncheck_cast(idx, typ)
     }
 
-    pub fn uncheck_apply<F>(&mut self, idx: usize, f: F) -> Result<()>
+    pub fn uncheck_apply<F>(&mut self, idx: usize, f: F) -> FqxResult<()>
     where
-        F: Fn(&mut FqxValue) -> Result<()>,
+        F: Fn(&mut FqxValue) -> FqxResult<()>,
     {
         f(&mut self[idx])?;
 
         Ok(())
     }
 
-    pub fn apply<F>(&mut self, idx: usize, f: F) -> Result<()>
+    pub fn apply<F>(&mut self, idx: usize, f: F) -> FqxResult<()>
     where
-        F: Fn(&mut FqxValue) -> Result<()>,
+        F: Fn(&mut FqxValue) -> FqxResult<()>,
     {
         guard!(self, idx);
 
@@ -396,11 +399,11 @@ impl FqxRow {
     }
 
     fn __repr__(&self) -> PyResult<String> {
-        Ok(serde_json::to_string_pretty(&self).map_err(anyhow::Error::msg)?)
+        Ok(serde_json::to_string_pretty(&self).map_err(FqxError::from)?)
     }
 
     fn __str__(&self) -> PyResult<String> {
-        Ok(serde_json::to_string(&self).map_err(anyhow::Error::msg)?)
+        Ok(serde_json::to_string(&self).map_err(FqxError::from)?)
     }
 
     fn __getitem__(&self, idx: isize) -> FqxValue {
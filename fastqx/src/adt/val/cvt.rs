@@ -5,19 +5,22 @@
 
 use std::str::FromStr;
 
-use anyhow::{anyhow, bail, Result};
 use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::{PyDate, PyDateAccess, PyDateTime, PyTime, PyTimeAccess};
+use pyo3::sync::GILOnceCell;
+use pyo3::types::{PyDate, PyDateAccess, PyDateTime, PyTime, PyTimeAccess, PyType};
+use rust_decimal::Decimal;
 
 use crate::adt::{FqxValue, FqxValueType};
+use crate::error::{FqxError, FqxResult};
 
 // ================================================================================================
 // FqxValue <-> Rust types
 // ================================================================================================
 
 impl TryFrom<FqxValue> for bool {
-    type Error = anyhow::Error;
+    type Error = FqxError;
 
     fn try_from(value: FqxValue) -> std::result::Result<Self, Self::Error> {
         match value {
@@ -32,22 +35,23 @@ impl TryFrom<FqxValue> for bool {
             FqxValue::I64(v) => Ok(v == 0),
             FqxValue::F32(v) => Ok(v == 0.0),
             FqxValue::F64(v) => Ok(v == 0.0),
+            FqxValue::Decimal(v) => Ok(v.is_zero()),
             FqxValue::String(v) => Ok(&v != "0"),
             FqxValue::Blob(v) => Ok(!v.is_empty()),
             FqxValue::Null => Ok(false),
-            _ => bail!("cannot cast bool from time-like types"),
+            v => Err(FqxError::cast(&v, FqxValueType::Bool)),
         }
     }
 }
 
 macro_rules! impl_try_from_value_for_numeric {
-    ($t:ty) => {
+    ($t:ty, $vt:ident) => {
         impl TryFrom<FqxValue> for $t {
-            type Error = anyhow::Error;
+            type Error = FqxError;
 
             fn try_from(value: FqxValue) -> std::result::Result<Self, Self::Error> {
                 match value {
-                    FqxValue::Bool(_) => bail!("failed to convert bool into numeric"),
+                    FqxValue::Bool(_) => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::$vt)),
                     FqxValue::U8(v) => Ok(v as $t),
                     FqxValue::U16(v) => Ok(v as $t),
                     FqxValue::U32(v) => Ok(v as $t),
@@ -58,20 +62,22 @@ macro_rules! impl_try_from_value_for_numeric {
                     FqxValue::I64(v) => Ok(v as $t),
                     FqxValue::F32(v) => Ok(v as $t),
                     FqxValue::F64(v) => Ok(v as $t),
-                    FqxValue::String(v) => Ok(v.parse::<$t>()?),
-                    FqxValue::Blob(_) => bail!("failed to convert Vec<u8> into numeric"),
-                    FqxValue::Null => bail!("failed to convert Null into numeric"),
-                    _ => bail!("cannot cast bool from time-like types"),
+                    FqxValue::Decimal(v) => <$t>::try_from(v)
+                        .map_err(|_| FqxError::cast(FqxValueType::Decimal, FqxValueType::$vt)),
+                    FqxValue::String(v) => v
+                        .parse::<$t>()
+                        .map_err(|_| FqxError::cast(FqxValueType::String, FqxValueType::$vt)),
+                    v => Err(FqxError::cast(&v, FqxValueType::$vt)),
                 }
             }
         }
 
         impl TryFrom<&FqxValue> for $t {
-            type Error = anyhow::Error;
+            type Error = FqxError;
 
             fn try_from(value: &FqxValue) -> std::result::Result<Self, Self::Error> {
                 match value {
-                    FqxValue::Bool(_) => bail!("failed to convert bool into numeric"),
+                    FqxValue::Bool(_) => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::$vt)),
                     FqxValue::U8(v) => Ok(*v as $t),
                     FqxValue::U16(v) => Ok(*v as $t),
                     FqxValue::U32(v) => Ok(*v as $t),
@@ -82,29 +88,63 @@ macro_rules! impl_try_from_value_for_numeric {
                     FqxValue::I64(v) => Ok(*v as $t),
                     FqxValue::F32(v) => Ok(*v as $t),
                     FqxValue::F64(v) => Ok(*v as $t),
-                    FqxValue::String(v) => Ok(v.parse::<$t>()?),
-                    FqxValue::Blob(_) => bail!("failed to convert Vec<u8> into numeric"),
-                    FqxValue::Null => bail!("failed to convert Null into numeric"),
-                    _ => bail!("cannot cast bool from time-like types"),
+                    FqxValue::Decimal(v) => <$t>::try_from(*v)
+                        .map_err(|_| FqxError::cast(FqxValueType::Decimal, FqxValueType::$vt)),
+                    FqxValue::String(v) => v
+                        .parse::<$t>()
+                        .map_err(|_| FqxError::cast(FqxValueType::String, FqxValueType::$vt)),
+                    v => Err(FqxError::cast(v, FqxValueType::$vt)),
                 }
             }
         }
     };
 }
 
-impl_try_from_value_for_numeric!(u8);
-impl_try_from_value_for_numeric!(u16);
-impl_try_from_value_for_numeric!(u32);
-impl_try_from_value_for_numeric!(u64);
-impl_try_from_value_for_numeric!(i8);
-impl_try_from_value_for_numeric!(i16);
-impl_try_from_value_for_numeric!(i32);
-impl_try_from_value_for_numeric!(i64);
-impl_try_from_value_for_numeric!(f32);
-impl_try_from_value_for_numeric!(f64);
+impl_try_from_value_for_numeric!(u8, U8);
+impl_try_from_value_for_numeric!(u16, U16);
+impl_try_from_value_for_numeric!(u32, U32);
+impl_try_from_value_for_numeric!(u64, U64);
+impl_try_from_value_for_numeric!(i8, I8);
+impl_try_from_value_for_numeric!(i16, I16);
+impl_try_from_value_for_numeric!(i32, I32);
+impl_try_from_value_for_numeric!(i64, I64);
+impl_try_from_value_for_numeric!(f32, F32);
+impl_try_from_value_for_numeric!(f64, F64);
+
+impl TryFrom<FqxValue> for Decimal {
+    type Error = FqxError;
+
+    fn try_from(value: FqxValue) -> std::result::Result<Self, Self::Error> {
+        Decimal::try_from(&value)
+    }
+}
+
+impl TryFrom<&FqxValue> for Decimal {
+    type Error = FqxError;
+
+    fn try_from(value: &FqxValue) -> std::result::Result<Self, Self::Error> {
+        match value {
+            FqxValue::Bool(_) => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::Decimal)),
+            FqxValue::U8(v) => Ok(Decimal::from(*v)),
+            FqxValue::U16(v) => Ok(Decimal::from(*v)),
+            FqxValue::U32(v) => Ok(Decimal::from(*v)),
+            FqxValue::U64(v) => Ok(Decimal::from(*v)),
+            FqxValue::I8(v) => Ok(Decimal::from(*v)),
+            FqxValue::I16(v) => Ok(Decimal::from(*v)),
+            FqxValue::I32(v) => Ok(Decimal::from(*v)),
+            FqxValue::I64(v) => Ok(Decimal::from(*v)),
+            FqxValue::F32(v) => Ok(Decimal::try_from(*v)?),
+            FqxValue::F64(v) => Ok(Decimal::try_from(*v)?),
+            FqxValue::Decimal(v) => Ok(*v),
+            FqxValue::String(v) => Decimal::from_str(v)
+                .map_err(|_| FqxError::cast(FqxValueType::String, FqxValueType::Decimal)),
+            v => Err(FqxError::cast(v, FqxValueType::Decimal)),
+        }
+    }
+}
 
 impl TryFrom<FqxValue> for String {
-    type Error = anyhow::Error;
+    type Error = FqxError;
 
     fn try_from(value: FqxValue) -> std::result::Result<Self, Self::Error> {
         match value {
@@ -119,6 +159,7 @@ impl TryFrom<FqxValue> for String {
             FqxValue::I64(v) => Ok(v.to_string()),
             FqxValue::F32(v) => Ok(v.to_string()),
             FqxValue::F64(v) => Ok(v.to_string()),
+            FqxValue::Decimal(v) => Ok(v.to_string()),
             FqxValue::String(v) => Ok(v),
             FqxValue::Blob(v) => Ok(String::from_utf8(v)?),
             FqxValue::Timestamp(v) => Ok(format!("{:?}", v)),
@@ -131,56 +172,56 @@ impl TryFrom<FqxValue> for String {
 }
 
 impl TryFrom<FqxValue> for Vec<u8> {
-    type Error = anyhow::Error;
+    type Error = FqxError;
 
     fn try_from(value: FqxValue) -> std::result::Result<Self, Self::Error> {
         match value {
             FqxValue::Blob(v) => Ok(v),
-            _ => bail!("convert to Vec<u8> failed"),
+            v => Err(FqxError::cast(&v, FqxValueType::Blob)),
         }
     }
 }
 
 impl TryFrom<FqxValue> for DateTime<Local> {
-    type Error = anyhow::Error;
+    type Error = FqxError;
 
     fn try_from(value: FqxValue) -> std::result::Result<Self, Self::Error> {
         match value {
             FqxValue::Timestamp(v) => Ok(v),
-            _ => bail!("convert to DateTime<Local> failed"),
+            v => Err(FqxError::cast(&v, FqxValueType::Timestamp)),
         }
     }
 }
 
 impl TryFrom<FqxValue> for NaiveDateTime {
-    type Error = anyhow::Error;
+    type Error = FqxError;
 
     fn try_from(value: FqxValue) -> std::result::Result<Self, Self::Error> {
         match value {
             FqxValue::DateTime(v) => Ok(v),
-            _ => bail!("convert to NaiveDateTime failed"),
+            v => Err(FqxError::cast(&v, FqxValueType::DateTime)),
         }
     }
 }
 
 impl TryFrom<FqxValue> for NaiveDate {
-    type Error = anyhow::Error;
+    type Error = FqxError;
 
     fn try_from(value: FqxValue) -> std::result::Result<Self, Self::Error> {
         match value {
             FqxValue::Date(v) => Ok(v),
-            _ => bail!("convert to NaiveDate failed"),
+            v => Err(FqxError::cast(&v, FqxValueType::Date)),
         }
     }
 }
 
 impl TryFrom<FqxValue> for NaiveTime {
-    type Error = anyhow::Error;
+    type Error = FqxError;
 
     fn try_from(value: FqxValue) -> std::result::Result<Self, Self::Error> {
         match value {
             FqxValue::Time(v) => Ok(v),
-            _ => bail!("convert to NaiveTime failed"),
+            v => Err(FqxError::cast(&v, FqxValueType::Time)),
         }
     }
 }
@@ -217,6 +258,7 @@ impl_from_rs_for_value!(i32, I32);
 impl_from_rs_for_value!(i64, I64);
 impl_from_rs_for_value!(f32, F32);
 impl_from_rs_for_value!(f64, F64);
+impl_from_rs_for_value!(Decimal, Decimal);
 impl_from_rs_for_value!(String, String);
 impl_from_rs_for_value!(DateTime<Local>, Timestamp);
 impl_from_rs_for_value!(NaiveDateTime, DateTime);
@@ -248,6 +290,7 @@ impl From<&FqxValue> for FqxValueType {
             FqxValue::I64(_) => FqxValueType::I64,
             FqxValue::F32(_) => FqxValueType::F32,
             FqxValue::F64(_) => FqxValueType::F64,
+            FqxValue::Decimal(_) => FqxValueType::Decimal,
             FqxValue::String(_) => FqxValueType::String,
             FqxValue::Blob(_) => FqxValueType::Blob,
             FqxValue::Timestamp(_) => FqxValueType::Timestamp,
@@ -273,6 +316,7 @@ impl From<&mut FqxValue> for FqxValueType {
             FqxValue::I64(_) => FqxValueType::I64,
             FqxValue::F32(_) => FqxValueType::F32,
             FqxValue::F64(_) => FqxValueType::F64,
+            FqxValue::Decimal(_) => FqxValueType::Decimal,
             FqxValue::String(_) => FqxValueType::String,
             FqxValue::Blob(_) => FqxValueType::Blob,
             FqxValue::Timestamp(_) => FqxValueType::Timestamp,
@@ -293,6 +337,8 @@ impl<'source> FromPyObject<'source> for FqxValue {
     fn extract(ob: &'source PyAny) -> PyResult<Self> {
         if let Ok(v) = ob.extract::<bool>() {
             Ok(FqxValue::Bool(v))
+        } else if is_py_decimal(ob)? {
+            Ok(FqxValue::Decimal(ob.extract::<Decimal>()?))
         } else if let Ok(v) = ob.extract::<i64>() {
             Ok(FqxValue::I64(v))
         } else if let Ok(v) = ob.extract::<f64>() {
@@ -307,14 +353,14 @@ impl<'source> FromPyObject<'source> for FqxValue {
                 v.get_month() as u32,
                 v.get_day() as u32,
             )
-            .ok_or::<PyErr>(anyhow!("naive_date casting failed").into())?;
+            .ok_or_else(|| PyValueError::new_err("naive_date casting failed"))?;
             let t = NaiveTime::from_hms_micro_opt(
                 v.get_hour() as u32,
                 v.get_minute() as u32,
                 v.get_second() as u32,
                 v.get_microsecond(),
             )
-            .ok_or::<PyErr>(anyhow!("naive_time casting failed").into())?;
+            .ok_or_else(|| PyValueError::new_err("naive_time casting failed"))?;
             let dt = NaiveDateTime::new(d, t);
             Ok(FqxValue::DateTime(dt))
         } else if let Ok(v) = ob.downcast::<PyDate>() {
@@ -323,7 +369,7 @@ impl<'source> FromPyObject<'source> for FqxValue {
                 v.get_month() as u32,
                 v.get_day() as u32,
             )
-            .ok_or::<PyErr>(anyhow!("naive_date casting failed").into())?;
+            .ok_or_else(|| PyValueError::new_err("naive_date casting failed"))?;
             Ok(FqxValue::Date(d))
         } else if let Ok(v) = ob.downcast::<PyTime>() {
             let t = NaiveTime::from_hms_micro_opt(
@@ -332,7 +378,7 @@ impl<'source> FromPyObject<'source> for FqxValue {
                 v.get_second() as u32,
                 v.get_microsecond(),
             )
-            .ok_or::<PyErr>(anyhow!("naive_time casting failed").into())?;
+            .ok_or_else(|| PyValueError::new_err("naive_time casting failed"))?;
             Ok(FqxValue::Time(t))
         } else {
             Ok(FqxValue::Null)
@@ -340,6 +386,21 @@ impl<'source> FromPyObject<'source> for FqxValue {
     }
 }
 
+static PY_DECIMAL: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+// `decimal.Decimal` can also be extracted as `f64`, hence an explicit instance check is required
+fn is_py_decimal(ob: &PyAny) -> PyResult<bool> {
+    let py = ob.py();
+    let cls = PY_DECIMAL.get_or_try_init(py, || {
+        PyModule::import_bound(py, "decimal")?
+            .getattr("Decimal")?
+            .downcast_into::<PyType>()
+            .map(Bound::unbind)
+            .map_err(PyErr::from)
+    })?;
+    ob.as_borrowed().is_instance(cls.bind(py))
+}
+
 impl IntoPy<PyObject> for FqxValue {
     fn into_py(self, py: Python<'_>) -> PyObject {
         match self {
@@ -354,6 +415,7 @@ impl IntoPy<PyObject> for FqxValue {
             FqxValue::I64(v) => v.into_py(py),
             FqxValue::F32(v) => v.into_py(py),
             FqxValue::F64(v) => v.into_py(py),
+            FqxValue::Decimal(v) => v.into_py(py),
             FqxValue::String(v) => v.into_py(py),
             FqxValue::Blob(v) => v.into_py(py),
             FqxValue::Timestamp(v) => v.timestamp().into_py(py),
@@ -406,6 +468,7 @@ impl ToString for FqxValue {
             FqxValue::I64(v) => v.to_string(),
             FqxValue::F32(v) => v.to_string(),
             FqxValue::F64(v) => v.to_string(),
+            FqxValue::Decimal(v) => v.to_string(),
             FqxValue::String(v) => v.to_string(),
             FqxValue::Blob(v) => String::from_utf8(v.to_vec())
                 .unwrap_or("Invalid conversion from Vec<u8>".to_string()),
@@ -423,42 +486,54 @@ impl ToString for FqxValue {
 // ================================================================================================
 
 pub trait TryCast {
-    fn try_cast(self, typ: &FqxValueType) -> Result<FqxValue>;
+    fn try_cast(self, typ: &FqxValueType) -> FqxResult<FqxValue>;
+}
+
+fn _parse<T: FromStr>(s: &str, typ: &FqxValueType) -> FqxResult<T> {
+    s.parse::<T>()
+        .map_err(|_| FqxError::cast(FqxValueType::String, typ.into()))
 }
 
 ///////////////////////////////////////////////////////////////////////////////////////////////////
 
 impl TryCast for bool {
-    fn try_cast(self, typ: &FqxValueType) -> Result<FqxValue> {
+    fn try_cast(self, typ: &FqxValueType) -> FqxResult<FqxValue> {
         match typ {
             FqxValueType::Bool => Ok(FqxValue::Bool(self)),
-            FqxValueType::U8 => bail!("cannot cast bool into u8"),
-            FqxValueType::U16 => bail!("cannot cast bool into u16"),
-            FqxValueType::U32 => bail!("cannot cast bool into u32"),
-            FqxValueType::U64 => bail!("cannot cast bool into u64"),
-            FqxValueType::I8 => bail!("cannot cast bool into i8"),
-            FqxValueType::I16 => bail!("cannot cast bool into i16"),
-            FqxValueType::I32 => bail!("cannot cast bool into i32"),
-            FqxValueType::I64 => bail!("cannot cast bool into i64"),
-            FqxValueType::F32 => bail!("cannot cast bool into f32"),
-            FqxValueType::F64 => bail!("cannot cast bool into f64"),
+            FqxValueType::U8 => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::U8)),
+            FqxValueType::U16 => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::U16)),
+            FqxValueType::U32 => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::U32)),
+            FqxValueType::U64 => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::U64)),
+            FqxValueType::I8 => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::I8)),
+            FqxValueType::I16 => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::I16)),
+            FqxValueType::I32 => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::I32)),
+            FqxValueType::I64 => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::I64)),
+            FqxValueType::F32 => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::F32)),
+            FqxValueType::F64 => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::F64)),
+            FqxValueType::Decimal => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::Decimal)),
             FqxValueType::String => Ok(FqxValue::String(self.to_string())),
-            FqxValueType::Blob => bail!("cannot cast bool into blob"),
-            FqxValueType::Timestamp => bail!("cannot cast bool into timestamp"),
-            FqxValueType::DateTime => bail!("cannot cast bool into date_time"),
-            FqxValueType::Date => bail!("cannot cast bool into date"),
-            FqxValueType::Time => bail!("cannot cast bool into time"),
+            FqxValueType::Blob => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::Blob)),
+            FqxValueType::Timestamp => {
+                Err(FqxError::cast(FqxValueType::Bool, FqxValueType::Timestamp))
+            }
+            FqxValueType::DateTime => {
+                Err(FqxError::cast(FqxValueType::Bool, FqxValueType::DateTime))
+            }
+            FqxValueType::Date => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::Date)),
+            FqxValueType::Time => Err(FqxError::cast(FqxValueType::Bool, FqxValueType::Time)),
             FqxValueType::Null => Ok(FqxValue::Null),
         }
     }
 }
 
 macro_rules! impl_try_cast_for_numeric {
-    ($t:ty) => {
+    ($t:ty, $vt:ident) => {
         impl TryCast for $t {
-            fn try_cast(self, typ: &FqxValueType) -> Result<FqxValue> {
+            fn try_cast(self, typ: &FqxValueType) -> FqxResult<FqxValue> {
                 match typ {
-                    FqxValueType::Bool => bail!("cannot cast numeric into bool"),
+                    FqxValueType::Bool => {
+                        Err(FqxError::cast(FqxValueType::$vt, FqxValueType::Bool))
+                    }
                     FqxValueType::U8 => Ok(FqxValue::U8(self as u8)),
                     FqxValueType::U16 => Ok(FqxValue::U16(self as u16)),
                     FqxValueType::U32 => Ok(FqxValue::U32(self as u32)),
@@ -469,135 +544,164 @@ macro_rules! impl_try_cast_for_numeric {
                     FqxValueType::I64 => Ok(FqxValue::I64(self as i64)),
                     FqxValueType::F32 => Ok(FqxValue::F32(self as f32)),
                     FqxValueType::F64 => Ok(FqxValue::F64(self as f64)),
+                    FqxValueType::Decimal => Ok(FqxValue::Decimal(Decimal::try_from(self)?)),
                     FqxValueType::String => Ok(FqxValue::String(self.to_string())),
-                    FqxValueType::Blob => bail!("cannot cast numeric into blob"),
+                    FqxValueType::Blob => {
+                        Err(FqxError::cast(FqxValueType::$vt, FqxValueType::Blob))
+                    }
                     FqxValueType::Null => Ok(FqxValue::Null),
-                    _ => bail!("cannot cast numeric into time-like types"),
+                    t => Err(FqxError::cast(FqxValueType::$vt, t.into())),
                 }
             }
         }
     };
 }
 
-impl_try_cast_for_numeric!(u8);
-impl_try_cast_for_numeric!(u16);
-impl_try_cast_for_numeric!(u32);
-impl_try_cast_for_numeric!(u64);
-impl_try_cast_for_numeric!(i8);
-impl_try_cast_for_numeric!(i16);
-impl_try_cast_for_numeric!(i32);
-impl_try_cast_for_numeric!(i64);
-impl_try_cast_for_numeric!(f32);
-impl_try_cast_for_numeric!(f64);
+impl_try_cast_for_numeric!(u8, U8);
+impl_try_cast_for_numeric!(u16, U16);
+impl_try_cast_for_numeric!(u32, U32);
+impl_try_cast_for_numeric!(u64, U64);
+impl_try_cast_for_numeric!(i8, I8);
+impl_try_cast_for_numeric!(i16, I16);
+impl_try_cast_for_numeric!(i32, I32);
+impl_try_cast_for_numeric!(i64, I64);
+impl_try_cast_for_numeric!(f32, F32);
+impl_try_cast_for_numeric!(f64, F64);
+
+impl TryCast for Decimal {
+    fn try_cast(self, typ: &FqxValueType) -> FqxResult<FqxValue> {
+        match typ {
+            FqxValueType::Bool => Err(FqxError::cast(FqxValueType::Decimal, FqxValueType::Bool)),
+            FqxValueType::U8 => Ok(FqxValue::U8(u8::try_from(self)?)),
+            FqxValueType::U16 => Ok(FqxValue::U16(u16::try_from(self)?)),
+            FqxValueType::U32 => Ok(FqxValue::U32(u32::try_from(self)?)),
+            FqxValueType::U64 => Ok(FqxValue::U64(u64::try_from(self)?)),
+            FqxValueType::I8 => Ok(FqxValue::I8(i8::try_from(self)?)),
+            FqxValueType::I16 => Ok(FqxValue::I16(i16::try_from(self)?)),
+            FqxValueType::I32 => Ok(FqxValue::I32(i32::try_from(self)?)),
+            FqxValueType::I64 => Ok(FqxValue::I64(i64::try_from(self)?)),
+            FqxValueType::F32 => Ok(FqxValue::F32(f32::try_from(self)?)),
+            FqxValueType::F64 => Ok(FqxValue::F64(f64::try_from(self)?)),
+            FqxValueType::Decimal => Ok(FqxValue::Decimal(self)),
+            FqxValueType::String => Ok(FqxValue::String(self.to_string())),
+            FqxValueType::Blob => Err(FqxError::cast(FqxValueType::Decimal, FqxValueType::Blob)),
+            FqxValueType::Null => Ok(FqxValue::Null),
+            t => Err(FqxError::cast(FqxValueType::Decimal, t.into())),
+        }
+    }
+}
 
 impl TryCast for String {
-    fn try_cast(self, typ: &FqxValueType) -> Result<FqxValue> {
+    fn try_cast(self, typ: &FqxValueType) -> FqxResult<FqxValue> {
         match typ {
-            FqxValueType::Bool => Ok(FqxValue::Bool(str::parse(&self)?)),
-            FqxValueType::U8 => Ok(FqxValue::U8(str::parse(&self)?)),
-            FqxValueType::U16 => Ok(FqxValue::U16(str::parse(&self)?)),
-            FqxValueType::U32 => Ok(FqxValue::U32(str::parse(&self)?)),
-            FqxValueType::U64 => Ok(FqxValue::U64(str::parse(&self)?)),
-            FqxValueType::I8 => Ok(FqxValue::I8(str::parse(&self)?)),
-            FqxValueType::I16 => Ok(FqxValue::I16(str::parse(&self)?)),
-            FqxValueType::I32 => Ok(FqxValue::I32(str::parse(&self)?)),
-            FqxValueType::I64 => Ok(FqxValue::I64(str::parse(&self)?)),
-            FqxValueType::F32 => Ok(FqxValue::F32(str::parse(&self)?)),
-            FqxValueType::F64 => Ok(FqxValue::F64(str::parse(&self)?)),
+            FqxValueType::Bool => Ok(FqxValue::Bool(_parse(&self, typ)?)),
+            FqxValueType::U8 => Ok(FqxValue::U8(_parse(&self, typ)?)),
+            FqxValueType::U16 => Ok(FqxValue::U16(_parse(&self, typ)?)),
+            FqxValueType::U32 => Ok(FqxValue::U32(_parse(&self, typ)?)),
+            FqxValueType::U64 => Ok(FqxValue::U64(_parse(&self, typ)?)),
+            FqxValueType::I8 => Ok(FqxValue::I8(_parse(&self, typ)?)),
+            FqxValueType::I16 => Ok(FqxValue::I16(_parse(&self, typ)?)),
+            FqxValueType::I32 => Ok(FqxValue::I32(_parse(&self, typ)?)),
+            FqxValueType::I64 => Ok(FqxValue::I64(_parse(&self, typ)?)),
+            FqxValueType::F32 => Ok(FqxValue::F32(_parse(&self, typ)?)),
+            FqxValueType::F64 => Ok(FqxValue::F64(_parse(&self, typ)?)),
+            FqxValueType::Decimal => Ok(FqxValue::Decimal(_parse(&self, typ)?)),
             FqxValueType::String => Ok(FqxValue::String(self)),
             FqxValueType::Blob => Ok(FqxValue::Blob(self.as_bytes().to_vec())),
-            FqxValueType::Timestamp => Ok(FqxValue::Timestamp(DateTime::<Local>::from_str(&self)?)),
-            FqxValueType::DateTime => Ok(FqxValue::DateTime(NaiveDateTime::from_str(&self)?)),
-            FqxValueType::Date => Ok(FqxValue::Date(NaiveDate::from_str(&self)?)),
-            FqxValueType::Time => Ok(FqxValue::Time(NaiveTime::from_str(&self)?)),
+            FqxValueType::Timestamp => Ok(FqxValue::Timestamp(_parse(&self, typ)?)),
+            FqxValueType::DateTime => Ok(FqxValue::DateTime(_parse(&self, typ)?)),
+            FqxValueType::Date => Ok(FqxValue::Date(_parse(&self, typ)?)),
+            FqxValueType::Time => Ok(FqxValue::Time(_parse(&self, typ)?)),
             FqxValueType::Null => Ok(FqxValue::Null),
         }
     }
 }
 
 impl<'a> TryCast for &'a str {
-    fn try_cast(self, typ: &FqxValueType) -> Result<FqxValue> {
+    fn try_cast(self, typ: &FqxValueType) -> FqxResult<FqxValue> {
         match typ {
-            FqxValueType::Bool => Ok(FqxValue::Bool(str::parse(&self)?)),
-            FqxValueType::U8 => Ok(FqxValue::U8(str::parse(&self)?)),
-            FqxValueType::U16 => Ok(FqxValue::U16(str::parse(&self)?)),
-            FqxValueType::U32 => Ok(FqxValue::U32(str::parse(&self)?)),
-            FqxValueType::U64 => Ok(FqxValue::U64(str::parse(&self)?)),
-            FqxValueType::I8 => Ok(FqxValue::I8(str::parse(&self)?)),
-            FqxValueType::I16 => Ok(FqxValue::I16(str::parse(&self)?)),
-            FqxValueType::I32 => Ok(FqxValue::I32(str::parse(&self)?)),
-            FqxValueType::I64 => Ok(FqxValue::I64(str::parse(&self)?)),
-            FqxValueType::F32 => Ok(FqxValue::F32(str::parse(&self)?)),
-            FqxValueType::F64 => Ok(FqxValue::F64(str::parse(&self)?)),
+            FqxValueType::Bool => Ok(FqxValue::Bool(_parse(self, typ)?)),
+            FqxValueType::U8 => Ok(FqxValue::U8(_parse(self, typ)?)),
+            FqxValueType::U16 => Ok(FqxValue::U16(_parse(self, typ)?)),
+            FqxValueType::U32 => Ok(FqxValue::U32(_parse(self, typ)?)),
+            FqxValueType::U64 => Ok(FqxValue::U64(_parse(self, typ)?)),
+            FqxValueType::I8 => Ok(FqxValue::I8(_parse(self, typ)?)),
+            FqxValueType::I16 => Ok(FqxValue::I16(_parse(self, typ)?)),
+            FqxValueType::I32 => Ok(FqxValue::I32(_parse(self, typ)?)),
+            FqxValueType::I64 => Ok(FqxValue::I64(_parse(self, typ)?)),
+            FqxValueType::F32 => Ok(FqxValue::F32(_parse(self, typ)?)),
+            FqxValueType::F64 => Ok(FqxValue::F64(_parse(self, typ)?)),
+            FqxValueType::Decimal => Ok(FqxValue::Decimal(_parse(self, typ)?)),
             FqxValueType::String => Ok(FqxValue::String(self.to_string())),
             FqxValueType::Blob => Ok(FqxValue::Blob(self.as_bytes().to_vec())),
-            FqxValueType::Timestamp => Ok(FqxValue::Timestamp(DateTime::<Local>::from_str(&self)?)),
-            FqxValueType::DateTime => Ok(FqxValue::DateTime(NaiveDateTime::from_str(&self)?)),
-            FqxValueType::Date => Ok(FqxValue::Date(NaiveDate::from_str(&self)?)),
-            FqxValueType::Time => Ok(FqxValue::Time(NaiveTime::from_str(&self)?)),
+            FqxValueType::Timestamp => Ok(FqxValue::Timestamp(_parse(self, typ)?)),
+            FqxValueType::DateTime => Ok(FqxValue::DateTime(_parse(self, typ)?)),
+            FqxValueType::Date => Ok(FqxValue::Date(_parse(self, typ)?)),
+            FqxValueType::Time => Ok(FqxValue::Time(_parse(self, typ)?)),
             FqxValueType::Null => Ok(FqxValue::Null),
         }
     }
 }
 
 impl TryCast for Vec<u8> {
-    fn try_cast(self, typ: &FqxValueType) -> Result<FqxValue> {
+    fn try_cast(self, typ: &FqxValueType) -> FqxResult<FqxValue> {
         match typ {
-            FqxValueType::Bool => bail!("cannot cast Vec<u8> into bool"),
-            FqxValueType::U8 => bail!("cannot cast Vec<u8> into u8"),
-            FqxValueType::U16 => bail!("cannot cast Vec<u8> into u16"),
-            FqxValueType::U32 => bail!("cannot cast Vec<u8> into u32"),
-            FqxValueType::U64 => bail!("cannot cast Vec<u8> into u64"),
-            FqxValueType::I8 => bail!("cannot cast Vec<u8> into i8"),
-            FqxValueType::I16 => bail!("cannot cast Vec<u8> into i16"),
-            FqxValueType::I32 => bail!("cannot cast Vec<u8> into i32"),
-            FqxValueType::I64 => bail!("cannot cast Vec<u8> into i64"),
-            FqxValueType::F32 => bail!("cannot cast Vec<u8> into f32"),
-            FqxValueType::F64 => bail!("cannot cast Vec<u8> into f64"),
+            FqxValueType::Bool => Err(FqxError::cast(FqxValueType::Blob, FqxValueType::Bool)),
+            FqxValueType::U8 => Err(FqxError::cast(FqxValueType::Blob, FqxValueType::U8)),
+            FqxValueType::U16 => Err(FqxError::cast(FqxValueType::Blob, FqxValueType::U16)),
+            FqxValueType::U32 => Err(FqxError::cast(FqxValueType::Blob, FqxValueType::U32)),
+            FqxValueType::U64 => Err(FqxError::cast(FqxValueType::Blob, FqxValueType::U64)),
+            FqxValueType::I8 => Err(FqxError::cast(FqxValueType::Blob, FqxValueType::I8)),
+            FqxValueType::I16 => Err(FqxError::cast(FqxValueType::Blob, FqxValueType::I16)),
+            FqxValueType::I32 => Err(FqxError::cast(FqxValueType::Blob, FqxValueType::I32)),
+            FqxValueType::I64 => Err(FqxError::cast(FqxValueType::Blob, FqxValueType::I64)),
+            FqxValueType::F32 => Err(FqxError::cast(FqxValueType::Blob, FqxValueType::F32)),
+            FqxValueType::F64 => Err(FqxError::cast(FqxValueType::Blob, FqxValueType::F64)),
+            FqxValueType::Decimal => Err(FqxError::cast(FqxValueType::Blob, FqxValueType::Decimal)),
             FqxValueType::String => Ok(FqxValue::String(String::from_utf8(self)?)),
             FqxValueType::Blob => Ok(FqxValue::Blob(self)),
             FqxValueType::Null => Ok(FqxValue::Null),
-            _ => bail!("cannot cast Vec<u8> into time-like types"),
+            t => Err(FqxError::cast(FqxValueType::Blob, t.into())),
         }
     }
 }
 
 impl TryCast for DateTime<Local> {
-    fn try_cast(self, typ: &FqxValueType) -> Result<FqxValue> {
+    fn try_cast(self, typ: &FqxValueType) -> FqxResult<FqxValue> {
         match typ {
             FqxValueType::Timestamp => Ok(FqxValue::Timestamp(self)),
             FqxValueType::String => Ok(FqxValue::String(format!("{:?}", self))),
-            _ => bail!("cannot cast into timestamp"),
+            t => Err(FqxError::cast(FqxValueType::Timestamp, t.into())),
         }
     }
 }
 
 impl TryCast for NaiveDateTime {
-    fn try_cast(self, typ: &FqxValueType) -> Result<FqxValue> {
+    fn try_cast(self, typ: &FqxValueType) -> FqxResult<FqxValue> {
         match typ {
             FqxValueType::DateTime => Ok(FqxValue::DateTime(self)),
             FqxValueType::String => Ok(FqxValue::String(format!("{:?}", self))),
-            _ => bail!("cannot cast into date_time"),
+            t => Err(FqxError::cast(FqxValueType::DateTime, t.into())),
         }
     }
 }
 
 impl TryCast for NaiveDate {
-    fn try_cast(self, typ: &FqxValueType) -> Result<FqxValue> {
+    fn try_cast(self, typ: &FqxValueType) -> FqxResult<FqxValue> {
         match typ {
             FqxValueType::Date => Ok(FqxValue::Date(self)),
             FqxValueType::String => Ok(FqxValue::String(format!("{:?}", self))),
-            _ => bail!("cannot cast into date"),
+            t => Err(FqxError::cast(FqxValueType::Date, t.into())),
         }
     }
 }
 
 impl TryCast for NaiveTime {
-    fn try_cast(self, typ: &FqxValueType) -> Result<FqxValue> {
+    fn try_cast(self, typ: &FqxValueType) -> FqxResult<FqxValue> {
         match typ {
             FqxValueType::Time => Ok(FqxValue::Time(self)),
             FqxValueType::String => Ok(FqxValue::String(format!("{:?}", self))),
-            _ => bail!("cannot cast into time"),
+            t => Err(FqxError::cast(FqxValueType::Time, t.into())),
         }
     }
 }
@@ -618,14 +722,14 @@ impl From<tiberius::ColumnType> for FqxValueType {
             tiberius::ColumnType::Datetime4 => unimplemented!(),
             tiberius::ColumnType::Float4 => FqxValueType::F32,
             tiberius::ColumnType::Float8 => FqxValueType::F64,
-            tiberius::ColumnType::Money => unimplemented!(),
+            tiberius::ColumnType::Money => FqxValueType::F64, // `tiberius` decodes money as f64
             tiberius::ColumnType::Datetime => FqxValueType::DateTime,
-            tiberius::ColumnType::Money4 => unimplemented!(),
+            tiberius::ColumnType::Money4 => FqxValueType::F64,
             tiberius::ColumnType::Guid => unimplemented!(),
             tiberius::ColumnType::Intn => FqxValueType::I64,
             tiberius::ColumnType::Bitn => FqxValueType::Bool,
-            tiberius::ColumnType::Decimaln => unimplemented!(),
-            tiberius::ColumnType::Numericn => unimplemented!(),
+            tiberius::ColumnType::Decimaln => FqxValueType::Decimal,
+            tiberius::ColumnType::Numericn => FqxValueType::Decimal,
             tiberius::ColumnType::Floatn => FqxValueType::F64,
             tiberius::ColumnType::Datetimen => FqxValueType::DateTime,
             tiberius::ColumnType::Daten => FqxValueType::Date,
@@ -726,4 +830,20 @@ mod test_cvt {
         println!("{:?}", v3_.try_cast(&FqxValueType::DateTime));
         println!("{:?}", v4_.try_cast(&FqxValueType::Timestamp));
     }
+
+    #[test]
+    fn fqx_decimal_value_conversions_success() {
+        let d = Decimal::from_str("1234.5678").unwrap();
+
+        let v = fqx!(d);
+        println!("{:?}", v);
+
+        let s = v.to_string();
+        assert_eq!(s, "1234.5678");
+        assert_eq!(s.try_cast(&FqxValueType::Decimal).unwrap(), v);
+
+        assert_eq!(v.clone().try_cast(&FqxValueType::I64).unwrap(), fqx!(1234i64));
+        println!("{:?}", v.clone().try_cast(&FqxValueType::F64));
+        println!("{:?}", fqx!(2.5f64).try_cast(&FqxValueType::Decimal));
+    }
 }
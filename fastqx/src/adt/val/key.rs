@@ -0,0 +1,185 @@
+//! file: key.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 21:40:12 Saturday
+//! brief:
+
+use std::borrow::Borrow;
+use std::hash::{Hash, Hasher};
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+
+use super::FqxValue;
+
+// ================================================================================================
+// Canonical
+// ================================================================================================
+
+/// The form in which a value is hashed and compared as a key.
+///
+/// Numbers of any type collapse into one form: integral values into `Int`, fractions into the
+/// shortest `Decimal` that converts back to the same float, and floats out of a decimal's reach
+/// (tiny, huge or NaN) into their bits. Hence `I32(1) == F64(1.0) == Decimal(1)` and
+/// `F32(0.1) == F64(0.1) == Decimal(0.1)` as keys, while `U8(255)` and `I8(-1)` are different.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Canonical<'a> {
+    Null,
+    Bool(bool),
+    Int(i128),
+    Decimal(Decimal),
+    Float(u64),
+    String(&'a str),
+    Blob(&'a [u8]),
+    Timestamp(DateTime<Local>),
+    DateTime(NaiveDateTime),
+    Date(NaiveDate),
+    Time(NaiveTime),
+}
+
+fn _canonical_decimal(d: Decimal) -> Canonical<'static> {
+    match d.fract().is_zero() {
+        true => Canonical::Int(d.to_i128().unwrap_or_default()),
+        false => Canonical::Decimal(d.normalize()),
+    }
+}
+
+fn _canonical_float(f: f64, d: Option<Decimal>) -> Canonical<'static> {
+    if f.is_nan() {
+        return Canonical::Float(f64::NAN.to_bits());
+    }
+    if f.fract() == 0.0 && f.abs() < i128::MAX as f64 {
+        return Canonical::Int(f as i128);
+    }
+
+    match d {
+        Some(d) => _canonical_decimal(d),
+        None => Canonical::Float(f.to_bits()),
+    }
+}
+
+impl FqxValue {
+    pub fn canonical(&self) -> Canonical<'_> {
+        match self {
+            FqxValue::Bool(v) => Canonical::Bool(*v),
+            FqxValue::U8(v) => Canonical::Int(*v as i128),
+            FqxValue::U16(v) => Canonical::Int(*v as i128),
+            FqxValue::U32(v) => Canonical::Int(*v as i128),
+            FqxValue::U64(v) => Canonical::Int(*v as i128),
+            FqxValue::I8(v) => Canonical::Int(*v as i128),
+            FqxValue::I16(v) => Canonical::Int(*v as i128),
+            FqxValue::I32(v) => Canonical::Int(*v as i128),
+            FqxValue::I64(v) => Canonical::Int(*v as i128),
+            FqxValue::F32(v) => _canonical_float(
+                *v as f64,
+                Decimal::from_f32(*v).filter(|d| d.to_f32() == Some(*v)),
+            ),
+            FqxValue::F64(v) => {
+                _canonical_float(*v, Decimal::from_f64(*v).filter(|d| d.to_f64() == Some(*v)))
+            }
+            FqxValue::Decimal(v) => _canonical_decimal(*v),
+            FqxValue::String(v) => Canonical::String(v),
+            FqxValue::Blob(v) => Canonical::Blob(v),
+            FqxValue::Timestamp(v) => Canonical::Timestamp(*v),
+            FqxValue::DateTime(v) => Canonical::DateTime(*v),
+            FqxValue::Date(v) => Canonical::Date(*v),
+            FqxValue::Time(v) => Canonical::Time(*v),
+            FqxValue::Null => Canonical::Null,
+        }
+    }
+}
+
+// ================================================================================================
+// FqxKey
+// ================================================================================================
+
+/// A composite key, owned or borrowed, whose `Eq` and `Hash` both go through [`Canonical`]; to be
+/// used for hash-based grouping and joining instead of `Vec<FqxValue>`.
+#[derive(Debug, Clone)]
+pub struct FqxKey<V>(pub Vec<V>);
+
+impl<V: Borrow<FqxValue>> PartialEq for FqxKey<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(other.0.iter())
+                .all(|(l, r)| l.borrow().canonical() == r.borrow().canonical())
+    }
+}
+
+impl<V: Borrow<FqxValue>> Eq for FqxKey<V> {}
+
+impl<V: Borrow<FqxValue>> Hash for FqxKey<V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for v in self.0.iter() {
+            v.borrow().canonical().hash(state);
+        }
+    }
+}
+
+impl<V> FromIterator<V> for FqxKey<V> {
+    fn from_iter<I: IntoIterator<Item = V>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_key {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn canonical_success() {
+        let one = [
+            FqxValue::U8(1),
+            FqxValue::I64(1),
+            FqxValue::F32(1.0),
+            FqxValue::F64(1.0),
+            FqxValue::Decimal(Decimal::new(100, 2)),
+        ];
+        assert!(one.iter().all(|v| v.canonical() == Canonical::Int(1)));
+
+        let tenth = [
+            FqxValue::F32(0.1),
+            FqxValue::F64(0.1),
+            FqxValue::Decimal(Decimal::new(1, 1)),
+        ];
+        assert!(tenth.iter().all(|v| v.canonical() == tenth[0].canonical()));
+
+        assert_ne!(FqxValue::U8(255).canonical(), FqxValue::I8(-1).canonical());
+        assert_ne!(
+            FqxValue::I64(i64::MAX).canonical(),
+            FqxValue::I64(i64::MAX - 1).canonical()
+        );
+        assert_ne!(
+            FqxValue::F64(1e-30).canonical(),
+            FqxValue::F64(0.0).canonical()
+        );
+        assert_eq!(
+            FqxValue::F64(-0.0).canonical(),
+            FqxValue::F64(0.0).canonical()
+        );
+        assert_eq!(
+            FqxValue::F64(f64::NAN).canonical(),
+            FqxValue::F32(f32::NAN).canonical()
+        );
+    }
+
+    #[test]
+    fn key_success() {
+        let owned = FqxKey(vec![FqxValue::I32(1), FqxValue::from("a")]);
+        let (v0, v1) = (FqxValue::F64(1.0), FqxValue::from("a"));
+        let borrowed = FqxKey(vec![&v0, &v1]);
+
+        let set = HashSet::from([owned]);
+        assert!(set.contains(&FqxKey(borrowed.0.into_iter().cloned().collect())));
+        assert!(!set.contains(&FqxKey(vec![FqxValue::I32(1)])));
+    }
+}
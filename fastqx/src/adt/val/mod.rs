@@ -4,7 +4,9 @@
 //! brief:
 
 pub mod cvt;
+pub mod key;
 pub mod value;
 
 pub use cvt::*;
+pub use key::*;
 pub use value::*;
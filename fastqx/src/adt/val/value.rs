@@ -8,12 +8,13 @@ use std::hash::Hash;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign};
 use std::str::FromStr;
 
-use anyhow::{bail, Result};
 use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime};
 use pyo3::{prelude::*, types::PyType};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 use crate::adt::TryCast;
+use crate::error::{FqxError, FqxResult};
 
 // ================================================================================================
 // FqxValueType & FqxValue
@@ -34,6 +35,7 @@ pub enum FqxValueType {
     I64,
     F32,
     F64,
+    Decimal,
     String,
     Blob,
     Timestamp,
@@ -57,6 +59,10 @@ pub enum FqxValue {
     I64(i64),
     F32(f32),
     F64(f64),
+    // serialized as a string, numeric-like strings shall not be deserialized as decimals; `FqxData`
+    // restores them by its column types
+    #[serde(skip_deserializing)]
+    Decimal(Decimal),
     String(String),
     Blob(Vec<u8>),
     Timestamp(DateTime<Local>),
@@ -84,6 +90,7 @@ impl FqxValue {
             FqxValue::I64(_) => matches!(t, FqxValueType::I64),
             FqxValue::F32(_) => matches!(t, FqxValueType::F32),
             FqxValue::F64(_) => matches!(t, FqxValueType::F64),
+            FqxValue::Decimal(_) => matches!(t, FqxValueType::Decimal),
             FqxValue::String(_) => matches!(t, FqxValueType::String),
             FqxValue::Blob(_) => matches!(t, FqxValueType::Blob),
             FqxValue::Timestamp(_) => matches!(t, FqxValueType::Timestamp),
@@ -97,120 +104,11 @@ impl FqxValue {
 
 ///////////////////////////////////////////////////////////////////////////////////////////////////
 
+// Compares the canonical form, as `Hash` does, so that both agree across types: `I32(1) == F64(1.0)`
+// but `U8(255) != I8(-1)`, and NaN equals itself.
 impl PartialEq for FqxValue {
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Self::Bool(l0), Self::Bool(r0)) => l0 == r0,
-            (Self::String(l0), Self::String(r0)) => l0 == r0,
-            (Self::Blob(l0), Self::Blob(r0)) => l0 == r0,
-            (Self::Timestamp(l0), Self::Timestamp(r0)) => l0 == r0,
-            (Self::DateTime(l0), Self::DateTime(r0)) => l0 == r0,
-            (Self::Date(l0), Self::Date(r0)) => l0 == r0,
-            (Self::Time(l0), Self::Time(r0)) => l0 == r0,
-            (Self::Null, Self::Null) => true,
-            // numeric types comparing
-            (Self::U8(l), Self::U8(r)) => l == r,
-            (Self::U8(l), Self::U16(r)) => *l as u16 == *r,
-            (Self::U8(l), Self::U32(r)) => *l as u32 == *r,
-            (Self::U8(l), Self::U64(r)) => *l as u64 == *r,
-            (Self::U8(l), Self::I8(r)) => *l as i8 == *r,
-            (Self::U8(l), Self::I16(r)) => *l as i16 == *r,
-            (Self::U8(l), Self::I32(r)) => *l as i32 == *r,
-            (Self::U8(l), Self::I64(r)) => *l as i64 == *r,
-            (Self::U8(l), Self::F32(r)) => *l as f32 == *r,
-            (Self::U8(l), Self::F64(r)) => *l as f64 == *r,
-            (Self::U16(l), Self::U8(r)) => *l == *r as u16,
-            (Self::U16(l), Self::U16(r)) => l == r,
-            (Self::U16(l), Self::U32(r)) => *l as u32 == *r,
-            (Self::U16(l), Self::U64(r)) => *l as u64 == *r,
-            (Self::U16(l), Self::I8(r)) => *l as i8 == *r,
-            (Self::U16(l), Self::I16(r)) => *l as i16 == *r,
-            (Self::U16(l), Self::I32(r)) => *l as i32 == *r,
-            (Self::U16(l), Self::I64(r)) => *l as i64 == *r,
-            (Self::U16(l), Self::F32(r)) => *l as f32 == *r,
-            (Self::U16(l), Self::F64(r)) => *l as f64 == *r,
-            (Self::U32(l), Self::U8(r)) => *l == *r as u32,
-            (Self::U32(l), Self::U16(r)) => *l == *r as u32,
-            (Self::U32(l), Self::U32(r)) => l == r,
-            (Self::U32(l), Self::U64(r)) => *l as u64 == *r,
-            (Self::U32(l), Self::I8(r)) => *l as i8 == *r,
-            (Self::U32(l), Self::I16(r)) => *l as i16 == *r,
-            (Self::U32(l), Self::I32(r)) => *l as i32 == *r,
-            (Self::U32(l), Self::I64(r)) => *l as i64 == *r,
-            (Self::U32(l), Self::F32(r)) => *l as f32 == *r,
-            (Self::U32(l), Self::F64(r)) => *l as f64 == *r,
-            (Self::U64(l), Self::U8(r)) => *l == *r as u64,
-            (Self::U64(l), Self::U16(r)) => *l == *r as u64,
-            (Self::U64(l), Self::U32(r)) => *l == *r as u64,
-            (Self::U64(l), Self::U64(r)) => l == r,
-            (Self::U64(l), Self::I8(r)) => *l as i8 == *r,
-            (Self::U64(l), Self::I16(r)) => *l as i16 == *r,
-            (Self::U64(l), Self::I32(r)) => *l as i32 == *r,
-            (Self::U64(l), Self::I64(r)) => *l as i64 == *r,
-            (Self::U64(l), Self::F32(r)) => *l as f32 == *r,
-            (Self::U64(l), Self::F64(r)) => *l as f64 == *r,
-            (Self::I8(l), Self::U8(r)) => *l == *r as i8,
-            (Self::I8(l), Self::U16(r)) => *l == *r as i8,
-            (Self::I8(l), Self::U32(r)) => *l == *r as i8,
-            (Self::I8(l), Self::U64(r)) => *l == *r as i8,
-            (Self::I8(l), Self::I8(r)) => l == r,
-            (Self::I8(l), Self::I16(r)) => *l as i16 == *r,
-            (Self::I8(l), Self::I32(r)) => *l as i32 == *r,
-            (Self::I8(l), Self::I64(r)) => *l as i64 == *r,
-            (Self::I8(l), Self::F32(r)) => *l as f32 == *r,
-            (Self::I8(l), Self::F64(r)) => *l as f64 == *r,
-            (Self::I16(l), Self::U8(r)) => *l == *r as i16,
-            (Self::I16(l), Self::U16(r)) => *l == *r as i16,
-            (Self::I16(l), Self::U32(r)) => *l == *r as i16,
-            (Self::I16(l), Self::U64(r)) => *l == *r as i16,
-            (Self::I16(l), Self::I8(r)) => *l == *r as i16,
-            (Self::I16(l), Self::I16(r)) => l == r,
-            (Self::I16(l), Self::I32(r)) => *l as i32 == *r,
-            (Self::I16(l), Self::I64(r)) => *l as i64 == *r,
-            (Self::I16(l), Self::F32(r)) => *l as f32 == *r,
-            (Self::I16(l), Self::F64(r)) => *l as f64 == *r,
-            (Self::I32(l), Self::U8(r)) => *l == *r as i32,
-            (Self::I32(l), Self::U16(r)) => *l == *r as i32,
-            (Self::I32(l), Self::U32(r)) => *l == *r as i32,
-            (Self::I32(l), Self::U64(r)) => *l == *r as i32,
-            (Self::I32(l), Self::I8(r)) => *l == *r as i32,
-            (Self::I32(l), Self::I16(r)) => *l == *r as i32,
-            (Self::I32(l), Self::I32(r)) => l == r,
-            (Self::I32(l), Self::I64(r)) => *l as i64 == *r,
-            (Self::I32(l), Self::F32(r)) => *l as f32 == *r,
-            (Self::I32(l), Self::F64(r)) => *l as f64 == *r,
-            (Self::I64(l), Self::U8(r)) => *l == *r as i64,
-            (Self::I64(l), Self::U16(r)) => *l == *r as i64,
-            (Self::I64(l), Self::U32(r)) => *l == *r as i64,
-            (Self::I64(l), Self::U64(r)) => *l == *r as i64,
-            (Self::I64(l), Self::I8(r)) => *l == *r as i64,
-            (Self::I64(l), Self::I16(r)) => *l == *r as i64,
-            (Self::I64(l), Self::I32(r)) => *l == *r as i64,
-            (Self::I64(l), Self::I64(r)) => l == r,
-            (Self::I64(l), Self::F32(r)) => *l as f32 == *r,
-            (Self::I64(l), Self::F64(r)) => *l as f64 == *r,
-            (Self::F32(l), Self::U8(r)) => *l == *r as f32,
-            (Self::F32(l), Self::U16(r)) => *l == *r as f32,
-            (Self::F32(l), Self::U32(r)) => *l == *r as f32,
-            (Self::F32(l), Self::U64(r)) => *l == *r as f32,
-            (Self::F32(l), Self::I8(r)) => *l == *r as f32,
-            (Self::F32(l), Self::I16(r)) => *l == *r as f32,
-            (Self::F32(l), Self::I32(r)) => *l == *r as f32,
-            (Self::F32(l), Self::I64(r)) => *l == *r as f32,
-            (Self::F32(l), Self::F32(r)) => l == r,
-            (Self::F32(l), Self::F64(r)) => *l == *r as f32,
-            (Self::F64(l), Self::U8(r)) => *l == *r as f64,
-            (Self::F64(l), Self::U16(r)) => *l == *r as f64,
-            (Self::F64(l), Self::U32(r)) => *l == *r as f64,
-            (Self::F64(l), Self::U64(r)) => *l == *r as f64,
-            (Self::F64(l), Self::I8(r)) => *l == *r as f64,
-            (Self::F64(l), Self::I16(r)) => *l == *r as f64,
-            (Self::F64(l), Self::I32(r)) => *l == *r as f64,
-            (Self::F64(l), Self::I64(r)) => *l == *r as f64,
-            (Self::F64(l), Self::F32(r)) => *l == *r as f64,
-            (Self::F64(l), Self::F64(r)) => l == r,
-            _ => core::mem::discriminant(self) == core::mem::discriminant(other),
-        }
+        self.canonical() == other.canonical()
     }
 }
 
@@ -328,6 +226,14 @@ impl PartialOrd for FqxValue {
             (Self::F64(l), Self::I64(r)) => l.partial_cmp(&(*r as f64)),
             (Self::F64(l), Self::F32(r)) => l.partial_cmp(&(*r as f64)),
             (Self::F64(l), Self::F64(r)) => l.partial_cmp(r),
+            // decimal ordering
+            (Self::Decimal(l), Self::Decimal(r)) => Some(l.cmp(r)),
+            (Self::Decimal(l), r) if r.is_numeric() => {
+                Decimal::try_from(r).ok().map(|r| l.cmp(&r))
+            }
+            (l, Self::Decimal(r)) if l.is_numeric() => {
+                Decimal::try_from(l).ok().map(|l| l.cmp(r))
+            }
             _ => None,
         }
     }
@@ -339,10 +245,9 @@ impl Ord for FqxValue {
     }
 }
 
-// IMPORTANT! Doesn't work on F32 & F64!
 impl Hash for FqxValue {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        core::mem::discriminant(self).hash(state);
+        self.canonical().hash(state);
     }
 }
 
@@ -360,6 +265,7 @@ impl PartialEq<FqxValueType> for FqxValue {
             FqxValue::I64(_) => matches!(other, &FqxValueType::I64),
             FqxValue::F32(_) => matches!(other, &FqxValueType::F32),
             FqxValue::F64(_) => matches!(other, &FqxValueType::F64),
+            FqxValue::Decimal(_) => matches!(other, &FqxValueType::Decimal),
             FqxValue::String(_) => matches!(other, &FqxValueType::String),
             FqxValue::Blob(_) => matches!(other, &FqxValueType::Blob),
             FqxValue::Timestamp(_) => matches!(other, &FqxValueType::Timestamp),
@@ -415,7 +321,24 @@ impl FqxValue {
         }
     }
 
-    pub fn try_cast(self, typ: &FqxValueType) -> Result<Self> {
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            FqxValue::U8(_)
+                | FqxValue::U16(_)
+                | FqxValue::U32(_)
+                | FqxValue::U64(_)
+                | FqxValue::I8(_)
+                | FqxValue::I16(_)
+                | FqxValue::I32(_)
+                | FqxValue::I64(_)
+                | FqxValue::F32(_)
+                | FqxValue::F64(_)
+                | FqxValue::Decimal(_)
+        )
+    }
+
+    pub fn try_cast(self, typ: &FqxValueType) -> FqxResult<Self> {
         match self {
             FqxValue::Bool(v) => v.try_cast(typ),
             FqxValue::U8(v) => v.try_cast(typ),
@@ -428,6 +351,7 @@ impl FqxValue {
             FqxValue::I64(v) => v.try_cast(typ),
             FqxValue::F32(v) => v.try_cast(typ),
             FqxValue::F64(v) => v.try_cast(typ),
+            FqxValue::Decimal(v) => v.try_cast(typ),
             FqxValue::String(v) => v.try_cast(typ),
             FqxValue::Blob(v) => v.try_cast(typ),
             FqxValue::Timestamp(v) => v.try_cast(typ),
@@ -438,7 +362,7 @@ impl FqxValue {
         }
     }
 
-    pub fn try_cast_mut(&mut self, typ: &FqxValueType) -> Result<()> {
+    pub fn try_cast_mut(&mut self, typ: &FqxValueType) -> FqxResult<()> {
         match self {
             FqxValue::Bool(v) => *self = v.try_cast(typ)?,
             FqxValue::U8(v) => *self = v.try_cast(typ)?,
@@ -451,6 +375,7 @@ impl FqxValue {
             FqxValue::I64(v) => *self = v.try_cast(typ)?,
             FqxValue::F32(v) => *self = v.try_cast(typ)?,
             FqxValue::F64(v) => *self = v.try_cast(typ)?,
+            FqxValue::Decimal(v) => *self = v.try_cast(typ)?,
             FqxValue::String(v) => *self = v.clone().try_cast(typ)?,
             FqxValue::Blob(v) => *self = v.clone().try_cast(typ)?,
             FqxValue::Timestamp(v) => *self = v.clone().try_cast(typ)?,
@@ -488,6 +413,7 @@ impl From<&FqxValueType> for FqxValueType {
             FqxValueType::I64 => FqxValueType::I64,
             FqxValueType::F32 => FqxValueType::F32,
             FqxValueType::F64 => FqxValueType::F64,
+            FqxValueType::Decimal => FqxValueType::Decimal,
             FqxValueType::String => FqxValueType::String,
             FqxValueType::Blob => FqxValueType::Blob,
             FqxValueType::Timestamp => FqxValueType::Timestamp,
@@ -500,7 +426,7 @@ impl From<&FqxValueType> for FqxValueType {
 }
 
 impl FromStr for FqxValueType {
-    type Err = anyhow::Error;
+    type Err = FqxError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -515,6 +441,7 @@ impl FromStr for FqxValueType {
             "i64" => Ok(Self::I64),
             "f32" => Ok(Self::F32),
             "f64" => Ok(Self::F64),
+            "decimal" => Ok(Self::Decimal),
             "string" => Ok(Self::String),
             "blob" => Ok(Self::Blob),
             "timestamp" => Ok(Self::Timestamp),
@@ -522,7 +449,7 @@ impl FromStr for FqxValueType {
             "date" => Ok(Self::Date),
             "time" => Ok(Self::Time),
             "null" => Ok(Self::Null),
-            _ => bail!("wrong type"),
+            _ => Err(FqxError::invalid(format!("wrong type: {s}"))),
         }
     }
 }
@@ -531,8 +458,10 @@ impl FromStr for FqxValueType {
 // Arithmetic: FqxValue
 // ================================================================================================
 
+// `Decimal` operators panic on overflow and division by zero, hence the checked methods (`$chk`)
+// which give null instead.
 macro_rules! binary_fn {
-    ($lhs:expr, $op:tt, $rhs:expr) => {
+    ($lhs:expr, $op:tt, $chk:ident, $rhs:expr) => {
         match $lhs {
             FqxValue::U8(v) => u8::try_from($rhs)
                 .map(|n| FqxValue::U8(v $op n))
@@ -564,13 +493,17 @@ macro_rules! binary_fn {
             FqxValue::F64(v) => f64::try_from($rhs)
                 .map(|n| FqxValue::F64(v $op n))
                 .unwrap_or_default(),
+            FqxValue::Decimal(v) => Decimal::try_from($rhs)
+                .ok()
+                .and_then(|n| v.$chk(n))
+                .map_or(FqxValue::Null, FqxValue::Decimal),
             _ => FqxValue::Null,
         }
     };
 }
 
 macro_rules! assign_fn {
-    ($lhs:expr, $op:tt, $rhs:expr) => {
+    ($lhs:expr, $op:tt, $chk:ident, $rhs:expr) => {
         match $lhs {
             FqxValue::U8(v) => {
                 if let Err(_) = u8::try_from($rhs).map(|n| *v $op n) {
@@ -622,6 +555,12 @@ macro_rules! assign_fn {
                     *$lhs = FqxValue::Null;
                 }
             }
+            FqxValue::Decimal(v) => {
+                *$lhs = Decimal::try_from($rhs)
+                    .ok()
+                    .and_then(|n| v.$chk(n))
+                    .map_or(FqxValue::Null, FqxValue::Decimal);
+            }
             _ => {
                 *$lhs = FqxValue::Null;
             }
@@ -630,18 +569,18 @@ macro_rules! assign_fn {
 }
 
 macro_rules! impl_arith_for_value {
-    ($t:ident, $tf:tt, $ta:ident, $taf:tt, $op:tt, $opa:tt) => {
+    ($t:ident, $tf:tt, $ta:ident, $taf:tt, $op:tt, $opa:tt, $chk:ident) => {
         impl $t for FqxValue {
             type Output = Self;
 
             fn $tf(self, rhs: Self) -> Self::Output {
-                binary_fn!(self, $op, rhs)
+                binary_fn!(self, $op, $chk, rhs)
             }
         }
 
         impl $ta for FqxValue {
             fn $taf(&mut self, rhs: Self) {
-                assign_fn!(self, $opa, rhs)
+                assign_fn!(self, $opa, $chk, rhs)
             }
         }
 
@@ -649,17 +588,17 @@ macro_rules! impl_arith_for_value {
             type Output = FqxValue;
 
             fn $tf(self, rhs: Self) -> Self::Output {
-                binary_fn!(self, $op, rhs)
+                binary_fn!(self, $op, $chk, rhs)
             }
         }
     };
 }
 
-impl_arith_for_value!(Add, add, AddAssign, add_assign, +, +=);
-impl_arith_for_value!(Sub, sub, SubAssign, sub_assign, -, -=);
-impl_arith_for_value!(Mul, mul, MulAssign, mul_assign, *, *=);
-impl_arith_for_value!(Div, div, DivAssign, div_assign, /, /=);
-impl_arith_for_value!(Rem, rem, RemAssign, rem_assign, %, %=);
+impl_arith_for_value!(Add, add, AddAssign, add_assign, +, +=, checked_add);
+impl_arith_for_value!(Sub, sub, SubAssign, sub_assign, -, -=, checked_sub);
+impl_arith_for_value!(Mul, mul, MulAssign, mul_assign, *, *=, checked_mul);
+impl_arith_for_value!(Div, div, DivAssign, div_assign, /, /=, checked_div);
+impl_arith_for_value!(Rem, rem, RemAssign, rem_assign, %, %=, checked_rem);
 
 // ================================================================================================
 // Py
@@ -704,6 +643,7 @@ impl FqxValueType {
             FqxValueType::I64 => "FqxValueType::I64",
             FqxValueType::F32 => "FqxValueType::F32",
             FqxValueType::F64 => "FqxValueType::F64",
+            FqxValueType::Decimal => "FqxValueType::Decimal",
             FqxValueType::String => "FqxValueType::String",
             FqxValueType::Blob => "FqxValueType::Blob",
             FqxValueType::Timestamp => "FqxValueType::Timestamp",
@@ -730,6 +670,32 @@ mod test_value {
         println!("{:?}", a1);
     }
 
+    #[test]
+    fn eq_hash_success() {
+        fn _hash(v: &FqxValue) -> u64 {
+            use std::hash::Hasher;
+
+            let mut h = std::collections::hash_map::DefaultHasher::new();
+            v.hash(&mut h);
+            h.finish()
+        }
+
+        let pairs = [
+            (FqxValue::I32(1), FqxValue::F64(1.0)),
+            (FqxValue::U64(7), FqxValue::Decimal(Decimal::new(700, 2))),
+            (FqxValue::F32(0.1), FqxValue::F64(0.1)),
+            (FqxValue::F64(f64::NAN), FqxValue::F64(f64::NAN)),
+        ];
+        for (l, r) in pairs.iter() {
+            assert_eq!(l, r);
+            assert_eq!(_hash(l), _hash(r));
+        }
+
+        assert_ne!(FqxValue::U8(255), FqxValue::I8(-1));
+        assert_ne!(FqxValue::I64(i64::MAX), FqxValue::F64(i64::MAX as f64));
+        assert_ne!(FqxValue::I32(1), FqxValue::from("1"));
+    }
+
     #[test]
     fn division_success() {
         let res = FqxValue::F32(6.6) / FqxValue::F32(3.0);
@@ -746,6 +712,43 @@ mod test_value {
         println!("{:?}", res);
     }
 
+    #[test]
+    fn decimal_arithmetic_success() {
+        let d1 = FqxValue::Decimal(Decimal::from_str("0.1").unwrap());
+        let d2 = FqxValue::Decimal(Decimal::from_str("0.2").unwrap());
+
+        let res = &d1 + &d2;
+        println!("{:?}", res);
+        assert_eq!(res, FqxValue::Decimal(Decimal::from_str("0.3").unwrap()));
+
+        let res = d2.clone() * FqxValue::I32(3);
+        println!("{:?}", res);
+        assert!(res > FqxValue::F64(0.5));
+        assert_eq!(FqxValue::I64(2), d2 * FqxValue::I32(10));
+    }
+
+    #[test]
+    fn decimal_arithmetic_overflow_success() {
+        let max = FqxValue::Decimal(Decimal::MAX);
+        let zero = FqxValue::Decimal(Decimal::ZERO);
+
+        assert_eq!(&max + &FqxValue::I32(1), FqxValue::Null);
+        assert_eq!(&max * &FqxValue::I32(2), FqxValue::Null);
+        assert_eq!(&FqxValue::Decimal(Decimal::MIN) - &max, FqxValue::Null);
+        assert_eq!(&max / &zero, FqxValue::Null);
+        assert_eq!(&max % &zero, FqxValue::Null);
+
+        let mut v = max.clone();
+        v += FqxValue::I32(1);
+        assert_eq!(v, FqxValue::Null);
+        let mut v = max;
+        v /= zero;
+        assert_eq!(v, FqxValue::Null);
+        let mut v = FqxValue::Decimal(Decimal::ONE);
+        v -= FqxValue::I32(3);
+        assert_eq!(v, FqxValue::Decimal(Decimal::from(-2)));
+    }
+
     #[test]
     fn type_from_str() {
         println!("{:?}", FqxValueType::from_str("time"));
@@ -753,5 +756,6 @@ mod test_value {
         println!("{:?}", FqxValueType::from_str("timestamp"));
         println!("{:?}", FqxValueType::from_str("string"));
         println!("{:?}", FqxValueType::from_str("f32"));
+        println!("{:?}", FqxValueType::from_str("decimal"));
     }
 }
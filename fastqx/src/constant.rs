@@ -13,6 +13,9 @@ use crate::adt::FqxValueType;
 // Const
 // ================================================================================================
 
+// column definition shared by all the drivers when creating a `FqxValueType::Decimal` column
+pub(crate) const DECIMAL_COLUMN_DEF: &str = "DECIMAL(38, 10)";
+
 // https://docs.rs/sqlx-mysql/0.7.2/sqlx_mysql/types/index.html
 pub(crate) static MYSQL_TMAP: Lazy<HashMap<&'static str, FqxValueType>> = Lazy::new(|| {
     HashMap::from([
@@ -28,6 +31,7 @@ pub(crate) static MYSQL_TMAP: Lazy<HashMap<&'static str, FqxValueType>> = Lazy::
         ("BIGINT", FqxValueType::I64),
         ("FLOAT", FqxValueType::F32),
         ("DOUBLE", FqxValueType::F64),
+        ("DECIMAL", FqxValueType::Decimal),
         ("VARCHAR", FqxValueType::String),
         ("CHAR", FqxValueType::String),
         ("TEXT", FqxValueType::String),
@@ -59,6 +63,7 @@ pub(crate) static POSTGRES_TMAP: Lazy<HashMap<&'static str, FqxValueType>> = Laz
         ("FLOAT4", FqxValueType::F32),
         ("DOUBLE PRECISION", FqxValueType::F64),
         ("FLOAT8", FqxValueType::F64),
+        ("NUMERIC", FqxValueType::Decimal),
         ("VARCHAR", FqxValueType::String),
         ("CHAR(N)", FqxValueType::String),
         ("TEXT", FqxValueType::String),
@@ -79,6 +84,7 @@ pub(crate) static SQLITE_TMAP: Lazy<HashMap<&'static str, FqxValueType>> = Lazy:
         ("BIGINT", FqxValueType::I64),
         ("INT8", FqxValueType::I64),
         ("REAL", FqxValueType::F64),
+        ("NUMERIC", FqxValueType::Decimal),
         ("VARCHAR", FqxValueType::String),
         ("CHAR(N)", FqxValueType::String),
         ("TEXT", FqxValueType::String),
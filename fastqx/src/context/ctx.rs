@@ -0,0 +1,589 @@
+//! file: ctx.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 18:31:54 Saturday
+//! brief:
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::adt::{FqxD, FqxData, FqxRow, FqxValue, FqxValueType};
+use crate::context::parse::{parse_query, Query, SelectItem, TableRef};
+use crate::error::{FqxError, FqxResult};
+use crate::ops::utils::{_cmp_keys, _cmp_nulls_last};
+use crate::ops::{Agg, Expr, FqxJoinOptions, OpAgg, OpExpr, OpFilter, OpGroup, OpMerge, OpSort};
+
+// ================================================================================================
+// FqxContext
+//
+// Named `FqxData` tables, queried with a subset of SQL (see `parse.rs`). While a query runs,
+// every column is qualified by its table alias, so that joined tables may share column names.
+// Output columns drop the qualifier unless it is needed to tell them apart.
+// ================================================================================================
+
+#[derive(Debug, Clone, Default)]
+pub struct FqxContext {
+    tables: HashMap<String, FqxData>,
+}
+
+impl FqxContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the table previously registered under `name`, if any.
+    pub fn register<S: Into<String>>(&mut self, name: S, data: FqxData) -> Option<FqxData> {
+        self.tables.insert(name.into(), data)
+    }
+
+    pub fn deregister(&mut self, name: &str) -> Option<FqxData> {
+        self.tables.remove(name)
+    }
+
+    pub fn table(&self, name: &str) -> Option<&FqxData> {
+        self.tables.get(name)
+    }
+
+    pub fn table_names(&self) -> Vec<&str> {
+        let mut res = self.tables.keys().map(String::as_str).collect::<Vec<_>>();
+        res.sort();
+        res
+    }
+
+    pub fn sql(&self, query: &str) -> FqxResult<FqxData> {
+        let q = parse_query(query)?;
+        let aliases = std::iter::once(&q.from)
+            .chain(q.joins.iter().map(|j| &j.table))
+            .map(|t| t.alias.clone())
+            .collect::<Vec<_>>();
+
+        let d = self.from(&q)?;
+        let d = match &q.selection {
+            Some(p) => _filter(d, p)?,
+            None => d,
+        };
+
+        let is_agg =
+            !q.group_by.is_empty() || q.items.iter().any(|i| matches!(i, SelectItem::Agg { .. }));
+        let d = if is_agg {
+            let d = _aggregate(d, &q, &aliases)?;
+            let by = _resolve_order(&q.order_by, d.columns(), &aliases)?;
+            _sort(d, &by)
+        } else {
+            let (exprs, names) = _projection(&q, d.columns(), &aliases)?;
+            match _resolve_order(&q.order_by, &names, &aliases) {
+                Ok(by) => _sort(d.select_exprs(&exprs)?, &by),
+                // ordered by columns which are not selected
+                Err(_) => {
+                    let by = _resolve_order(&q.order_by, d.columns(), &aliases)?;
+                    _sort(d, &by).select_exprs(&exprs)?
+                }
+            }
+        };
+
+        let (c, t, mut data) = d.dcst();
+        if let Some(n) = q.limit {
+            data.truncate(n);
+        }
+
+        Ok(FqxData::cst(c, t, data))
+    }
+
+    fn scan(&self, t: &TableRef) -> FqxResult<FqxData> {
+        let mut d = self
+            .tables
+            .get(&t.name)
+            .cloned()
+            .ok_or_else(|| FqxError::invalid(format!("table {} is not registered", t.name)))?;
+        d.columns = d
+            .columns
+            .iter()
+            .map(|c| format!("{}.{}", t.alias, c))
+            .collect();
+
+        Ok(d)
+    }
+
+    fn from(&self, q: &Query) -> FqxResult<FqxData> {
+        let mut d = self.scan(&q.from)?;
+
+        for j in q.joins.iter() {
+            let r = self.scan(&j.table)?;
+
+            // `ON` may name either side first
+            let mut pairs = vec![];
+            for (a, b) in j.on.iter() {
+                let pair = match (_resolve(a, d.columns()), _resolve(b, r.columns())) {
+                    (Ok(lc), Ok(rc)) => (lc, rc),
+                    _ => (_resolve(b, d.columns())?, _resolve(a, r.columns())?),
+                };
+                pairs.push(pair);
+            }
+
+            let (left_on, right_on): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+            // SQL never matches null keys
+            let options = FqxJoinOptions {
+                nulls_equal: false,
+                ..Default::default()
+            };
+            d = d.merge_with(r, &left_on, &right_on, j.how, &options)?;
+        }
+
+        Ok(d)
+    }
+}
+
+// ================================================================================================
+// Helpers
+// ================================================================================================
+
+/// An unqualified name matches a single table's column.
+fn _resolve(name: &str, columns: &[String]) -> FqxResult<String> {
+    if columns.iter().any(|c| c == name) {
+        return Ok(name.to_string());
+    }
+
+    let suffix = format!(".{name}");
+    let mut found = columns.iter().filter(|c| c.ends_with(&suffix));
+    match (found.next(), found.next()) {
+        (Some(c), None) => Ok(c.clone()),
+        (Some(_), Some(_)) => Err(FqxError::invalid(format!("column {name} is ambiguous"))),
+        (None, _) => Err(FqxError::ColumnNotFound(name.to_string())),
+    }
+}
+
+fn _resolve_expr(expr: &Expr, columns: &[String]) -> FqxResult<Expr> {
+    let r = |e: &Expr| _resolve_expr(e, columns).map(Box::new);
+    let e = match expr {
+        Expr::Column(c) => Expr::Column(_resolve(c, columns)?),
+        Expr::Literal(v) => Expr::Literal(v.clone()),
+        Expr::Binary { lhs, op, rhs } => Expr::Binary {
+            lhs: r(lhs)?,
+            op: *op,
+            rhs: r(rhs)?,
+        },
+        Expr::Not(e) => Expr::Not(r(e)?),
+        Expr::IsNull(e) => Expr::IsNull(r(e)?),
+        Expr::IsNotNull(e) => Expr::IsNotNull(r(e)?),
+        Expr::Cast(e, t) => Expr::Cast(r(e)?, t.clone()),
+        Expr::Alias(e, name) => Expr::Alias(r(e)?, name.clone()),
+    };
+
+    Ok(e)
+}
+
+/// Drops a table alias qualifier.
+fn _bare<'a>(name: &'a str, aliases: &[String]) -> &'a str {
+    match name.split_once('.') {
+        Some((a, c)) if aliases.iter().any(|e| e == a) => c,
+        _ => name,
+    }
+}
+
+fn _resolve_order(
+    order_by: &[(String, bool)],
+    columns: &[String],
+    aliases: &[String],
+) -> FqxResult<Vec<(usize, bool)>> {
+    order_by
+        .iter()
+        .map(|(c, desc)| {
+            let pos = columns
+                .iter()
+                .position(|e| e == c)
+                .or_else(|| columns.iter().position(|e| e == _bare(c, aliases)));
+            match pos {
+                Some(i) => Ok((i, *desc)),
+                None => _resolve(c, columns)
+                    .map(|q| (columns.iter().position(|e| e == &q).unwrap(), *desc)),
+            }
+        })
+        .collect()
+}
+
+fn _filter(d: FqxData, predicate: &Expr) -> FqxResult<FqxData> {
+    let p = _resolve_expr(predicate, d.columns())?.bind(d.columns(), d.types())?;
+
+    let mut err = None;
+    let d = d.filter(|r| {
+        p.eval_predicate(r).unwrap_or_else(|e| {
+            err.get_or_insert(e);
+            false
+        })
+    });
+
+    match err {
+        Some(e) => Err(e),
+        None => Ok(d),
+    }
+}
+
+/// Sorted through `OpSort`, whose comparison is a `bool`: a trailing row number breaks ties, so
+/// that the order is total and equal rows keep their order.
+fn _sort(d: FqxData, by: &[(usize, bool)]) -> FqxData {
+    if by.is_empty() {
+        return d;
+    }
+
+    let (c, t, data) = d.dcst();
+    let w = c.len();
+    let data = data
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut r)| {
+            r.0.push(FqxValue::U64(i as u64));
+            r
+        })
+        .collect();
+
+    let d = FqxData::cst(c, t, data).sorted_by(|a, b| {
+        let o = by
+            .iter()
+            .map(|&(i, desc)| _cmp_nulls_last(&a[i], &b[i], desc))
+            .find(|o| o.is_ne())
+            .unwrap_or_else(|| _cmp_nulls_last(&a[w], &b[w], false));
+        o == Ordering::Less
+    });
+
+    let (c, t, mut data) = d.dcst();
+    data.iter_mut().for_each(|r| {
+        r.0.pop();
+    });
+    FqxData::cst(c, t, data)
+}
+
+fn _projection(
+    q: &Query,
+    columns: &[String],
+    aliases: &[String],
+) -> FqxResult<(Vec<Expr>, Vec<String>)> {
+    // (expression, output name, qualified name if the output name is taken)
+    let mut items = vec![];
+    for item in q.items.iter() {
+        match item {
+            SelectItem::Wildcard => items.extend(columns.iter().map(|c| {
+                (
+                    Expr::Column(c.clone()),
+                    _bare(c, aliases).to_string(),
+                    Some(c.clone()),
+                )
+            })),
+            SelectItem::Expr { expr, alias } => {
+                let e = _resolve_expr(expr, columns)?;
+                match (alias, &e) {
+                    (Some(a), _) => items.push((e, a.clone(), None)),
+                    (None, Expr::Column(c)) => {
+                        let name = _bare(c, aliases).to_string();
+                        let qualified = Some(c.clone());
+                        items.push((e, name, qualified))
+                    }
+                    (None, _) => {
+                        let name = _bare(&expr.output_name(), aliases).to_string();
+                        items.push((e, name, None))
+                    }
+                }
+            }
+            SelectItem::Agg { .. } => unreachable!("not an aggregate query"),
+        }
+    }
+
+    let mut exprs = vec![];
+    let mut names = vec![];
+    for (e, name, qualified) in items.iter() {
+        let taken = items.iter().filter(|(_, n, _)| n == name).count() > 1;
+        let name = match (taken, qualified) {
+            (true, Some(q)) => q.clone(),
+            _ => name.clone(),
+        };
+        exprs.push(e.clone().alias(name.clone()));
+        names.push(name);
+    }
+
+    Ok((exprs, names))
+}
+
+// ================================================================================================
+// Aggregation
+// ================================================================================================
+
+/// Nulls are skipped, except by `count(*)`.
+fn _agg_value(g: &FqxData, agg: Agg, arg: Option<&Expr>) -> FqxResult<FqxValue> {
+    let arg = match arg {
+        Some(a) => a,
+        None => return Ok(FqxValue::I64(g.height() as i64)),
+    };
+    let v = g
+        .clone()
+        .select_exprs(std::slice::from_ref(arg))?
+        .filter(|r| !r[0].is_null());
+    let first = |r: Option<FqxRow>| r.map_or(FqxValue::Null, |r| r[0].clone());
+
+    let res = match agg {
+        Agg::Count => FqxValue::I64(v.height() as i64),
+        Agg::Sum => first(OpAgg::sum(&v)),
+        Agg::Min => first(OpAgg::min(&v)),
+        Agg::Max => first(OpAgg::max(&v)),
+        Agg::Mean => match first(OpAgg::sum(&v)) {
+            FqxValue::Null => FqxValue::Null,
+            s => s.try_cast(&FqxValueType::F64)? / FqxValue::F64(v.height() as f64),
+        },
+        agg => agg.eval(v.data().iter().map(|r| &r[0])),
+    };
+
+    Ok(res)
+}
+
+/// One row per group, in the order of the keys.
+fn _aggregate(d: FqxData, q: &Query, aliases: &[String]) -> FqxResult<FqxData> {
+    let keys = q
+        .group_by
+        .iter()
+        .map(|k| _resolve(k, d.columns()))
+        .collect::<FqxResult<Vec<_>>>()?;
+    let key_pos = keys
+        .iter()
+        .map(|k| d.columns().iter().position(|c| c == k).unwrap())
+        .collect::<Vec<_>>();
+
+    // each item is either the n-th key or an aggregation
+    enum Out {
+        Key(usize),
+        Agg(Agg, Option<Expr>),
+    }
+    let (mut outs, mut columns, mut types) = (vec![], vec![], vec![]);
+    for item in q.items.iter() {
+        match item {
+            SelectItem::Wildcard => {
+                return Err(FqxError::invalid("`*` can't be selected with aggregations"))
+            }
+            SelectItem::Expr { expr, alias } => {
+                let k = match _resolve_expr(expr, d.columns())? {
+                    Expr::Column(c) => keys.iter().position(|k| k == &c),
+                    _ => None,
+                }
+                .ok_or_else(|| {
+                    FqxError::invalid(format!("{expr} must be in GROUP BY or aggregated"))
+                })?;
+                let name = alias
+                    .clone()
+                    .unwrap_or_else(|| _bare(&keys[k], aliases).into());
+                outs.push(Out::Key(k));
+                columns.push(name);
+                types.push(d.types()[key_pos[k]].clone());
+            }
+            SelectItem::Agg {
+                func,
+                agg,
+                arg,
+                alias,
+            } => {
+                let name = match (alias, arg) {
+                    (Some(a), _) => a.clone(),
+                    (None, Some(a)) => format!("{func}({a})"),
+                    (None, None) => format!("{func}(*)"),
+                };
+                let arg = arg
+                    .as_ref()
+                    .map(|a| _resolve_expr(a, d.columns()))
+                    .transpose()?;
+                let typ = match &arg {
+                    Some(a) => {
+                        let t = a.bind(d.columns(), d.types())?.output_type();
+                        agg.output_type(&t)
+                    }
+                    None => FqxValueType::I64,
+                };
+                outs.push(Out::Agg(*agg, arg));
+                columns.push(name);
+                types.push(typ);
+            }
+        }
+    }
+
+    // without keys the whole table is a single group, even when empty
+    let mut groups = if keys.is_empty() {
+        vec![(vec![], d)]
+    } else {
+        d.group_by_fn_(|r| key_pos.iter().map(|&i| r[i].clone()).collect())
+            .to_hashmap()
+            .into_iter()
+            .collect::<Vec<_>>()
+    };
+    groups.sort_by(|(a, _), (b, _)| _cmp_keys(a, b));
+
+    let mut data = vec![];
+    for (k, g) in groups.into_iter() {
+        let row = outs
+            .iter()
+            .map(|o| match o {
+                Out::Key(i) => Ok(k[*i].clone()),
+                Out::Agg(agg, arg) => _agg_value(&g, *agg, arg.as_ref()),
+            })
+            .collect::<FqxResult<Vec<_>>>()?;
+        data.push(FqxRow::from(row));
+    }
+
+    Ok(FqxData::cst(columns, types, data))
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_ctx {
+    use super::*;
+    use crate::adt::RowProps;
+    use crate::fqx;
+
+    fn ctx() -> FqxContext {
+        let trades = FqxData::new(
+            vec![
+                String::from("id"),
+                String::from("sym"),
+                String::from("price"),
+                String::from("qty"),
+            ],
+            vec![
+                FqxValueType::I32,
+                FqxValueType::String,
+                FqxValueType::F64,
+                FqxValueType::I64,
+            ],
+            vec![
+                vec![fqx!(1), fqx!("A"), fqx!(10.0), fqx!(100i64)],
+                vec![fqx!(2), fqx!("B"), fqx!(20.0), fqx!(200i64)],
+                vec![fqx!(3), fqx!("A"), fqx!(12.0), fqx!()],
+                vec![fqx!(4), fqx!("C"), fqx!(), fqx!(50i64)],
+                vec![fqx!(5), fqx!("B"), fqx!(22.0), fqx!(300i64)],
+            ],
+        )
+        .unwrap();
+        let syms = FqxData::new(
+            vec![String::from("sym"), String::from("name")],
+            vec![FqxValueType::String, FqxValueType::String],
+            vec![
+                vec![fqx!("A"), fqx!("Alpha")],
+                vec![fqx!("B"), fqx!("Beta")],
+            ],
+        )
+        .unwrap();
+
+        let mut ctx = FqxContext::new();
+        ctx.register("trades", trades);
+        ctx.register("syms", syms);
+        ctx
+    }
+
+    #[test]
+    fn select_where_order_limit_success() {
+        let res = ctx()
+            .sql("SELECT id, price * 2 AS p2 FROM trades WHERE price > 10 OR qty IS NULL ORDER BY p2 DESC LIMIT 2")
+            .unwrap();
+        assert_eq!(res.columns(), &["id", "p2"]);
+        assert_eq!(res.types(), &[FqxValueType::I32, FqxValueType::F64]);
+        assert_eq!(res.data()[0].values(), &[fqx!(5), fqx!(44.0)]);
+        assert_eq!(res.data()[1].values(), &[fqx!(2), fqx!(40.0)]);
+
+        // ordered by a column which is not selected, nulls last
+        let res = ctx()
+            .sql("select sym from trades t order by t.price desc, id")
+            .unwrap();
+        assert_eq!(res.columns(), &["sym"]);
+        let syms = res.data().iter().map(|r| r[0].clone()).collect::<Vec<_>>();
+        assert_eq!(
+            syms,
+            vec![fqx!("B"), fqx!("B"), fqx!("A"), fqx!("A"), fqx!("C")]
+        );
+    }
+
+    #[test]
+    fn group_by_success() {
+        let res = ctx()
+            .sql(
+                "SELECT sym, count(*) AS n, count(qty), sum(qty) AS total, avg(price) \
+                 FROM trades GROUP BY sym ORDER BY total DESC",
+            )
+            .unwrap();
+        println!("{:?}", res);
+
+        assert_eq!(
+            res.columns(),
+            &["sym", "n", "count(qty)", "total", "avg(price)"]
+        );
+        assert_eq!(
+            res.types(),
+            &[
+                FqxValueType::String,
+                FqxValueType::I64,
+                FqxValueType::I64,
+                FqxValueType::I64,
+                FqxValueType::F64
+            ]
+        );
+        assert_eq!(
+            res.data()[0].values(),
+            &[fqx!("B"), fqx!(2i64), fqx!(2i64), fqx!(500i64), fqx!(21.0)]
+        );
+        assert_eq!(
+            res.data()[1].values(),
+            &[fqx!("A"), fqx!(2i64), fqx!(1i64), fqx!(100i64), fqx!(11.0)]
+        );
+        assert_eq!(
+            res.data()[2].values(),
+            &[fqx!("C"), fqx!(1i64), fqx!(1i64), fqx!(50i64), fqx!()]
+        );
+
+        // a single group without GROUP BY
+        let res = ctx()
+            .sql("select count(*), max(price) from trades where id > 10")
+            .unwrap();
+        assert_eq!(res.data()[0].values(), &[fqx!(0i64), fqx!()]);
+    }
+
+    #[test]
+    fn join_success() {
+        let res = ctx()
+            .sql(
+                "SELECT t.id, s.name, t.sym FROM trades t \
+                 LEFT JOIN syms s ON s.sym = t.sym WHERE t.id < 5 ORDER BY id DESC",
+            )
+            .unwrap();
+        assert_eq!(res.columns(), &["id", "name", "sym"]);
+        assert_eq!(res.height(), 4);
+        assert_eq!(res.data()[0].values(), &[fqx!(4), fqx!(), fqx!("C")]);
+        assert_eq!(res.data()[1].values(), &[fqx!(3), fqx!("Alpha"), fqx!("A")]);
+
+        let res = ctx()
+            .sql("select * from trades join syms on trades.sym = syms.sym")
+            .unwrap();
+        assert_eq!(
+            res.columns(),
+            &["id", "trades.sym", "price", "qty", "syms.sym", "name"]
+        );
+        assert_eq!(res.height(), 4);
+    }
+
+    #[test]
+    fn sql_fail() {
+        let e = ctx().sql("select x from trades").unwrap_err();
+        assert!(matches!(e, FqxError::ColumnNotFound(c) if c == "x"));
+
+        let e = ctx().sql("select id from nope").unwrap_err();
+        assert!(matches!(e, FqxError::Invalid(_)));
+
+        let e = ctx()
+            .sql("select sym from trades join syms on trades.sym = syms.sym")
+            .unwrap_err();
+        assert!(matches!(e, FqxError::Invalid(m) if m.contains("ambiguous")));
+
+        let e = ctx()
+            .sql("select id, count(*) from trades group by sym")
+            .unwrap_err();
+        assert!(matches!(e, FqxError::Invalid(m) if m.contains("GROUP BY")));
+
+        let e = ctx()
+            .sql("select sym, count(*) from trades group by sym order by price")
+            .unwrap_err();
+        assert!(matches!(e, FqxError::ColumnNotFound(c) if c == "price"));
+    }
+}
@@ -0,0 +1,10 @@
+//! file: mod.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 17:40:22 Saturday
+//! brief:
+
+pub mod ctx;
+mod parse;
+mod token;
+
+pub use ctx::*;
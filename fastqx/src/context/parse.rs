@@ -0,0 +1,532 @@
+//! file: parse.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 17:58:36 Saturday
+//! brief:
+
+use crate::adt::FqxValue;
+use crate::context::token::{tokenize, Token};
+use crate::error::{FqxError, FqxResult};
+use crate::ops::{col, lit, Agg, BinaryOp, Expr, FqxJoinType};
+
+// ================================================================================================
+// Query
+//
+// The supported subset:
+//
+// SELECT * | item [, item ...]
+// FROM table [[AS] alias]
+// [[INNER | LEFT [OUTER] | RIGHT [OUTER] | FULL [OUTER]] JOIN table [[AS] alias] ON a = b [AND ...]]
+// [WHERE expr]
+// [GROUP BY column [, column ...]]
+// [ORDER BY column [ASC | DESC] [, ...]]
+// [LIMIT n]
+//
+// where an item is `expr [[AS] alias]` or `count(*) | agg(expr) [[AS] alias]`, with `agg` among
+// count, sum, min, max and avg (or mean).
+// ================================================================================================
+
+#[derive(Debug, Clone)]
+pub(crate) enum SelectItem {
+    Wildcard,
+    Expr {
+        expr: Expr,
+        alias: Option<String>,
+    },
+    /// `arg` is `None` for `count(*)`
+    Agg {
+        func: String,
+        agg: Agg,
+        arg: Option<Expr>,
+        alias: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TableRef {
+    pub(crate) name: String,
+    pub(crate) alias: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct JoinClause {
+    pub(crate) table: TableRef,
+    pub(crate) how: FqxJoinType,
+    /// column pairs from `ON a = b AND ...`, in written order
+    pub(crate) on: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Query {
+    pub(crate) items: Vec<SelectItem>,
+    pub(crate) from: TableRef,
+    pub(crate) joins: Vec<JoinClause>,
+    pub(crate) selection: Option<Expr>,
+    pub(crate) group_by: Vec<String>,
+    pub(crate) order_by: Vec<(String, bool)>,
+    pub(crate) limit: Option<usize>,
+}
+
+const RESERVED: [&str; 25] = [
+    "SELECT", "FROM", "WHERE", "GROUP", "BY", "ORDER", "LIMIT", "JOIN", "INNER", "LEFT", "RIGHT",
+    "FULL", "OUTER", "CROSS", "ON", "AS", "AND", "OR", "NOT", "IS", "NULL", "ASC", "DESC", "TRUE",
+    "FALSE",
+];
+
+fn _agg_of(func: &str) -> Option<Agg> {
+    match func.to_ascii_lowercase().as_str() {
+        "count" => Some(Agg::Count),
+        "sum" => Some(Agg::Sum),
+        "min" => Some(Agg::Min),
+        "max" => Some(Agg::Max),
+        "avg" | "mean" => Some(Agg::Mean),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_query(query: &str) -> FqxResult<Query> {
+    let mut p = Parser {
+        tokens: tokenize(query)?,
+        pos: 0,
+    };
+    let q = p.query()?;
+    match p.peek() {
+        None => Ok(q),
+        Some(t) => Err(FqxError::invalid(format!("unexpected {t:?} in query"))),
+    }
+}
+
+// ================================================================================================
+// Parser
+// ================================================================================================
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset)
+    }
+
+    fn next(&mut self) -> FqxResult<Token> {
+        let t = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| FqxError::invalid("unexpected end of query"))?;
+        self.pos += 1;
+        Ok(t)
+    }
+
+    fn eat_kw(&mut self, kw: &str) -> bool {
+        let hit = self.peek().is_some_and(|t| t.is_keyword(kw));
+        if hit {
+            self.pos += 1;
+        }
+        hit
+    }
+
+    fn expect_kw(&mut self, kw: &str) -> FqxResult<()> {
+        match self.eat_kw(kw) {
+            true => Ok(()),
+            false => Err(FqxError::invalid(format!(
+                "expected {kw}, found {:?}",
+                self.peek()
+            ))),
+        }
+    }
+
+    fn eat_sym(&mut self, sym: &str) -> bool {
+        let hit = matches!(self.peek(), Some(Token::Sym(s)) if *s == sym);
+        if hit {
+            self.pos += 1;
+        }
+        hit
+    }
+
+    fn expect_sym(&mut self, sym: &str) -> FqxResult<()> {
+        match self.eat_sym(sym) {
+            true => Ok(()),
+            false => Err(FqxError::invalid(format!(
+                "expected `{sym}`, found {:?}",
+                self.peek()
+            ))),
+        }
+    }
+
+    fn is_ident(t: Option<&Token>) -> bool {
+        match t {
+            Some(Token::Quoted(_)) => true,
+            Some(Token::Word(w)) => !RESERVED.iter().any(|r| w.eq_ignore_ascii_case(r)),
+            _ => false,
+        }
+    }
+
+    fn ident(&mut self) -> FqxResult<String> {
+        if !Self::is_ident(self.peek()) {
+            return Err(FqxError::invalid(format!(
+                "expected an identifier, found {:?}",
+                self.peek()
+            )));
+        }
+        match self.next()? {
+            Token::Word(w) | Token::Quoted(w) => Ok(w),
+            _ => unreachable!(),
+        }
+    }
+
+    /// `column` or `table.column`
+    fn column_ref(&mut self) -> FqxResult<String> {
+        let mut name = self.ident()?;
+        if self.eat_sym(".") {
+            name = format!("{name}.{}", self.ident()?);
+        }
+        Ok(name)
+    }
+
+    fn alias(&mut self) -> FqxResult<Option<String>> {
+        if self.eat_kw("AS") || Self::is_ident(self.peek()) {
+            return self.ident().map(Some);
+        }
+        Ok(None)
+    }
+
+    fn comma_separated<T, F>(&mut self, mut f: F) -> FqxResult<Vec<T>>
+    where
+        F: FnMut(&mut Self) -> FqxResult<T>,
+    {
+        let mut res = vec![f(self)?];
+        while self.eat_sym(",") {
+            res.push(f(self)?);
+        }
+        Ok(res)
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    fn query(&mut self) -> FqxResult<Query> {
+        self.expect_kw("SELECT")?;
+        let items = self.comma_separated(Self::select_item)?;
+
+        self.expect_kw("FROM")?;
+        let from = self.table_ref()?;
+
+        let mut joins = vec![];
+        while let Some(how) = self.join_type()? {
+            let table = self.table_ref()?;
+            self.expect_kw("ON")?;
+            let on = self.join_on()?;
+            joins.push(JoinClause { table, how, on });
+        }
+
+        let selection = match self.eat_kw("WHERE") {
+            true => Some(self.expr()?),
+            false => None,
+        };
+
+        let mut group_by = vec![];
+        if self.eat_kw("GROUP") {
+            self.expect_kw("BY")?;
+            group_by = self.comma_separated(Self::column_ref)?;
+        }
+
+        let mut order_by = vec![];
+        if self.eat_kw("ORDER") {
+            self.expect_kw("BY")?;
+            order_by = self.comma_separated(|p| {
+                let c = p.column_ref()?;
+                let desc = p.eat_kw("DESC");
+                if !desc {
+                    p.eat_kw("ASC");
+                }
+                Ok((c, desc))
+            })?;
+        }
+
+        let limit = match self.eat_kw("LIMIT") {
+            true => match self.next()? {
+                Token::Number(n) => Some(
+                    n.parse::<usize>()
+                        .map_err(|_| FqxError::invalid(format!("invalid LIMIT {n}")))?,
+                ),
+                t => return Err(FqxError::invalid(format!("invalid LIMIT {t:?}"))),
+            },
+            false => None,
+        };
+
+        Ok(Query {
+            items,
+            from,
+            joins,
+            selection,
+            group_by,
+            order_by,
+            limit,
+        })
+    }
+
+    fn select_item(&mut self) -> FqxResult<SelectItem> {
+        if self.eat_sym("*") {
+            return Ok(SelectItem::Wildcard);
+        }
+
+        let func = match (self.peek(), self.peek_at(1)) {
+            (Some(Token::Word(w)), Some(Token::Sym("("))) if Self::is_ident(self.peek()) => {
+                Some(w.clone())
+            }
+            _ => None,
+        };
+        if let Some(func) = func {
+            let agg = _agg_of(&func)
+                .ok_or_else(|| FqxError::invalid(format!("unsupported function {func}")))?;
+            self.pos += 2;
+            let arg = match agg == Agg::Count && self.eat_sym("*") {
+                true => None,
+                false => Some(self.expr()?),
+            };
+            self.expect_sym(")")?;
+            let alias = self.alias()?;
+            return Ok(SelectItem::Agg {
+                func: func.to_ascii_lowercase(),
+                agg,
+                arg,
+                alias,
+            });
+        }
+
+        let expr = self.expr()?;
+        let alias = self.alias()?;
+        Ok(SelectItem::Expr { expr, alias })
+    }
+
+    fn table_ref(&mut self) -> FqxResult<TableRef> {
+        let name = self.ident()?;
+        let alias = self.alias()?.unwrap_or_else(|| name.clone());
+        Ok(TableRef { name, alias })
+    }
+
+    fn join_type(&mut self) -> FqxResult<Option<FqxJoinType>> {
+        let how = if self.eat_kw("JOIN") {
+            return Ok(Some(FqxJoinType::Inner));
+        } else if self.eat_kw("INNER") {
+            FqxJoinType::Inner
+        } else if self.eat_kw("LEFT") {
+            FqxJoinType::Left
+        } else if self.eat_kw("RIGHT") {
+            FqxJoinType::Right
+        } else if self.eat_kw("FULL") {
+            FqxJoinType::Outer
+        } else {
+            return Ok(None);
+        };
+        if how != FqxJoinType::Inner {
+            self.eat_kw("OUTER");
+        }
+        self.expect_kw("JOIN")?;
+        Ok(Some(how))
+    }
+
+    fn join_on(&mut self) -> FqxResult<Vec<(String, String)>> {
+        let mut res = vec![];
+        loop {
+            let l = self.column_ref()?;
+            self.expect_sym("=")?;
+            let r = self.column_ref()?;
+            res.push((l, r));
+            if !self.eat_kw("AND") {
+                return Ok(res);
+            }
+        }
+    }
+
+    // --------------------------------------------------------------------------------------------
+    // expressions, from the loosest binding
+
+    fn expr(&mut self) -> FqxResult<Expr> {
+        let mut e = self.expr_and()?;
+        while self.eat_kw("OR") {
+            e = e.or(self.expr_and()?);
+        }
+        Ok(e)
+    }
+
+    fn expr_and(&mut self) -> FqxResult<Expr> {
+        let mut e = self.expr_not()?;
+        while self.eat_kw("AND") {
+            e = e.and(self.expr_not()?);
+        }
+        Ok(e)
+    }
+
+    fn expr_not(&mut self) -> FqxResult<Expr> {
+        match self.eat_kw("NOT") {
+            true => Ok(!self.expr_not()?),
+            false => self.expr_cmp(),
+        }
+    }
+
+    fn expr_cmp(&mut self) -> FqxResult<Expr> {
+        let e = self.expr_add()?;
+
+        if self.eat_kw("IS") {
+            let not = self.eat_kw("NOT");
+            self.expect_kw("NULL")?;
+            return Ok(if not { e.is_not_null() } else { e.is_null() });
+        }
+
+        let op = match self.peek() {
+            Some(Token::Sym("=")) => BinaryOp::Eq,
+            Some(Token::Sym("<>")) | Some(Token::Sym("!=")) => BinaryOp::Neq,
+            Some(Token::Sym("<")) => BinaryOp::Lt,
+            Some(Token::Sym("<=")) => BinaryOp::Le,
+            Some(Token::Sym(">")) => BinaryOp::Gt,
+            Some(Token::Sym(">=")) => BinaryOp::Ge,
+            _ => return Ok(e),
+        };
+        self.pos += 1;
+        Ok(e.binary(op, self.expr_add()?))
+    }
+
+    fn expr_add(&mut self) -> FqxResult<Expr> {
+        let mut e = self.expr_mul()?;
+        loop {
+            e = match self.peek() {
+                Some(Token::Sym("+")) => {
+                    self.pos += 1;
+                    e + self.expr_mul()?
+                }
+                Some(Token::Sym("-")) => {
+                    self.pos += 1;
+                    e - self.expr_mul()?
+                }
+                _ => return Ok(e),
+            };
+        }
+    }
+
+    fn expr_mul(&mut self) -> FqxResult<Expr> {
+        let mut e = self.expr_unary()?;
+        loop {
+            e = match self.peek() {
+                Some(Token::Sym("*")) => {
+                    self.pos += 1;
+                    e * self.expr_unary()?
+                }
+                Some(Token::Sym("/")) => {
+                    self.pos += 1;
+                    e / self.expr_unary()?
+                }
+                Some(Token::Sym("%")) => {
+                    self.pos += 1;
+                    e % self.expr_unary()?
+                }
+                _ => return Ok(e),
+            };
+        }
+    }
+
+    fn expr_unary(&mut self) -> FqxResult<Expr> {
+        if !self.eat_sym("-") {
+            return self.expr_primary();
+        }
+        match self.expr_primary()? {
+            Expr::Literal(FqxValue::I64(v)) => Ok(lit(-v)),
+            Expr::Literal(FqxValue::F64(v)) => Ok(lit(-v)),
+            // keeps the type of `e`
+            e => Ok(e * lit(-1)),
+        }
+    }
+
+    fn expr_primary(&mut self) -> FqxResult<Expr> {
+        if Self::is_ident(self.peek()) {
+            if let Some(Token::Sym("(")) = self.peek_at(1) {
+                return Err(FqxError::invalid(format!(
+                    "function {:?} is only supported as a select item",
+                    self.peek()
+                )));
+            }
+            return self.column_ref().map(col);
+        }
+
+        match self.next()? {
+            Token::Number(n) if n.contains('.') => n
+                .parse::<f64>()
+                .map(lit)
+                .map_err(|_| FqxError::invalid(format!("invalid number {n}"))),
+            Token::Number(n) => n
+                .parse::<i64>()
+                .map(lit)
+                .map_err(|_| FqxError::invalid(format!("invalid number {n}"))),
+            Token::Str(s) => Ok(lit(s)),
+            Token::Sym("(") => {
+                let e = self.expr()?;
+                self.expect_sym(")")?;
+                Ok(e)
+            }
+            t if t.is_keyword("NULL") => Ok(Expr::Literal(FqxValue::Null)),
+            t if t.is_keyword("TRUE") => Ok(lit(true)),
+            t if t.is_keyword("FALSE") => Ok(lit(false)),
+            t => Err(FqxError::invalid(format!("unexpected {t:?} in expression"))),
+        }
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    #[test]
+    fn parse_query_success() {
+        let q = parse_query(
+            "select t.name, count(*) as n, avg(price * 2) \"avg price\"
+             from trades t left join users as u on t.uid = u.id
+             where price >= -1.5 and not (name is null or name <> 'it''s') -- note
+             group by t.name order by n desc, t.name limit 10;",
+        )
+        .unwrap();
+
+        assert_eq!(q.items.len(), 3);
+        assert!(
+            matches!(&q.items[1], SelectItem::Agg { agg: Agg::Count, arg: None, alias: Some(a), .. } if a == "n")
+        );
+        assert!(
+            matches!(&q.items[2], SelectItem::Agg { func, alias: Some(a), .. } if func == "avg" && a == "avg price")
+        );
+        assert_eq!(q.from.alias, "t");
+        assert_eq!(q.joins[0].table.name, "users");
+        assert_eq!(q.joins[0].how, FqxJoinType::Left);
+        assert_eq!(
+            q.joins[0].on,
+            vec![(String::from("t.uid"), String::from("u.id"))]
+        );
+        assert_eq!(
+            q.selection.unwrap().to_string(),
+            "((price >= -1.5) AND NOT (name IS NULL OR (name != 'it's')))"
+        );
+        assert_eq!(q.group_by, ["t.name"]);
+        assert_eq!(
+            q.order_by,
+            vec![(String::from("n"), true), (String::from("t.name"), false)]
+        );
+        assert_eq!(q.limit, Some(10));
+    }
+
+    #[test]
+    fn parse_query_fail() {
+        assert!(parse_query("select from t").is_err());
+        assert!(parse_query("select a from t where").is_err());
+        assert!(parse_query("select median(a) from t").is_err());
+        assert!(parse_query("select a from t where sum(a) > 1").is_err());
+        assert!(parse_query("select a from t limit x").is_err());
+        assert!(parse_query("select 'a from t").is_err());
+    }
+}
@@ -0,0 +1,106 @@
+//! file: token.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 17:42:10 Saturday
+//! brief:
+
+use crate::error::{FqxError, FqxResult};
+
+// ================================================================================================
+// Token
+// ================================================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token {
+    /// A keyword or an unquoted identifier
+    Word(String),
+    /// `"name"`, `` `name` `` or `[name]`, never a keyword
+    Quoted(String),
+    Number(String),
+    Str(String),
+    Sym(&'static str),
+}
+
+impl Token {
+    pub(crate) fn is_keyword(&self, kw: &str) -> bool {
+        matches!(self, Token::Word(w) if w.eq_ignore_ascii_case(kw))
+    }
+}
+
+const SYMBOLS: [&str; 16] = [
+    "<>", "!=", "<=", ">=", "=", "<", ">", "(", ")", ",", ".", "*", "+", "-", "/", "%",
+];
+
+fn _quoted(chars: &[char], start: usize, close: char) -> FqxResult<(String, usize)> {
+    let mut res = String::new();
+    let mut i = start;
+    loop {
+        match chars.get(i) {
+            // a doubled closing char escapes itself
+            Some(&c) if c == close && chars.get(i + 1) == Some(&close) => {
+                res.push(c);
+                i += 2;
+            }
+            Some(&c) if c == close => return Ok((res, i + 1)),
+            Some(&c) => {
+                res.push(c);
+                i += 1;
+            }
+            None => {
+                return Err(FqxError::invalid(format!(
+                    "unterminated `{close}` in query"
+                )))
+            }
+        }
+    }
+}
+
+pub(crate) fn tokenize(query: &str) -> FqxResult<Vec<Token>> {
+    let chars = query.chars().collect::<Vec<_>>();
+    let mut res = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() || c == ';' {
+            i += 1;
+        } else if c == '-' && chars.get(i + 1) == Some(&'-') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '\'' {
+            let (s, next) = _quoted(&chars, i + 1, '\'')?;
+            res.push(Token::Str(s));
+            i = next;
+        } else if c == '"' || c == '`' || c == '[' {
+            let close = if c == '[' { ']' } else { c };
+            let (s, next) = _quoted(&chars, i + 1, close)?;
+            res.push(Token::Quoted(s));
+            i = next;
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            res.push(Token::Number(chars[start..i].iter().collect()));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            res.push(Token::Word(chars[start..i].iter().collect()));
+        } else {
+            let sym = SYMBOLS
+                .iter()
+                .find(|s| {
+                    s.chars()
+                        .enumerate()
+                        .all(|(j, sc)| chars.get(i + j) == Some(&sc))
+                })
+                .ok_or_else(|| FqxError::invalid(format!("unexpected `{c}` in query")))?;
+            res.push(Token::Sym(sym));
+            i += sym.len();
+        }
+    }
+
+    Ok(res)
+}
@@ -0,0 +1,271 @@
+//! file: error.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 13:02:17 Saturday
+//! brief:
+
+use pyo3::exceptions::PyException;
+use pyo3::{create_exception, PyErr};
+use thiserror::Error;
+
+use crate::adt::FqxValueType;
+
+// ================================================================================================
+// FqxError
+// ================================================================================================
+
+pub type FqxResult<T> = std::result::Result<T, FqxError>;
+
+pub(crate) type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug, Error)]
+pub enum FqxError {
+    #[error(
+        "type mismatch{}: expected {expected:?}, actual {actual:?}",
+        _at(column, row)
+    )]
+    TypeMismatch {
+        column: Option<String>,
+        row: Option<usize>,
+        expected: FqxValueType,
+        actual: FqxValueType,
+    },
+    /// `what` is the measured dimension, e.g. "columns" or "row length"
+    #[error("shape mismatch{}: expected {expected} {what}, actual {actual}", _at(&None, row))]
+    ShapeMismatch {
+        what: &'static str,
+        row: Option<usize>,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("column not found: {0}")]
+    ColumnNotFound(String),
+    #[error("index {index} out of bounds {len}")]
+    OutOfBounds { index: usize, len: usize },
+    #[error("cannot cast {from:?} into {to:?}")]
+    Cast {
+        from: FqxValueType,
+        to: FqxValueType,
+    },
+    /// a wrong argument, connect string or config
+    #[error("{0}")]
+    Invalid(String),
+    #[error("sql: {0}")]
+    Sql(#[source] BoxError),
+    #[error("http: {0}")]
+    Http(#[source] BoxError),
+    #[error("csv: {0}")]
+    Csv(#[from] csv::Error),
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("arrow: {0}")]
+    Arrow(#[from] arrow::error::ArrowError),
+    #[error("parquet: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+    /// a malformed number, date, decimal or config string
+    #[error("parse: {0}")]
+    Parse(#[source] BoxError),
+}
+
+fn _at(column: &Option<String>, row: &Option<usize>) -> String {
+    match (column, row) {
+        (Some(c), Some(r)) => format!(" at column `{c}`, row {r}"),
+        (Some(c), None) => format!(" at column `{c}`"),
+        (None, Some(r)) => format!(" at row {r}"),
+        (None, None) => String::new(),
+    }
+}
+
+impl FqxError {
+    pub fn invalid<S: ToString>(msg: S) -> Self {
+        Self::Invalid(msg.to_string())
+    }
+
+    pub fn parse<S: ToString>(msg: S) -> Self {
+        Self::Parse(msg.to_string().into())
+    }
+
+    pub fn type_mismatch(expected: FqxValueType, actual: FqxValueType) -> Self {
+        Self::TypeMismatch {
+            column: None,
+            row: None,
+            expected,
+            actual,
+        }
+    }
+
+    pub fn shape_mismatch(what: &'static str, expected: usize, actual: usize) -> Self {
+        Self::ShapeMismatch {
+            what,
+            row: None,
+            expected,
+            actual,
+        }
+    }
+
+    pub fn cast<F: Into<FqxValueType>>(from: F, to: FqxValueType) -> Self {
+        Self::Cast {
+            from: from.into(),
+            to,
+        }
+    }
+
+    /// Fills the row index in, unless already known.
+    pub fn at_row(mut self, idx: usize) -> Self {
+        match &mut self {
+            Self::TypeMismatch { row, .. } | Self::ShapeMismatch { row, .. } => {
+                row.get_or_insert(idx);
+            }
+            _ => {}
+        }
+        self
+    }
+
+    /// Fills the column name in, unless already known.
+    pub fn at_column<S: ToString>(mut self, name: S) -> Self {
+        if let Self::TypeMismatch { column, .. } = &mut self {
+            column.get_or_insert_with(|| name.to_string());
+        }
+        self
+    }
+}
+
+// ================================================================================================
+// From
+// ================================================================================================
+
+macro_rules! impl_from_boxed {
+    ($variant:ident, $($t:ty),*) => {
+        $(
+            impl From<$t> for FqxError {
+                fn from(e: $t) -> Self {
+                    FqxError::$variant(Box::new(e))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_boxed!(
+    Sql,
+    sqlx::Error,
+    tiberius::error::Error,
+    sea_query::error::Error,
+    bb8::RunError<tiberius::error::Error>,
+    futures::channel::oneshot::Canceled
+);
+impl_from_boxed!(Http, reqwest::Error, reqwest::header::InvalidHeaderValue);
+impl_from_boxed!(
+    Parse,
+    std::num::ParseIntError,
+    std::num::ParseFloatError,
+    std::num::TryFromIntError,
+    std::str::ParseBoolError,
+    std::str::Utf8Error,
+    std::string::FromUtf8Error,
+    chrono::ParseError,
+    rust_decimal::Error,
+    toml::de::Error,
+    std::fmt::Error
+);
+
+impl From<std::convert::Infallible> for FqxError {
+    fn from(e: std::convert::Infallible) -> Self {
+        match e {}
+    }
+}
+
+// ================================================================================================
+// Python exceptions
+// ================================================================================================
+
+pub mod exceptions {
+    use super::*;
+
+    create_exception!(fastqx, FqxError, PyException);
+    create_exception!(fastqx, FqxTypeMismatchError, FqxError);
+    create_exception!(fastqx, FqxShapeMismatchError, FqxError);
+    create_exception!(fastqx, FqxColumnNotFoundError, FqxError);
+    create_exception!(fastqx, FqxOutOfBoundsError, FqxError);
+    create_exception!(fastqx, FqxCastError, FqxError);
+    create_exception!(fastqx, FqxInvalidError, FqxError);
+    create_exception!(fastqx, FqxSqlError, FqxError);
+    create_exception!(fastqx, FqxHttpError, FqxError);
+    create_exception!(fastqx, FqxCsvError, FqxError);
+    create_exception!(fastqx, FqxIoError, FqxError);
+    create_exception!(fastqx, FqxJsonError, FqxError);
+    create_exception!(fastqx, FqxArrowError, FqxError);
+    create_exception!(fastqx, FqxParquetError, FqxError);
+    create_exception!(fastqx, FqxParseError, FqxError);
+}
+
+impl From<FqxError> for PyErr {
+    fn from(e: FqxError) -> Self {
+        use exceptions as exc;
+
+        let msg = e.to_string();
+        match e {
+            FqxError::TypeMismatch { .. } => exc::FqxTypeMismatchError::new_err(msg),
+            FqxError::ShapeMismatch { .. } => exc::FqxShapeMismatchError::new_err(msg),
+            FqxError::ColumnNotFound(_) => exc::FqxColumnNotFoundError::new_err(msg),
+            FqxError::OutOfBounds { .. } => exc::FqxOutOfBoundsError::new_err(msg),
+            FqxError::Cast { .. } => exc::FqxCastError::new_err(msg),
+            FqxError::Invalid(_) => exc::FqxInvalidError::new_err(msg),
+            FqxError::Sql(_) => exc::FqxSqlError::new_err(msg),
+            FqxError::Http(_) => exc::FqxHttpError::new_err(msg),
+            FqxError::Csv(_) => exc::FqxCsvError::new_err(msg),
+            FqxError::Io(_) => exc::FqxIoError::new_err(msg),
+            FqxError::Json(_) => exc::FqxJsonError::new_err(msg),
+            FqxError::Arrow(_) => exc::FqxArrowError::new_err(msg),
+            FqxError::Parquet(_) => exc::FqxParquetError::new_err(msg),
+            FqxError::Parse(_) => exc::FqxParseError::new_err(msg),
+        }
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_error {
+    use super::*;
+
+    #[test]
+    fn display_success() {
+        let e = FqxError::type_mismatch(FqxValueType::I32, FqxValueType::String)
+            .at_row(3)
+            .at_column("a");
+        assert_eq!(
+            e.to_string(),
+            "type mismatch at column `a`, row 3: expected I32, actual String"
+        );
+
+        let e = FqxError::shape_mismatch("columns", 2, 3)
+            .at_row(1)
+            .at_row(5);
+        assert_eq!(
+            e.to_string(),
+            "shape mismatch at row 1: expected 2 columns, actual 3"
+        );
+
+        let e = FqxError::cast(FqxValueType::Bool, FqxValueType::U8);
+        assert_eq!(e.to_string(), "cannot cast Bool into U8");
+    }
+
+    #[test]
+    fn from_foreign_success() {
+        let e: FqxError = sqlx::Error::PoolTimedOut.into();
+        assert!(matches!(e, FqxError::Sql(_)));
+
+        let e: FqxError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+        assert!(matches!(e, FqxError::Io(_)));
+
+        let e: FqxError = "x".parse::<i32>().unwrap_err().into();
+        assert!(matches!(e, FqxError::Parse(_)));
+
+        let e: FqxError = serde_json::from_str::<i32>("x").unwrap_err().into();
+        assert!(matches!(e, FqxError::Json(_)));
+    }
+}
@@ -0,0 +1,128 @@
+//! file: exec.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 16:37:52 Saturday
+//! brief:
+
+use std::cmp::Ordering;
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+
+use crate::adt::{FqxD, FqxData};
+use crate::error::{FqxError, FqxResult};
+use crate::lazy::plan::{LogicalPlan, ScanSource};
+use crate::lazy::sql::scan_statement;
+use crate::ops::utils::_cmp_nulls_last;
+use crate::ops::{col, AggSpec, Expr, OpExpr, OpLazyGroup, OpMerge, OpStats};
+use crate::sources::csv::read::csv_read_rd;
+
+// ================================================================================================
+// Execution
+// ================================================================================================
+
+pub(crate) fn execute(plan: &LogicalPlan) -> BoxFuture<'_, FqxResult<FqxData>> {
+    async move {
+        let d = match plan {
+            LogicalPlan::Scan {
+                source,
+                projection,
+                predicate,
+                ..
+            } => _scan(source, projection.as_deref(), predicate.as_ref()).await?,
+            LogicalPlan::Projection { input, exprs } => {
+                execute(input).await?.select_exprs(exprs)?
+            }
+            LogicalPlan::Filter { input, predicate } => {
+                execute(input).await?.filter_expr(predicate)?
+            }
+            LogicalPlan::Aggregate { input, keys, aggs } => {
+                _aggregate(execute(input).await?, keys, aggs)?
+            }
+            LogicalPlan::Sort { input, by } => _sort(execute(input).await?, by)?,
+            LogicalPlan::Join {
+                left,
+                right,
+                left_on,
+                right_on,
+                how,
+            } => {
+                let (l, r) = (execute(left).await?, execute(right).await?);
+                l.merge(r, left_on, right_on, *how)?
+            }
+            LogicalPlan::Limit { input, n } => {
+                let (c, t, mut d) = execute(input).await?.dcst();
+                d.truncate(*n);
+                FqxData::cst(c, t, d)
+            }
+        };
+
+        Ok(d)
+    }
+    .boxed()
+}
+
+async fn _scan(
+    source: &ScanSource,
+    projection: Option<&[String]>,
+    predicate: Option<&Expr>,
+) -> FqxResult<FqxData> {
+    let d = match source {
+        ScanSource::Data(d) => d.clone(),
+        ScanSource::Csv { path, type_hints } => csv_read_rd(path, type_hints)?,
+        ScanSource::Sql { conn, table, .. } => {
+            let (sql, params) = scan_statement(conn.driver(), table, projection, predicate);
+            return if params.is_empty() {
+                conn.dyn_fetch(&sql).await
+            } else {
+                conn.dyn_fetch_with(&sql, &params).await
+            };
+        }
+    };
+
+    let d = match predicate {
+        Some(p) => d.filter_expr(p)?,
+        None => d,
+    };
+    match projection {
+        Some(p) => d.select_exprs(&p.iter().map(col).collect::<Vec<_>>()),
+        None => Ok(d),
+    }
+}
+
+fn _position(d: &FqxData, column: &str) -> FqxResult<usize> {
+    d.columns()
+        .iter()
+        .position(|c| c == column)
+        .ok_or_else(|| FqxError::ColumnNotFound(column.to_string()))
+}
+
+/// Groups come out in the order of their keys, with the keys as leading columns, see `OpStats`.
+fn _aggregate(d: FqxData, keys: &[String], aggs: &[AggSpec]) -> FqxResult<FqxData> {
+    if keys.is_empty() {
+        return d.agg_specs(aggs);
+    }
+
+    // `group_by` takes the keys in the order of the columns
+    let others = d.columns().iter().filter(|c| !keys.contains(c));
+    let exprs = keys.iter().chain(others).map(col).collect::<Vec<_>>();
+    d.select_exprs(&exprs)?
+        .group_by(&keys.to_vec())
+        .agg_specs(aggs)
+}
+
+fn _sort(d: FqxData, by: &[(String, bool)]) -> FqxResult<FqxData> {
+    let by = by
+        .iter()
+        .map(|(c, desc)| Ok((_position(&d, c)?, *desc)))
+        .collect::<FqxResult<Vec<_>>>()?;
+
+    let (c, t, mut data) = d.dcst();
+    data.sort_by(|a, b| {
+        by.iter()
+            .map(|&(i, desc)| _cmp_nulls_last(&a[i], &b[i], desc))
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+
+    Ok(FqxData::cst(c, t, data))
+}
@@ -0,0 +1,299 @@
+//! file: frame.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 16:58:23 Saturday
+//! brief:
+
+use std::path::Path;
+
+use csv::Reader;
+
+use crate::adt::{FqxData, FqxValueType};
+use crate::error::FqxResult;
+use crate::lazy::exec::execute;
+use crate::lazy::optimize::optimize;
+use crate::lazy::plan::{LogicalPlan, ScanSource};
+use crate::ops::{AggSpec, Expr, FqxJoinType};
+use crate::sources::sql::SqlConnector;
+
+// ================================================================================================
+// LazyFqx
+//
+// Records each step into a `LogicalPlan` instead of running it. `collect` optimizes the plan,
+// then runs it through the `ops` traits, or through the database for a SQL scan.
+// ================================================================================================
+
+#[derive(Debug, Clone)]
+pub struct LazyFqx {
+    plan: LogicalPlan,
+}
+
+impl From<FqxData> for LazyFqx {
+    fn from(d: FqxData) -> Self {
+        LazyFqx::scan(d)
+    }
+}
+
+impl LazyFqx {
+    fn wrap(self, f: impl FnOnce(Box<LogicalPlan>) -> LogicalPlan) -> Self {
+        LazyFqx {
+            plan: f(Box::new(self.plan)),
+        }
+    }
+
+    pub fn scan(data: FqxData) -> Self {
+        LazyFqx {
+            plan: LogicalPlan::Scan {
+                schema: data.columns.clone(),
+                source: ScanSource::Data(data),
+                projection: None,
+                predicate: None,
+            },
+        }
+    }
+
+    /// Only the header is read here, see `csv_read_rd` for `type_hints`.
+    pub fn scan_csv<P: AsRef<Path>>(path: P, type_hints: &[FqxValueType]) -> FqxResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let schema = Reader::from_path(&path)?
+            .headers()?
+            .iter()
+            .map(String::from)
+            .collect();
+
+        Ok(LazyFqx {
+            plan: LogicalPlan::Scan {
+                source: ScanSource::Csv {
+                    path,
+                    type_hints: type_hints.to_vec(),
+                },
+                schema,
+                projection: None,
+                predicate: None,
+            },
+        })
+    }
+
+    /// Only the table's columns are fetched here, see `SqlConnector::describe_table`.
+    pub async fn scan_sql(conn: &SqlConnector, table: &str) -> FqxResult<Self> {
+        let (schema, types) = conn
+            .describe_table(table)
+            .await?
+            .into_iter()
+            .map(|c| (c.name, c.value_type))
+            .unzip();
+
+        Ok(LazyFqx {
+            plan: LogicalPlan::Scan {
+                source: ScanSource::Sql {
+                    conn: conn.clone(),
+                    table: table.to_string(),
+                    types,
+                },
+                schema,
+                projection: None,
+                predicate: None,
+            },
+        })
+    }
+
+    pub fn select(self, exprs: &[Expr]) -> Self {
+        self.wrap(|input| LogicalPlan::Projection {
+            input,
+            exprs: exprs.to_vec(),
+        })
+    }
+
+    pub fn filter(self, predicate: Expr) -> Self {
+        self.wrap(|input| LogicalPlan::Filter { input, predicate })
+    }
+
+    pub fn group_by<I, S>(self, keys: I) -> LazyGroupBy
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        LazyGroupBy {
+            lz: self,
+            keys: keys.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// `(column, descending)` pairs.
+    pub fn sort_by<I, S>(self, by: I) -> Self
+    where
+        I: IntoIterator<Item = (S, bool)>,
+        S: Into<String>,
+    {
+        let by = by.into_iter().map(|(c, d)| (c.into(), d)).collect();
+        self.wrap(|input| LogicalPlan::Sort { input, by })
+    }
+
+    pub fn join<S: AsRef<str>>(
+        self,
+        other: LazyFqx,
+        left_on: &[S],
+        right_on: &[S],
+        how: FqxJoinType,
+    ) -> Self {
+        let on = |s: &[S]| s.iter().map(|e| e.as_ref().to_string()).collect();
+        self.wrap(|left| LogicalPlan::Join {
+            left,
+            right: Box::new(other.plan),
+            left_on: on(left_on),
+            right_on: on(right_on),
+            how,
+        })
+    }
+
+    pub fn limit(self, n: usize) -> Self {
+        self.wrap(|input| LogicalPlan::Limit { input, n })
+    }
+
+    pub fn logical_plan(&self) -> &LogicalPlan {
+        &self.plan
+    }
+
+    pub fn optimized_plan(&self) -> LogicalPlan {
+        optimize(self.plan.clone())
+    }
+
+    /// The optimized plan, one node per line.
+    pub fn explain(&self) -> String {
+        self.optimized_plan().to_string()
+    }
+
+    pub async fn collect(&self) -> FqxResult<FqxData> {
+        execute(&self.optimized_plan()).await
+    }
+}
+
+// ================================================================================================
+// LazyGroupBy
+// ================================================================================================
+
+#[derive(Debug, Clone)]
+pub struct LazyGroupBy {
+    lz: LazyFqx,
+    keys: Vec<String>,
+}
+
+impl LazyGroupBy {
+    pub fn agg<I, A>(self, specs: I) -> LazyFqx
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<AggSpec>,
+    {
+        let LazyGroupBy { lz, keys } = self;
+        let aggs = specs.into_iter().map(Into::into).collect();
+        lz.wrap(|input| LogicalPlan::Aggregate { input, keys, aggs })
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_lazy_frame {
+    use super::*;
+    use crate::adt::{FqxD, FqxValue, RowProps};
+    use crate::fqx;
+    use crate::ops::mock::data::D1;
+    use crate::ops::{col, lit, Agg};
+    use crate::sources::adt::SaveMode;
+
+    #[tokio::test]
+    async fn collect_data_success() {
+        let res = LazyFqx::scan(D1.clone())
+            .filter(col("col_2").is_not_null())
+            .group_by(["col_1"])
+            .agg([
+                ("col_0", Agg::Sum, "total"),
+                ("col_2", Agg::Mean, "avg"),
+                ("*", Agg::Count, "n"),
+            ])
+            .sort_by([("n", true)])
+            .limit(2)
+            .collect()
+            .await
+            .unwrap();
+        println!("{:?}", res);
+
+        assert_eq!(res.columns(), &["col_1", "total", "avg", "n"]);
+        assert_eq!(
+            res.types(),
+            &[
+                FqxValueType::String,
+                FqxValueType::I32,
+                FqxValueType::F64,
+                FqxValueType::I64
+            ]
+        );
+        assert_eq!(res.height(), 2);
+    }
+
+    #[tokio::test]
+    async fn collect_csv_success() {
+        let path = std::env::temp_dir().join("fqx_lazy.csv");
+        std::fs::write(&path, "id,name,v\n1,a,1.5\n2,b,2.5\n3,a,3.5\n").unwrap();
+
+        let lz = LazyFqx::scan_csv(
+            &path,
+            &[FqxValueType::I32, FqxValueType::String, FqxValueType::F64],
+        )
+        .unwrap()
+        .filter(col("name").eq(lit("a")))
+        .select(&[col("v")]);
+
+        assert_eq!(
+            lz.explain(),
+            format!(
+                "Projection: [v]\n  Scan: csv {} projection=[v] predicate=(name = 'a')\n",
+                path.display()
+            )
+        );
+
+        let res = lz.collect().await.unwrap();
+        assert_eq!(res.data()[0].values(), &[fqx!(1.5)]);
+        assert_eq!(res.data()[1].values(), &[fqx!(3.5)]);
+    }
+
+    #[tokio::test]
+    async fn collect_sql_success() {
+        let conn = SqlConnector::sqlite_memory().await.unwrap();
+        let data = FqxData::new(
+            vec![String::from("id"), String::from("name"), String::from("v")],
+            vec![FqxValueType::I64, FqxValueType::String, FqxValueType::F64],
+            vec![
+                vec![fqx!(1i64), fqx!("a"), fqx!(1.5)],
+                vec![fqx!(2i64), fqx!("b"), fqx!(2.5)],
+                vec![fqx!(3i64), fqx!("a"), FqxValue::Null],
+            ],
+        )
+        .unwrap();
+        conn.dyn_save(data, "t", SaveMode::Override, false)
+            .await
+            .unwrap();
+
+        let lz = LazyFqx::scan_sql(&conn, "t")
+            .await
+            .unwrap()
+            .filter(col("v").is_not_null())
+            .filter(col("name").eq(lit("a")) & !(col("id") / lit(2)).eq(lit(1)))
+            .select(&[col("id")]);
+
+        // the division and the string comparison stay out of the generated SQL
+        assert_eq!(
+            lz.explain(),
+            "Projection: [id]
+  Filter: ((name = 'a') AND NOT ((id / 2) = 1))
+    Scan: sql t [SQLITE] projection=[id, name] predicate=v IS NOT NULL
+"
+        );
+
+        let res = lz.collect().await.unwrap();
+        assert_eq!(res.columns(), &["id"]);
+        assert_eq!(res.height(), 1);
+        assert_eq!(res.data()[0].values(), &[fqx!(1i64)]);
+    }
+}
@@ -0,0 +1,13 @@
+//! file: mod.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 15:32:08 Saturday
+//! brief:
+
+mod exec;
+pub mod frame;
+mod optimize;
+pub mod plan;
+pub(crate) mod sql;
+
+pub use frame::*;
+pub use plan::*;
@@ -0,0 +1,432 @@
+//! file: optimize.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 16:08:15 Saturday
+//! brief:
+
+use std::collections::HashSet;
+
+use crate::lazy::plan::{LogicalPlan, ScanSource};
+use crate::lazy::sql::is_translatable;
+use crate::ops::{BinaryOp, Expr, FqxJoinType, DEFAULT_SUFFIXES};
+
+// ================================================================================================
+// Optimizer
+// ================================================================================================
+
+/// Predicates are pushed down first, so that a predicate moved into a scan no longer requires
+/// its columns to be projected.
+pub(crate) fn optimize(plan: LogicalPlan) -> LogicalPlan {
+    let plan = push_down_predicates(plan, vec![]);
+    push_down_projections(plan, None)
+}
+
+fn split_conjuncts(expr: Expr, acc: &mut Vec<Expr>) {
+    match expr {
+        Expr::Binary {
+            lhs,
+            op: BinaryOp::And,
+            rhs,
+        } => {
+            split_conjuncts(*lhs, acc);
+            split_conjuncts(*rhs, acc);
+        }
+        e => acc.push(e),
+    }
+}
+
+fn conjoin(preds: Vec<Expr>) -> Option<Expr> {
+    preds.into_iter().reduce(Expr::and)
+}
+
+fn filter_above(input: LogicalPlan, preds: Vec<Expr>) -> LogicalPlan {
+    match conjoin(preds) {
+        Some(predicate) => LogicalPlan::Filter {
+            input: Box::new(input),
+            predicate,
+        },
+        None => input,
+    }
+}
+
+fn refers_only_to(expr: &Expr, columns: &[String]) -> bool {
+    expr.columns()
+        .iter()
+        .all(|c| columns.iter().any(|e| e == c))
+}
+
+// ================================================================================================
+// Predicate pushdown
+// ================================================================================================
+
+/// `preds` are the conjuncts collected from the filters above `plan`.
+fn push_down_predicates(plan: LogicalPlan, mut preds: Vec<Expr>) -> LogicalPlan {
+    match plan {
+        LogicalPlan::Filter { input, predicate } => {
+            split_conjuncts(predicate, &mut preds);
+            push_down_predicates(*input, preds)
+        }
+        LogicalPlan::Scan {
+            source,
+            schema,
+            projection,
+            predicate,
+        } => {
+            // a database only receives what it evaluates the same way
+            let (pushed, kept): (Vec<_>, Vec<_>) = preds.into_iter().partition(|p| match &source {
+                ScanSource::Sql { conn, types, .. } => {
+                    is_translatable(p, conn.driver(), &schema, types)
+                }
+                _ => true,
+            });
+            let predicate = conjoin(predicate.into_iter().chain(pushed).collect());
+            let scan = LogicalPlan::Scan {
+                source,
+                schema,
+                projection,
+                predicate,
+            };
+            filter_above(scan, kept)
+        }
+        LogicalPlan::Projection { input, exprs } => {
+            // only through columns passed as they are
+            let passed = exprs
+                .iter()
+                .filter_map(|e| match e {
+                    Expr::Column(c) => Some(c.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            let (pushed, kept) = preds.into_iter().partition(|p| refers_only_to(p, &passed));
+            let plan = LogicalPlan::Projection {
+                input: Box::new(push_down_predicates(*input, pushed)),
+                exprs,
+            };
+            filter_above(plan, kept)
+        }
+        LogicalPlan::Aggregate { input, keys, aggs } => {
+            let (pushed, kept) = preds.into_iter().partition(|p| refers_only_to(p, &keys));
+            let plan = LogicalPlan::Aggregate {
+                input: Box::new(push_down_predicates(*input, pushed)),
+                keys,
+                aggs,
+            };
+            filter_above(plan, kept)
+        }
+        LogicalPlan::Sort { input, by } => LogicalPlan::Sort {
+            input: Box::new(push_down_predicates(*input, preds)),
+            by,
+        },
+        LogicalPlan::Join {
+            left,
+            right,
+            left_on,
+            right_on,
+            how,
+        } => {
+            let (ls, rs) = (left.schema(), right.schema());
+            let (mut to_left, mut to_right, mut kept) = (vec![], vec![], vec![]);
+            // a key named alike on both sides is kept once, any other name found on both sides
+            // is suffixed, see `schema`
+            let shared = |c: &str| {
+                how != FqxJoinType::Cross
+                    && left_on.iter().zip(&right_on).any(|(a, b)| a == c && b == c)
+            };
+            let renamed = |c: &str| {
+                !matches!(how, FqxJoinType::LeftSemi | FqxJoinType::LeftAnti)
+                    && ls.iter().any(|e| e == c)
+                    && rs.iter().any(|e| e == c)
+                    && !shared(c)
+            };
+            for p in preds {
+                if p.columns().into_iter().any(renamed) {
+                    kept.push(p);
+                    continue;
+                }
+                let in_l = refers_only_to(&p, &ls);
+                let in_r = refers_only_to(&p, &rs);
+                let none_in_l = p.columns().iter().all(|c| !ls.iter().any(|e| e == c));
+                match how {
+                    FqxJoinType::Inner if in_l => to_left.push(p),
+                    FqxJoinType::Inner if in_r && none_in_l => to_right.push(p),
+                    FqxJoinType::Left if in_l => to_left.push(p),
+                    FqxJoinType::Right if in_r => to_right.push(p),
+                    // only the left columns are left
+                    FqxJoinType::LeftSemi | FqxJoinType::LeftAnti if in_l => to_left.push(p),
+                    FqxJoinType::Cross if in_l => to_left.push(p),
+                    FqxJoinType::Cross if in_r => to_right.push(p),
+                    _ => kept.push(p),
+                }
+            }
+            let plan = LogicalPlan::Join {
+                left: Box::new(push_down_predicates(*left, to_left)),
+                right: Box::new(push_down_predicates(*right, to_right)),
+                left_on,
+                right_on,
+                how,
+            };
+            filter_above(plan, kept)
+        }
+        // filtering before a limit changes which rows are kept
+        LogicalPlan::Limit { input, n } => {
+            let plan = LogicalPlan::Limit {
+                input: Box::new(push_down_predicates(*input, vec![])),
+                n,
+            };
+            filter_above(plan, preds)
+        }
+    }
+}
+
+// ================================================================================================
+// Projection pushdown
+// ================================================================================================
+
+fn with_columns<'a, I>(required: &Option<HashSet<String>>, columns: I) -> Option<HashSet<String>>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    required.as_ref().map(|r| {
+        let mut r = r.clone();
+        r.extend(columns.into_iter().map(String::from));
+        r
+    })
+}
+
+/// `required` is `None` when every column of `plan` is needed.
+fn push_down_projections(plan: LogicalPlan, required: Option<HashSet<String>>) -> LogicalPlan {
+    match plan {
+        LogicalPlan::Scan {
+            source,
+            schema,
+            projection,
+            predicate,
+        } => {
+            let projection = match required {
+                Some(r) => {
+                    let p = schema
+                        .iter()
+                        .filter(|c| r.contains(*c))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    // nothing required, e.g. `count(*)`, still needs the rows
+                    if p.is_empty() || p.len() == schema.len() {
+                        projection
+                    } else {
+                        Some(p)
+                    }
+                }
+                None => projection,
+            };
+            LogicalPlan::Scan {
+                source,
+                schema,
+                projection,
+                predicate,
+            }
+        }
+        LogicalPlan::Projection { input, exprs } => {
+            let r = exprs
+                .iter()
+                .flat_map(|e| e.columns())
+                .map(String::from)
+                .collect();
+            LogicalPlan::Projection {
+                input: Box::new(push_down_projections(*input, Some(r))),
+                exprs,
+            }
+        }
+        LogicalPlan::Filter { input, predicate } => {
+            let r = with_columns(&required, predicate.columns());
+            LogicalPlan::Filter {
+                input: Box::new(push_down_projections(*input, r)),
+                predicate,
+            }
+        }
+        LogicalPlan::Aggregate { input, keys, aggs } => {
+            let r = keys
+                .iter()
+                .cloned()
+                .chain(
+                    aggs.iter()
+                        .filter(|a| !a.is_count_all())
+                        .map(|a| a.column.clone()),
+                )
+                .collect();
+            LogicalPlan::Aggregate {
+                input: Box::new(push_down_projections(*input, Some(r))),
+                keys,
+                aggs,
+            }
+        }
+        LogicalPlan::Sort { input, by } => {
+            let r = with_columns(&required, by.iter().map(|(c, _)| c.as_str()));
+            LogicalPlan::Sort {
+                input: Box::new(push_down_projections(*input, r)),
+                by,
+            }
+        }
+        LogicalPlan::Join {
+            left,
+            right,
+            left_on,
+            right_on,
+            how,
+        } => {
+            // a suffixed name found on both sides must be kept on both, see `schema`
+            let (ls, rs) = (left.schema(), right.schema());
+            let renamed = |c: &String, r: &HashSet<String>| {
+                !matches!(how, FqxJoinType::LeftSemi | FqxJoinType::LeftAnti)
+                    && ls.contains(c)
+                    && rs.contains(c)
+                    && (r.contains(&format!("{c}{}", DEFAULT_SUFFIXES.0))
+                        || r.contains(&format!("{c}{}", DEFAULT_SUFFIXES.1)))
+            };
+            let side = |schema: &[String], on: &[String]| {
+                required.as_ref().map(|r| {
+                    schema
+                        .iter()
+                        .filter(|c| r.contains(*c) || on.contains(c) || renamed(c, r))
+                        .cloned()
+                        .collect::<HashSet<_>>()
+                })
+            };
+            let (lr, rr) = (side(&ls, &left_on), side(&rs, &right_on));
+            LogicalPlan::Join {
+                left: Box::new(push_down_projections(*left, lr)),
+                right: Box::new(push_down_projections(*right, rr)),
+                left_on,
+                right_on,
+                how,
+            }
+        }
+        LogicalPlan::Limit { input, n } => LogicalPlan::Limit {
+            input: Box::new(push_down_projections(*input, required)),
+            n,
+        },
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_optimize {
+    use super::*;
+    use crate::lazy::LazyFqx;
+    use crate::ops::mock::data::{D1, D2};
+    use crate::ops::{col, lit, Agg};
+
+    #[test]
+    fn predicate_pushdown_success() {
+        let plan = LazyFqx::scan(D2.clone())
+            .select(&[
+                col("col_0"),
+                col("col_1"),
+                (col("col_2") * lit(2)).alias("x"),
+            ])
+            .filter(col("col_0").gt(lit(1)) & col("x").lt(lit(10.0)))
+            .limit(3)
+            .optimized_plan();
+
+        // `x` is computed, so its predicate stays above the projection
+        assert_eq!(
+            plan.to_string(),
+            "Limit: 3
+  Filter: (x < 10)
+    Projection: [col_0, col_1, (col_2 * 2) AS x]
+      Scan: data [9 rows] predicate=(col_0 > 1)
+"
+        );
+    }
+
+    #[test]
+    fn predicate_pushdown_barrier_success() {
+        let plan = LazyFqx::scan(D2.clone())
+            .limit(3)
+            .filter(col("col_0").gt(lit(1)))
+            .optimized_plan();
+        assert!(matches!(plan, LogicalPlan::Filter { .. }));
+
+        let plan = LazyFqx::scan(D1.clone())
+            .group_by(["col_1"])
+            .agg([("col_0", Agg::Sum, "s")])
+            .filter(col("col_1").neq(lit("A")) & col("s").gt(lit(1)))
+            .optimized_plan();
+        assert_eq!(
+            plan.to_string(),
+            "Filter: (s > 1)
+  Aggregate: keys=[col_1] aggs=[sum(col_0) AS s]
+    Scan: data [9 rows] projection=[col_0, col_1] predicate=(col_1 != 'A')
+"
+        );
+    }
+
+    #[test]
+    fn join_pushdown_success() {
+        let right = LazyFqx::scan(D1.clone()).select(&[
+            col("col_0").alias("id"),
+            col("col_1").alias("name"),
+            col("col_2").alias("v"),
+        ]);
+        let plan = LazyFqx::scan(D2.clone())
+            .join(right, &["col_0"], &["id"], FqxJoinType::Left)
+            .filter(col("col_2").gt(lit(1.0)) & col("v").gt(lit(1.0)))
+            .select(&[col("col_1"), col("name")])
+            .optimized_plan();
+
+        // the right side of a left join is never filtered before joining
+        assert_eq!(
+            plan.to_string(),
+            "Projection: [col_1, name]
+  Filter: (v > 1)
+    Join: Left [col_0] = [id]
+      Scan: data [9 rows] projection=[col_0, col_1] predicate=(col_2 > 1)
+      Projection: [col_0 AS id, col_1 AS name, col_2 AS v]
+        Scan: data [9 rows]
+"
+        );
+    }
+
+    #[test]
+    fn join_semi_cross_pushdown_success() {
+        let right = || LazyFqx::scan(D1.clone()).select(&[col("col_0").alias("id")]);
+        let plan = LazyFqx::scan(D2.clone())
+            .join(right(), &["col_0"], &["id"], FqxJoinType::LeftSemi)
+            .filter(col("col_2").gt(lit(1.0)))
+            .select(&[col("col_1")])
+            .optimized_plan();
+
+        assert_eq!(
+            plan.to_string(),
+            "Projection: [col_1]
+  Join: LeftSemi [col_0] = [id]
+    Scan: data [9 rows] projection=[col_0, col_1] predicate=(col_2 > 1)
+    Projection: [col_0 AS id]
+      Scan: data [9 rows] projection=[col_0]
+"
+        );
+
+        let plan = LazyFqx::scan(D2.clone())
+            .join(
+                LazyFqx::scan(D1.clone()),
+                &[] as &[&str],
+                &[],
+                FqxJoinType::Cross,
+            )
+            .filter(col("col_0_x").gt(lit(1)))
+            .select(&[col("col_0_x"), col("col_1_y")])
+            .optimized_plan();
+
+        // a renamed column is filtered above the join, and kept on both sides
+        assert_eq!(
+            plan.to_string(),
+            "Projection: [col_0_x, col_1_y]
+  Filter: (col_0_x > 1)
+    Join: Cross [] = []
+      Scan: data [9 rows] projection=[col_0, col_1]
+      Scan: data [9 rows] projection=[col_0, col_1]
+"
+        );
+    }
+}
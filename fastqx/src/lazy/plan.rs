@@ -0,0 +1,202 @@
+//! file: plan.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 15:33:41 Saturday
+//! brief:
+
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::adt::{FqxD, FqxData, FqxValueType};
+use crate::ops::utils::JoinLayout;
+use crate::ops::{AggSpec, Expr, FqxJoinType, DEFAULT_SUFFIXES};
+use crate::sources::sql::SqlConnector;
+
+// ================================================================================================
+// ScanSource
+// ================================================================================================
+
+#[derive(Debug, Clone)]
+pub enum ScanSource {
+    Data(FqxData),
+    Csv {
+        path: PathBuf,
+        type_hints: Vec<FqxValueType>,
+    },
+    Sql {
+        conn: SqlConnector,
+        table: String,
+        /// the types `dyn_fetch` reads the columns of the scan's `schema` as
+        types: Vec<FqxValueType>,
+    },
+}
+
+// ================================================================================================
+// LogicalPlan
+//
+// Nothing is read until the plan is executed. `Scan` carries its own projection and predicate,
+// which are filled by the optimizer rather than by the builder.
+// ================================================================================================
+
+#[derive(Debug, Clone)]
+pub enum LogicalPlan {
+    Scan {
+        source: ScanSource,
+        schema: Vec<String>,
+        projection: Option<Vec<String>>,
+        predicate: Option<Expr>,
+    },
+    Projection {
+        input: Box<LogicalPlan>,
+        exprs: Vec<Expr>,
+    },
+    Filter {
+        input: Box<LogicalPlan>,
+        predicate: Expr,
+    },
+    Aggregate {
+        input: Box<LogicalPlan>,
+        keys: Vec<String>,
+        aggs: Vec<AggSpec>,
+    },
+    /// `(column, descending)`, nulls are placed last
+    Sort {
+        input: Box<LogicalPlan>,
+        by: Vec<(String, bool)>,
+    },
+    Join {
+        left: Box<LogicalPlan>,
+        right: Box<LogicalPlan>,
+        left_on: Vec<String>,
+        right_on: Vec<String>,
+        how: FqxJoinType,
+    },
+    Limit {
+        input: Box<LogicalPlan>,
+        n: usize,
+    },
+}
+
+impl LogicalPlan {
+    /// Output column names.
+    pub fn schema(&self) -> Vec<String> {
+        match self {
+            LogicalPlan::Scan {
+                schema, projection, ..
+            } => projection.clone().unwrap_or_else(|| schema.clone()),
+            LogicalPlan::Projection { exprs, .. } => exprs.iter().map(Expr::output_name).collect(),
+            LogicalPlan::Filter { input, .. }
+            | LogicalPlan::Sort { input, .. }
+            | LogicalPlan::Limit { input, .. } => input.schema(),
+            LogicalPlan::Aggregate { keys, aggs, .. } => keys
+                .iter()
+                .cloned()
+                .chain(aggs.iter().map(|a| a.alias.clone()))
+                .collect(),
+            // `OpMerge` puts the columns of the preserved side first
+            LogicalPlan::Join {
+                left,
+                right,
+                left_on,
+                right_on,
+                how,
+            } => {
+                let (s0, s1) = DEFAULT_SUFFIXES;
+                let (ls, rs) = (left.schema(), right.schema());
+                let layout = match how {
+                    FqxJoinType::LeftSemi | FqxJoinType::LeftAnti => return ls,
+                    FqxJoinType::Right => JoinLayout::new(&rs, &ls, right_on, left_on, (s1, s0)),
+                    FqxJoinType::Cross => {
+                        JoinLayout::new::<&str>(&ls, &rs, &[], &[], DEFAULT_SUFFIXES)
+                    }
+                    _ => JoinLayout::new(&ls, &rs, left_on, right_on, DEFAULT_SUFFIXES),
+                };
+                // unknown keys fail once the join runs
+                layout.map(|l| l.columns).unwrap_or_default()
+            }
+        }
+    }
+
+    fn fmt_indent(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{:indent$}", "", indent = depth * 2)?;
+
+        match self {
+            LogicalPlan::Scan {
+                source,
+                projection,
+                predicate,
+                ..
+            } => {
+                match source {
+                    ScanSource::Data(d) => write!(f, "Scan: data [{} rows]", d.height())?,
+                    ScanSource::Csv { path, .. } => write!(f, "Scan: csv {}", path.display())?,
+                    ScanSource::Sql { conn, table, .. } => {
+                        write!(f, "Scan: sql {table} [{:?}]", conn.driver())?
+                    }
+                }
+                if let Some(p) = projection {
+                    write!(f, " projection=[{}]", p.join(", "))?;
+                }
+                if let Some(p) = predicate {
+                    write!(f, " predicate={p}")?;
+                }
+                writeln!(f)
+            }
+            LogicalPlan::Projection { input, exprs } => {
+                let exprs = exprs.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+                writeln!(f, "Projection: [{}]", exprs.join(", "))?;
+                input.fmt_indent(f, depth + 1)
+            }
+            LogicalPlan::Filter { input, predicate } => {
+                writeln!(f, "Filter: {predicate}")?;
+                input.fmt_indent(f, depth + 1)
+            }
+            LogicalPlan::Aggregate { input, keys, aggs } => {
+                let aggs = aggs
+                    .iter()
+                    .map(|a| format!("{}({}) AS {}", a.agg.name(), a.column, a.alias))
+                    .collect::<Vec<_>>();
+                writeln!(
+                    f,
+                    "Aggregate: keys=[{}] aggs=[{}]",
+                    keys.join(", "),
+                    aggs.join(", ")
+                )?;
+                input.fmt_indent(f, depth + 1)
+            }
+            LogicalPlan::Sort { input, by } => {
+                let by = by
+                    .iter()
+                    .map(|(c, desc)| format!("{c} {}", if *desc { "DESC" } else { "ASC" }))
+                    .collect::<Vec<_>>();
+                writeln!(f, "Sort: [{}]", by.join(", "))?;
+                input.fmt_indent(f, depth + 1)
+            }
+            LogicalPlan::Join {
+                left,
+                right,
+                left_on,
+                right_on,
+                how,
+            } => {
+                writeln!(
+                    f,
+                    "Join: {how:?} [{}] = [{}]",
+                    left_on.join(", "),
+                    right_on.join(", ")
+                )?;
+                left.fmt_indent(f, depth + 1)?;
+                right.fmt_indent(f, depth + 1)
+            }
+            LogicalPlan::Limit { input, n } => {
+                writeln!(f, "Limit: {n}")?;
+                input.fmt_indent(f, depth + 1)
+            }
+        }
+    }
+}
+
+impl fmt::Display for LogicalPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indent(f, 0)
+    }
+}
@@ -0,0 +1,216 @@
+//! file: sql.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 15:51:27 Saturday
+//! brief:
+
+use crate::adt::{FqxValue, FqxValueType};
+use crate::ops::expr::BoundExpr;
+use crate::ops::{BinaryOp, Expr};
+use crate::sources::sql::Driver;
+
+// ================================================================================================
+// Scan statement
+//
+// Literals are bound as `?` parameters, which `dyn_fetch_with` rewrites for each driver.
+// ================================================================================================
+
+/// Whether the predicate `expr`, over a table of `columns` and `types`, keeps the same meaning
+/// once written in SQL. What doesn't:
+///
+/// - division and casts: integer division by zero is `null` for `Expr`, and cast targets differ
+///   between databases;
+/// - string comparisons: MySql and MsSql compare with a case-insensitive collation by default;
+/// - arithmetic mixing floats with integers or decimals, which a database may compute exactly;
+/// - for MsSql, a column or literal used as a condition, e.g. `NOT [a]`, since `BIT` is no
+///   boolean there.
+pub(crate) fn is_translatable(
+    expr: &Expr,
+    driver: &Driver,
+    columns: &[String],
+    types: &[FqxValueType],
+) -> bool {
+    match expr.bind(columns, types) {
+        Ok(b) => _translatable(&b, driver) && _is_condition(&b, driver),
+        Err(_) => false,
+    }
+}
+
+/// Only MsSql tells conditions apart from values.
+fn _is_condition(expr: &BoundExpr, driver: &Driver) -> bool {
+    match expr {
+        _ if !matches!(driver, Driver::MSSQL) => true,
+        BoundExpr::Binary(_, op, _) => {
+            op.is_compare() || matches!(op, BinaryOp::And | BinaryOp::Or)
+        }
+        BoundExpr::Not(_) | BoundExpr::IsNull(..) => true,
+        BoundExpr::Column(..) | BoundExpr::Literal(_) | BoundExpr::Cast(..) => false,
+    }
+}
+
+fn _translatable(expr: &BoundExpr, driver: &Driver) -> bool {
+    match expr {
+        BoundExpr::Column(..) | BoundExpr::Literal(_) => true,
+        BoundExpr::Binary(lhs, op, rhs) => {
+            let (lt, rt) = (lhs.output_type(), rhs.output_type());
+            let same_meaning = match op {
+                BinaryOp::Div | BinaryOp::FloorDiv | BinaryOp::Rem => false,
+                BinaryOp::And | BinaryOp::Or => {
+                    _is_condition(lhs, driver) && _is_condition(rhs, driver)
+                }
+                op if op.is_compare() => lt != FqxValueType::String && rt != FqxValueType::String,
+                _ => !(lt.is_numeric() && rt.is_numeric() && lt.is_float() != rt.is_float()),
+            };
+            same_meaning && _translatable(lhs, driver) && _translatable(rhs, driver)
+        }
+        BoundExpr::Not(e) => _is_condition(e, driver) && _translatable(e, driver),
+        BoundExpr::IsNull(e, _) => _translatable(e, driver),
+        BoundExpr::Cast(..) => false,
+    }
+}
+
+pub(crate) fn _quote(driver: &Driver, name: &str) -> String {
+    match driver {
+        Driver::MYSQL => format!("`{}`", name.replace('`', "``")),
+        Driver::MSSQL => format!("[{}]", name.replace(']', "]]")),
+        Driver::POSTGRES | Driver::SQLITE => format!("\"{}\"", name.replace('"', "\"\"")),
+    }
+}
+
+/// A schema-qualified table name is quoted part by part.
+pub(crate) fn quote_ident(driver: &Driver, ident: &str) -> String {
+    ident
+        .split('.')
+        .map(|p| _quote(driver, p))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+pub(crate) fn scan_statement(
+    driver: &Driver,
+    table: &str,
+    projection: Option<&[String]>,
+    predicate: Option<&Expr>,
+) -> (String, Vec<FqxValue>) {
+    let columns = match projection {
+        Some(p) => p
+            .iter()
+            .map(|c| _quote(driver, c))
+            .collect::<Vec<_>>()
+            .join(", "),
+        None => String::from("*"),
+    };
+    let mut sql = format!("SELECT {} FROM {}", columns, quote_ident(driver, table));
+
+    let mut params = vec![];
+    if let Some(p) = predicate {
+        sql.push_str(" WHERE ");
+        sql.push_str(&_expr_to_sql(p, driver, &mut params));
+    }
+
+    (sql, params)
+}
+
+fn _expr_to_sql(expr: &Expr, driver: &Driver, params: &mut Vec<FqxValue>) -> String {
+    match expr {
+        Expr::Column(c) => _quote(driver, c),
+        Expr::Literal(FqxValue::Null) => String::from("NULL"),
+        Expr::Literal(v) => {
+            params.push(v.clone());
+            String::from("?")
+        }
+        Expr::Binary { lhs, op, rhs } => {
+            let op = match op {
+                BinaryOp::Neq => String::from("<>"),
+                op => op.to_string(),
+            };
+            format!(
+                "({} {} {})",
+                _expr_to_sql(lhs, driver, params),
+                op,
+                _expr_to_sql(rhs, driver, params)
+            )
+        }
+        Expr::Not(e) => format!("(NOT {})", _expr_to_sql(e, driver, params)),
+        Expr::IsNull(e) => format!("({} IS NULL)", _expr_to_sql(e, driver, params)),
+        Expr::IsNotNull(e) => format!("({} IS NOT NULL)", _expr_to_sql(e, driver, params)),
+        Expr::Alias(e, _) => _expr_to_sql(e, driver, params),
+        Expr::Cast(..) => unreachable!("checked by `is_translatable`"),
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_lazy_sql {
+    use super::*;
+    use crate::fqx;
+    use crate::ops::{col, lit};
+
+    #[test]
+    fn scan_statement_success() {
+        let p = col("price").gt(lit(10)) & (col("name").neq(lit("A")) | col("name").is_null());
+
+        let (sql, params) = scan_statement(
+            &Driver::POSTGRES,
+            "public.trades",
+            Some(&[String::from("name"), String::from("price")]),
+            Some(&p),
+        );
+        assert_eq!(
+            sql,
+            r#"SELECT "name", "price" FROM "public"."trades" WHERE (("price" > ?) AND (("name" <> ?) OR ("name" IS NULL)))"#
+        );
+        assert_eq!(params, vec![fqx!(10), fqx!("A")]);
+
+        let (sql, params) = scan_statement(&Driver::MSSQL, "trades", None, None);
+        assert_eq!(sql, "SELECT * FROM [trades]");
+        assert!(params.is_empty());
+
+        let (sql, _) = scan_statement(&Driver::MYSQL, "trades", None, Some(&!col("a")));
+        assert_eq!(sql, "SELECT * FROM `trades` WHERE (NOT `a`)");
+    }
+
+    #[test]
+    fn is_translatable_success() {
+        let columns = ["a", "b", "f", "s", "t"].map(String::from);
+        let types = [
+            FqxValueType::I64,
+            FqxValueType::I64,
+            FqxValueType::F64,
+            FqxValueType::String,
+            FqxValueType::Bool,
+        ];
+        let check = |e: &Expr, d: &Driver| is_translatable(e, d, &columns, &types);
+        let pg = &Driver::POSTGRES;
+
+        assert!(check(&(col("a") + lit(1)).ge(col("b")), pg));
+        assert!(check(&(col("f") * lit(2.5)).lt(lit(1)), pg));
+        assert!(check(&(col("s").is_null() | !col("t")), pg));
+        assert!(!check(&(col("a") / lit(2)).ge(col("b")), pg));
+        assert!(!check(&col("a").cast(FqxValueType::I64).eq(lit(1)), pg));
+        assert!(!check(&col("x").eq(lit(1)), pg));
+
+        // case-insensitive collations
+        assert!(!check(&col("s").eq(lit("A")), &Driver::MYSQL));
+        assert!(!check(&lit("A").neq(col("s")), pg));
+
+        // exact integer arithmetic against floats
+        assert!(!check(&(col("a") + lit(0.5)).gt(lit(1)), pg));
+        assert!(!check(&(col("f") - col("b")).gt(lit(1)), pg));
+
+        // `BIT` is no boolean
+        let mssql = &Driver::MSSQL;
+        assert!(check(&col("t"), pg));
+        assert!(check(&!col("t"), &Driver::SQLITE));
+        assert!(!check(&col("t"), mssql));
+        assert!(!check(&!col("t"), mssql));
+        assert!(!check(&(col("t") & col("a").gt(lit(1))), mssql));
+        assert!(check(&!col("t").eq(lit(true)), mssql));
+        assert!(check(
+            &(col("a").gt(lit(1)) | col("b").is_not_null()),
+            mssql
+        ));
+    }
+}
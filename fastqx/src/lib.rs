@@ -5,6 +5,9 @@
 
 pub mod adt;
 mod constant;
+pub mod context;
+pub mod error;
+pub mod lazy;
 pub mod ops;
 pub mod sources;
 pub mod utils;
@@ -14,10 +17,14 @@ pub use fastqx_macros;
 
 // re-export
 #[doc(hidden)]
-pub use anyhow;
+pub use arrow;
 #[doc(hidden)]
 pub use chrono;
 #[doc(hidden)]
+pub use parquet;
+#[doc(hidden)]
+pub use rust_decimal;
+#[doc(hidden)]
 pub use sea_query;
 #[doc(hidden)]
 pub use serde;
@@ -32,18 +39,30 @@ pub use tiberius;
 pub mod prelude {
     pub use super::fastqx_macros::*;
 
+    pub use super::error::{FqxError, FqxResult};
+
     pub use super::adt::*;
+    pub use super::context::*;
+    pub use super::lazy::*;
     pub use super::ops::*;
     pub use super::sources::adt::*;
     pub use super::sources::csv::read::*;
     pub use super::sources::csv::write::*;
     pub use super::sources::http::adt::*;
     pub use super::sources::http::conn::*;
+    pub use super::sources::parquet::read::*;
+    pub use super::sources::parquet::write::*;
+    pub use super::sources::registry::*;
     pub use super::sources::sql::ab::*;
     pub use super::sources::sql::adt::*;
+    pub use super::sources::sql::bulk::*;
     pub use super::sources::sql::conn::*;
+    pub use super::sources::sql::dynm::*;
+    pub use super::sources::sql::options::*;
+    pub use super::sources::sql::schema::*;
     pub use super::sources::sql::sqx::*;
     pub use super::sources::sql::tbr::*;
+    pub use super::sources::sql::trans::*;
     pub use super::{fqx, fqxt};
 }
 
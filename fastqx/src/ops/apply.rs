@@ -3,9 +3,8 @@
 //! date: 2023/09/23 18:34:46 Saturday
 //! brief:
 
-use anyhow::Result;
-
 use crate::adt::FqxD;
+use crate::error::FqxResult;
 
 // ================================================================================================
 // OpApply
@@ -18,9 +17,9 @@ pub trait OpApply<const OWNED: bool> {
     where
         F: Fn(Self::Item) -> O;
 
-    fn try_apply<O, F>(self, f: F) -> Result<Vec<O>>
+    fn try_apply<O, F>(self, f: F) -> FqxResult<Vec<O>>
     where
-        F: Fn(Self::Item) -> Result<O>;
+        F: Fn(Self::Item) -> FqxResult<O>;
 }
 
 // ================================================================================================
@@ -43,14 +42,14 @@ where
             .collect::<Vec<_>>()
     }
 
-    fn try_apply<O, F>(self, f: F) -> Result<Vec<O>>
+    fn try_apply<O, F>(self, f: F) -> FqxResult<Vec<O>>
     where
-        F: Fn(Self::Item) -> Result<O>,
+        F: Fn(Self::Item) -> FqxResult<O>,
     {
         self.data()
             .into_iter()
             .map(|r| f(r.clone()))
-            .collect::<Result<Vec<_>>>()
+            .collect::<FqxResult<Vec<_>>>()
     }
 }
 
@@ -67,14 +66,14 @@ where
         self.data().into_iter().map(|r| f(r)).collect::<Vec<_>>()
     }
 
-    fn try_apply<O, F>(self, f: F) -> Result<Vec<O>>
+    fn try_apply<O, F>(self, f: F) -> FqxResult<Vec<O>>
     where
-        F: Fn(Self::Item) -> Result<O>,
+        F: Fn(Self::Item) -> FqxResult<O>,
     {
         self.data()
             .into_iter()
             .map(|r| f(r))
-            .collect::<Result<Vec<_>>>()
+            .collect::<FqxResult<Vec<_>>>()
     }
 }
 
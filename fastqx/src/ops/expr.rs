@@ -0,0 +1,688 @@
+//! file: expr.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 14:20:36 Saturday
+//! brief:
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, BitAnd, BitOr, Div, Mul, Not, Rem, Sub};
+
+use rust_decimal::Decimal;
+
+use crate::adt::{FqxD, FqxValue, FqxValueType, RowProps};
+use crate::error::{FqxError, FqxResult};
+
+// ================================================================================================
+// Expr
+//
+// A column expression, resolved against the columns of a `FqxD` before being evaluated row by
+// row. Unlike the closures taken by `OpFilter` and `OpApply`, an `Expr` can be inspected, e.g.
+// to be translated into SQL.
+// ================================================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    /// Python's `//`: the quotient rounded towards negative infinity.
+    FloorDiv,
+    Rem,
+    Eq,
+    Neq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    And,
+    Or,
+}
+
+impl BinaryOp {
+    pub fn is_arith(&self) -> bool {
+        matches!(
+            self,
+            BinaryOp::Add
+                | BinaryOp::Sub
+                | BinaryOp::Mul
+                | BinaryOp::Div
+                | BinaryOp::FloorDiv
+                | BinaryOp::Rem
+        )
+    }
+
+    pub fn is_compare(&self) -> bool {
+        matches!(
+            self,
+            BinaryOp::Eq
+                | BinaryOp::Neq
+                | BinaryOp::Gt
+                | BinaryOp::Ge
+                | BinaryOp::Lt
+                | BinaryOp::Le
+        )
+    }
+}
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::FloorDiv => "//",
+            BinaryOp::Rem => "%",
+            BinaryOp::Eq => "=",
+            BinaryOp::Neq => "!=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Ge => ">=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Le => "<=",
+            BinaryOp::And => "AND",
+            BinaryOp::Or => "OR",
+        };
+        f.write_str(s)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Column(String),
+    Literal(FqxValue),
+    Binary {
+        lhs: Box<Expr>,
+        op: BinaryOp,
+        rhs: Box<Expr>,
+    },
+    Not(Box<Expr>),
+    IsNull(Box<Expr>),
+    IsNotNull(Box<Expr>),
+    Cast(Box<Expr>, FqxValueType),
+    Alias(Box<Expr>, String),
+}
+
+pub fn col<S: Into<String>>(name: S) -> Expr {
+    Expr::Column(name.into())
+}
+
+pub fn lit<V: Into<FqxValue>>(value: V) -> Expr {
+    Expr::Literal(value.into())
+}
+
+impl Expr {
+    pub fn binary(self, op: BinaryOp, rhs: Expr) -> Expr {
+        Expr::Binary {
+            lhs: Box::new(self),
+            op,
+            rhs: Box::new(rhs),
+        }
+    }
+
+    pub fn eq(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::Eq, rhs)
+    }
+
+    pub fn neq(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::Neq, rhs)
+    }
+
+    pub fn gt(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::Gt, rhs)
+    }
+
+    pub fn ge(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::Ge, rhs)
+    }
+
+    pub fn lt(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::Lt, rhs)
+    }
+
+    pub fn le(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::Le, rhs)
+    }
+
+    pub fn and(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::And, rhs)
+    }
+
+    pub fn or(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOp::Or, rhs)
+    }
+
+    pub fn is_null(self) -> Expr {
+        Expr::IsNull(Box::new(self))
+    }
+
+    pub fn is_not_null(self) -> Expr {
+        Expr::IsNotNull(Box::new(self))
+    }
+
+    pub fn cast(self, typ: FqxValueType) -> Expr {
+        Expr::Cast(Box::new(self), typ)
+    }
+
+    pub fn alias<S: Into<String>>(self, name: S) -> Expr {
+        Expr::Alias(Box::new(self), name.into())
+    }
+
+    /// The alias if any, otherwise the left-most column name.
+    pub fn output_name(&self) -> String {
+        match self {
+            Expr::Column(c) => c.clone(),
+            Expr::Literal(_) => String::from("literal"),
+            Expr::Binary { lhs, .. } => lhs.output_name(),
+            Expr::Not(e) | Expr::IsNull(e) | Expr::IsNotNull(e) | Expr::Cast(e, _) => {
+                e.output_name()
+            }
+            Expr::Alias(_, name) => name.clone(),
+        }
+    }
+
+    /// Every column referred to, in order of appearance.
+    pub fn columns(&self) -> Vec<&str> {
+        let mut res = vec![];
+        self.visit_columns(&mut res);
+        res
+    }
+
+    fn visit_columns<'a>(&'a self, acc: &mut Vec<&'a str>) {
+        match self {
+            Expr::Column(c) => acc.push(c),
+            Expr::Literal(_) => {}
+            Expr::Binary { lhs, rhs, .. } => {
+                lhs.visit_columns(acc);
+                rhs.visit_columns(acc);
+            }
+            Expr::Not(e)
+            | Expr::IsNull(e)
+            | Expr::IsNotNull(e)
+            | Expr::Cast(e, _)
+            | Expr::Alias(e, _) => e.visit_columns(acc),
+        }
+    }
+
+    /// Resolves column names into positions, so that rows are evaluated without lookups.
+    pub(crate) fn bind(&self, columns: &[String], types: &[FqxValueType]) -> FqxResult<BoundExpr> {
+        let b = match self {
+            Expr::Column(c) => {
+                let idx = columns
+                    .iter()
+                    .position(|e| e == c)
+                    .ok_or_else(|| FqxError::ColumnNotFound(c.clone()))?;
+                BoundExpr::Column(idx, types[idx].clone())
+            }
+            Expr::Literal(v) => BoundExpr::Literal(v.clone()),
+            Expr::Binary { lhs, op, rhs } => BoundExpr::Binary(
+                Box::new(lhs.bind(columns, types)?),
+                *op,
+                Box::new(rhs.bind(columns, types)?),
+            ),
+            Expr::Not(e) => BoundExpr::Not(Box::new(e.bind(columns, types)?)),
+            Expr::IsNull(e) => BoundExpr::IsNull(Box::new(e.bind(columns, types)?), true),
+            Expr::IsNotNull(e) => BoundExpr::IsNull(Box::new(e.bind(columns, types)?), false),
+            Expr::Cast(e, t) => BoundExpr::Cast(Box::new(e.bind(columns, types)?), t.clone()),
+            Expr::Alias(e, _) => e.bind(columns, types)?,
+        };
+
+        Ok(b)
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Column(c) => write!(f, "{c}"),
+            Expr::Literal(FqxValue::Null) => write!(f, "null"),
+            Expr::Literal(FqxValue::String(v)) => write!(f, "'{v}'"),
+            Expr::Literal(v) => match String::try_from(v.clone()) {
+                Ok(v) => write!(f, "{v}"),
+                Err(_) => write!(f, "{v:?}"),
+            },
+            Expr::Binary { lhs, op, rhs } => write!(f, "({lhs} {op} {rhs})"),
+            Expr::Not(e) => write!(f, "NOT {e}"),
+            Expr::IsNull(e) => write!(f, "{e} IS NULL"),
+            Expr::IsNotNull(e) => write!(f, "{e} IS NOT NULL"),
+            Expr::Cast(e, t) => write!(f, "CAST({e} AS {t:?})"),
+            Expr::Alias(e, name) => write!(f, "{e} AS {name}"),
+        }
+    }
+}
+
+macro_rules! impl_op_for_expr {
+    ($t:ident, $tf:ident, $op:ident) => {
+        impl $t for Expr {
+            type Output = Expr;
+
+            fn $tf(self, rhs: Expr) -> Self::Output {
+                self.binary(BinaryOp::$op, rhs)
+            }
+        }
+    };
+}
+
+impl_op_for_expr!(Add, add, Add);
+impl_op_for_expr!(Sub, sub, Sub);
+impl_op_for_expr!(Mul, mul, Mul);
+impl_op_for_expr!(Div, div, Div);
+impl_op_for_expr!(Rem, rem, Rem);
+impl_op_for_expr!(BitAnd, bitand, And);
+impl_op_for_expr!(BitOr, bitor, Or);
+
+impl Not for Expr {
+    type Output = Expr;
+
+    fn not(self) -> Self::Output {
+        Expr::Not(Box::new(self))
+    }
+}
+
+// ================================================================================================
+// BoundExpr
+// ================================================================================================
+
+#[derive(Debug, Clone)]
+pub(crate) enum BoundExpr {
+    Column(usize, FqxValueType),
+    Literal(FqxValue),
+    Binary(Box<BoundExpr>, BinaryOp, Box<BoundExpr>),
+    Not(Box<BoundExpr>),
+    IsNull(Box<BoundExpr>, bool),
+    Cast(Box<BoundExpr>, FqxValueType),
+}
+
+impl BoundExpr {
+    /// Arithmetic keeps the type of its left operand, as `FqxValue` does.
+    pub(crate) fn output_type(&self) -> FqxValueType {
+        match self {
+            BoundExpr::Column(_, t) => t.clone(),
+            BoundExpr::Literal(v) => v.into(),
+            BoundExpr::Binary(lhs, op, rhs) if op.is_arith() => match lhs.output_type() {
+                FqxValueType::Null => rhs.output_type(),
+                t => t,
+            },
+            BoundExpr::Binary(..) | BoundExpr::Not(_) | BoundExpr::IsNull(..) => FqxValueType::Bool,
+            BoundExpr::Cast(_, t) => t.clone(),
+        }
+    }
+
+    pub(crate) fn eval<R: RowProps>(&self, row: &R) -> FqxResult<FqxValue> {
+        let v = match self {
+            BoundExpr::Column(idx, _) => row.get(*idx).cloned().unwrap_or(FqxValue::Null),
+            BoundExpr::Literal(v) => v.clone(),
+            BoundExpr::Binary(lhs, op, rhs) => _eval_binary(lhs.eval(row)?, *op, rhs.eval(row)?)?,
+            BoundExpr::Not(e) => match _to_bool(e.eval(row)?)? {
+                Some(b) => FqxValue::Bool(!b),
+                None => FqxValue::Null,
+            },
+            BoundExpr::IsNull(e, null) => FqxValue::Bool(e.eval(row)?.is_null() == *null),
+            BoundExpr::Cast(e, t) => e.eval(row)?.try_cast(t)?,
+        };
+
+        Ok(v)
+    }
+
+    /// A `null` predicate does not hold.
+    pub(crate) fn eval_predicate<R: RowProps>(&self, row: &R) -> FqxResult<bool> {
+        Ok(_to_bool(self.eval(row)?)?.unwrap_or(false))
+    }
+}
+
+fn _to_bool(v: FqxValue) -> FqxResult<Option<bool>> {
+    match v {
+        FqxValue::Bool(b) => Ok(Some(b)),
+        FqxValue::Null => Ok(None),
+        v => Err(FqxError::type_mismatch(FqxValueType::Bool, (&v).into())),
+    }
+}
+
+macro_rules! is_zero_as {
+    ($t:ty, $rhs:expr) => {
+        <$t>::try_from($rhs).map_or(false, |n| n == <$t>::default())
+    };
+}
+
+// `rhs` is cast to the type of `lhs` before the operation, so `int / 0.5` divides by zero as well
+fn _divides_by_zero(lhs: &FqxValue, rhs: &FqxValue) -> bool {
+    match lhs {
+        FqxValue::U8(_) => is_zero_as!(u8, rhs),
+        FqxValue::U16(_) => is_zero_as!(u16, rhs),
+        FqxValue::U32(_) => is_zero_as!(u32, rhs),
+        FqxValue::U64(_) => is_zero_as!(u64, rhs),
+        FqxValue::I8(_) => is_zero_as!(i8, rhs),
+        FqxValue::I16(_) => is_zero_as!(i16, rhs),
+        FqxValue::I32(_) => is_zero_as!(i32, rhs),
+        FqxValue::I64(_) => is_zero_as!(i64, rhs),
+        FqxValue::Decimal(_) => is_zero_as!(Decimal, rhs),
+        _ => false,
+    }
+}
+
+// the quotient is of the type of `lhs`, and integer division truncates towards zero
+fn _floor_div(lhs: FqxValue, rhs: FqxValue) -> FqxValue {
+    match &lhs / &rhs {
+        FqxValue::F32(v) => FqxValue::F32(v.floor()),
+        FqxValue::F64(v) => FqxValue::F64(v.floor()),
+        FqxValue::Decimal(v) => FqxValue::Decimal(v.floor()),
+        q => {
+            let zero = FqxValue::I8(0);
+            let r = &lhs % &rhs;
+            match r != zero && (r < zero) != (rhs < zero) {
+                true => q - FqxValue::I8(1),
+                false => q,
+            }
+        }
+    }
+}
+
+fn _eval_binary(lhs: FqxValue, op: BinaryOp, rhs: FqxValue) -> FqxResult<FqxValue> {
+    // three-valued logic: `false and null` is false, `true or null` is true
+    if let BinaryOp::And | BinaryOp::Or = op {
+        let (l, r) = (_to_bool(lhs)?, _to_bool(rhs)?);
+        let res = match (op, l, r) {
+            (BinaryOp::And, Some(false), _) | (BinaryOp::And, _, Some(false)) => Some(false),
+            (BinaryOp::And, Some(true), Some(true)) => Some(true),
+            (BinaryOp::Or, Some(true), _) | (BinaryOp::Or, _, Some(true)) => Some(true),
+            (BinaryOp::Or, Some(false), Some(false)) => Some(false),
+            _ => None,
+        };
+        return Ok(res.map_or(FqxValue::Null, FqxValue::Bool));
+    }
+
+    if lhs.is_null() || rhs.is_null() {
+        return Ok(FqxValue::Null);
+    }
+
+    if op.is_arith() && !(lhs.is_numeric() && rhs.is_numeric()) {
+        let (exp, act) = match (lhs.is_numeric(), rhs.is_numeric()) {
+            (true, _) => ((&lhs).into(), (&rhs).into()),
+            (_, true) => ((&rhs).into(), (&lhs).into()),
+            _ => (FqxValueType::F64, (&lhs).into()),
+        };
+        return Err(FqxError::type_mismatch(exp, act));
+    }
+
+    let v = match op {
+        BinaryOp::Add => lhs + rhs,
+        BinaryOp::Sub => lhs - rhs,
+        BinaryOp::Mul => lhs * rhs,
+        // integer and decimal division by zero panics
+        BinaryOp::Div | BinaryOp::FloorDiv | BinaryOp::Rem if _divides_by_zero(&lhs, &rhs) => {
+            FqxValue::Null
+        }
+        BinaryOp::Div => lhs / rhs,
+        BinaryOp::FloorDiv => _floor_div(lhs, rhs),
+        BinaryOp::Rem => lhs % rhs,
+        _ => {
+            let ord = lhs
+                .partial_cmp(&rhs)
+                .ok_or_else(|| FqxError::type_mismatch((&lhs).into(), (&rhs).into()))?;
+            let b = match op {
+                BinaryOp::Eq => ord == Ordering::Equal,
+                BinaryOp::Neq => ord != Ordering::Equal,
+                BinaryOp::Gt => ord == Ordering::Greater,
+                BinaryOp::Ge => ord != Ordering::Less,
+                BinaryOp::Lt => ord == Ordering::Less,
+                BinaryOp::Le => ord != Ordering::Greater,
+                _ => unreachable!(),
+            };
+            FqxValue::Bool(b)
+        }
+    };
+
+    Ok(v)
+}
+
+// ================================================================================================
+// OpExpr
+// ================================================================================================
+
+pub trait OpExpr
+where
+    Self: Sized,
+{
+    /// Keeps the rows where `predicate` is true.
+    fn filter_expr(self, predicate: &Expr) -> FqxResult<Self>;
+
+    /// Appends the column `expr.output_name()`, or replaces it if it already exists.
+    fn with_column(self, expr: &Expr) -> FqxResult<Self>;
+
+    fn select_exprs(self, exprs: &[Expr]) -> FqxResult<Self>;
+}
+
+// ================================================================================================
+// Impl
+// ================================================================================================
+
+impl<U> OpExpr for U
+where
+    U: FqxD,
+    U::ColumnsT: FromIterator<String>,
+    U::TypesT: FromIterator<FqxValueType>,
+{
+    fn filter_expr(self, predicate: &Expr) -> FqxResult<Self> {
+        let p = predicate.bind(self.columns(), self.types())?;
+        let (c, t, d) = self.dcst();
+
+        let mut res = vec![];
+        for (idx, row) in d.into_iter().enumerate() {
+            if p.eval_predicate(&row).map_err(|e| e.at_row(idx))? {
+                res.push(row);
+            }
+        }
+
+        Ok(U::cst(c, t, res))
+    }
+
+    fn with_column(self, expr: &Expr) -> FqxResult<Self> {
+        let b = expr.bind(self.columns(), self.types())?;
+        let name = expr.output_name();
+        let pos = self.columns().iter().position(|c| c == &name);
+
+        let mut columns = self.columns().to_vec();
+        let mut types = self.types().to_vec();
+        match pos {
+            Some(i) => types[i] = b.output_type(),
+            None => {
+                columns.push(name);
+                types.push(b.output_type());
+            }
+        }
+
+        let (_, _, d) = self.dcst();
+        let mut res = Vec::with_capacity(d.len());
+        for (idx, mut row) in d.into_iter().enumerate() {
+            let v = b.eval(&row).map_err(|e| e.at_row(idx))?;
+            match pos.and_then(|i| row.get_mut(i)) {
+                Some(e) => *e = v,
+                None => row.extend([v]),
+            }
+            res.push(row);
+        }
+
+        Ok(U::cst(
+            columns.into_iter().collect(),
+            types.into_iter().collect(),
+            res,
+        ))
+    }
+
+    fn select_exprs(self, exprs: &[Expr]) -> FqxResult<Self> {
+        let bs = exprs
+            .iter()
+            .map(|e| e.bind(self.columns(), self.types()))
+            .collect::<FqxResult<Vec<_>>>()?;
+        let columns = exprs.iter().map(Expr::output_name).collect();
+        let types = bs.iter().map(BoundExpr::output_type).collect();
+
+        let mut res = Vec::with_capacity(self.height());
+        for (idx, row) in self.data().iter().enumerate() {
+            let r = bs
+                .iter()
+                .map(|b| b.eval(row))
+                .collect::<FqxResult<U::RowT>>()
+                .map_err(|e| e.at_row(idx))?;
+            res.push(r);
+        }
+
+        Ok(U::cst(columns, types, res))
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_expr {
+    use super::*;
+    use crate::adt::FqxData;
+    use crate::fqx;
+    use crate::ops::mock::data::{D1, D2};
+    use crate::ops::OpSelect;
+
+    #[test]
+    fn filter_expr_success() {
+        let data = D2.clone();
+
+        let p = col("col_0").gt(lit(2)) & col("col_2").le(lit(5.5));
+        let res = data.filter_expr(&p).unwrap();
+        assert_eq!(res.height(), 3);
+        assert_eq!(res.data()[0][1], fqx!("C"));
+
+        // nulls are filtered out
+        let data = D1.clone();
+        let res = data.rf().filter_expr(&col("col_1").neq(lit("A"))).unwrap();
+        assert_eq!(res.height(), 7);
+
+        let res = data.filter_expr(&col("col_1").is_null()).unwrap();
+        assert_eq!(res.height(), 1);
+        assert_eq!(res.data()[0][0], fqx!(4));
+    }
+
+    #[test]
+    fn filter_expr_fail() {
+        let data = D2.clone();
+
+        let e = data.clone().filter_expr(&col("x").gt(lit(1))).unwrap_err();
+        assert!(matches!(e, FqxError::ColumnNotFound(c) if c == "x"));
+
+        let e = data.filter_expr(&col("col_0")).unwrap_err();
+        assert!(matches!(e, FqxError::TypeMismatch { row: Some(0), .. }));
+    }
+
+    #[test]
+    fn with_column_success() {
+        let data = D1.clone();
+
+        let res = data
+            .clone()
+            .with_column(&(col("col_2") * lit(2.0)).alias("double"))
+            .unwrap();
+        assert_eq!(res.columns().last().unwrap(), "double");
+        assert_eq!(res.types().last().unwrap(), &FqxValueType::F64);
+        assert_eq!(res.data()[1][3], fqx!(4.4));
+        assert_eq!(res.data()[4][3], fqx!());
+
+        // replaces the existing column
+        let res = data
+            .with_column(&col("col_0").cast(FqxValueType::String))
+            .unwrap();
+        assert_eq!(res.width(), 3);
+        assert_eq!(res.types()[0], FqxValueType::String);
+        assert_eq!(res.data()[0][0], fqx!("1"));
+    }
+
+    #[test]
+    fn select_exprs_success() {
+        let data = D2.clone();
+
+        let res = data
+            .select_exprs(&[
+                col("col_1"),
+                (col("col_0") % lit(2)).eq(lit(0)).alias("even"),
+                (col("col_0") / lit(0)).alias("nan"),
+            ])
+            .unwrap();
+        assert_eq!(res.columns(), &["col_1", "even", "nan"]);
+        assert_eq!(
+            res.types(),
+            &[FqxValueType::String, FqxValueType::Bool, FqxValueType::I32]
+        );
+        assert_eq!(res.data()[1][1], fqx!(true));
+        assert_eq!(res.data()[1][2], fqx!());
+    }
+
+    #[test]
+    fn floor_div_success() {
+        let data = FqxData::new_by_data(vec![
+            vec![fqx!(7), fqx!(7.5), fqx!(2)],
+            vec![fqx!(-7), fqx!(-7.5), fqx!(2)],
+            vec![fqx!(7), fqx!(7.5), fqx!(-2)],
+            vec![fqx!(-8), fqx!(-8.0), fqx!(2)],
+        ])
+        .unwrap();
+
+        let res = data
+            .select_exprs(&[
+                col("col_0").binary(BinaryOp::FloorDiv, col("col_2")),
+                col("col_1").binary(BinaryOp::FloorDiv, col("col_2")),
+                col("col_0").binary(BinaryOp::FloorDiv, lit(0)),
+            ])
+            .unwrap();
+        assert_eq!(
+            res.types(),
+            &[FqxValueType::I32, FqxValueType::F64, FqxValueType::I32]
+        );
+        assert_eq!(res.data()[0].values(), &[fqx!(3), fqx!(3.0), fqx!()]);
+        assert_eq!(res.data()[1].values(), &[fqx!(-4), fqx!(-4.0), fqx!()]);
+        assert_eq!(res.data()[2].values(), &[fqx!(-4), fqx!(-4.0), fqx!()]);
+        assert_eq!(res.data()[3].values(), &[fqx!(-4), fqx!(-4.0), fqx!()]);
+    }
+
+    #[test]
+    fn arith_fail() {
+        let data = D2.clone();
+
+        // the float literal is cast to the int column's type, i.e. zero
+        let res = data
+            .clone()
+            .select_exprs(&[
+                (col("col_0") / lit(0.5)).alias("div"),
+                (col("col_0") % lit(0.0)).alias("rem"),
+                (col("col_2") / lit(0)).alias("float"),
+            ])
+            .unwrap();
+        assert_eq!(res.data()[0][0], fqx!());
+        assert_eq!(res.data()[0][1], fqx!());
+        assert!(res.data()[0][2].is_float());
+
+        let e = data.select_exprs(&[col("col_1") + lit(1)]).unwrap_err();
+        assert!(matches!(
+            e,
+            FqxError::TypeMismatch {
+                actual: FqxValueType::String,
+                row: Some(0),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn three_valued_logic_success() {
+        let data = FqxData::new_by_data(vec![vec![fqx!(true), fqx!()], vec![fqx!(false), fqx!()]])
+            .unwrap();
+
+        let res = data
+            .select_exprs(&[
+                (col("col_0") & col("col_1")).alias("and"),
+                (col("col_0") | col("col_1")).alias("or"),
+                !col("col_1"),
+            ])
+            .unwrap();
+        assert_eq!(res.data()[0].values(), &[fqx!(), fqx!(true), fqx!()]);
+        assert_eq!(res.data()[1].values(), &[fqx!(false), fqx!(), fqx!()]);
+    }
+}
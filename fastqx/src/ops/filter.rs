@@ -71,7 +71,7 @@ where
             res.insert(k, U::cst(c, t, d));
         }
 
-        FqxGroup(res)
+        FqxGroup(res, self.1)
     }
 }
 
@@ -5,9 +5,8 @@
 
 use std::collections::HashMap;
 
-use anyhow::Result;
-
 use crate::adt::{FqxD, FqxValue};
+use crate::error::FqxResult;
 use crate::ops::FqxGroup;
 
 // ================================================================================================
@@ -23,10 +22,10 @@ pub trait OpFold<const OWNED: bool> {
         A: Clone,
         F: FnMut(A, Self::Item) -> A;
 
-    fn try_fold<A, F>(self, accumulator: A, f: F) -> Result<Self::Ret<A>>
+    fn try_fold<A, F>(self, accumulator: A, f: F) -> FqxResult<Self::Ret<A>>
     where
         A: Clone,
-        F: FnMut(A, Self::Item) -> Result<A>;
+        F: FnMut(A, Self::Item) -> FqxResult<A>;
 }
 
 // ================================================================================================
@@ -54,10 +53,10 @@ where
         })
     }
 
-    fn try_fold<A, F>(self, accumulator: A, mut f: F) -> Result<Self::Ret<A>>
+    fn try_fold<A, F>(self, accumulator: A, mut f: F) -> FqxResult<Self::Ret<A>>
     where
         A: Clone,
-        F: FnMut(A, Self::Item) -> Result<A>,
+        F: FnMut(A, Self::Item) -> FqxResult<A>,
     {
         Iterator::try_fold(&mut self.data().into_iter(), accumulator, |acc, r| {
             f(acc, r.clone())
@@ -83,10 +82,10 @@ where
             .fold(accumulator, |acc, r| f(acc, r))
     }
 
-    fn try_fold<A, F>(self, accumulator: A, mut f: F) -> Result<Self::Ret<A>>
+    fn try_fold<A, F>(self, accumulator: A, mut f: F) -> FqxResult<Self::Ret<A>>
     where
         A: Clone,
-        F: FnMut(A, Self::Item) -> Result<A>,
+        F: FnMut(A, Self::Item) -> FqxResult<A>,
     {
         self.data()
             .into_iter()
@@ -122,10 +121,10 @@ where
         res
     }
 
-    fn try_fold<A, F>(self, accumulator: A, mut f: F) -> Result<Self::Ret<A>>
+    fn try_fold<A, F>(self, accumulator: A, mut f: F) -> FqxResult<Self::Ret<A>>
     where
         A: Clone,
-        F: FnMut(A, Self::Item) -> Result<A>,
+        F: FnMut(A, Self::Item) -> FqxResult<A>,
     {
         let mut res = HashMap::new();
 
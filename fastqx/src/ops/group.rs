@@ -6,7 +6,6 @@
 use std::collections::HashMap;
 
 use itertools::Itertools;
-use ref_cast::RefCast;
 
 use crate::adt::*;
 
@@ -36,14 +35,39 @@ where
 // FqxGroup
 // ================================================================================================
 
-#[derive(RefCast, Debug)]
-#[repr(transparent)]
-pub struct FqxGroup<T>(pub(crate) HashMap<Vec<FqxValue>, T>);
+/// Groups by their key values, along with the names of the key columns.
+///
+/// The key names are only known when grouped by columns, and are empty when grouped by a function;
+/// aggregations and stats use them to name their key columns, `key_0`, `key_1`, ... otherwise.
+///
+/// Since it carries the key names, `FqxGroup` is no longer a `#[repr(transparent)]` wrapper over
+/// its map, and cannot be ref-cast from one: build it by `from_hashmap`, or `From<HashMap<..>>`
+/// without key names, and borrow the map by `as_hashmap`.
+#[derive(Debug)]
+pub struct FqxGroup<T>(pub(crate) HashMap<Vec<FqxValue>, T>, pub(crate) Vec<String>);
+
+impl<T> From<HashMap<Vec<FqxValue>, T>> for FqxGroup<T> {
+    fn from(groups: HashMap<Vec<FqxValue>, T>) -> Self {
+        Self(groups, vec![])
+    }
+}
 
 impl<T> FqxGroup<T> {
+    pub fn from_hashmap(groups: HashMap<Vec<FqxValue>, T>, key_names: Vec<String>) -> Self {
+        Self(groups, key_names)
+    }
+
+    pub fn as_hashmap(&self) -> &HashMap<Vec<FqxValue>, T> {
+        &self.0
+    }
+
     pub fn to_hashmap(self) -> HashMap<Vec<FqxValue>, T> {
         self.0
     }
+
+    pub fn key_names(&self) -> &[String] {
+        &self.1
+    }
 }
 
 // ================================================================================================
@@ -66,11 +90,15 @@ where
         for<'a> &'a N: IntoIterator<Item = &'a Self::Col>,
     {
         let pos = self.columns_position(by);
-        self.group_by_fn_(|r| {
+        let key_names = pos.iter().map(|&i| self.columns()[i].clone()).collect();
+        let mut res = self.group_by_fn_(|r| {
             pos.iter()
                 .filter_map(|&i| r.get(i).cloned())
                 .collect::<Vec<_>>()
-        })
+        });
+        res.1 = key_names;
+
+        res
     }
 
     fn group_by_fn_<F>(self, f: F) -> Self::Ret<Self>
@@ -89,7 +117,7 @@ where
             .map(|(k, g)| (k, U::cst(c.clone(), t.clone(), g)))
             .collect::<HashMap<_, _>>();
 
-        FqxGroup(res)
+        FqxGroup(res, vec![])
     }
 }
 
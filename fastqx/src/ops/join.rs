@@ -4,8 +4,8 @@
 //! brief:
 
 use crate::adt::FqxD;
-use crate::ops::utils::{_join, _outer_join};
-use crate::ops::FqxJoinType;
+use crate::error::FqxResult;
+use crate::ops::{FqxJoinOptions, FqxJoinType, OpMerge};
 
 // ================================================================================================
 // OpJoin
@@ -14,7 +14,22 @@ use crate::ops::FqxJoinType;
 pub trait OpJoin: Sized {
     type Ret;
 
-    fn join<O, N, S>(self, other: O, on: &N, how: FqxJoinType) -> Self::Ret
+    fn join<O, N, S>(self, other: O, on: &N, how: FqxJoinType) -> FqxResult<Self::Ret>
+    where
+        Self: From<O>,
+        for<'a> &'a N: IntoIterator<Item = &'a S>,
+        S: AsRef<str>,
+    {
+        self.join_with(other, on, how, &FqxJoinOptions::default())
+    }
+
+    fn join_with<O, N, S>(
+        self,
+        other: O,
+        on: &N,
+        how: FqxJoinType,
+        options: &FqxJoinOptions,
+    ) -> FqxResult<Self::Ret>
     where
         Self: From<O>,
         for<'a> &'a N: IntoIterator<Item = &'a S>,
@@ -30,19 +45,19 @@ where
 {
     type Ret = U;
 
-    fn join<O, N, S>(self, other: O, on: &N, how: FqxJoinType) -> Self::Ret
+    fn join_with<O, N, S>(
+        self,
+        other: O,
+        on: &N,
+        how: FqxJoinType,
+        options: &FqxJoinOptions,
+    ) -> FqxResult<Self::Ret>
     where
         Self: From<O>,
         for<'a> &'a N: IntoIterator<Item = &'a S>,
         S: AsRef<str>,
     {
-        let (l, r) = (self, other.into());
-        match how {
-            FqxJoinType::Left => _join(l, r, on, on, false),
-            FqxJoinType::Right => _join(r, l, on, on, false),
-            FqxJoinType::Inner => _join(l, r, on, on, true),
-            FqxJoinType::Outer => _outer_join(l, r, on, on),
-        }
+        self.merge_with(other, on, on, how, options)
     }
 }
 
@@ -53,9 +68,19 @@ where
 #[cfg(test)]
 mod test_join {
     use super::*;
+    use crate::adt::{FqxData, FqxValue, RowProps};
     use crate::ops::mock::data::{D7, D8};
     use crate::ops::OpSelect;
 
+    fn row(n: &str, a: Option<i32>, b: Option<i32>) -> Vec<FqxValue> {
+        let i = |v: Option<i32>| v.map_or(FqxValue::Null, FqxValue::I32);
+        vec![FqxValue::from(n), i(a), i(b)]
+    }
+
+    fn rows(d: &FqxData) -> Vec<Vec<FqxValue>> {
+        d.data().iter().map(|r| r.clone().to_values()).collect()
+    }
+
     #[test]
     fn join_self_success() {
         ///////////////////////////////////////////////////////////////////////////////////////////////////
@@ -64,13 +89,25 @@ mod test_join {
         let d1 = D7.clone();
         let d2 = D8.clone();
 
-        let res = d1.join(d2.rf(), &["Name"], FqxJoinType::Left);
-        println!("join left:");
-        println!("{:?}", res.columns());
-        println!("{:?}", res.types());
-        for r in res.data().iter() {
-            println!("{:?}", r);
-        }
+        let res = d1.join(d2.rf(), &["Name"], FqxJoinType::Left).unwrap();
+        // the shared key is kept once
+        assert_eq!(
+            res.columns(),
+            &["Name", "Potassium (mg/100g)", "Phosphorus (mg/100g)"]
+        );
+        assert_eq!(
+            rows(&res),
+            vec![
+                row("Apple", Some(107), Some(22)),
+                row("Pear", Some(358), Some(11)),
+                row("Pear", Some(358), Some(12)),
+                row("Pear", Some(115), Some(11)),
+                row("Pear", Some(115), Some(12)),
+                row("Pineapple", Some(200), None),
+                row("Pear", Some(116), Some(11)),
+                row("Pear", Some(116), Some(12)),
+            ]
+        );
 
         ///////////////////////////////////////////////////////////////////////////////////////////////////
         // join right
@@ -78,42 +115,119 @@ mod test_join {
         let d1 = D7.clone();
         let d2 = D8.clone();
 
-        let res = d1.join(d2, &["Name"], FqxJoinType::Right);
-        println!("join right:");
-        println!("{:?}", res.columns());
-        println!("{:?}", res.types());
-        for r in res.data().iter() {
-            println!("{:?}", r);
-        }
+        let res = d1.join(d2, &["Name"], FqxJoinType::Right).unwrap();
+        assert_eq!(
+            res.columns(),
+            &["Name", "Phosphorus (mg/100g)", "Potassium (mg/100g)"]
+        );
+        assert_eq!(
+            rows(&res),
+            vec![
+                row("Pear", Some(11), Some(358)),
+                row("Pear", Some(11), Some(115)),
+                row("Pear", Some(11), Some(116)),
+                row("Apple", Some(22), Some(107)),
+                row("Pear", Some(12), Some(358)),
+                row("Pear", Some(12), Some(115)),
+                row("Pear", Some(12), Some(116)),
+                row("Banana", Some(15), None),
+            ]
+        );
     }
 
     #[test]
     fn join_self_success2() {
+        let matched = vec![
+            row("Apple", Some(107), Some(22)),
+            row("Pear", Some(358), Some(11)),
+            row("Pear", Some(358), Some(12)),
+            row("Pear", Some(115), Some(11)),
+            row("Pear", Some(115), Some(12)),
+            row("Pear", Some(116), Some(11)),
+            row("Pear", Some(116), Some(12)),
+        ];
+
         ///////////////////////////////////////////////////////////////////////////////////////////////////
         // join inner
 
         let d1 = D7.clone();
         let d2 = D8.clone();
 
-        let res = d1.join(d2, &["Name"], FqxJoinType::Inner);
-        println!("join inner:");
-        println!("{:?}", res.columns());
-        println!("{:?}", res.types());
-        for r in res.data().iter() {
-            println!("{:?}", r);
-        }
+        let res = d1.join(d2, &["Name"], FqxJoinType::Inner).unwrap();
+        assert_eq!(rows(&res), matched);
+
         ///////////////////////////////////////////////////////////////////////////////////////////////////
         // join outer
 
         let d1 = D7.clone();
         let d2 = D8.clone();
 
-        let res = d1.join(d2, &["Name"], FqxJoinType::Outer);
-        println!("join outer:");
-        println!("{:?}", res.columns());
-        println!("{:?}", res.types());
-        for r in res.data().iter() {
-            println!("{:?}", r);
-        }
+        let res = d1.join(d2, &["Name"], FqxJoinType::Outer).unwrap();
+        let mut expected = matched;
+        expected.insert(5, row("Pineapple", Some(200), None));
+        // a right row without a match keeps its key
+        expected.push(row("Banana", None, Some(15)));
+        assert_eq!(rows(&res), expected);
+    }
+
+    fn names(d: &FqxData) -> Vec<FqxValue> {
+        d.data().iter().map(|r| r[0].clone()).collect()
+    }
+
+    #[test]
+    fn join_semi_anti_cross_success() {
+        let res = D7
+            .clone()
+            .join(D8.clone(), &["Name"], FqxJoinType::LeftSemi)
+            .unwrap();
+        assert_eq!(res.columns(), D7.columns());
+        assert_eq!(
+            names(&res),
+            ["Apple", "Pear", "Pear", "Pear"].map(FqxValue::from)
+        );
+
+        let res = D7
+            .clone()
+            .join(D8.clone(), &["Name"], FqxJoinType::LeftAnti)
+            .unwrap();
+        assert_eq!(res.columns(), D7.columns());
+        assert_eq!(names(&res), vec![FqxValue::from("Pineapple")]);
+
+        let res = D7
+            .clone()
+            .join(D8.clone(), &["Name"], FqxJoinType::Cross)
+            .unwrap();
+        assert_eq!(
+            res.columns(),
+            &[
+                "Name_x",
+                "Potassium (mg/100g)",
+                "Name_y",
+                "Phosphorus (mg/100g)"
+            ]
+        );
+        assert_eq!(res.height(), D7.height() * D8.height());
+        assert_eq!(
+            res.data()[1].clone().to_values()[..3],
+            [
+                FqxValue::from("Apple"),
+                FqxValue::I32(107),
+                FqxValue::from("Apple")
+            ]
+        );
+    }
+
+    #[test]
+    fn join_type_from_str() {
+        assert_eq!(
+            "semi".parse::<FqxJoinType>().unwrap(),
+            FqxJoinType::LeftSemi
+        );
+        assert_eq!(
+            "left_anti".parse::<FqxJoinType>().unwrap(),
+            FqxJoinType::LeftAnti
+        );
+        assert_eq!("cross".parse::<FqxJoinType>().unwrap(), FqxJoinType::Cross);
+        assert!("full".parse::<FqxJoinType>().is_err());
     }
 }
@@ -3,19 +3,28 @@
 //! date: 2023/10/10 09:11:09 Tuesday
 //! brief:
 
+use std::str::FromStr;
+
 use crate::adt::FqxD;
-use crate::ops::utils::{_join, _outer_join};
+use crate::error::{FqxError, FqxResult};
+use crate::ops::utils::{_asof_join, _cross_join, _join, _outer_join, _semi_join};
 
 // ================================================================================================
 // FqxJoinType
 // ================================================================================================
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FqxJoinType {
     Left,
     Right,
     Outer,
     Inner,
-    // Cross,
+    /// Every left row with every right row; `on` is ignored.
+    Cross,
+    /// Left rows with a match, left columns only.
+    LeftSemi,
+    /// Left rows without a match, left columns only.
+    LeftAnti,
 }
 
 impl Default for FqxJoinType {
@@ -24,6 +33,141 @@ impl Default for FqxJoinType {
     }
 }
 
+impl FromStr for FqxJoinType {
+    type Err = FqxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(Self::Left),
+            "right" => Ok(Self::Right),
+            "outer" => Ok(Self::Outer),
+            "inner" => Ok(Self::Inner),
+            "cross" => Ok(Self::Cross),
+            "semi" | "left_semi" => Ok(Self::LeftSemi),
+            "anti" | "left_anti" => Ok(Self::LeftAnti),
+            _ => Err(FqxError::invalid(format!(
+                "how: left/right/outer/inner/cross/semi/anti, got {s}"
+            ))),
+        }
+    }
+}
+
+/// Appended to a non-key column name found on both sides of a join.
+pub const DEFAULT_SUFFIXES: (&str, &str) = ("_x", "_y");
+
+// ================================================================================================
+// FqxJoinOptions
+// ================================================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FqxJoinOptions<'s> {
+    /// Appended to the left and right name of a non-key column found on both sides. A key named
+    /// alike on both sides is kept once.
+    pub suffixes: (&'s str, &'s str),
+    /// Whether a null key matches another null key, as in pandas; SQL never matches them.
+    pub nulls_equal: bool,
+}
+
+impl Default for FqxJoinOptions<'_> {
+    fn default() -> Self {
+        Self {
+            suffixes: DEFAULT_SUFFIXES,
+            nulls_equal: true,
+        }
+    }
+}
+
+// ================================================================================================
+// AsofStrategy & AsofOptions
+// ================================================================================================
+
+/// Which right row an as-of merge pairs with a left row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AsofStrategy {
+    /// The last right row whose key is `<=` the left key.
+    #[default]
+    Backward,
+    /// The first right row whose key is `>=` the left key.
+    Forward,
+    /// The closer of both; a tie goes backward.
+    Nearest,
+}
+
+impl FromStr for AsofStrategy {
+    type Err = FqxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "backward" => Ok(Self::Backward),
+            "forward" => Ok(Self::Forward),
+            "nearest" => Ok(Self::Nearest),
+            _ => Err(FqxError::invalid(format!(
+                "asof strategy: backward/forward/nearest, got {s}"
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AsofOptions {
+    /// Columns of the left side which must equal `right_by` before keys are compared.
+    pub left_by: Vec<String>,
+    pub right_by: Vec<String>,
+    pub strategy: AsofStrategy,
+    /// The widest gap allowed between both keys: in seconds for times, as is for numbers.
+    pub tolerance: Option<f64>,
+    /// As in `FqxJoinOptions`; `by` and `on` columns named alike on both sides are kept once.
+    pub suffixes: (String, String),
+}
+
+impl Default for AsofOptions {
+    fn default() -> Self {
+        Self {
+            left_by: vec![],
+            right_by: vec![],
+            strategy: AsofStrategy::default(),
+            tolerance: None,
+            suffixes: (
+                DEFAULT_SUFFIXES.0.to_string(),
+                DEFAULT_SUFFIXES.1.to_string(),
+            ),
+        }
+    }
+}
+
+impl AsofOptions {
+    pub fn by<I, S>(self, by: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let by = by.into_iter().map(Into::into).collect::<Vec<_>>();
+        Self {
+            left_by: by.clone(),
+            right_by: by,
+            ..self
+        }
+    }
+
+    pub fn strategy(self, strategy: AsofStrategy) -> Self {
+        Self { strategy, ..self }
+    }
+
+    pub fn tolerance(self, tolerance: f64) -> Self {
+        Self {
+            tolerance: Some(tolerance),
+            ..self
+        }
+    }
+
+    pub fn suffixes<S: Into<String>>(self, left: S, right: S) -> Self {
+        Self {
+            suffixes: (left.into(), right.into()),
+            ..self
+        }
+    }
+}
+
 // ================================================================================================
 // OpMerge
 // ================================================================================================
@@ -31,11 +175,54 @@ impl Default for FqxJoinType {
 pub trait OpMerge: Sized {
     type Ret;
 
-    fn merge<O, N, S>(self, other: O, left_on: &N, right_on: &N, how: FqxJoinType) -> Self::Ret
+    fn merge<O, N, S>(
+        self,
+        other: O,
+        left_on: &N,
+        right_on: &N,
+        how: FqxJoinType,
+    ) -> FqxResult<Self::Ret>
+    where
+        Self: From<O>,
+        for<'a> &'a N: IntoIterator<Item = &'a S>,
+        S: AsRef<str>,
+    {
+        self.merge_with(other, left_on, right_on, how, &FqxJoinOptions::default())
+    }
+
+    /// Keys are paired in the given order, and a name missing on its side is a
+    /// `ColumnNotFound`; the smaller side is hashed on its keys.
+    ///
+    /// The result holds the left columns, then the right ones but for the keys named alike on
+    /// both sides, which appear once. Rows follow the left side, each with its matches in right
+    /// order, and the unmatched right rows of an outer join come last; the result is no longer
+    /// sorted on the keys.
+    fn merge_with<O, N, S>(
+        self,
+        other: O,
+        left_on: &N,
+        right_on: &N,
+        how: FqxJoinType,
+        options: &FqxJoinOptions,
+    ) -> FqxResult<Self::Ret>
     where
         Self: From<O>,
         for<'a> &'a N: IntoIterator<Item = &'a S>,
         S: AsRef<str>;
+
+    /// Pairs every left row with at most one right row, found by `options.strategy` on the sorted
+    /// `left_on` / `right_on` keys. Left rows keep their order, and those without a match get a
+    /// right side of nulls. Columns are laid out as by `merge_with`, and time keys must be of the
+    /// same type.
+    fn merge_asof<O>(
+        self,
+        other: O,
+        left_on: &str,
+        right_on: &str,
+        options: &AsofOptions,
+    ) -> FqxResult<Self::Ret>
+    where
+        Self: From<O>;
 }
 
 ///////////////////////////////////////////////////////////////////////////////////////////////////
@@ -47,20 +234,48 @@ where
 {
     type Ret = U;
 
-    fn merge<O, N, S>(self, other: O, left_on: &N, right_on: &N, how: FqxJoinType) -> Self::Ret
+    fn merge_with<O, N, S>(
+        self,
+        other: O,
+        left_on: &N,
+        right_on: &N,
+        how: FqxJoinType,
+        options: &FqxJoinOptions,
+    ) -> FqxResult<Self::Ret>
     where
         Self: From<O>,
         for<'a> &'a N: IntoIterator<Item = &'a S>,
         S: AsRef<str>,
     {
         let (l, r) = (self, other.into());
+        // the preserved side leads, yet suffixes stay with their side
+        let swapped = FqxJoinOptions {
+            suffixes: (options.suffixes.1, options.suffixes.0),
+            ..*options
+        };
         match how {
-            FqxJoinType::Left => _join(l, r, left_on, right_on, false),
-            FqxJoinType::Right => _join(r, l, right_on, left_on, false),
-            FqxJoinType::Inner => _join(l, r, left_on, right_on, true),
-            FqxJoinType::Outer => _outer_join(l, r, left_on, right_on),
+            FqxJoinType::Left => _join(l, r, left_on, right_on, false, options),
+            FqxJoinType::Right => _join(r, l, right_on, left_on, false, &swapped),
+            FqxJoinType::Inner => _join(l, r, left_on, right_on, true, options),
+            FqxJoinType::Outer => _outer_join(l, r, left_on, right_on, options),
+            FqxJoinType::Cross => Ok(_cross_join(l, r, options.suffixes)),
+            FqxJoinType::LeftSemi => _semi_join(l, r, left_on, right_on, false, options),
+            FqxJoinType::LeftAnti => _semi_join(l, r, left_on, right_on, true, options),
         }
     }
+
+    fn merge_asof<O>(
+        self,
+        other: O,
+        left_on: &str,
+        right_on: &str,
+        options: &AsofOptions,
+    ) -> FqxResult<Self::Ret>
+    where
+        Self: From<O>,
+    {
+        _asof_join(self, other.into(), left_on, right_on, options)
+    }
 }
 
 // ================================================================================================
@@ -70,8 +285,18 @@ where
 #[cfg(test)]
 mod test_merge {
     use super::*;
+    use crate::adt::{FqxData, FqxValue, FqxValueType, RowProps};
     use crate::ops::mock::data::{D6, D7};
     use crate::ops::OpSelect;
+    use chrono::NaiveDate;
+
+    fn s(v: Option<&str>) -> FqxValue {
+        v.map_or(FqxValue::Null, FqxValue::from)
+    }
+
+    fn i(v: Option<i32>) -> FqxValue {
+        v.map_or(FqxValue::Null, FqxValue::I32)
+    }
 
     #[test]
     fn merge_self_success() {
@@ -81,13 +306,34 @@ mod test_merge {
         let d1 = D6.clone();
         let d2 = D7.clone();
 
-        let res = d1.merge(d2.rf(), &["Fruit"], &["Name"], FqxJoinType::Left);
-        println!("merge left:");
-        println!("{:?}", res.columns());
-        println!("{:?}", res.types());
-        for r in res.data().iter() {
-            println!("{:?}", r);
-        }
+        let res = d1
+            .merge(d2.rf(), &["Fruit"], &["Name"], FqxJoinType::Left)
+            .unwrap();
+        assert_eq!(
+            res.columns(),
+            &[
+                "Fruit",
+                "Phosphorus (mg/100g)",
+                "Name",
+                "Potassium (mg/100g)"
+            ]
+        );
+        let row = |f: &str, p: i32, n: Option<&str>, k: Option<i32>| {
+            vec![s(Some(f)), i(Some(p)), s(n), i(k)]
+        };
+        assert_eq!(
+            rows(&res),
+            vec![
+                row("Pear", 11, Some("Pear"), Some(358)),
+                row("Pear", 11, Some("Pear"), Some(115)),
+                row("Pear", 11, Some("Pear"), Some(116)),
+                row("Apple", 22, Some("Apple"), Some(107)),
+                row("Pear", 12, Some("Pear"), Some(358)),
+                row("Pear", 12, Some("Pear"), Some(115)),
+                row("Pear", 12, Some("Pear"), Some(116)),
+                row("Banana", 15, None, None),
+            ]
+        );
 
         ///////////////////////////////////////////////////////////////////////////////////////////////////
         // merge right
@@ -95,42 +341,385 @@ mod test_merge {
         let d1 = D6.clone();
         let d2 = D7.clone();
 
-        let res = d1.merge(d2, &["Fruit"], &["Name"], FqxJoinType::Right);
-        println!("merge right:");
-        println!("{:?}", res.columns());
-        println!("{:?}", res.types());
-        for r in res.data().iter() {
-            println!("{:?}", r);
-        }
+        let res = d1
+            .merge(d2, &["Fruit"], &["Name"], FqxJoinType::Right)
+            .unwrap();
+        assert_eq!(
+            res.columns(),
+            &[
+                "Name",
+                "Potassium (mg/100g)",
+                "Fruit",
+                "Phosphorus (mg/100g)"
+            ]
+        );
+        assert_eq!(
+            rows(&res),
+            vec![
+                row("Apple", 107, Some("Apple"), Some(22)),
+                row("Pear", 358, Some("Pear"), Some(11)),
+                row("Pear", 358, Some("Pear"), Some(12)),
+                row("Pear", 115, Some("Pear"), Some(11)),
+                row("Pear", 115, Some("Pear"), Some(12)),
+                row("Pineapple", 200, None, None),
+                row("Pear", 116, Some("Pear"), Some(11)),
+                row("Pear", 116, Some("Pear"), Some(12)),
+            ]
+        );
     }
 
     #[test]
     fn merge_self_success2() {
+        let row = |f: Option<&str>, p: Option<i32>, n: Option<&str>, k: Option<i32>| {
+            vec![s(f), i(p), s(n), i(k)]
+        };
+        let matched = vec![
+            row(Some("Pear"), Some(11), Some("Pear"), Some(358)),
+            row(Some("Pear"), Some(11), Some("Pear"), Some(115)),
+            row(Some("Pear"), Some(11), Some("Pear"), Some(116)),
+            row(Some("Apple"), Some(22), Some("Apple"), Some(107)),
+            row(Some("Pear"), Some(12), Some("Pear"), Some(358)),
+            row(Some("Pear"), Some(12), Some("Pear"), Some(115)),
+            row(Some("Pear"), Some(12), Some("Pear"), Some(116)),
+        ];
+
         ///////////////////////////////////////////////////////////////////////////////////////////////////
         // merge inner
 
         let d1 = D6.clone();
         let d2 = D7.clone();
 
-        let res = d1.merge(d2, &["Fruit"], &["Name"], FqxJoinType::Inner);
-        println!("merge inner:");
-        println!("{:?}", res.columns());
-        println!("{:?}", res.types());
-        for r in res.data().iter() {
-            println!("{:?}", r);
-        }
+        let res = d1
+            .merge(d2, &["Fruit"], &["Name"], FqxJoinType::Inner)
+            .unwrap();
+        assert_eq!(rows(&res), matched);
+
         ///////////////////////////////////////////////////////////////////////////////////////////////////
         // merge outer
 
         let d1 = D6.clone();
         let d2 = D7.clone();
 
-        let res = d1.merge(d2, &["Fruit"], &["Name"], FqxJoinType::Outer);
-        println!("merge outer:");
-        println!("{:?}", res.columns());
-        println!("{:?}", res.types());
-        for r in res.data().iter() {
-            println!("{:?}", r);
-        }
+        let res = d1
+            .merge(d2, &["Fruit"], &["Name"], FqxJoinType::Outer)
+            .unwrap();
+        let mut expected = matched;
+        expected.push(row(Some("Banana"), Some(15), None, None));
+        expected.push(row(None, None, Some("Pineapple"), Some(200)));
+        assert_eq!(rows(&res), expected);
+    }
+
+    #[test]
+    fn merge_self_fail() {
+        let res = D6
+            .clone()
+            .merge(D7.clone(), &["Fruit"], &["Missing"], FqxJoinType::Left);
+        assert!(matches!(res, Err(FqxError::ColumnNotFound(c)) if c == "Missing"));
+
+        let res = D6
+            .clone()
+            .merge(D7.clone(), &vec!["Fruit"], &vec![], FqxJoinType::Inner);
+        assert!(matches!(res, Err(FqxError::ShapeMismatch { .. })));
+    }
+
+    fn dt(m: u32, sec: u32) -> FqxValue {
+        FqxValue::DateTime(
+            NaiveDate::from_ymd_opt(2024, 1, 2)
+                .unwrap()
+                .and_hms_opt(9, m, sec)
+                .unwrap(),
+        )
+    }
+
+    fn trades() -> FqxData {
+        let data = vec![
+            vec![dt(30, 0), "A".into(), FqxValue::I64(100)],
+            vec![dt(30, 5), "B".into(), FqxValue::I64(200)],
+            vec![dt(30, 20), "A".into(), FqxValue::I64(300)],
+            vec![dt(29, 0), "A".into(), FqxValue::I64(400)],
+            vec![FqxValue::Null, "A".into(), FqxValue::I64(500)],
+        ];
+        FqxData::new(
+            ["time", "sym", "qty"],
+            [
+                FqxValueType::DateTime,
+                FqxValueType::String,
+                FqxValueType::I64,
+            ],
+            data,
+        )
+        .unwrap()
+    }
+
+    fn quotes() -> FqxData {
+        let data = vec![
+            vec![dt(30, 18), "A".into(), FqxValue::F64(1.2)],
+            vec![dt(30, 0), "A".into(), FqxValue::F64(1.0)],
+            vec![dt(30, 1), "B".into(), FqxValue::F64(2.0)],
+            vec![dt(30, 0), "A".into(), FqxValue::F64(1.1)],
+            vec![FqxValue::Null, "A".into(), FqxValue::F64(9.9)],
+        ];
+        FqxData::new(
+            ["ts", "sym", "bid"],
+            [
+                FqxValueType::DateTime,
+                FqxValueType::String,
+                FqxValueType::F64,
+            ],
+            data,
+        )
+        .unwrap()
+    }
+
+    fn column(d: &FqxData, col: usize) -> Vec<FqxValue> {
+        d.data().iter().map(|r| r[col].clone()).collect()
+    }
+
+    #[test]
+    fn merge_asof_success() {
+        let bid = |v: f64| FqxValue::F64(v);
+
+        let opt = AsofOptions::default().by(["sym"]);
+        let res = trades().merge_asof(quotes(), "time", "ts", &opt).unwrap();
+        // the shared `by` column is kept once
+        assert_eq!(res.columns(), &["time", "sym", "qty", "ts", "bid"]);
+        assert_eq!(column(&res, 2), column(&trades(), 2));
+        assert_eq!(
+            column(&res, 4),
+            vec![bid(1.1), bid(2.0), bid(1.2), FqxValue::Null, FqxValue::Null]
+        );
+
+        let opt = opt.strategy(AsofStrategy::Forward);
+        let res = trades().merge_asof(quotes(), "time", "ts", &opt).unwrap();
+        assert_eq!(
+            column(&res, 4),
+            vec![
+                bid(1.0),
+                FqxValue::Null,
+                FqxValue::Null,
+                bid(1.0),
+                FqxValue::Null
+            ]
+        );
+
+        let opt = opt.strategy(AsofStrategy::Nearest).tolerance(5.0);
+        let res = trades().merge_asof(quotes(), "time", "ts", &opt).unwrap();
+        assert_eq!(
+            column(&res, 4),
+            vec![bid(1.1), bid(2.0), bid(1.2), FqxValue::Null, FqxValue::Null]
+        );
+
+        // no `by`: any symbol will do, but the B quote is 4 seconds old
+        let opt = AsofOptions::default().tolerance(3.0);
+        let res = trades().merge_asof(quotes(), "time", "ts", &opt).unwrap();
+        assert_eq!(
+            res.columns(),
+            &["time", "sym_x", "qty", "ts", "sym_y", "bid"]
+        );
+        assert_eq!(
+            column(&res, 5),
+            vec![
+                bid(1.1),
+                FqxValue::Null,
+                bid(1.2),
+                FqxValue::Null,
+                FqxValue::Null
+            ]
+        );
+        let opt = AsofOptions::default().strategy(AsofStrategy::Forward);
+        let res = trades().merge_asof(quotes(), "time", "ts", &opt).unwrap();
+        assert_eq!(
+            column(&res, 5),
+            vec![bid(1.0), bid(1.2), FqxValue::Null, bid(1.0), FqxValue::Null]
+        );
+    }
+
+    #[test]
+    fn merge_asof_fail() {
+        let res = trades().merge_asof(quotes(), "time", "missing", &AsofOptions::default());
+        assert!(matches!(res, Err(FqxError::ColumnNotFound(_))));
+
+        let opt = AsofOptions {
+            left_by: vec!["sym".to_string()],
+            ..Default::default()
+        };
+        assert!(trades().merge_asof(quotes(), "time", "ts", &opt).is_err());
+
+        let opt = AsofOptions::default().tolerance(-1.0);
+        assert!(trades().merge_asof(quotes(), "time", "ts", &opt).is_err());
+
+        // a `DateTime` key never meets a `Date` one
+        let mut dates = quotes();
+        dates.types_mut()[0] = FqxValueType::Date;
+        let res = trades().merge_asof(dates, "time", "ts", &AsofOptions::default());
+        assert!(matches!(res, Err(FqxError::TypeMismatch { .. })));
+
+        assert!("sideways".parse::<AsofStrategy>().is_err());
+    }
+
+    #[test]
+    fn merge_asof_keys_success() {
+        // `by` keys match by value, whatever their number type
+        let left = FqxData::new(
+            ["t", "id"],
+            [FqxValueType::I64, FqxValueType::I64],
+            vec![
+                vec![FqxValue::I64(5), FqxValue::I64(1)],
+                vec![FqxValue::I64(5), FqxValue::I64(2)],
+            ],
+        )
+        .unwrap();
+        let right = FqxData::new(
+            ["t", "id", "v"],
+            [FqxValueType::I64, FqxValueType::F64, FqxValueType::F64],
+            vec![
+                vec![FqxValue::I64(4), FqxValue::F64(1.0), FqxValue::F64(0.1)],
+                vec![FqxValue::I64(3), FqxValue::F64(2.0), FqxValue::F64(0.2)],
+            ],
+        )
+        .unwrap();
+
+        let opt = AsofOptions::default().by(["id"]).suffixes("_l", "_r");
+        let res = left.merge_asof(right, "t", "t", &opt).unwrap();
+        // and a shared `on` column is kept once
+        assert_eq!(res.columns(), &["t", "id", "v"]);
+        assert_eq!(column(&res, 2), [0.1, 0.2].map(FqxValue::F64).to_vec());
+    }
+
+    fn fruits() -> FqxData {
+        let data = vec![
+            vec!["a".into(), FqxValue::I64(1), FqxValue::F64(1.0)],
+            vec!["b".into(), FqxValue::I64(1), FqxValue::F64(2.0)],
+            vec![FqxValue::Null, FqxValue::I64(1), FqxValue::F64(3.0)],
+            vec!["c".into(), FqxValue::I64(2), FqxValue::F64(4.0)],
+        ];
+        FqxData::new(
+            ["k", "g", "v"],
+            [FqxValueType::String, FqxValueType::I64, FqxValueType::F64],
+            data,
+        )
+        .unwrap()
+    }
+
+    fn prices() -> FqxData {
+        let data = vec![
+            vec!["b".into(), FqxValue::I64(10), FqxValue::F64(20.0)],
+            vec![FqxValue::Null, FqxValue::I64(30), FqxValue::F64(30.0)],
+            vec!["a".into(), FqxValue::I64(11), FqxValue::F64(10.0)],
+            vec!["d".into(), FqxValue::I64(40), FqxValue::F64(40.0)],
+            vec!["a".into(), FqxValue::I64(12), FqxValue::F64(11.0)],
+        ];
+        FqxData::new(
+            ["k", "w", "v"],
+            [FqxValueType::String, FqxValueType::I64, FqxValueType::F64],
+            data,
+        )
+        .unwrap()
+    }
+
+    fn rows(d: &FqxData) -> Vec<Vec<FqxValue>> {
+        d.data().iter().map(|r| r.clone().to_values()).collect()
+    }
+
+    /// `k, g, v_x, w, v_y`
+    fn row(
+        k: Option<&str>,
+        g: Option<i64>,
+        vx: Option<f64>,
+        w: Option<i64>,
+        vy: Option<f64>,
+    ) -> Vec<FqxValue> {
+        vec![
+            k.map_or(FqxValue::Null, FqxValue::from),
+            g.map_or(FqxValue::Null, FqxValue::I64),
+            vx.map_or(FqxValue::Null, FqxValue::F64),
+            w.map_or(FqxValue::Null, FqxValue::I64),
+            vy.map_or(FqxValue::Null, FqxValue::F64),
+        ]
+    }
+
+    #[test]
+    fn merge_hash_success() {
+        let on = ["k"];
+
+        // the right side is larger, so the left one is built
+        let res = fruits()
+            .merge(prices(), &on, &on, FqxJoinType::Left)
+            .unwrap();
+        assert_eq!(res.columns(), &["k", "g", "v_x", "w", "v_y"]);
+        let expected = vec![
+            row(Some("a"), Some(1), Some(1.0), Some(11), Some(10.0)),
+            row(Some("a"), Some(1), Some(1.0), Some(12), Some(11.0)),
+            row(Some("b"), Some(1), Some(2.0), Some(10), Some(20.0)),
+            row(None, Some(1), Some(3.0), Some(30), Some(30.0)),
+            row(Some("c"), Some(2), Some(4.0), None, None),
+        ];
+        assert_eq!(rows(&res), expected);
+
+        let res = fruits()
+            .merge(prices(), &on, &on, FqxJoinType::Inner)
+            .unwrap();
+        assert_eq!(rows(&res), expected[..4]);
+
+        // and here the smaller left side is built, with the right side leading
+        let res = fruits()
+            .merge(prices(), &on, &on, FqxJoinType::Right)
+            .unwrap();
+        assert_eq!(res.columns(), &["k", "w", "v_y", "g", "v_x"]);
+        assert_eq!(res.height(), 5);
+        assert_eq!(
+            rows(&res)[3][..4],
+            [
+                FqxValue::from("d"),
+                FqxValue::I64(40),
+                FqxValue::F64(40.0),
+                FqxValue::Null
+            ]
+        );
+
+        let options = FqxJoinOptions {
+            suffixes: ("_l", "_r"),
+            nulls_equal: false,
+        };
+        let res = fruits()
+            .merge_with(prices(), &on, &on, FqxJoinType::Outer, &options)
+            .unwrap();
+        assert_eq!(res.columns(), &["k", "g", "v_l", "w", "v_r"]);
+        let expected = vec![
+            expected[0].clone(),
+            expected[1].clone(),
+            expected[2].clone(),
+            row(None, Some(1), Some(3.0), None, None),
+            expected[4].clone(),
+            // a right row without a match keeps its key
+            row(None, None, None, Some(30), Some(30.0)),
+            row(Some("d"), None, None, Some(40), Some(40.0)),
+        ];
+        assert_eq!(rows(&res), expected);
+
+        let res = fruits()
+            .merge_with(prices(), &on, &on, FqxJoinType::LeftSemi, &options)
+            .unwrap();
+        assert_eq!(res.height(), 2);
+        let res = fruits()
+            .merge(prices(), &on, &on, FqxJoinType::LeftSemi)
+            .unwrap();
+        assert_eq!(res.height(), 3);
+    }
+
+    #[test]
+    fn merge_hash_keys_success() {
+        // keys pair in the given order, and keep both names when they differ
+        let res = fruits()
+            .merge(prices(), &["g", "v"], &["w", "v"], FqxJoinType::Inner)
+            .unwrap();
+        assert_eq!(res.columns(), &["k_x", "g", "v", "k_y", "w"]);
+        assert_eq!(res.height(), 0);
+
+        let res = fruits()
+            .merge(prices(), &["v", "k"], &["v", "k"], FqxJoinType::Outer)
+            .unwrap();
+        assert_eq!(res.columns(), &["k", "g", "v", "w"]);
+        assert_eq!(res.height(), 9);
     }
 }
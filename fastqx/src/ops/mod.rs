@@ -7,6 +7,7 @@ pub mod agg;
 pub mod apply;
 pub mod compare;
 pub mod cumagg;
+pub mod expr;
 pub mod filter;
 pub mod fold;
 pub mod group;
@@ -17,14 +18,18 @@ pub mod merge;
 pub mod owned;
 pub mod position;
 pub mod reduce;
+pub mod resample;
 pub mod select;
 pub mod sort;
+pub mod stats;
 pub(crate) mod utils;
+pub mod window;
 
 pub use agg::*;
 pub use apply::*;
 pub use compare::*;
 pub use cumagg::*;
+pub use expr::*;
 pub use filter::*;
 pub use fold::*;
 pub use group::*;
@@ -35,8 +40,11 @@ pub use merge::*;
 pub use owned::*;
 pub use position::*;
 pub use reduce::*;
+pub use resample::*;
 pub use select::*;
 pub use sort::*;
+pub use stats::*;
+pub use window::*;
 
 #[cfg(test)]
 pub(crate) mod mock;
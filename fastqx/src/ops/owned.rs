@@ -33,13 +33,13 @@ where
     type Ret = FqxGroup<U>;
 
     fn to_owned(self) -> Self::Ret {
-        let inner = self
-            .0
+        let FqxGroup(groups, key_names) = self;
+        let inner = groups
             .into_iter()
             .map(|(k, v)| (k, v))
             .collect::<HashMap<_, _>>();
 
-        FqxGroup(inner)
+        FqxGroup(inner, key_names)
     }
 }
 
@@ -5,9 +5,8 @@
 
 use std::collections::HashMap;
 
-use anyhow::Result;
-
 use crate::adt::{FqxD, FqxValue};
+use crate::error::FqxResult;
 use crate::ops::FqxGroup;
 
 // ================================================================================================
@@ -22,9 +21,9 @@ pub trait OpReduce<const OWNED: bool> {
     where
         F: FnMut(Self::Item, Self::Item) -> Self::Item;
 
-    fn try_reduce<F>(self, f: F) -> Result<Self::Ret<Self::Item>>
+    fn try_reduce<F>(self, f: F) -> FqxResult<Self::Ret<Self::Item>>
     where
-        F: FnMut(Self::Item, Self::Item) -> Result<Self::Item>;
+        F: FnMut(Self::Item, Self::Item) -> FqxResult<Self::Item>;
 }
 
 // ================================================================================================
@@ -49,9 +48,9 @@ where
         Iterator::reduce(self.data_take().into_iter(), |p, c| f(p, c))
     }
 
-    fn try_reduce<F>(self, mut f: F) -> Result<Self::Ret<Self::Item>>
+    fn try_reduce<F>(self, mut f: F) -> FqxResult<Self::Ret<Self::Item>>
     where
-        F: FnMut(Self::Item, Self::Item) -> Result<Self::Item>,
+        F: FnMut(Self::Item, Self::Item) -> FqxResult<Self::Item>,
     {
         let mut iter = self.data_take().into_iter();
         iter.next()
@@ -75,9 +74,9 @@ where
         Iterator::reduce(self.data().into_iter().cloned(), |p, c| f(p, c))
     }
 
-    fn try_reduce<F>(self, mut f: F) -> Result<Self::Ret<Self::Item>>
+    fn try_reduce<F>(self, mut f: F) -> FqxResult<Self::Ret<Self::Item>>
     where
-        F: FnMut(Self::Item, Self::Item) -> Result<Self::Item>,
+        F: FnMut(Self::Item, Self::Item) -> FqxResult<Self::Item>,
     {
         // try_reduce is not stable
         let mut iter = self.data().into_iter().cloned();
@@ -112,9 +111,9 @@ where
         res
     }
 
-    fn try_reduce<F>(self, mut f: F) -> Result<Self::Ret<Self::Item>>
+    fn try_reduce<F>(self, mut f: F) -> FqxResult<Self::Ret<Self::Item>>
     where
-        F: FnMut(Self::Item, Self::Item) -> Result<Self::Item>,
+        F: FnMut(Self::Item, Self::Item) -> FqxResult<Self::Item>,
     {
         let mut res = HashMap::new();
 
@@ -0,0 +1,559 @@
+//! file: resample.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 20:48:31 Saturday
+//! brief:
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use chrono::{Datelike, Duration, Local, Months, NaiveDate, NaiveDateTime, TimeZone};
+
+use crate::adt::{FqxD, FqxData, FqxKey, FqxRow, FqxValue, FqxValueType, RowProps};
+use crate::error::{FqxError, FqxResult};
+use crate::ops::stats::{_eval_specs, _resolve_specs, Groups};
+use crate::ops::AggSpec;
+
+// ================================================================================================
+// Every
+// ================================================================================================
+
+/// A calendar-free `Duration`, or a number of months. Parsed from `"<n><unit>"`, with the units
+/// `ms`, `s`, `m`/`min`, `h`, `d`, `w`, `mo` and `y`, e.g. `"15min"` or `"1mo"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Every {
+    Duration(Duration),
+    Months(u32),
+}
+
+impl From<Duration> for Every {
+    fn from(d: Duration) -> Self {
+        Every::Duration(d)
+    }
+}
+
+impl FromStr for Every {
+    type Err = FqxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || FqxError::parse(format!("wrong interval: {s}"));
+        let split = s.find(|c: char| !c.is_ascii_digit()).ok_or_else(err)?;
+        let n = s[..split].parse::<i64>().map_err(|_| err())?;
+        let months = |n: Option<i64>| n.and_then(|n| u32::try_from(n).ok()).map(Every::Months);
+
+        let res = match &s[split..] {
+            "ms" => Duration::try_milliseconds(n).map(Every::Duration),
+            "s" => Duration::try_seconds(n).map(Every::Duration),
+            "m" | "min" => Duration::try_minutes(n).map(Every::Duration),
+            "h" => Duration::try_hours(n).map(Every::Duration),
+            "d" => Duration::try_days(n).map(Every::Duration),
+            "w" => Duration::try_weeks(n).map(Every::Duration),
+            "mo" => months(Some(n)),
+            "y" => months(n.checked_mul(12)),
+            _ => None,
+        }
+        .ok_or_else(err)?;
+        match res.is_valid() {
+            true => Ok(res),
+            false => Err(err()),
+        }
+    }
+}
+
+const WEEK_MS: i64 = 7 * 24 * 3600 * 1000;
+
+impl Every {
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Every::Duration(d) => d.num_milliseconds() > 0,
+            Every::Months(m) => *m > 0,
+        }
+    }
+
+    /// The start of the bucket holding `t`. Buckets are aligned to 1970-01-01, or to the Monday
+    /// after it for whole weeks, and months to January.
+    pub fn bucket(&self, t: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self {
+            Every::Duration(d) => {
+                let ms = d.num_milliseconds();
+                let origin = match ms % WEEK_MS == 0 {
+                    true => NaiveDate::from_ymd_opt(1970, 1, 5)?,
+                    false => NaiveDate::from_ymd_opt(1970, 1, 1)?,
+                }
+                .and_hms_opt(0, 0, 0)?;
+                let since = (t - origin).num_milliseconds();
+                origin.checked_add_signed(Duration::milliseconds(since.div_euclid(ms) * ms))
+            }
+            Every::Months(n) => {
+                let m = t.year() as i64 * 12 + t.month0() as i64;
+                let b = m - m.rem_euclid(*n as i64);
+                NaiveDate::from_ymd_opt(b.div_euclid(12) as i32, b.rem_euclid(12) as u32 + 1, 1)?
+                    .and_hms_opt(0, 0, 0)
+            }
+        }
+    }
+
+    /// `t` moved back by one interval.
+    pub fn back(&self, t: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self {
+            Every::Duration(d) => t.checked_sub_signed(*d),
+            Every::Months(n) => t.checked_sub_months(Months::new(*n)),
+        }
+    }
+}
+
+/// Timestamps are taken in UTC, so that distances are free of DST shifts.
+pub(crate) fn _to_naive(v: &FqxValue) -> Option<NaiveDateTime> {
+    match v {
+        FqxValue::Timestamp(t) => Some(t.naive_utc()),
+        FqxValue::DateTime(t) => Some(*t),
+        FqxValue::Date(d) => d.and_hms_opt(0, 0, 0),
+        _ => None,
+    }
+}
+
+/// Timestamps are taken at their own offset, so that buckets follow the local calendar.
+fn _to_local_naive(v: &FqxValue) -> Option<NaiveDateTime> {
+    match v {
+        FqxValue::Timestamp(t) => Some(t.naive_local()),
+        v => _to_naive(v),
+    }
+}
+
+/// The reverse of `_to_local_naive`. A local time skipped by a DST change is taken an hour later.
+fn _from_local_naive(t: NaiveDateTime, typ: &FqxValueType) -> FqxValue {
+    match typ {
+        FqxValueType::Timestamp => Local
+            .from_local_datetime(&t)
+            .earliest()
+            .or_else(|| {
+                Local
+                    .from_local_datetime(&(t + Duration::hours(1)))
+                    .earliest()
+            })
+            .map_or(FqxValue::Null, FqxValue::Timestamp),
+        FqxValueType::DateTime => FqxValue::DateTime(t),
+        FqxValueType::Date => FqxValue::Date(t.date()),
+        _ => FqxValue::Null,
+    }
+}
+
+// ================================================================================================
+// OpResample
+//
+// Both take a `Timestamp`, `DateTime` or `Date` column; rows without a time are left out of every
+// bucket and window. A `Timestamp` is bucketed on the calendar of its own (local) offset, while
+// windows measure the time elapsed. With `by`, each combination of its values is resampled on its
+// own, and its columns lead the result.
+// ================================================================================================
+
+pub trait OpResample {
+    /// One row per non-empty bucket, in the order of `by` and bucket start. The time column holds
+    /// the bucket start, and every spec may name any column but those of `by`.
+    fn resample_specs(
+        &self,
+        by: &[String],
+        time_col: &str,
+        every: Every,
+        specs: &[AggSpec],
+    ) -> FqxResult<FqxData>;
+
+    /// One row per row, in the same order, aggregating the rows of its group whose time is
+    /// within `window` before its own, up to itself. The time column is kept as it is.
+    fn rolling_time_specs(
+        &self,
+        by: &[String],
+        time_col: &str,
+        window: Every,
+        specs: &[AggSpec],
+    ) -> FqxResult<FqxData>;
+
+    fn resample<I, A>(&self, time_col: &str, every: Every, specs: I) -> FqxResult<FqxData>
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<AggSpec>,
+    {
+        let specs = specs.into_iter().map(Into::into).collect::<Vec<_>>();
+        self.resample_specs(&[], time_col, every, &specs)
+    }
+
+    fn resample_by<I, A>(
+        &self,
+        by: &[String],
+        time_col: &str,
+        every: Every,
+        specs: I,
+    ) -> FqxResult<FqxData>
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<AggSpec>,
+    {
+        let specs = specs.into_iter().map(Into::into).collect::<Vec<_>>();
+        self.resample_specs(by, time_col, every, &specs)
+    }
+
+    fn rolling_time<I, A>(&self, time_col: &str, window: Every, specs: I) -> FqxResult<FqxData>
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<AggSpec>,
+    {
+        let specs = specs.into_iter().map(Into::into).collect::<Vec<_>>();
+        self.rolling_time_specs(&[], time_col, window, &specs)
+    }
+
+    fn rolling_time_by<I, A>(
+        &self,
+        by: &[String],
+        time_col: &str,
+        window: Every,
+        specs: I,
+    ) -> FqxResult<FqxData>
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<AggSpec>,
+    {
+        let specs = specs.into_iter().map(Into::into).collect::<Vec<_>>();
+        self.rolling_time_specs(by, time_col, window, &specs)
+    }
+}
+
+// ================================================================================================
+// Impl
+// ================================================================================================
+
+type TimeKeys = (Vec<usize>, usize, Vec<String>, Vec<FqxValueType>);
+
+/// The positions of `by` and of the time column, with the key columns they make.
+fn _time_keys<D: FqxD>(d: &D, by: &[String], time_col: &str, every: &Every) -> FqxResult<TimeKeys> {
+    if !every.is_valid() {
+        return Err(FqxError::invalid(format!("wrong interval: {every:?}")));
+    }
+    let position = |c: &str| {
+        d.columns()
+            .iter()
+            .position(|e| e == c)
+            .ok_or_else(|| FqxError::ColumnNotFound(c.to_string()))
+    };
+
+    let by_pos = by
+        .iter()
+        .map(|c| position(c))
+        .collect::<FqxResult<Vec<_>>>()?;
+    let t = position(time_col)?;
+    let typ = &d.types()[t];
+    if !matches!(
+        typ,
+        FqxValueType::Timestamp | FqxValueType::DateTime | FqxValueType::Date
+    ) {
+        return Err(
+            FqxError::type_mismatch(FqxValueType::DateTime, typ.clone()).at_column(time_col)
+        );
+    }
+
+    let mut key_columns = by.to_vec();
+    key_columns.push(time_col.to_string());
+    let mut key_types = by_pos
+        .iter()
+        .map(|&i| d.types()[i].clone())
+        .collect::<Vec<_>>();
+    key_types.push(typ.clone());
+
+    Ok((by_pos, t, key_columns, key_types))
+}
+
+impl<U> OpResample for U
+where
+    U: FqxD,
+{
+    fn resample_specs(
+        &self,
+        by: &[String],
+        time_col: &str,
+        every: Every,
+        specs: &[AggSpec],
+    ) -> FqxResult<FqxData> {
+        let (by_pos, t, key_columns, key_types) = _time_keys(self, by, time_col, &every)?;
+        let typ = key_types[key_types.len() - 1].clone();
+
+        let mut groups: HashMap<FqxKey<FqxValue>, Vec<&U::RowT>> = HashMap::new();
+        for r in self.iter() {
+            let start = match r
+                .get(t)
+                .and_then(_to_local_naive)
+                .and_then(|v| every.bucket(v))
+            {
+                Some(s) => s,
+                None => continue,
+            };
+            let mut k = by_pos
+                .iter()
+                .map(|&i| r.get(i).cloned().unwrap_or(FqxValue::Null))
+                .collect::<Vec<_>>();
+            k.push(_from_local_naive(start, &typ));
+            groups.entry(FqxKey(k)).or_default().push(r);
+        }
+
+        let pos = (0..self.width()).filter(|i| !by_pos.contains(i)).collect();
+        let groups = groups.into_iter().map(|(k, v)| (k.0, v)).collect();
+
+        Groups::new(self, (key_columns, key_types), pos, groups).agg_specs(specs)
+    }
+
+    fn rolling_time_specs(
+        &self,
+        by: &[String],
+        time_col: &str,
+        window: Every,
+        specs: &[AggSpec],
+    ) -> FqxResult<FqxData> {
+        let (by_pos, t, mut columns, mut types) = _time_keys(self, by, time_col, &window)?;
+        let (idx, names, res_types) =
+            _resolve_specs(&columns, self.columns(), self.types(), specs)?;
+        columns.extend(names);
+        types.extend(res_types);
+
+        let times = self
+            .iter()
+            .map(|r| r.get(t).and_then(_to_naive))
+            .collect::<Vec<_>>();
+        let mut groups: HashMap<FqxKey<&FqxValue>, Vec<usize>> = HashMap::new();
+        for (i, r) in self.iter().enumerate() {
+            let k = by_pos.iter().filter_map(|&p| r.get(p)).collect();
+            groups.entry(k).or_default().push(i);
+        }
+
+        let mut results = vec![vec![FqxValue::Null; specs.len()]; self.height()];
+        for mut idx_in in groups.into_values() {
+            // stable, so that rows at the same time stay in row order
+            idx_in.retain(|&i| times[i].is_some());
+            idx_in.sort_by_key(|&i| times[i]);
+
+            let mut start = 0;
+            for (p, &i) in idx_in.iter().enumerate() {
+                if let Some(lower) = times[i].and_then(|v| window.back(v)) {
+                    while times[idx_in[start]].is_some_and(|s| s <= lower) {
+                        start += 1;
+                    }
+                }
+                let rows = idx_in[start..=p]
+                    .iter()
+                    .map(|&j| &self.data()[j])
+                    .collect::<Vec<_>>();
+                results[i] = _eval_specs(&rows, specs, &idx);
+            }
+        }
+
+        let data = self
+            .iter()
+            .zip(results)
+            .map(|(r, res)| {
+                let mut row = by_pos
+                    .iter()
+                    .chain(std::iter::once(&t))
+                    .map(|&i| r.get(i).cloned().unwrap_or(FqxValue::Null))
+                    .collect::<Vec<_>>();
+                row.extend(res);
+                FqxRow(row)
+            })
+            .collect();
+
+        Ok(FqxData::new_uncheck(columns, types, data))
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_resample {
+    use super::*;
+    use crate::ops::Agg;
+
+    fn dt(d: u32, h: u32) -> FqxValue {
+        FqxValue::DateTime(
+            NaiveDate::from_ymd_opt(2024, 1, d)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap(),
+        )
+    }
+
+    fn quotes() -> FqxData {
+        let data = vec![
+            vec![dt(1, 9), "A".into(), FqxValue::F64(1.0)],
+            vec![dt(1, 15), "B".into(), FqxValue::F64(10.0)],
+            vec![dt(1, 11), "A".into(), FqxValue::F64(2.0)],
+            vec![dt(2, 9), "A".into(), FqxValue::F64(4.0)],
+            vec![FqxValue::Null, "A".into(), FqxValue::F64(100.0)],
+            vec![dt(3, 10), "B".into(), FqxValue::Null],
+            vec![dt(31, 10), "A".into(), FqxValue::F64(8.0)],
+        ];
+        FqxData::new(
+            ["ts", "sym", "px"],
+            [
+                FqxValueType::DateTime,
+                FqxValueType::String,
+                FqxValueType::F64,
+            ],
+            data,
+        )
+        .unwrap()
+    }
+
+    fn column(d: &FqxData, col: usize) -> Vec<FqxValue> {
+        d.data().iter().map(|r| r[col].clone()).collect()
+    }
+
+    #[test]
+    fn every_success() {
+        assert_eq!(
+            "15min".parse::<Every>().unwrap(),
+            Every::Duration(Duration::minutes(15))
+        );
+        assert_eq!("1y".parse::<Every>().unwrap(), Every::Months(12));
+        assert!("0d".parse::<Every>().is_err());
+        assert!("d".parse::<Every>().is_err());
+        assert!("1x".parse::<Every>().is_err());
+        assert!(matches!(
+            "999999999999999d".parse::<Every>(),
+            Err(FqxError::Parse(_))
+        ));
+        assert!("9223372036854775807y".parse::<Every>().is_err());
+
+        let t = NaiveDate::from_ymd_opt(2024, 5, 15)
+            .unwrap()
+            .and_hms_opt(13, 45, 0)
+            .unwrap();
+        let bucket = |s: &str| s.parse::<Every>().unwrap().bucket(t);
+        assert_eq!(bucket("1h").unwrap().to_string(), "2024-05-15 13:00:00");
+        assert_eq!(bucket("1d").unwrap().to_string(), "2024-05-15 00:00:00");
+        // weeks start on Monday
+        assert_eq!(bucket("1w").unwrap().to_string(), "2024-05-13 00:00:00");
+        assert_eq!(bucket("3mo").unwrap().to_string(), "2024-04-01 00:00:00");
+    }
+
+    #[test]
+    fn resample_success() {
+        let d = quotes();
+
+        let daily = d
+            .resample(
+                "ts",
+                "1d".parse().unwrap(),
+                [("px", Agg::Last, "close"), ("*", Agg::Count, "n")],
+            )
+            .unwrap();
+        assert_eq!(daily.columns(), &["ts", "close", "n"]);
+        assert_eq!(
+            daily.types(),
+            &[FqxValueType::DateTime, FqxValueType::F64, FqxValueType::I64]
+        );
+        assert_eq!(
+            column(&daily, 0),
+            vec![dt(1, 0), dt(2, 0), dt(3, 0), dt(31, 0)]
+        );
+        assert_eq!(column(&daily, 1)[0], FqxValue::F64(2.0));
+        assert_eq!(column(&daily, 2), [3, 1, 1, 1].map(FqxValue::I64).to_vec());
+
+        let by = vec!["sym".to_string()];
+        let monthly = d
+            .resample_by(&by, "ts", "1mo".parse().unwrap(), [("px", Agg::Sum, "px")])
+            .unwrap();
+        assert_eq!(monthly.columns(), &["sym", "ts", "px"]);
+        assert_eq!(
+            column(&monthly, 2),
+            vec![FqxValue::F64(15.0), FqxValue::F64(10.0)]
+        );
+
+        assert!(d
+            .resample("sym", "1d".parse().unwrap(), [("px", Agg::Sum, "s")])
+            .is_err());
+        assert!(d
+            .resample_by(&by, "ts", "1d".parse().unwrap(), [("sym", Agg::Count, "n")])
+            .is_err());
+    }
+
+    #[test]
+    fn resample_keys_success() {
+        // one `by` key, held as two number types
+        let data = vec![
+            vec![FqxValue::I64(1), dt(1, 9), FqxValue::F64(1.0)],
+            vec![FqxValue::F64(1.0), dt(1, 10), FqxValue::F64(2.0)],
+            vec![FqxValue::I64(2), dt(1, 11), FqxValue::F64(4.0)],
+        ];
+        let d = FqxData::new(
+            ["id", "ts", "px"],
+            [FqxValueType::I64, FqxValueType::DateTime, FqxValueType::F64],
+            data,
+        )
+        .unwrap();
+        let by = vec!["id".to_string()];
+
+        let r = d
+            .resample_by(&by, "ts", "1d".parse().unwrap(), [("px", Agg::Sum, "px")])
+            .unwrap();
+        assert_eq!(column(&r, 2), vec![FqxValue::F64(3.0), FqxValue::F64(4.0)]);
+
+        let r = d
+            .rolling_time_by(&by, "ts", "1d".parse().unwrap(), [("px", Agg::Sum, "px")])
+            .unwrap();
+        assert_eq!(column(&r, 2), [1.0, 3.0, 4.0].map(FqxValue::F64).to_vec());
+    }
+
+    #[test]
+    fn resample_timestamp_success() {
+        let ts = |h: u32| {
+            let t = NaiveDate::from_ymd_opt(2024, 3, 10)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap();
+            FqxValue::Timestamp(Local.from_local_datetime(&t).unwrap())
+        };
+        let d = FqxData::new(
+            ["ts", "px"],
+            [FqxValueType::Timestamp, FqxValueType::F64],
+            vec![
+                vec![ts(1), FqxValue::F64(1.0)],
+                vec![ts(23), FqxValue::F64(2.0)],
+            ],
+        )
+        .unwrap();
+
+        // buckets are local days, whatever their offset from UTC
+        let r = d
+            .resample("ts", "1d".parse().unwrap(), [("px", Agg::Sum, "px")])
+            .unwrap();
+        assert_eq!(r.height(), 1);
+        assert_eq!(column(&r, 0), vec![ts(0)]);
+        assert_eq!(column(&r, 1), vec![FqxValue::F64(3.0)]);
+    }
+
+    #[test]
+    fn rolling_time_success() {
+        let d = quotes();
+        let by = vec!["sym".to_string()];
+
+        let r = d
+            .rolling_time_by(
+                &by,
+                "ts",
+                "1d".parse().unwrap(),
+                [("px", Agg::Sum, "px_1d")],
+            )
+            .unwrap();
+        assert_eq!(r.columns(), &["sym", "ts", "px_1d"]);
+        assert_eq!(
+            column(&r, 2),
+            vec![
+                FqxValue::F64(1.0),
+                FqxValue::F64(10.0),
+                FqxValue::F64(3.0),
+                // 2024-01-01 09:00 is exactly a day before
+                FqxValue::F64(6.0),
+                FqxValue::Null,
+                FqxValue::Null,
+                FqxValue::F64(8.0),
+            ]
+        );
+    }
+}
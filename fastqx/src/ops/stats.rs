@@ -0,0 +1,830 @@
+//! file: stats.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 19:12:40 Saturday
+//! brief:
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use crate::adt::{FqxD, FqxData, FqxKey, FqxRow, FqxValue, FqxValueType, RowProps};
+use crate::error::{FqxError, FqxResult};
+use crate::ops::utils::_cmp_keys;
+use crate::ops::{FqxGroup, FqxLazyGroup};
+
+// ================================================================================================
+// Agg
+//
+// A named aggregation over the values of a single column, where nulls are skipped. Unlike
+// `OpAgg`, which folds every column of a row at once, `Agg` is chosen per column.
+//
+// The statistics `Median`, `Quantile`, `Std` and `Var` only see numbers: they are `null` once a
+// non-null value cannot be read as `f64`.
+// ================================================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Agg {
+    Sum,
+    Min,
+    Max,
+    Mean,
+    Count,
+    Median,
+    /// Linearly interpolated between the closest ranks, `q` in `[0, 1]`
+    Quantile(f64),
+    NUnique,
+    First,
+    Last,
+    /// Sample standard deviation, `null` with fewer than two values
+    Std,
+    /// Sample variance, `null` with fewer than two values
+    Var,
+}
+
+fn _floats<'v, I>(values: I) -> Option<Vec<f64>>
+where
+    I: Iterator<Item = &'v FqxValue>,
+{
+    values.map(|v| f64::try_from(v).ok()).collect()
+}
+
+/// `sorted` must not be empty.
+fn _quantile(sorted: &[f64], q: f64) -> f64 {
+    let rank = q * (sorted.len() - 1) as f64;
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+}
+
+fn _var(values: &[f64]) -> Option<f64> {
+    let n = values.len();
+    if n < 2 {
+        return None;
+    }
+    let mean = values.iter().sum::<f64>() / n as f64;
+    Some(values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64)
+}
+
+impl Agg {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Agg::Sum => "sum",
+            Agg::Min => "min",
+            Agg::Max => "max",
+            Agg::Mean => "mean",
+            Agg::Count => "count",
+            Agg::Median => "median",
+            Agg::Quantile(_) => "quantile",
+            Agg::NUnique => "n_unique",
+            Agg::First => "first",
+            Agg::Last => "last",
+            Agg::Std => "std",
+            Agg::Var => "var",
+        }
+    }
+
+    pub fn output_type(&self, input: &FqxValueType) -> FqxValueType {
+        match self {
+            Agg::Sum | Agg::Min | Agg::Max | Agg::First | Agg::Last => input.clone(),
+            Agg::Mean | Agg::Median | Agg::Quantile(_) | Agg::Std | Agg::Var => FqxValueType::F64,
+            Agg::Count | Agg::NUnique => FqxValueType::I64,
+        }
+    }
+
+    /// Returns `null` when there is nothing left to aggregate, except for `Count` and `NUnique`.
+    pub fn eval<'v, I>(&self, values: I) -> FqxValue
+    where
+        I: IntoIterator<Item = &'v FqxValue>,
+    {
+        let mut iter = values.into_iter().filter(|v| !v.is_null());
+        let cmp = |a: &&FqxValue, b: &&FqxValue| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+
+        match self {
+            Agg::Sum => match iter.next() {
+                Some(ini) => iter.fold(ini.clone(), |acc, v| &acc + v),
+                None => FqxValue::Null,
+            },
+            Agg::Min => iter.min_by(cmp).cloned().unwrap_or(FqxValue::Null),
+            Agg::Max => iter.max_by(cmp).cloned().unwrap_or(FqxValue::Null),
+            Agg::Mean => {
+                let (mut sum, mut count) = (0f64, 0usize);
+                for v in iter {
+                    match f64::try_from(v) {
+                        Ok(f) => sum += f,
+                        Err(_) => return FqxValue::Null,
+                    }
+                    count += 1;
+                }
+                if count == 0 {
+                    FqxValue::Null
+                } else {
+                    FqxValue::F64(sum / count as f64)
+                }
+            }
+            Agg::Count => FqxValue::I64(iter.count() as i64),
+            Agg::NUnique => {
+                let unique = iter.map(FqxValue::canonical).collect::<HashSet<_>>();
+                FqxValue::I64(unique.len() as i64)
+            }
+            Agg::First => iter.next().cloned().unwrap_or(FqxValue::Null),
+            Agg::Last => iter.last().cloned().unwrap_or(FqxValue::Null),
+            Agg::Median | Agg::Quantile(_) => {
+                let q = match self {
+                    Agg::Quantile(q) if (0.0..=1.0).contains(q) => *q,
+                    Agg::Quantile(_) => return FqxValue::Null,
+                    _ => 0.5,
+                };
+                match _floats(iter) {
+                    Some(mut f) if !f.is_empty() => {
+                        f.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                        FqxValue::F64(_quantile(&f, q))
+                    }
+                    _ => FqxValue::Null,
+                }
+            }
+            Agg::Std | Agg::Var => match _floats(iter).and_then(|f| _var(&f)) {
+                Some(v) if *self == Agg::Std => FqxValue::F64(v.sqrt()),
+                Some(v) => FqxValue::F64(v),
+                None => FqxValue::Null,
+            },
+        }
+    }
+}
+
+/// Parses `Agg::name`, plus `avg`, `nunique` and `quantile(q)`.
+impl FromStr for Agg {
+    type Err = FqxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sum" => Ok(Agg::Sum),
+            "min" => Ok(Agg::Min),
+            "max" => Ok(Agg::Max),
+            "mean" | "avg" => Ok(Agg::Mean),
+            "count" => Ok(Agg::Count),
+            "median" => Ok(Agg::Median),
+            "n_unique" | "nunique" => Ok(Agg::NUnique),
+            "first" => Ok(Agg::First),
+            "last" => Ok(Agg::Last),
+            "std" => Ok(Agg::Std),
+            "var" => Ok(Agg::Var),
+            _ => s
+                .strip_prefix("quantile(")
+                .and_then(|q| q.strip_suffix(')'))
+                .and_then(|q| q.trim().parse::<f64>().ok())
+                .filter(|q| (0.0..=1.0).contains(q))
+                .map(Agg::Quantile)
+                .ok_or_else(|| FqxError::invalid(format!("wrong aggregation: {s}"))),
+        }
+    }
+}
+
+/// `column` is aggregated by `agg` into the output column `alias`. With `Agg::Count`, the column
+/// `"*"` counts rows, nulls included.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggSpec {
+    pub column: String,
+    pub agg: Agg,
+    pub alias: String,
+}
+
+impl AggSpec {
+    pub fn new<S1: Into<String>, S2: Into<String>>(column: S1, agg: Agg, alias: S2) -> Self {
+        Self {
+            column: column.into(),
+            agg,
+            alias: alias.into(),
+        }
+    }
+
+    pub fn is_count_all(&self) -> bool {
+        self.agg == Agg::Count && self.column == "*"
+    }
+}
+
+impl<S1: Into<String>, S2: Into<String>> From<(S1, Agg, S2)> for AggSpec {
+    fn from((column, agg, alias): (S1, Agg, S2)) -> Self {
+        AggSpec::new(column, agg, alias)
+    }
+}
+
+impl<S1, S2> From<&(S1, Agg, S2)> for AggSpec
+where
+    S1: Clone + Into<String>,
+    S2: Clone + Into<String>,
+{
+    fn from((column, agg, alias): &(S1, Agg, S2)) -> Self {
+        AggSpec::new(column.clone(), *agg, alias.clone())
+    }
+}
+
+// ================================================================================================
+// OpStats
+//
+// Statistics computed per column, see `Agg` for how each one treats nulls. A plain `FqxD` gives a
+// single row; a group gives one row per group, in the order of the keys, with the key columns
+// leading. `head` and `tail` keep rows as they are, nulls included.
+// ================================================================================================
+
+pub trait OpStats {
+    /// Applies `agg` to every non-key column.
+    fn agg_columns(&self, agg: Agg) -> FqxData;
+
+    /// The first `n` rows of every group, or the last `n` when `from_end`.
+    fn take_rows(&self, n: usize, from_end: bool) -> FqxData;
+
+    /// One output column per spec, named by its alias, see `AggSpec`.
+    fn agg_specs(&self, specs: &[AggSpec]) -> FqxResult<FqxData>;
+
+    /// E.g. `agg(&[("price", Agg::Mean, "avg_price"), ("*", Agg::Count, "n")])`.
+    fn agg<I, A>(&self, specs: I) -> FqxResult<FqxData>
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<AggSpec>,
+    {
+        let specs = specs.into_iter().map(Into::into).collect::<Vec<_>>();
+        self.agg_specs(&specs)
+    }
+
+    fn count(&self) -> FqxData {
+        self.agg_columns(Agg::Count)
+    }
+
+    fn n_unique(&self) -> FqxData {
+        self.agg_columns(Agg::NUnique)
+    }
+
+    fn first(&self) -> FqxData {
+        self.agg_columns(Agg::First)
+    }
+
+    fn last(&self) -> FqxData {
+        self.agg_columns(Agg::Last)
+    }
+
+    fn median(&self) -> FqxData {
+        self.agg_columns(Agg::Median)
+    }
+
+    fn quantile(&self, q: f64) -> FqxResult<FqxData> {
+        if !(0.0..=1.0).contains(&q) {
+            return Err(FqxError::invalid(format!("quantile {q} is out of [0, 1]")));
+        }
+
+        Ok(self.agg_columns(Agg::Quantile(q)))
+    }
+
+    fn std(&self) -> FqxData {
+        self.agg_columns(Agg::Std)
+    }
+
+    fn var(&self) -> FqxData {
+        self.agg_columns(Agg::Var)
+    }
+
+    fn head(&self, n: usize) -> FqxData {
+        self.take_rows(n, false)
+    }
+
+    fn tail(&self, n: usize) -> FqxData {
+        self.take_rows(n, true)
+    }
+}
+
+// ================================================================================================
+// Groups
+// ================================================================================================
+
+/// Rows gathered by their keys, sorted. `pos` are the positions of the non-key columns in a row.
+pub(crate) struct Groups<'g, R> {
+    key_columns: Vec<String>,
+    key_types: Vec<FqxValueType>,
+    columns: Vec<String>,
+    types: Vec<FqxValueType>,
+    pos: Vec<usize>,
+    groups: Vec<(Vec<FqxValue>, Vec<&'g R>)>,
+}
+
+impl<'g, R: RowProps> Groups<'g, R> {
+    pub(crate) fn new<D>(
+        d: &D,
+        keys: (Vec<String>, Vec<FqxValueType>),
+        pos: Vec<usize>,
+        mut groups: Vec<(Vec<FqxValue>, Vec<&'g R>)>,
+    ) -> Self
+    where
+        D: FqxD,
+    {
+        groups.sort_by(|(a, _), (b, _)| _cmp_keys(a, b));
+
+        Self {
+            key_columns: keys.0,
+            key_types: keys.1,
+            columns: pos.iter().map(|&i| d.columns()[i].clone()).collect(),
+            types: pos.iter().map(|&i| d.types()[i].clone()).collect(),
+            pos,
+            groups,
+        }
+    }
+
+    /// `columns` and `types` follow the key columns.
+    fn build<F>(&self, columns: Vec<String>, types: Vec<FqxValueType>, rows: F) -> FqxData
+    where
+        F: Fn(&[&'g R]) -> Vec<Vec<FqxValue>>,
+    {
+        let mut res_columns = self.key_columns.clone();
+        res_columns.extend(columns);
+        let mut res_types = self.key_types.clone();
+        res_types.extend(types);
+
+        let data = self
+            .groups
+            .iter()
+            .flat_map(|(k, g)| {
+                rows(g).into_iter().map(move |r| {
+                    let mut row = k.clone();
+                    row.extend(r);
+                    FqxRow(row)
+                })
+            })
+            .collect();
+
+        FqxData::new_uncheck(res_columns, res_types, data)
+    }
+
+    fn agg(&self, agg: Agg) -> FqxData {
+        let types = self.types.iter().map(|t| agg.output_type(t)).collect();
+        self.build(self.columns.clone(), types, |g| {
+            let row = self
+                .pos
+                .iter()
+                .map(|&i| agg.eval(g.iter().filter_map(|r| r.get(i))))
+                .collect();
+            vec![row]
+        })
+    }
+
+    fn take(&self, n: usize, from_end: bool) -> FqxData {
+        self.build(self.columns.clone(), self.types.clone(), |g| {
+            let rows = match from_end {
+                true => &g[g.len().saturating_sub(n)..],
+                false => &g[..n.min(g.len())],
+            };
+            rows.iter()
+                .map(|r| {
+                    self.pos
+                        .iter()
+                        .map(|&i| r.get(i).cloned().unwrap_or(FqxValue::Null))
+                        .collect()
+                })
+                .collect()
+        })
+    }
+
+    /// Every `spec` names a non-key column, or `"*"` for counting rows.
+    pub(crate) fn agg_specs(&self, specs: &[AggSpec]) -> FqxResult<FqxData> {
+        let (idx, columns, types) =
+            _resolve_specs(&self.key_columns, &self.columns, &self.types, specs)?;
+        let pos = idx
+            .iter()
+            .map(|i| i.map(|j| self.pos[j]))
+            .collect::<Vec<_>>();
+
+        Ok(self.build(columns, types, |g| vec![_eval_specs(g, specs, &pos)]))
+    }
+}
+
+type ResolvedSpecs = (Vec<Option<usize>>, Vec<String>, Vec<FqxValueType>);
+
+/// The position of each spec's column within `columns`, `None` for `"*"`, and the output schema.
+/// Aliases may not repeat each other nor `taken`.
+pub(crate) fn _resolve_specs(
+    taken: &[String],
+    columns: &[String],
+    types: &[FqxValueType],
+    specs: &[AggSpec],
+) -> FqxResult<ResolvedSpecs> {
+    let mut seen = taken.iter().collect::<HashSet<_>>();
+    let mut idx = vec![];
+    for s in specs {
+        if !seen.insert(&s.alias) {
+            return Err(FqxError::invalid(format!(
+                "duplicated output column {}",
+                s.alias
+            )));
+        }
+        let i = match s.is_count_all() {
+            true => None,
+            false => Some(
+                columns
+                    .iter()
+                    .position(|c| c == &s.column)
+                    .ok_or_else(|| FqxError::ColumnNotFound(s.column.clone()))?,
+            ),
+        };
+        idx.push(i);
+    }
+
+    let names = specs.iter().map(|s| s.alias.clone()).collect();
+    let res_types = specs
+        .iter()
+        .zip(idx.iter())
+        .map(|(s, i)| match i {
+            Some(j) => s.agg.output_type(&types[*j]),
+            None => FqxValueType::I64,
+        })
+        .collect();
+
+    Ok((idx, names, res_types))
+}
+
+/// `pos` are the positions in a row of the columns resolved by `_resolve_specs`.
+pub(crate) fn _eval_specs<R: RowProps>(
+    rows: &[&R],
+    specs: &[AggSpec],
+    pos: &[Option<usize>],
+) -> Vec<FqxValue> {
+    specs
+        .iter()
+        .zip(pos.iter())
+        .map(|(s, p)| match p {
+            Some(i) => s.agg.eval(rows.iter().filter_map(|r| r.get(*i))),
+            None => FqxValue::I64(rows.len() as i64),
+        })
+        .collect()
+}
+
+/// Rows of `d` gathered by the values at `key_pos`. Without keys, all rows make a single group,
+/// even if there are none, so that an empty `d` still gives a row of stats.
+fn _gather<'g, D: FqxD>(d: &'g D, key_pos: &[usize]) -> Vec<(Vec<FqxValue>, Vec<&'g D::RowT>)> {
+    if key_pos.is_empty() {
+        return vec![(vec![], d.iter().collect())];
+    }
+
+    let mut res: HashMap<FqxKey<&FqxValue>, Vec<&D::RowT>> = HashMap::new();
+    for r in d.iter() {
+        let k = key_pos
+            .iter()
+            .map(|&i| r.get(i).unwrap_or(&FqxValue::Null))
+            .collect();
+        res.entry(k).or_default().push(r);
+    }
+
+    res.into_iter()
+        .map(|(k, g)| (k.0.into_iter().cloned().collect(), g))
+        .collect()
+}
+
+// ================================================================================================
+// Impl
+// ================================================================================================
+
+impl<U> OpStats for U
+where
+    U: FqxD,
+{
+    fn agg_columns(&self, agg: Agg) -> FqxData {
+        let pos = (0..self.width()).collect();
+        Groups::new(self, (vec![], vec![]), pos, _gather(self, &[])).agg(agg)
+    }
+
+    fn take_rows(&self, n: usize, from_end: bool) -> FqxData {
+        let pos = (0..self.width()).collect();
+        Groups::new(self, (vec![], vec![]), pos, _gather(self, &[])).take(n, from_end)
+    }
+
+    fn agg_specs(&self, specs: &[AggSpec]) -> FqxResult<FqxData> {
+        let pos = (0..self.width()).collect();
+        Groups::new(self, (vec![], vec![]), pos, _gather(self, &[])).agg_specs(specs)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// FqxGroup<T>
+
+impl<U> FqxGroup<U>
+where
+    U: FqxD,
+{
+    /// Key columns are named `key_0`, `key_1`, ... when grouped by a function, and their types
+    /// are taken from the first non-null key values.
+    fn to_groups(&self) -> Option<Groups<'_, U::RowT>> {
+        let first = self.0.values().next()?;
+        let width = self.0.keys().next().map_or(0, |k| k.len());
+
+        let key_pos = self
+            .1
+            .iter()
+            .filter_map(|k| first.columns().iter().position(|c| c == k))
+            .collect::<Vec<_>>();
+        let keys = if !self.1.is_empty() && key_pos.len() == width {
+            let types = key_pos.iter().map(|&i| first.types()[i].clone()).collect();
+            (self.1.clone(), types)
+        } else {
+            let names = (0..width).map(|i| format!("key_{i}")).collect();
+            let types = (0..width)
+                .map(|i| {
+                    self.0
+                        .keys()
+                        .map(|k| &k[i])
+                        .find(|v| !v.is_null())
+                        .map_or(FqxValueType::Null, FqxValueType::from)
+                })
+                .collect();
+            (names, types)
+        };
+
+        let key_names = &keys.0;
+        let pos = (0..first.width())
+            .filter(|&i| !key_names.contains(&first.columns()[i]))
+            .collect();
+        let groups = self
+            .0
+            .iter()
+            .map(|(k, d)| (k.clone(), d.iter().collect()))
+            .collect();
+
+        Some(Groups::new(first, keys, pos, groups))
+    }
+
+    fn empty_stats(&self, specs: &[AggSpec]) -> FqxData {
+        let mut columns = self.1.clone();
+        let mut types = vec![FqxValueType::Null; columns.len()];
+        columns.extend(specs.iter().map(|s| s.alias.clone()));
+        types.extend(specs.iter().map(|s| match s.is_count_all() {
+            true => FqxValueType::I64,
+            false => s.agg.output_type(&FqxValueType::Null),
+        }));
+        FqxData::new_uncheck(columns, types, vec![])
+    }
+}
+
+impl<U> OpStats for FqxGroup<U>
+where
+    U: FqxD,
+{
+    fn agg_columns(&self, agg: Agg) -> FqxData {
+        match self.to_groups() {
+            Some(g) => g.agg(agg),
+            None => self.empty_stats(&[]),
+        }
+    }
+
+    fn take_rows(&self, n: usize, from_end: bool) -> FqxData {
+        match self.to_groups() {
+            Some(g) => g.take(n, from_end),
+            None => self.empty_stats(&[]),
+        }
+    }
+
+    fn agg_specs(&self, specs: &[AggSpec]) -> FqxResult<FqxData> {
+        match self.to_groups() {
+            Some(g) => g.agg_specs(specs),
+            None => Ok(self.empty_stats(specs)),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// FqxLazyGroup<T>
+
+impl<'a, U> FqxLazyGroup<'a, U>
+where
+    U: FqxD,
+{
+    fn to_groups(&self) -> Groups<'a, U::RowT> {
+        let keys = (
+            self.selected_keys
+                .iter()
+                .map(|&i| self.d.columns()[i].clone())
+                .collect(),
+            self.selected_keys
+                .iter()
+                .map(|&i| self.d.types()[i].clone())
+                .collect(),
+        );
+        let groups = _gather(self.d, &self.selected_keys);
+
+        Groups::new(self.d, keys, self.selected_aggs.clone(), groups)
+    }
+}
+
+impl<'a, U> OpStats for FqxLazyGroup<'a, U>
+where
+    U: FqxD,
+{
+    fn agg_columns(&self, agg: Agg) -> FqxData {
+        self.to_groups().agg(agg)
+    }
+
+    fn take_rows(&self, n: usize, from_end: bool) -> FqxData {
+        self.to_groups().take(n, from_end)
+    }
+
+    fn agg_specs(&self, specs: &[AggSpec]) -> FqxResult<FqxData> {
+        self.to_groups().agg_specs(specs)
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_stats {
+    use super::*;
+    use crate::ops::mock::data::{D1, D5};
+    use crate::ops::{OpGroup, OpLazyGroup};
+
+    fn floats(d: &FqxData, col: usize) -> Vec<f64> {
+        d.data()
+            .iter()
+            .map(|r| f64::try_from(&r[col]).unwrap())
+            .collect()
+    }
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        a.iter()
+            .zip(b)
+            .for_each(|(x, y)| assert!((x - y).abs() < 1e-9, "{x} != {y}"));
+    }
+
+    #[test]
+    fn stats_success() {
+        let d = D1.clone();
+
+        // every column has a single null
+        let count = d.count();
+        assert_eq!(count.height(), 1);
+        assert_eq!(count.data()[0].0, vec![FqxValue::I64(8); 3]);
+        assert_eq!(count.types(), vec![FqxValueType::I64; 3]);
+
+        assert_eq!(d.first().data()[0].0[2], FqxValue::F64(1.1));
+        assert_eq!(d.last().data()[0].0[0], FqxValue::I32(9));
+        assert_eq!(d.n_unique().data()[0].0[1], FqxValue::I64(8));
+
+        let median = d.median();
+        assert_eq!(median.data()[0].0[0], FqxValue::F64(5.5));
+        // strings are not numbers
+        assert_eq!(median.data()[0].0[1], FqxValue::Null);
+
+        assert_close(&floats(&d.var(), 0), &[55.5 / 7.0]);
+        assert_close(&floats(&d.std(), 0), &[(55.5f64 / 7.0).sqrt()]);
+        assert_close(&floats(&d.quantile(0.25).unwrap(), 0), &[3.5]);
+        assert!(d.quantile(1.5).is_err());
+
+        assert_eq!(d.head(2).data(), &D1.data()[..2]);
+        assert_eq!(d.tail(2).data(), &D1.data()[7..]);
+        assert_eq!(d.tail(20).height(), 9);
+    }
+
+    #[test]
+    fn empty_stats_success() {
+        let d = FqxData::new(
+            ["a", "b"],
+            [FqxValueType::I64, FqxValueType::String],
+            Vec::<FqxRow>::new(),
+        )
+        .unwrap();
+
+        let count = d.count();
+        assert_eq!(count.height(), 1);
+        assert_eq!(count.data()[0].0, vec![FqxValue::I64(0); 2]);
+        assert_eq!(d.n_unique().data()[0].0, vec![FqxValue::I64(0); 2]);
+        assert_eq!(d.median().data()[0].0, vec![FqxValue::Null; 2]);
+        assert_eq!(d.head(2).height(), 0);
+
+        let n = d.agg([("*", Agg::Count, "n")]).unwrap();
+        assert_eq!(n.data()[0].0, vec![FqxValue::I64(0)]);
+    }
+
+    #[test]
+    fn mixed_type_keys_success() {
+        let d = FqxData::new_uncheck(
+            vec![String::from("k"), String::from("v")],
+            vec![FqxValueType::I64, FqxValueType::F64],
+            vec![
+                FqxRow(vec![FqxValue::I64(1), FqxValue::F64(1.0)]),
+                FqxRow(vec![FqxValue::I32(1), FqxValue::I64(1)]),
+                FqxRow(vec![FqxValue::F64(2.0), FqxValue::F32(0.5)]),
+            ],
+        );
+
+        assert_eq!(d.n_unique().data()[0].0, vec![FqxValue::I64(2); 2]);
+
+        let count = d.group_by(&["k"]).count();
+        assert_eq!(count.height(), 2);
+        assert_eq!(count.data()[0].0[1], FqxValue::I64(2));
+    }
+
+    #[test]
+    fn group_stats_success() {
+        let by = vec![String::from("col_0")];
+        let g = D5.clone().group_by_(&by);
+        assert_eq!(g.key_names(), &by);
+
+        let median = g.median();
+        assert_eq!(median.columns(), &["col_0", "col_1", "col_2"]);
+        assert_eq!(
+            median.types(),
+            &[FqxValueType::I32, FqxValueType::F64, FqxValueType::F64]
+        );
+        assert_close(&floats(&median, 0), &[1.0, 2.0, 3.0]);
+        assert_close(&floats(&median, 2), &[5.5, 4.4, 7.7]);
+
+        let first = g.first();
+        let names = first
+            .data()
+            .iter()
+            .map(|r| r[1].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            ["A", "B", "C"].map(|s| FqxValue::String(s.to_string()))
+        );
+
+        let tail = g.tail(1);
+        assert_eq!(tail.height(), 3);
+        assert_eq!(tail.data()[0].0[1], FqxValue::String("I".to_string()));
+
+        // unnamed keys
+        let g = D5.clone().group_by_fn_(|r| vec![r[0].clone()]);
+        let count = g.count();
+        assert_eq!(count.columns(), &["key_0", "col_0", "col_1", "col_2"]);
+        assert_eq!(count.data()[2].0, vec![FqxValue::I64(3); 4]);
+    }
+
+    #[test]
+    fn lazy_group_stats_success() {
+        let d = D5.clone();
+        let g = d.group_by(&["col_0"]);
+
+        let q = g.quantile(0.5).unwrap();
+        assert_close(&floats(&q, 2), &[5.5, 4.4, 7.7]);
+        assert_eq!(q.data(), g.median().data());
+
+        let last = g.last();
+        assert_eq!(last.columns(), &["col_0", "col_1", "col_2"]);
+        assert_close(&floats(&last, 2), &[9.9, 6.6, 8.8]);
+
+        let head = g.head(2);
+        assert_eq!(head.height(), 6);
+        assert_close(&floats(&head, 0), &[1.0, 1.0, 2.0, 2.0, 3.0, 3.0]);
+
+        assert_close(&floats(&g.std(), 2), &[4.4, 2.2, 8.47f64.sqrt()]);
+    }
+
+    #[test]
+    fn agg_spec_success() {
+        let specs = [
+            ("col_2", Agg::Mean, "avg"),
+            ("col_2", Agg::Sum, "total"),
+            ("col_1", Agg::Last, "last"),
+            ("*", Agg::Count, "n"),
+        ];
+        let expected_types = [
+            FqxValueType::I32,
+            FqxValueType::F64,
+            FqxValueType::F64,
+            FqxValueType::String,
+            FqxValueType::I64,
+        ];
+
+        let d = D5.clone();
+        let lazy = d.group_by(&["col_0"]).agg(specs).unwrap();
+        assert_eq!(lazy.columns(), &["col_0", "avg", "total", "last", "n"]);
+        assert_eq!(lazy.types(), &expected_types);
+        assert_close(&floats(&lazy, 1), &[5.5, 4.4, 6.6]);
+        assert_close(&floats(&lazy, 2), &[16.5, 13.2, 19.8]);
+        assert_eq!(lazy.data()[1].0[3], FqxValue::String("F".to_string()));
+        assert_eq!(lazy.data()[2].0[4], FqxValue::I64(3));
+
+        let by = vec![String::from("col_0")];
+        let group = D5.clone().group_by_(&by).agg(specs).unwrap();
+        assert_eq!(group.columns(), lazy.columns());
+        assert_eq!(group.types(), &expected_types);
+        assert_eq!(group.data(), lazy.data());
+
+        // nulls are only counted by `*`
+        let total = D1.agg([("*", Agg::Count, "n"), ("col_1", Agg::Count, "c")]);
+        assert_eq!(
+            total.unwrap().data()[0].0,
+            vec![FqxValue::I64(9), FqxValue::I64(8)]
+        );
+        assert_eq!("quantile(0.5)".parse::<Agg>().unwrap(), Agg::Quantile(0.5));
+    }
+
+    #[test]
+    fn agg_spec_fail() {
+        let d = D5.clone();
+        let g = d.group_by(&["col_0"]);
+
+        assert!(g.agg([("col_9", Agg::Sum, "s")]).is_err());
+        assert!(g.agg([("*", Agg::Sum, "s")]).is_err());
+        assert!(g.agg([("col_2", Agg::Sum, "col_0")]).is_err());
+        assert!(g
+            .agg([("col_2", Agg::Sum, "s"), ("col_2", Agg::Max, "s")])
+            .is_err());
+        assert!("quantile(2)".parse::<Agg>().is_err());
+    }
+}
@@ -4,12 +4,14 @@
 //! brief:
 
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use itertools::{EitherOrBoth, Itertools};
+use itertools::Itertools;
 
-use crate::adt::{FqxD, FqxValue, FqxValueType, RowProps, SeqAppend, SeqSlice};
-use crate::ops::FqxLazyGroup;
+use crate::adt::{FqxD, FqxKey, FqxValue, FqxValueType, RowProps, SeqAppend, SeqSlice};
+use crate::error::{FqxError, FqxResult};
+use crate::ops::resample::_to_naive;
+use crate::ops::{AsofOptions, AsofStrategy, FqxJoinOptions, FqxLazyGroup};
 
 ///////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -21,6 +23,31 @@ pub(crate) fn _sort_bool_to_ordering(b: bool) -> Ordering {
     }
 }
 
+/// Nulls come last, whatever the direction.
+pub(crate) fn _cmp_nulls_last(a: &FqxValue, b: &FqxValue, desc: bool) -> Ordering {
+    match (a.is_null(), b.is_null()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        _ => {
+            let o = a.partial_cmp(b).unwrap_or(Ordering::Equal);
+            if desc {
+                o.reverse()
+            } else {
+                o
+            }
+        }
+    }
+}
+
+pub(crate) fn _cmp_keys(a: &[FqxValue], b: &[FqxValue]) -> Ordering {
+    a.iter()
+        .zip(b.iter())
+        .map(|(x, y)| _cmp_nulls_last(x, y, false))
+        .find(|o| o.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
 ///////////////////////////////////////////////////////////////////////////////////////////////////
 
 fn _get_min<'c>(a: &'c FqxValue, b: &'c FqxValue) -> &'c FqxValue {
@@ -93,140 +120,484 @@ where
     U::cst(new_cols, new_typs, d)
 }
 
-fn _group<I, R>(iter: I, pos: &[usize]) -> HashMap<Vec<FqxValue>, Vec<R>>
+fn _group<I, R>(iter: I, pos: &[usize]) -> HashMap<FqxKey<FqxValue>, Vec<R>>
 where
     I: IntoIterator<Item = R>,
     R: RowProps,
 {
-    let mut gr = HashMap::new();
-    Itertools::chunk_by(iter.into_iter(), |row| row.select_vals_owned(pos))
-        .into_iter()
-        .for_each(|(k, g)| gr.entry(k).or_insert(vec![]).extend(g.collect_vec()));
+    let mut gr = HashMap::<_, Vec<R>>::new();
+    for row in iter {
+        gr.entry(FqxKey(row.select_vals_owned(pos)))
+            .or_default()
+            .push(row);
+    }
     gr
 }
 
-///////////////////////////////////////////////////////////////////////////////////////////////////
+// ================================================================================================
+// Hash join
+//
+// The smaller side is built into a table of row indices keyed on its `on` columns, which the other
+// side then probes. Rows come in the order of the left side, then of the right one; right rows
+// without a match, kept by an outer join, come last.
+// ================================================================================================
+
+/// How the columns of both sides make up those of a join.
+pub(crate) struct JoinLayout {
+    /// `(left, right)` positions of the keys, paired in the given order.
+    pub(crate) keys: Vec<(usize, usize)>,
+    /// The keys named alike on both sides, which only keep their left column.
+    pub(crate) shared: Vec<(usize, usize)>,
+    /// Right positions carried into the result.
+    pub(crate) r_keep: Vec<usize>,
+    /// The left columns, then the kept right ones, with overlapping names suffixed.
+    pub(crate) columns: Vec<String>,
+}
+
+impl JoinLayout {
+    pub(crate) fn new<S: AsRef<str>>(
+        l: &[String],
+        r: &[String],
+        left_on: &[S],
+        right_on: &[S],
+        suffixes: (&str, &str),
+    ) -> FqxResult<Self> {
+        if left_on.len() != right_on.len() {
+            return Err(FqxError::shape_mismatch(
+                "join keys",
+                left_on.len(),
+                right_on.len(),
+            ));
+        }
+        let position = |cols: &[String], c: &str| {
+            cols.iter()
+                .position(|e| e == c)
+                .ok_or_else(|| FqxError::ColumnNotFound(c.to_string()))
+        };
+        let keys = left_on
+            .iter()
+            .zip(right_on)
+            .map(|(a, b)| Ok((position(l, a.as_ref())?, position(r, b.as_ref())?)))
+            .collect::<FqxResult<Vec<_>>>()?;
+        let shared = keys
+            .iter()
+            .filter(|(i, j)| l[*i] == r[*j])
+            .copied()
+            .collect_vec();
+        let r_keep = (0..r.len())
+            .filter(|j| !shared.iter().any(|(_, k)| k == j))
+            .collect_vec();
+
+        let all = l
+            .iter()
+            .chain(r_keep.iter().map(|&j| &r[j]))
+            .cloned()
+            .collect_vec();
+        let columns = _suffix_overlaps(&all, l.len(), suffixes);
+
+        Ok(Self {
+            keys,
+            shared,
+            r_keep,
+            columns,
+        })
+    }
+}
 
-pub(crate) fn _join<U, N, S>(l: U, r: U, left_on: &N, right_on: &N, ignore_missing: bool) -> U
+/// `None` for a key holding a null, unless nulls match each other. Keys match by value, so `1`
+/// still meets `1.0`.
+fn _join_key<'r, R: RowProps>(
+    row: &'r R,
+    on: &[usize],
+    nulls_equal: bool,
+) -> Option<FqxKey<&'r FqxValue>> {
+    let key = on.iter().map(|&i| &row.values()[i]).collect_vec();
+    match !nulls_equal && key.iter().any(|v| v.is_null()) {
+        true => None,
+        false => Some(FqxKey(key)),
+    }
+}
+
+/// `(probe, build)` index pairs of matching rows, in the order of `probe`, then of `build`.
+fn _build_probe<R: RowProps>(
+    build: &[R],
+    build_on: &[usize],
+    probe: &[R],
+    probe_on: &[usize],
+    nulls_equal: bool,
+) -> Vec<(usize, usize)> {
+    let mut table = HashMap::<_, Vec<usize>>::with_capacity(build.len());
+    for (i, row) in build.iter().enumerate() {
+        if let Some(k) = _join_key(row, build_on, nulls_equal) {
+            table.entry(k).or_default().push(i);
+        }
+    }
+
+    probe
+        .iter()
+        .enumerate()
+        .filter_map(|(i, row)| Some((i, table.get(&_join_key(row, probe_on, nulls_equal)?)?)))
+        .flat_map(|(i, m)| m.iter().map(move |&j| (i, j)))
+        .collect()
+}
+
+/// `keep`: whether left and right rows without a match are kept.
+fn _hash_join<U, N, S>(
+    l: U,
+    r: U,
+    left_on: &N,
+    right_on: &N,
+    keep: (bool, bool),
+    options: &FqxJoinOptions,
+) -> FqxResult<U>
 where
     U: FqxD,
     for<'a> &'a N: IntoIterator<Item = &'a S>,
     S: AsRef<str>,
 {
-    let l_positions = l.columns_position(left_on);
-    let r_positions = r.columns_position(right_on);
-    let r_empty_row = r.empty_row();
+    let on = |n: &N| n.into_iter().map(|s| s.as_ref().to_string()).collect_vec();
+    let layout = JoinLayout::new(
+        l.columns(),
+        r.columns(),
+        &on(left_on),
+        &on(right_on),
+        options.suffixes,
+    )?;
+    let (l_on, r_on): (Vec<_>, Vec<_>) = layout.keys.iter().copied().unzip();
+    let l_width = l.width();
 
     let (l_cols, l_types, l_data) = l.dcst();
     let (r_cols, r_types, r_data) = r.dcst();
 
-    let gr = _group(r_data, &r_positions);
+    let mut pairs = match r_data.len() <= l_data.len() {
+        true => _build_probe(&r_data, &r_on, &l_data, &l_on, options.nulls_equal),
+        false => {
+            let mut p = _build_probe(&l_data, &l_on, &r_data, &r_on, options.nulls_equal)
+                .into_iter()
+                .map(|(j, i)| (i, j))
+                .collect_vec();
+            p.sort_unstable();
+            p
+        }
+    };
 
-    let d = Iterator::fold(l_data.into_iter(), vec![], |mut acc, mut row| {
-        let keys = row.select_vals_owned(&l_positions);
+    let mut l_matched = vec![false; l_data.len()];
+    let mut r_matched = vec![false; r_data.len()];
+    for &(i, j) in pairs.iter() {
+        l_matched[i] = true;
+        r_matched[j] = true;
+    }
 
-        match gr.get(&keys) {
-            Some(v) => {
-                for r in v.into_iter() {
-                    let mut new_row = row.clone();
-                    new_row.extend(r.clone().iter_owned());
-                    acc.push(new_row);
-                }
-            }
-            None => {
-                if !ignore_missing {
-                    let empty_row = r_empty_row.clone();
-                    row.extend(empty_row.iter_owned());
-                    acc.push(row);
+    // `None` is a side of nulls, but for the shared keys of a right row
+    let mut rows = pairs
+        .drain(..)
+        .map(|(i, j)| (Some(i), Some(j)))
+        .collect_vec();
+    if keep.0 {
+        rows.extend(
+            (0..l_data.len())
+                .filter(|&i| !l_matched[i])
+                .map(|i| (Some(i), None)),
+        );
+        rows.sort_unstable_by_key(|&(i, j)| (i, j));
+    }
+    if keep.1 {
+        rows.extend(
+            (0..r_data.len())
+                .filter(|&j| !r_matched[j])
+                .map(|j| (None, Some(j))),
+        );
+    }
+
+    let d = rows
+        .into_iter()
+        .map(|(i, j)| {
+            let mut left = match i {
+                Some(i) => l_data[i].values().to_vec(),
+                None => vec![FqxValue::Null; l_width],
+            };
+            if let (None, Some(j)) = (i, j) {
+                for &(lp, rp) in layout.shared.iter() {
+                    left[lp] = r_data[j].values()[rp].clone();
                 }
             }
-        }
-
-        acc
-    });
+            let right = layout.r_keep.iter().map(|&k| match j {
+                Some(j) => r_data[j].values()[k].clone(),
+                None => FqxValue::Null,
+            });
+            left.into_iter().chain(right).collect::<U::RowT>()
+        })
+        .collect_vec();
 
     let mut c = l_cols;
     let mut t = l_types;
+    c.append(r_cols.takes(layout.r_keep.clone()));
+    t.append(r_types.takes(layout.r_keep));
 
-    c.append(r_cols);
-    t.append(r_types);
-
-    U::cst(c, t, d)
+    let mut res = U::cst(c, t, d);
+    res.columns_mut().clone_from_slice(&layout.columns);
+    Ok(res)
 }
 
-fn _l_empty_extends<R: RowProps>(le: &R, r: Vec<R>) -> Vec<R> {
-    r.into_iter()
-        .map(|w| {
-            let mut l = le.clone();
-            l.extend(w.iter_owned());
-            l
-        })
-        .collect_vec()
+/// The left rows, each with its matches or, unless `ignore_missing`, a right side of nulls.
+pub(crate) fn _join<U, N, S>(
+    l: U,
+    r: U,
+    left_on: &N,
+    right_on: &N,
+    ignore_missing: bool,
+    options: &FqxJoinOptions,
+) -> FqxResult<U>
+where
+    U: FqxD,
+    for<'a> &'a N: IntoIterator<Item = &'a S>,
+    S: AsRef<str>,
+{
+    _hash_join(l, r, left_on, right_on, (!ignore_missing, false), options)
 }
 
-fn _r_empty_extends<R: RowProps>(re: &R, l: Vec<R>) -> Vec<R> {
-    l.into_iter()
-        .map(|mut w| {
-            w.extend(re.clone().iter_owned());
-            w
-        })
-        .collect_vec()
+/// Matched rows, plus those of either side without a match; a shared key takes the value of the
+/// side it comes from.
+pub(crate) fn _outer_join<U, N, S>(
+    l: U,
+    r: U,
+    left_on: &N,
+    right_on: &N,
+    options: &FqxJoinOptions,
+) -> FqxResult<U>
+where
+    U: FqxD,
+    for<'a> &'a N: IntoIterator<Item = &'a S>,
+    S: AsRef<str>,
+{
+    _hash_join(l, r, left_on, right_on, (true, true), options)
 }
 
-fn _lr_extends<R: RowProps>(l: Vec<R>, r: Vec<R>) -> Vec<R> {
-    Itertools::cartesian_product(l.into_iter(), r.into_iter())
+/// Left rows whose keys are (`anti`: are not) found on the right, with the left columns only.
+pub(crate) fn _semi_join<U, N, S>(
+    l: U,
+    r: U,
+    left_on: &N,
+    right_on: &N,
+    anti: bool,
+    options: &FqxJoinOptions,
+) -> FqxResult<U>
+where
+    U: FqxD,
+    for<'a> &'a N: IntoIterator<Item = &'a S>,
+    S: AsRef<str>,
+{
+    let on = |n: &N| n.into_iter().map(|s| s.as_ref().to_string()).collect_vec();
+    let layout = JoinLayout::new(
+        l.columns(),
+        r.columns(),
+        &on(left_on),
+        &on(right_on),
+        options.suffixes,
+    )?;
+    let (l_on, r_on): (Vec<_>, Vec<_>) = layout.keys.into_iter().unzip();
+
+    let keys = r
+        .iter()
+        .filter_map(|row| _join_key(row, &r_on, options.nulls_equal))
+        .collect::<HashSet<_>>();
+    let found = l
+        .iter()
+        .map(|row| _join_key(row, &l_on, options.nulls_equal).is_some_and(|k| keys.contains(&k)))
+        .collect_vec();
+
+    let (l_cols, l_types, l_data) = l.dcst();
+    let d = l_data
         .into_iter()
+        .zip(found)
+        .filter_map(|(row, f)| (f != anti).then_some(row))
+        .collect_vec();
+
+    Ok(U::cst(l_cols, l_types, d))
+}
+
+/// Names of `columns[..split]` also found in `columns[split..]`, and the other way round, take
+/// the suffix of their side.
+pub(crate) fn _suffix_overlaps(
+    columns: &[String],
+    split: usize,
+    suffixes: (&str, &str),
+) -> Vec<String> {
+    let (l, r) = columns.split_at(split);
+    let l_res = l.iter().map(|c| match r.contains(c) {
+        true => format!("{c}{}", suffixes.0),
+        false => c.clone(),
+    });
+    let r_res = r.iter().map(|c| match l.contains(c) {
+        true => format!("{c}{}", suffixes.1),
+        false => c.clone(),
+    });
+    l_res.chain(r_res).collect()
+}
+
+pub(crate) fn _cross_join<U>(l: U, r: U, suffixes: (&str, &str)) -> U
+where
+    U: FqxD,
+{
+    let split = l.width();
+    let (l_cols, l_types, l_data) = l.dcst();
+    let (r_cols, r_types, r_data) = r.dcst();
+
+    let d = Itertools::cartesian_product(l_data.into_iter(), r_data)
         .map(|(mut row_l, row_r)| {
             row_l.extend(row_r.iter_owned());
             row_l
         })
-        .collect_vec()
+        .collect_vec();
+
+    let mut c = l_cols;
+    let mut t = l_types;
+    c.append(r_cols);
+    t.append(r_types);
+
+    let mut res = U::cst(c, t, d);
+    let renamed = _suffix_overlaps(res.columns(), split, suffixes);
+    res.columns_mut().clone_from_slice(&renamed);
+    res
 }
 
-pub(crate) fn _outer_join<U, N, S>(l: U, r: U, left_on: &N, right_on: &N) -> U
+///////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Seconds between two times, or the plain gap between two numbers.
+fn _asof_distance(a: &FqxValue, b: &FqxValue) -> Option<f64> {
+    match (_to_naive(a), _to_naive(b)) {
+        (Some(a), Some(b)) => Some((a - b).num_milliseconds().abs() as f64 / 1000.0),
+        _ => Some((f64::try_from(a).ok()? - f64::try_from(b).ok()?).abs()),
+    }
+}
+
+/// `group` is sorted on `pos`, and holds no null there.
+fn _asof_pick<'g, R: RowProps>(
+    group: &'g [R],
+    pos: usize,
+    key: &FqxValue,
+    options: &AsofOptions,
+) -> Option<&'g R> {
+    let backward = group
+        .partition_point(|r| r.values()[pos] <= *key)
+        .checked_sub(1);
+    let forward =
+        Some(group.partition_point(|r| r.values()[pos] < *key)).filter(|&i| i < group.len());
+    let distance = |i: usize| _asof_distance(key, &group[i].values()[pos]);
+
+    let picked = match options.strategy {
+        AsofStrategy::Backward => backward,
+        AsofStrategy::Forward => forward,
+        AsofStrategy::Nearest => match (backward, forward) {
+            (Some(b), Some(f)) => match (distance(b), distance(f)) {
+                (Some(db), Some(df)) if df < db => Some(f),
+                (None, Some(_)) => Some(f),
+                _ => Some(b),
+            },
+            (b, f) => b.or(f),
+        },
+    }?;
+
+    match options.tolerance {
+        Some(t) => distance(picked).filter(|d| *d <= t).map(|_| &group[picked]),
+        None => Some(&group[picked]),
+    }
+}
+
+pub(crate) fn _asof_join<U>(
+    l: U,
+    r: U,
+    left_on: &str,
+    right_on: &str,
+    options: &AsofOptions,
+) -> FqxResult<U>
 where
     U: FqxD,
-    for<'a> &'a N: IntoIterator<Item = &'a S>,
-    S: AsRef<str>,
 {
-    let l_positions = l.columns_position(left_on);
-    let r_positions = r.columns_position(right_on);
-    let l_empty_row = l.empty_row();
-    let r_empty_row = r.empty_row();
+    if options.left_by.len() != options.right_by.len() {
+        return Err(FqxError::invalid(format!(
+            "asof by: {:?} and {:?} differ in length",
+            options.left_by, options.right_by
+        )));
+    }
+    if options.tolerance.is_some_and(|t| t.is_nan() || t < 0.0) {
+        return Err(FqxError::invalid(format!(
+            "asof tolerance: {:?}",
+            options.tolerance
+        )));
+    }
+
+    // `by` pairs first, then the `on` one
+    let on = |by: &[String], key: &str| {
+        by.iter()
+            .map(String::as_str)
+            .chain(std::iter::once(key))
+            .map(str::to_string)
+            .collect_vec()
+    };
+    let (s0, s1) = &options.suffixes;
+    let layout = JoinLayout::new(
+        l.columns(),
+        r.columns(),
+        &on(&options.left_by, left_on),
+        &on(&options.right_by, right_on),
+        (s0, s1),
+    )?;
+    let ((l_by, r_by), (l_key, r_key)): ((Vec<_>, Vec<_>), _) = {
+        let (by, key) = layout.keys.split_at(layout.keys.len() - 1);
+        (by.iter().copied().unzip(), key[0])
+    };
+
+    // times of different types never compare
+    let (l_typ, r_typ) = (&l.types()[l_key], &r.types()[r_key]);
+    let is_time = |t: &FqxValueType| {
+        matches!(
+            t,
+            FqxValueType::Timestamp | FqxValueType::DateTime | FqxValueType::Date
+        )
+    };
+    if l_typ != r_typ && (is_time(l_typ) || is_time(r_typ)) {
+        return Err(FqxError::type_mismatch(l_typ.clone(), r_typ.clone()).at_column(right_on));
+    }
 
     let (l_cols, l_types, l_data) = l.dcst();
     let (r_cols, r_types, r_data) = r.dcst();
 
-    let gl = _group(l_data, &l_positions);
-    let gr = _group(r_data, &r_positions);
-
-    let mut d = vec![];
-
-    Itertools::merge_join_by(
-        gl.into_iter().sorted_by_key(|x| x.0.clone()),
-        gr.into_iter().sorted_by_key(|x| x.0.clone()),
-        |(i, _), (j, _)| i.cmp(j),
-    )
-    .into_iter()
-    .for_each(|e| match e {
-        EitherOrBoth::Both((_, l), (_, r)) => {
-            let nw = _lr_extends(l, r);
-            d.extend(nw);
-        }
-        EitherOrBoth::Left((_, l)) => {
-            let l = _r_empty_extends(&r_empty_row, l);
-            d.extend(l);
-        }
-        EitherOrBoth::Right((_, r)) => {
-            let r = _l_empty_extends(&l_empty_row, r);
-            d.extend(r);
-        }
+    let mut gr = _group(
+        r_data
+            .into_iter()
+            .filter(|row| !row.values()[r_key].is_null()),
+        &r_by,
+    );
+    gr.values_mut().for_each(|g| {
+        g.sort_by(|a, b| _cmp_nulls_last(&a.values()[r_key], &b.values()[r_key], false))
     });
 
+    let d = l_data
+        .into_iter()
+        .map(|mut row| {
+            let key = &row.values()[l_key];
+            let picked = match key.is_null() {
+                true => None,
+                false => gr
+                    .get(&FqxKey(row.select_vals_owned(&l_by)))
+                    .and_then(|g| _asof_pick(g, r_key, key, options)),
+            };
+            let right = layout.r_keep.iter().map(|&k| match picked {
+                Some(p) => p.values()[k].clone(),
+                None => FqxValue::Null,
+            });
+            row.extend(right);
+            row
+        })
+        .collect_vec();
+
     let mut c = l_cols;
     let mut t = l_types;
-    c.append(r_cols);
-    t.append(r_types);
+    c.append(r_cols.takes(layout.r_keep.clone()));
+    t.append(r_types.takes(layout.r_keep));
 
-    U::cst(c, t, d)
+    let mut res = U::cst(c, t, d);
+    res.columns_mut().clone_from_slice(&layout.columns);
+    Ok(res)
 }
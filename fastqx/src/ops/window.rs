@@ -0,0 +1,468 @@
+//! file: window.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 20:03:27 Saturday
+//! brief:
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::adt::{FqxD, FqxData, FqxRow, FqxValue, FqxValueType, RowProps};
+use crate::error::FqxError;
+use crate::ops::{Agg, FqxGroup, FqxLazyGroup};
+
+// ================================================================================================
+// Window
+//
+// Row-wise functions over an ordered column, evaluated in row order. A group is its own window,
+// so nothing leaks from one group into another; key columns are kept as they are and lead.
+// ================================================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RankMethod {
+    /// Ties share the mean of their ranks
+    Average,
+    /// Ties share the lowest of their ranks
+    Min,
+    /// Ties share the highest of their ranks
+    Max,
+    /// Ties are ranked in row order
+    First,
+    /// Like `Min`, without gaps after ties
+    Dense,
+}
+
+impl FromStr for RankMethod {
+    type Err = FqxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "average" => Ok(RankMethod::Average),
+            "min" => Ok(RankMethod::Min),
+            "max" => Ok(RankMethod::Max),
+            "first" => Ok(RankMethod::First),
+            "dense" => Ok(RankMethod::Dense),
+            _ => Err(FqxError::invalid(format!("wrong rank method: {s}"))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Window {
+    /// Values moved down by `n` rows, or up when `n` is negative; vacated rows are null
+    Shift(isize),
+    /// The value minus the one `n` rows before
+    Diff(isize),
+    /// The relative change from the value `n` rows before, as `f64`
+    PctChange(isize),
+    /// Ascending ranks from 1, nulls are not ranked
+    Rank(RankMethod),
+    /// A single `row_number` column counting from 1
+    RowNumber,
+    /// `Agg` over the last `window` rows, nulls skipped; null until `window` rows are seen
+    Rolling(usize, Agg),
+}
+
+impl Window {
+    pub fn output_type(&self, input: &FqxValueType) -> FqxValueType {
+        match self {
+            Window::Shift(_) | Window::Diff(_) => input.clone(),
+            Window::PctChange(_) | Window::Rank(RankMethod::Average) => FqxValueType::F64,
+            Window::Rank(_) | Window::RowNumber => FqxValueType::I64,
+            Window::Rolling(_, agg) => agg.output_type(input),
+        }
+    }
+
+    fn lagged<'v>(values: &[&'v FqxValue], i: usize, n: isize) -> Option<&'v FqxValue> {
+        let j = i as isize - n;
+        if j < 0 {
+            return None;
+        }
+        values.get(j as usize).copied().filter(|v| !v.is_null())
+    }
+
+    /// One result per value, in the same order. `RowNumber` ignores the values.
+    pub fn eval(&self, values: &[&FqxValue]) -> Vec<FqxValue> {
+        let len = values.len();
+        match *self {
+            Window::Shift(n) => (0..len)
+                .map(|i| {
+                    Self::lagged(values, i, n)
+                        .cloned()
+                        .unwrap_or(FqxValue::Null)
+                })
+                .collect(),
+            Window::Diff(n) => (0..len)
+                .map(|i| match (values[i], Self::lagged(values, i, n)) {
+                    (v, Some(p)) if !v.is_null() => v - p,
+                    _ => FqxValue::Null,
+                })
+                .collect(),
+            Window::PctChange(n) => (0..len)
+                .map(|i| {
+                    let cur = f64::try_from(values[i]).ok();
+                    let prev = Self::lagged(values, i, n).and_then(|p| f64::try_from(p).ok());
+                    match (cur, prev) {
+                        (Some(c), Some(p)) if p != 0.0 => FqxValue::F64(c / p - 1.0),
+                        _ => FqxValue::Null,
+                    }
+                })
+                .collect(),
+            Window::Rank(method) => _rank(values, method),
+            Window::RowNumber => (1..=len as i64).map(FqxValue::I64).collect(),
+            Window::Rolling(window, agg) => (0..len)
+                .map(|i| match i + 1 >= window {
+                    true => agg.eval(values[i + 1 - window..=i].iter().copied()),
+                    false => FqxValue::Null,
+                })
+                .collect(),
+        }
+    }
+}
+
+fn _rank(values: &[&FqxValue], method: RankMethod) -> Vec<FqxValue> {
+    let mut order = (0..values.len())
+        .filter(|&i| !values[i].is_null())
+        .collect::<Vec<_>>();
+    // stable, so that ties stay in row order
+    order.sort_by(|&a, &b| values[a].partial_cmp(values[b]).unwrap_or(Ordering::Equal));
+
+    let mut res = vec![FqxValue::Null; values.len()];
+    let (mut start, mut dense) = (0, 0);
+    while start < order.len() {
+        let mut end = start;
+        while end + 1 < order.len()
+            && values[order[end + 1]].partial_cmp(values[order[start]]) == Some(Ordering::Equal)
+        {
+            end += 1;
+        }
+        dense += 1;
+        for (offset, &i) in order[start..=end].iter().enumerate() {
+            res[i] = match method {
+                RankMethod::Average => FqxValue::F64((start + end) as f64 / 2.0 + 1.0),
+                RankMethod::Min => FqxValue::I64(start as i64 + 1),
+                RankMethod::Max => FqxValue::I64(end as i64 + 1),
+                RankMethod::First => FqxValue::I64((start + offset) as i64 + 1),
+                RankMethod::Dense => FqxValue::I64(dense),
+            };
+        }
+        start = end + 1;
+    }
+
+    res
+}
+
+// ================================================================================================
+// OpWindow
+// ================================================================================================
+
+pub trait OpWindow {
+    type Ret;
+
+    /// Applies `w` to every non-key column.
+    fn window_columns(&self, w: Window) -> Self::Ret;
+
+    fn shift(&self, n: isize) -> Self::Ret {
+        self.window_columns(Window::Shift(n))
+    }
+
+    fn diff(&self, n: isize) -> Self::Ret {
+        self.window_columns(Window::Diff(n))
+    }
+
+    fn pct_change(&self, n: isize) -> Self::Ret {
+        self.window_columns(Window::PctChange(n))
+    }
+
+    fn rank(&self, method: RankMethod) -> Self::Ret {
+        self.window_columns(Window::Rank(method))
+    }
+
+    fn row_number(&self) -> Self::Ret {
+        self.window_columns(Window::RowNumber)
+    }
+
+    fn rolling(&self, window: usize, agg: Agg) -> Self::Ret {
+        self.window_columns(Window::Rolling(window, agg))
+    }
+
+    fn rolling_sum(&self, window: usize) -> Self::Ret {
+        self.rolling(window, Agg::Sum)
+    }
+
+    fn rolling_mean(&self, window: usize) -> Self::Ret {
+        self.rolling(window, Agg::Mean)
+    }
+
+    fn rolling_min(&self, window: usize) -> Self::Ret {
+        self.rolling(window, Agg::Min)
+    }
+
+    fn rolling_max(&self, window: usize) -> Self::Ret {
+        self.rolling(window, Agg::Max)
+    }
+
+    fn rolling_std(&self, window: usize) -> Self::Ret {
+        self.rolling(window, Agg::Std)
+    }
+}
+
+// ================================================================================================
+// Helpers
+// ================================================================================================
+
+/// The columns produced from the non-key columns at `pos`.
+fn _window_schema<D: FqxD>(d: &D, pos: &[usize], w: Window) -> (Vec<String>, Vec<FqxValueType>) {
+    match w {
+        Window::RowNumber => (vec!["row_number".to_string()], vec![FqxValueType::I64]),
+        _ => pos
+            .iter()
+            .map(|&i| (d.columns()[i].clone(), w.output_type(&d.types()[i])))
+            .unzip(),
+    }
+}
+
+/// For `rows` of a single window, the values of `key_pos` followed by the results from `pos`.
+fn _window_rows<R: RowProps>(
+    rows: &[&R],
+    key_pos: &[usize],
+    pos: &[usize],
+    w: Window,
+) -> Vec<Vec<FqxValue>> {
+    let null = FqxValue::Null;
+    let get = |r: &R, i: usize| r.get(i).unwrap_or(&null).clone();
+    // a single column, whatever its values
+    let pos = match w {
+        Window::RowNumber => &[0],
+        _ => pos,
+    };
+
+    let results = pos
+        .iter()
+        .map(|&p| {
+            let values = rows
+                .iter()
+                .map(|r| r.get(p).unwrap_or(&null))
+                .collect::<Vec<_>>();
+            w.eval(&values)
+        })
+        .collect::<Vec<_>>();
+
+    rows.iter()
+        .enumerate()
+        .map(|(i, r)| {
+            let mut row = key_pos.iter().map(|&k| get(r, k)).collect::<Vec<_>>();
+            row.extend(results.iter().map(|c| c[i].clone()));
+            row
+        })
+        .collect()
+}
+
+fn _window_data<D: FqxD>(d: &D, key_pos: &[usize], w: Window) -> FqxData {
+    let pos = (0..d.width())
+        .filter(|i| !key_pos.contains(i))
+        .collect::<Vec<_>>();
+    let (mut columns, mut types) = key_pos
+        .iter()
+        .map(|&i| (d.columns()[i].clone(), d.types()[i].clone()))
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+    let (c, t) = _window_schema(d, &pos, w);
+    columns.extend(c);
+    types.extend(t);
+
+    let rows = d.iter().collect::<Vec<_>>();
+    let data = _window_rows(&rows, key_pos, &pos, w)
+        .into_iter()
+        .map(FqxRow)
+        .collect();
+
+    FqxData::new_uncheck(columns, types, data)
+}
+
+// ================================================================================================
+// Impl
+// ================================================================================================
+
+impl<U> OpWindow for U
+where
+    U: FqxD,
+{
+    type Ret = FqxData;
+
+    fn window_columns(&self, w: Window) -> Self::Ret {
+        _window_data(self, &[], w)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// FqxGroup<T>
+
+impl<U> OpWindow for FqxGroup<U>
+where
+    U: FqxD,
+{
+    type Ret = FqxGroup<FqxData>;
+
+    fn window_columns(&self, w: Window) -> Self::Ret {
+        let res = self
+            .0
+            .iter()
+            .map(|(k, d)| {
+                let key_pos = self
+                    .1
+                    .iter()
+                    .filter_map(|n| d.columns().iter().position(|c| c == n))
+                    .collect::<Vec<_>>();
+                (k.clone(), _window_data(d, &key_pos, w))
+            })
+            .collect();
+
+        FqxGroup(res, self.1.clone())
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////////////////
+// FqxLazyGroup<T>
+
+/// Rows stay in their original order.
+impl<'a, U> OpWindow for FqxLazyGroup<'a, U>
+where
+    U: FqxD,
+{
+    type Ret = FqxData;
+
+    fn window_columns(&self, w: Window) -> Self::Ret {
+        let (keys, aggs) = (&self.selected_keys, &self.selected_aggs);
+        let (mut columns, mut types) = keys
+            .iter()
+            .map(|&i| (self.d.columns()[i].clone(), self.d.types()[i].clone()))
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+        let (c, t) = _window_schema(self.d, aggs, w);
+        columns.extend(c);
+        types.extend(t);
+
+        let mut groups: HashMap<Vec<&FqxValue>, Vec<usize>> = HashMap::new();
+        for (i, r) in self.d.iter().enumerate() {
+            let k = keys.iter().filter_map(|&p| r.get(p)).collect();
+            groups.entry(k).or_default().push(i);
+        }
+
+        let mut data = vec![FqxRow(vec![]); self.d.height()];
+        for idx in groups.into_values() {
+            let rows = idx.iter().map(|&i| &self.d.data()[i]).collect::<Vec<_>>();
+            for (i, row) in idx.into_iter().zip(_window_rows(&rows, keys, aggs, w)) {
+                data[i] = FqxRow(row);
+            }
+        }
+
+        FqxData::new_uncheck(columns, types, data)
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_window {
+    use super::*;
+    use crate::ops::mock::data::{D1, D5};
+    use crate::ops::{OpGroup, OpLazyGroup, OpSelect};
+
+    fn column(d: &FqxData, col: usize) -> Vec<FqxValue> {
+        d.data().iter().map(|r| r[col].clone()).collect()
+    }
+
+    #[test]
+    fn window_eval_success() {
+        let (v1, v2, v3) = (FqxValue::I64(1), FqxValue::I64(3), FqxValue::I64(3));
+        let values = [&v1, &v2, &FqxValue::Null, &v3];
+
+        assert_eq!(
+            Window::Shift(1).eval(&values),
+            vec![FqxValue::Null, 1.into(), 3.into(), FqxValue::Null]
+        );
+        assert_eq!(Window::Shift(-1).eval(&values)[0], FqxValue::I64(3));
+        assert_eq!(
+            Window::Diff(1).eval(&values),
+            vec![FqxValue::Null, 2.into(), FqxValue::Null, FqxValue::Null]
+        );
+        assert_eq!(Window::PctChange(1).eval(&values)[1], FqxValue::F64(2.0));
+        assert_eq!(
+            Window::Rank(RankMethod::Average).eval(&values),
+            vec![1.0.into(), 2.5.into(), FqxValue::Null, 2.5.into()]
+        );
+        assert_eq!(
+            Window::Rank(RankMethod::First).eval(&values),
+            vec![1.into(), 2.into(), FqxValue::Null, 3.into()]
+        );
+        assert_eq!(
+            Window::Rank(RankMethod::Dense).eval(&values)[3],
+            FqxValue::I64(2)
+        );
+        assert_eq!(
+            Window::Rolling(2, Agg::Sum).eval(&values),
+            vec![FqxValue::Null, 4.into(), 3.into(), 3.into()]
+        );
+        assert!("median".parse::<RankMethod>().is_err());
+    }
+
+    #[test]
+    fn window_success() {
+        let d = D1.clone();
+
+        let shifted = d.shift(1);
+        assert_eq!(shifted.types(), d.types());
+        assert_eq!(shifted.data()[0].0, vec![FqxValue::Null; 3]);
+        assert_eq!(shifted.data()[1], d.data()[0]);
+
+        let pct = d.select([0].as_slice()).pct_change(1);
+        assert_eq!(pct.columns(), &["col_0"]);
+        assert_eq!(pct.types(), &[FqxValueType::F64]);
+        assert_eq!(pct.data()[1][0], FqxValue::F64(1.0));
+        assert_eq!(pct.data()[3][0], FqxValue::Null);
+
+        let mean = d.rolling_mean(3);
+        assert_eq!(
+            column(&mean, 0)[..3],
+            [FqxValue::Null, FqxValue::Null, 1.5.into()]
+        );
+
+        let n = d.row_number();
+        assert_eq!(n.columns(), &["row_number"]);
+        assert_eq!(column(&n, 0)[8], FqxValue::I64(9));
+    }
+
+    #[test]
+    fn group_window_success() {
+        let by = vec![String::from("col_0")];
+        let g = D5.clone().group_by_(&by).diff(1).to_hashmap();
+
+        // col_2 of group 1 is 1.1, 5.5, 9.9
+        let d = &g[&vec![FqxValue::I32(1)]];
+        assert_eq!(d.columns(), &["col_0", "col_1", "col_2"]);
+        assert_eq!(column(d, 0), vec![FqxValue::I32(1); 3]);
+        assert_eq!(column(d, 2)[0], FqxValue::Null);
+        assert!((f64::try_from(&column(d, 2)[2]).unwrap() - 4.4).abs() < 1e-9);
+
+        let d = D5.clone();
+        let lazy = d.group_by(&["col_0"]).select(&["col_2"]);
+
+        // original row order
+        let rn = lazy.row_number();
+        assert_eq!(rn.columns(), &["col_0", "row_number"]);
+        assert_eq!(
+            column(&rn, 1),
+            [1, 1, 1, 2, 2, 3, 2, 3, 3].map(FqxValue::I64).to_vec()
+        );
+
+        let rank = lazy.rank(RankMethod::Min);
+        assert_eq!(rank.columns(), &["col_0", "col_2"]);
+        assert_eq!(column(&rank, 1), column(&rn, 1));
+
+        let sum = lazy.rolling_sum(2);
+        assert_eq!(sum.types(), &[FqxValueType::I32, FqxValueType::F64]);
+        assert_eq!(column(&sum, 1)[..3], vec![FqxValue::Null; 3]);
+        assert!((f64::try_from(&column(&sum, 1)[4]).unwrap() - 6.6).abs() < 1e-9);
+    }
+}
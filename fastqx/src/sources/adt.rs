@@ -10,10 +10,83 @@ use serde::{Deserialize, Serialize};
 // SaveMode
 // ================================================================================================
 
-#[pyclass]
-#[pyo3(name = "FqxSaveMode")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SaveMode {
+    /// drop the table and create it by the data's schema
     Override,
+    /// insert into the table, which must exist
     Append,
+    /// keep the table's DDL (indexes, grants, defaults...) and delete all the rows before inserting;
+    /// the table is created if it doesn't exist
+    Truncate,
+    /// create the table if it doesn't exist, then insert
+    CreateIfNotExists,
+    /// fail if the table exists, otherwise create it and insert
+    ErrorIfExists,
+    /// insert rows, and update the non-key columns of the rows whose `keys` already exist; the
+    /// target table must have a primary key or an unique constraint on `keys`
+    Upsert { keys: Vec<String> },
+}
+
+// ================================================================================================
+// PySaveMode
+//
+// pyo3 cannot expose an enum mixing unit and struct variants, hence the wrapper
+// ================================================================================================
+
+#[pyclass]
+#[pyo3(name = "FqxSaveMode")]
+#[derive(Debug, Clone)]
+pub struct PySaveMode(pub SaveMode);
+
+#[pymethods]
+impl PySaveMode {
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn Override() -> Self {
+        Self(SaveMode::Override)
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn Append() -> Self {
+        Self(SaveMode::Append)
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn Truncate() -> Self {
+        Self(SaveMode::Truncate)
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn CreateIfNotExists() -> Self {
+        Self(SaveMode::CreateIfNotExists)
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn ErrorIfExists() -> Self {
+        Self(SaveMode::ErrorIfExists)
+    }
+
+    #[staticmethod]
+    fn upsert(keys: Vec<String>) -> Self {
+        Self(SaveMode::Upsert { keys })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl From<PySaveMode> for SaveMode {
+    fn from(value: PySaveMode) -> Self {
+        value.0
+    }
 }
@@ -5,13 +5,13 @@
 
 use std::path::Path;
 
-use anyhow::Result;
 use csv::Reader;
 use serde::de::DeserializeOwned;
 
 use crate::adt::*;
+use crate::error::FqxResult;
 
-pub fn csv_read<S, P>(path: P) -> Result<Vec<S>>
+pub fn csv_read<S, P>(path: P) -> FqxResult<Vec<S>>
 where
     S: DeserializeOwned,
     P: AsRef<Path>,
@@ -27,7 +27,7 @@ where
     Ok(res)
 }
 
-pub fn csv_read_rd<P>(path: P, type_hints: &[FqxValueType]) -> Result<FqxData>
+pub fn csv_read_rd<P>(path: P, type_hints: &[FqxValueType]) -> FqxResult<FqxData>
 where
     P: AsRef<Path>,
 {
@@ -6,13 +6,13 @@
 use std::borrow::Borrow;
 use std::path::Path;
 
-use anyhow::Result;
 use csv::Writer;
 use serde::Serialize;
 
 use crate::adt::FqxData;
+use crate::error::FqxResult;
 
-pub fn csv_write<I, E, S, P>(data: I, path: P) -> Result<()>
+pub fn csv_write<I, E, S, P>(data: I, path: P) -> FqxResult<()>
 where
     I: IntoIterator<Item = S>,
     S: Borrow<E>,
@@ -30,7 +30,7 @@ where
     Ok(())
 }
 
-pub fn csv_write_rd<P>(data: &FqxData, path: P) -> Result<()>
+pub fn csv_write_rd<P>(data: &FqxData, path: P) -> FqxResult<()>
 where
     P: AsRef<Path>,
 {
@@ -3,11 +3,11 @@
 //! date: 2023/10/02 23:22:11 Monday
 //! brief:
 
-use anyhow::{bail, Result};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::adt::FqxData;
+use crate::error::{FqxError, FqxResult};
 use crate::sources::http::HttpConnector;
 
 // ================================================================================================
@@ -56,7 +56,7 @@ impl FqxData {
         subpath: &str,
         mtd: &HttpMethod,
         payload: Option<P>,
-    ) -> Result<Self>
+    ) -> FqxResult<Self>
     where
         P: Serialize,
     {
@@ -66,24 +66,24 @@ impl FqxData {
                 if let Some(p) = payload {
                     client.post(subpath, &p).await
                 } else {
-                    bail!("method POST payload is empty")
+                    Err(FqxError::invalid("method POST payload is empty"))
                 }
             }
             HttpMethod::Put => {
                 if let Some(p) = payload {
                     client.put(subpath, &p).await
                 } else {
-                    bail!("method PUT payload is empty")
+                    Err(FqxError::invalid("method PUT payload is empty"))
                 }
             }
             HttpMethod::Patch => {
                 if let Some(p) = payload {
                     client.patch(subpath, &p).await
                 } else {
-                    bail!("method PATCH payload is empty")
+                    Err(FqxError::invalid("method PATCH payload is empty"))
                 }
             }
-            _ => bail!("only accept GET/POST/PUT/PATCH"),
+            _ => Err(FqxError::invalid("only accept GET/POST/PUT/PATCH")),
         }
     }
 
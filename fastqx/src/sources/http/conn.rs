@@ -3,7 +3,6 @@
 //! date: 2023/10/02 12:37:03 Monday
 //! brief:
 
-use anyhow::Result;
 use pyo3::pyclass;
 use reqwest::header::{HeaderMap, AUTHORIZATION};
 use reqwest::{Client, ClientBuilder, Url};
@@ -11,6 +10,7 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 use crate::adt::FqxData;
+use crate::error::{FqxError, FqxResult};
 
 // ================================================================================================
 // HttpConnector
@@ -28,14 +28,14 @@ pub struct HttpConnector {
 macro_rules! send {
     ($s:expr, $sp:expr, $mtd:ident) => {{
         let pth = format!("{}/{}", $s.url, $sp.as_ref());
-        let encoded = Url::parse(&pth)?;
+        let encoded = Url::parse(&pth).map_err(|e| FqxError::Http(Box::new(e)))?;
         let res = $s.client.$mtd(encoded).send().await?.json::<R>().await?;
 
         Ok(res)
     }};
     ($s:expr, $sp:expr, $mtd:ident, $b:expr) => {{
         let pth = format!("{}/{}", $s.url, $sp.as_ref());
-        let encoded = Url::parse(&pth)?;
+        let encoded = Url::parse(&pth).map_err(|e| FqxError::Http(Box::new(e)))?;
         let res = $s
             .client
             .$mtd(encoded)
@@ -54,7 +54,7 @@ impl HttpConnector {
         &self.url
     }
 
-    pub fn new<S: ToString>(url: S, auth: Option<&str>) -> Result<Self> {
+    pub fn new<S: ToString>(url: S, auth: Option<&str>) -> FqxResult<Self> {
         let mut builder = ClientBuilder::new();
 
         if let Some(a) = auth {
@@ -70,7 +70,7 @@ impl HttpConnector {
         })
     }
 
-    pub async fn raw_get<P, R>(&self, subpath: P) -> Result<R>
+    pub async fn raw_get<P, R>(&self, subpath: P) -> FqxResult<R>
     where
         P: AsRef<str>,
         R: DeserializeOwned,
@@ -78,7 +78,7 @@ impl HttpConnector {
         send!(self, subpath, get)
     }
 
-    pub async fn raw_post<P, T, R>(&self, subpath: P, req: &T) -> Result<R>
+    pub async fn raw_post<P, T, R>(&self, subpath: P, req: &T) -> FqxResult<R>
     where
         P: AsRef<str>,
         T: Serialize,
@@ -87,7 +87,7 @@ impl HttpConnector {
         send!(self, subpath, post, req)
     }
 
-    pub async fn raw_put<P, T, R>(&self, subpath: P, req: &T) -> Result<R>
+    pub async fn raw_put<P, T, R>(&self, subpath: P, req: &T) -> FqxResult<R>
     where
         P: AsRef<str>,
         T: Serialize,
@@ -96,7 +96,7 @@ impl HttpConnector {
         send!(self, subpath, put, req)
     }
 
-    pub async fn raw_delete<P, R>(&self, subpath: P) -> Result<R>
+    pub async fn raw_delete<P, R>(&self, subpath: P) -> FqxResult<R>
     where
         P: AsRef<str>,
         R: DeserializeOwned,
@@ -104,7 +104,7 @@ impl HttpConnector {
         send!(self, subpath, delete)
     }
 
-    pub async fn raw_patch<P, T, R>(&self, subpath: P, req: &T) -> Result<R>
+    pub async fn raw_patch<P, T, R>(&self, subpath: P, req: &T) -> FqxResult<R>
     where
         P: AsRef<str>,
         T: Serialize,
@@ -115,19 +115,31 @@ impl HttpConnector {
 
     ///////////////////////////////////////////////////////////////////////////////////////////////////
 
-    pub async fn get<P: AsRef<str>>(&self, subpath: P) -> Result<FqxData> {
+    pub async fn get<P: AsRef<str>>(&self, subpath: P) -> FqxResult<FqxData> {
         self.raw_get::<_, FqxData>(subpath).await
     }
 
-    pub async fn post<P: AsRef<str>, T: Serialize>(&self, subpath: P, req: &T) -> Result<FqxData> {
+    pub async fn post<P: AsRef<str>, T: Serialize>(
+        &self,
+        subpath: P,
+        req: &T,
+    ) -> FqxResult<FqxData> {
         self.raw_post::<_, _, FqxData>(subpath, req).await
     }
 
-    pub async fn put<P: AsRef<str>, T: Serialize>(&self, subpath: P, req: &T) -> Result<FqxData> {
+    pub async fn put<P: AsRef<str>, T: Serialize>(
+        &self,
+        subpath: P,
+        req: &T,
+    ) -> FqxResult<FqxData> {
         self.raw_put::<_, _, FqxData>(subpath, req).await
     }
 
-    pub async fn patch<P: AsRef<str>, T: Serialize>(&self, subpath: P, req: &T) -> Result<FqxData> {
+    pub async fn patch<P: AsRef<str>, T: Serialize>(
+        &self,
+        subpath: P,
+        req: &T,
+    ) -> FqxResult<FqxData> {
         self.raw_patch::<_, _, FqxData>(subpath, req).await
     }
 }
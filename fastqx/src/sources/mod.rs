@@ -6,6 +6,9 @@
 pub mod adt;
 pub mod csv;
 pub mod http;
+pub mod parquet;
+pub mod registry;
 pub mod sql;
 
 pub use adt::*;
+pub use registry::*;
@@ -0,0 +1,10 @@
+//! file: mod.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 15:41:17 Saturday
+//! brief:
+
+pub mod read;
+pub mod write;
+
+pub use read::*;
+pub use write::*;
@@ -0,0 +1,119 @@
+//! file: read.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 15:42:03 Saturday
+//! brief:
+
+use std::fs::File;
+use std::path::Path;
+
+use arrow::record_batch::{RecordBatch, RecordBatchReader};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ProjectionMask;
+
+use crate::adt::*;
+use crate::error::{FqxError, FqxResult};
+
+/// Read a parquet file into `FqxData`, types are restored from the file's schema.
+///
+/// `columns` selects (and orders) the columns to read, only those are decoded;
+/// `row_filter` keeps the rows it returns `true` for.
+pub fn parquet_read_rd<P>(
+    path: P,
+    columns: Option<&[&str]>,
+    row_filter: Option<&dyn Fn(&FqxRow) -> bool>,
+) -> FqxResult<FqxData>
+where
+    P: AsRef<Path>,
+{
+    let mut builder = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?;
+
+    // the reader follows the file's column order, `order` turns it back into the requested one
+    let mut order = None;
+    if let Some(cols) = columns {
+        let schema = builder.schema();
+        let mut indices = vec![];
+        for c in cols.iter() {
+            match schema.index_of(c) {
+                Ok(i) => indices.push(i),
+                Err(_) => return Err(FqxError::ColumnNotFound(c.to_string())),
+            }
+        }
+
+        let mut sorted = indices.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        order = Some(
+            indices
+                .iter()
+                .map(|i| sorted.binary_search(i).unwrap())
+                .collect::<Vec<_>>(),
+        );
+
+        let mask = ProjectionMask::roots(builder.parquet_schema(), sorted);
+        builder = builder.with_projection(mask);
+    }
+
+    let reorder = |batch: RecordBatch| -> FqxResult<RecordBatch> {
+        match &order {
+            Some(o) => Ok(batch.project(o)?),
+            None => Ok(batch),
+        }
+    };
+
+    let reader = builder.build()?;
+    let mut res =
+        FqxData::try_from_record_batch(&reorder(RecordBatch::new_empty(reader.schema()))?)?;
+
+    for batch in reader {
+        let d = FqxData::try_from_record_batch(&reorder(batch?)?)?;
+
+        match row_filter {
+            Some(f) => res.data.extend(d.data.into_iter().filter(|r| f(r))),
+            None => res.data.extend(d.data),
+        }
+    }
+
+    Ok(res)
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_read {
+    use super::*;
+    use crate::fqx;
+    use crate::sources::parquet::parquet_write_rd;
+    use parquet::basic::Compression;
+
+    #[test]
+    fn parquet_read_rd_success() {
+        let data = fqx!((1, "A", 1.1), (2, "B", 2.2), (3, fqx!(), 3.3),).unwrap();
+        let path = std::env::temp_dir().join("fqx_parquet_read.parquet");
+        parquet_write_rd(&data, &path, Compression::SNAPPY).unwrap();
+
+        let res = parquet_read_rd(&path, None, None).unwrap();
+        println!("{:?}", res);
+        assert_eq!(res.types, data.types);
+        assert_eq!(res.data, data.data);
+
+        let res = parquet_read_rd(
+            &path,
+            Some(&["col_2", "col_0"]),
+            Some(&|r: &FqxRow| r[1] != fqx!(1)),
+        )
+        .unwrap();
+        println!("{:?}", res);
+        assert_eq!(res.columns, vec!["col_2", "col_0"]);
+        assert_eq!(
+            res.data,
+            vec![
+                FqxRow(vec![fqx!(2.2), fqx!(2)]),
+                FqxRow(vec![fqx!(3.3), fqx!(3)])
+            ]
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
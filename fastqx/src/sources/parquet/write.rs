@@ -0,0 +1,93 @@
+//! file: write.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 15:42:11 Saturday
+//! brief:
+
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+
+use crate::adt::dat::arrow::record_batch_from_rows;
+use crate::adt::FqxData;
+use crate::error::FqxResult;
+
+// rows converted into one record batch at a time, bounds the memory used by the conversion
+const WRITE_BATCH_SIZE: usize = 65_536;
+
+/// Write `FqxData` into a parquet file. The Arrow schema is embedded in the file, so that
+/// `parquet_read_rd` gives back the exact same `FqxValueType`s.
+pub fn parquet_write_rd<P>(data: &FqxData, path: P, compression: Compression) -> FqxResult<()>
+where
+    P: AsRef<Path>,
+{
+    let schema = Arc::new(data.arrow_schema());
+    let props = WriterProperties::builder()
+        .set_compression(compression)
+        .build();
+    let mut wtr = ArrowWriter::try_new(File::create(path)?, schema.clone(), Some(props))?;
+
+    for rows in data.data.chunks(WRITE_BATCH_SIZE) {
+        let batch = record_batch_from_rows(schema.clone(), &data.types, rows)?;
+        wtr.write(&batch)?;
+    }
+
+    wtr.close()?;
+
+    Ok(())
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_write {
+    use super::*;
+    use crate::adt::{FqxValue, FqxValueType};
+    use crate::sources::parquet::parquet_read_rd;
+
+    #[test]
+    fn parquet_write_rd_success() {
+        let data = FqxData::new(
+            vec!["c1", "c2", "c3", "c4", "c5"],
+            vec![
+                FqxValueType::U8,
+                FqxValueType::String,
+                FqxValueType::F32,
+                FqxValueType::Timestamp,
+                FqxValueType::Null,
+            ],
+            vec![
+                vec![
+                    FqxValue::U8(1),
+                    FqxValue::String(String::from("A")),
+                    FqxValue::F32(2.1),
+                    FqxValue::Timestamp(chrono::Local::now()),
+                    FqxValue::Null,
+                ],
+                vec![
+                    FqxValue::U8(2),
+                    FqxValue::Null,
+                    FqxValue::F32(1.3),
+                    FqxValue::Null,
+                    FqxValue::Null,
+                ],
+            ],
+        )
+        .unwrap();
+
+        let path = std::env::temp_dir().join("fqx_parquet_write.parquet");
+        let res = parquet_write_rd(&data, &path, Compression::ZSTD(Default::default()));
+        assert!(res.is_ok());
+
+        let res = parquet_read_rd(&path, None, None).unwrap();
+        println!("{:?}", res);
+        assert_eq!(res.types, data.types);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
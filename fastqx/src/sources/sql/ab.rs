@@ -3,15 +3,17 @@
 //! date: 2023/12/19 20:40:50 Tuesday
 //! brief:
 
-use anyhow::Result;
 use futures::TryStreamExt;
 use sqlx::mysql::{MySql, MySqlRow};
 use sqlx::postgres::{PgRow, Postgres};
-use sqlx::sqlite::{Sqlite, SqliteRow};
+use sqlx::sqlite::SqliteRow;
 
 use super::adt::*;
+use super::dynm::truncate_table_statement;
+use super::options::ConnectorOptions;
 use super::sqx::*;
 use super::tbr::*;
+use crate::error::{FqxError, FqxResult};
 use crate::sources::SaveMode;
 
 // ================================================================================================
@@ -28,11 +30,19 @@ where
     Self: FromSqlxRow<SqliteRow>,
     Self: FromTiberiusRow,
 {
-    fn create_table(driver: &Driver) -> Result<String>;
+    fn table_name() -> &'static str;
 
-    fn drop_table(driver: &Driver) -> Result<String>;
+    fn create_table(driver: &Driver) -> FqxResult<String>;
 
-    fn insert<I: IntoIterator<Item = Self>>(driver: &Driver, data: I) -> Result<String>;
+    fn drop_table(driver: &Driver) -> FqxResult<String>;
+
+    fn insert<I: IntoIterator<Item = Self>>(driver: &Driver, data: I) -> FqxResult<String>;
+
+    fn upsert<I: IntoIterator<Item = Self>>(
+        driver: &Driver,
+        data: I,
+        keys: &[String],
+    ) -> FqxResult<String>;
 }
 
 // ================================================================================================
@@ -44,41 +54,72 @@ pub(crate) trait FqxSqlPool: Sized {
 
     fn driver() -> Driver;
 
-    async fn new(host: &str, port: Option<u16>, user: &str, pass: &str, db: &str) -> Result<Self>;
+    async fn new(
+        host: &str,
+        port: Option<u16>,
+        user: &str,
+        pass: &str,
+        db: &str,
+    ) -> FqxResult<Self>;
 
-    async fn new_by_str(url: &str) -> Result<Self>;
+    async fn new_with_options(url: &str, options: &ConnectorOptions) -> FqxResult<Self>;
 
-    async fn close(&self) -> Result<()>;
+    async fn close(&self) -> FqxResult<()>;
 
     fn is_closed(&self) -> bool;
 
-    async fn acquire(&self) -> Result<Self::PoolConnection>;
+    async fn acquire(&self) -> FqxResult<Self::PoolConnection>;
 
-    async fn execute(&self, sql: &str) -> Result<()>;
+    async fn execute(&self, sql: &str) -> FqxResult<()>;
 
-    async fn fetch<R>(&self, sql: &str) -> Result<Vec<R>>
+    async fn fetch<R>(&self, sql: &str) -> FqxResult<Vec<R>>
     where
         R: FqxSqlRow;
 
-    async fn save<I, R>(&self, data: I, mode: SaveMode) -> Result<()>
+    /// `exists` is only read by the modes whose `check_existence` is true.
+    async fn save<I, R>(&self, data: I, mode: SaveMode, exists: bool) -> FqxResult<()>
     where
         I: IntoIterator<Item = R> + Send,
         R: FqxSqlRow,
     {
-        let insert_data = R::insert(&Self::driver(), data)?;
+        let driver = Self::driver();
 
         match mode {
             SaveMode::Override => {
-                let drop_table = R::drop_table(&Self::driver())?;
-                let create_table = R::create_table(&Self::driver())?;
+                let insert_data = R::insert(&driver, data)?;
+                let drop_table = R::drop_table(&driver)?;
+                let create_table = R::create_table(&driver)?;
 
-                let _ = self.execute(&drop_table).await;
+                self.execute(&drop_table).await?;
                 self.execute(&create_table).await?;
                 self.execute(&insert_data).await?;
             }
             SaveMode::Append => {
+                let insert_data = R::insert(&driver, data)?;
+                self.execute(&insert_data).await?;
+            }
+            SaveMode::Truncate if exists => {
+                let insert_data = R::insert(&driver, data)?;
+                let truncate_table = truncate_table_statement(&driver, R::table_name());
+
+                self.execute(&truncate_table).await?;
+                self.execute(&insert_data).await?;
+            }
+            SaveMode::ErrorIfExists if exists => {
+                let msg = format!("table {} already exists", R::table_name());
+                return Err(FqxError::invalid(msg));
+            }
+            SaveMode::Truncate | SaveMode::CreateIfNotExists | SaveMode::ErrorIfExists => {
+                let insert_data = R::insert(&driver, data)?;
+                let create_table = R::create_table(&driver)?;
+
+                self.execute(&create_table).await?;
                 self.execute(&insert_data).await?;
             }
+            SaveMode::Upsert { keys } => {
+                let upsert_data = R::upsert(&driver, data, &keys)?;
+                self.execute(&upsert_data).await?;
+            }
         }
 
         Ok(())
@@ -96,22 +137,36 @@ impl FqxSqlPool for PoolMsSql {
         Driver::MSSQL
     }
 
-    async fn new(host: &str, port: Option<u16>, user: &str, pass: &str, db: &str) -> Result<Self> {
+    async fn new(
+        host: &str,
+        port: Option<u16>,
+        user: &str,
+        pass: &str,
+        db: &str,
+    ) -> FqxResult<Self> {
         let m = MsSqlConnectionManager::new(host, port, user, pass, db)?;
         let pool = bb8::Pool::builder().build(m).await?;
 
         Ok(Self(pool))
     }
 
-    async fn new_by_str(url: &str) -> Result<Self> {
+    async fn new_with_options(url: &str, options: &ConnectorOptions) -> FqxResult<Self> {
+        options.validate(&Driver::MSSQL)?;
         let m = MsSqlConnectionManager::new_from_str(url)?;
 
-        let pool = bb8::Pool::builder().build(m).await?;
+        // `build` only opens (and so only fails on) the `min_idle` connections
+        let min_idle = options.min_connections.unwrap_or(0).max(1);
+        let pool = options
+            .retry(|| async {
+                let b = options.bb8_builder().min_idle(min_idle);
+                Ok(b.build(m.clone()).await?)
+            })
+            .await?;
 
         Ok(Self(pool))
     }
 
-    async fn close(&self) -> Result<()> {
+    async fn close(&self) -> FqxResult<()> {
         // TODO: `close` method takes ownership
         // let conn = self.0.get_owned().await?;
         // conn.close().await?;
@@ -122,11 +177,11 @@ impl FqxSqlPool for PoolMsSql {
         false
     }
 
-    async fn acquire(&self) -> Result<Self::PoolConnection> {
+    async fn acquire(&self) -> FqxResult<Self::PoolConnection> {
         Ok(self.0.get_owned().await?)
     }
 
-    async fn execute(&self, sql: &str) -> Result<()> {
+    async fn execute(&self, sql: &str) -> FqxResult<()> {
         let mut conn = self.0.get().await?;
 
         conn.execute(sql, &[]).await?;
@@ -134,7 +189,7 @@ impl FqxSqlPool for PoolMsSql {
         Ok(())
     }
 
-    async fn fetch<R>(&self, sql: &str) -> Result<Vec<R>>
+    async fn fetch<R>(&self, sql: &str) -> FqxResult<Vec<R>>
     where
         R: FqxSqlRow,
     {
@@ -161,16 +216,30 @@ impl FqxSqlPool for PoolMySql {
         Driver::MYSQL
     }
 
-    async fn new(host: &str, port: Option<u16>, user: &str, pass: &str, db: &str) -> Result<Self> {
+    async fn new(
+        host: &str,
+        port: Option<u16>,
+        user: &str,
+        pass: &str,
+        db: &str,
+    ) -> FqxResult<Self> {
         let cs = &gen_sqlx_str("mysql", host, port.unwrap_or(3306), user, pass, db);
         Ok(Self(sqlx::Pool::<MySql>::connect(cs).await?))
     }
 
-    async fn new_by_str(url: &str) -> Result<Self> {
-        Ok(Self(sqlx::Pool::<MySql>::connect(url).await?))
+    async fn new_with_options(url: &str, options: &ConnectorOptions) -> FqxResult<Self> {
+        options.validate(&Driver::MYSQL)?;
+        let pool = options
+            .retry(|| async {
+                let po = options.sqlx_pool_options::<MySql>(&Driver::MYSQL);
+                Ok(po.connect(url).await?)
+            })
+            .await?;
+
+        Ok(Self(pool))
     }
 
-    async fn close(&self) -> Result<()> {
+    async fn close(&self) -> FqxResult<()> {
         self.0.close().await;
         Ok(())
     }
@@ -179,16 +248,16 @@ impl FqxSqlPool for PoolMySql {
         self.0.is_closed()
     }
 
-    async fn acquire(&self) -> Result<Self::PoolConnection> {
+    async fn acquire(&self) -> FqxResult<Self::PoolConnection> {
         Ok(PoolConnectionMySql(self.0.acquire().await?))
     }
 
-    async fn execute(&self, sql: &str) -> Result<()> {
+    async fn execute(&self, sql: &str) -> FqxResult<()> {
         sqlx::query(sql).execute(&self.0).await?;
         Ok(())
     }
 
-    async fn fetch<R>(&self, sql: &str) -> Result<Vec<R>>
+    async fn fetch<R>(&self, sql: &str) -> FqxResult<Vec<R>>
     where
         R: FqxSqlRow,
     {
@@ -206,16 +275,30 @@ impl FqxSqlPool for PoolPostgres {
         Driver::POSTGRES
     }
 
-    async fn new(host: &str, port: Option<u16>, user: &str, pass: &str, db: &str) -> Result<Self> {
+    async fn new(
+        host: &str,
+        port: Option<u16>,
+        user: &str,
+        pass: &str,
+        db: &str,
+    ) -> FqxResult<Self> {
         let cs = &gen_sqlx_str("postgres", host, port.unwrap_or(5432), user, pass, db);
         Ok(Self(sqlx::Pool::<Postgres>::connect(cs).await?))
     }
 
-    async fn new_by_str(url: &str) -> Result<Self> {
-        Ok(Self(sqlx::Pool::<Postgres>::connect(url).await?))
+    async fn new_with_options(url: &str, options: &ConnectorOptions) -> FqxResult<Self> {
+        options.validate(&Driver::POSTGRES)?;
+        let pool = options
+            .retry(|| async {
+                let po = options.sqlx_pool_options::<Postgres>(&Driver::POSTGRES);
+                Ok(po.connect(url).await?)
+            })
+            .await?;
+
+        Ok(Self(pool))
     }
 
-    async fn close(&self) -> Result<()> {
+    async fn close(&self) -> FqxResult<()> {
         self.0.close().await;
         Ok(())
     }
@@ -224,16 +307,16 @@ impl FqxSqlPool for PoolPostgres {
         self.0.is_closed()
     }
 
-    async fn acquire(&self) -> Result<Self::PoolConnection> {
+    async fn acquire(&self) -> FqxResult<Self::PoolConnection> {
         Ok(PoolConnectionPostgres(self.0.acquire().await?))
     }
 
-    async fn execute(&self, sql: &str) -> Result<()> {
+    async fn execute(&self, sql: &str) -> FqxResult<()> {
         sqlx::query(sql).execute(&self.0).await?;
         Ok(())
     }
 
-    async fn fetch<R>(&self, sql: &str) -> Result<Vec<R>>
+    async fn fetch<R>(&self, sql: &str) -> FqxResult<Vec<R>>
     where
         R: FqxSqlRow,
     {
@@ -251,21 +334,23 @@ impl FqxSqlPool for PoolSqlite {
         Driver::SQLITE
     }
 
+    /// `db` is the database file path, the others are ignored
     async fn new(
         _host: &str,
         _port: Option<u16>,
         _user: &str,
         _pass: &str,
-        _db: &str,
-    ) -> Result<Self> {
-        panic!("PoolSqlite does not have `new` method!")
+        db: &str,
+    ) -> FqxResult<Self> {
+        Self::new_file(db, false).await
     }
 
-    async fn new_by_str(url: &str) -> Result<Self> {
-        Ok(Self(sqlx::Pool::<Sqlite>::connect(url).await?))
+    async fn new_with_options(url: &str, options: &ConnectorOptions) -> FqxResult<Self> {
+        options.validate(&Driver::SQLITE)?;
+        options.retry(|| Self::connect(url, options)).await
     }
 
-    async fn close(&self) -> Result<()> {
+    async fn close(&self) -> FqxResult<()> {
         self.0.close().await;
         Ok(())
     }
@@ -274,16 +359,16 @@ impl FqxSqlPool for PoolSqlite {
         self.0.is_closed()
     }
 
-    async fn acquire(&self) -> Result<Self::PoolConnection> {
+    async fn acquire(&self) -> FqxResult<Self::PoolConnection> {
         Ok(PoolConnectionSqlite(self.0.acquire().await?))
     }
 
-    async fn execute(&self, sql: &str) -> Result<()> {
+    async fn execute(&self, sql: &str) -> FqxResult<()> {
         sqlx::query(sql).execute(&self.0).await?;
         Ok(())
     }
 
-    async fn fetch<R>(&self, sql: &str) -> Result<Vec<R>>
+    async fn fetch<R>(&self, sql: &str) -> FqxResult<Vec<R>>
     where
         R: FqxSqlRow,
     {
@@ -3,6 +3,7 @@
 //! date: 2023/09/16 10:32:32 Saturday
 //! brief:
 
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -15,13 +16,22 @@ pub(crate) const POSTGRES: &str = "postgresql";
 pub(crate) const MSSQL: &str = "mssql";
 pub(crate) const SQLITE: &str = "sqlite";
 
+/// Everything but the unreserved characters of RFC 3986, for the user and password of a URL.
+pub(crate) const USERINFO: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
 // ================================================================================================
 // ConnectorConfig
 // ================================================================================================
 
+/// Missing fields default to empty when deserialized, e.g. Sqlite only needs `db`.
 #[pyclass]
 #[pyo3(name = "FqxSqlConnectorConfig")]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ConnectorConfig {
     host: String,
     port: u16,
@@ -45,6 +55,35 @@ pub enum Driver {
     SQLITE,
 }
 
+#[pymethods]
+impl ConnectorConfig {
+    #[new]
+    #[pyo3(signature = (host, port, user, pswd, db, extra=None))]
+    pub fn new(
+        host: String,
+        port: u16,
+        user: String,
+        pswd: String,
+        db: String,
+        extra: Option<String>,
+    ) -> Self {
+        Self {
+            host,
+            port,
+            user,
+            pswd,
+            db,
+            extra,
+        }
+    }
+
+    /// Sqlite only reads `db`, as the database file path (`:memory:` for an in-memory database).
+    #[staticmethod]
+    pub fn sqlite(path: String) -> Self {
+        Self::new(String::new(), 0, String::new(), String::new(), path, None)
+    }
+}
+
 #[pymethods]
 impl Driver {
     pub fn to_conn_str(&self, config: ConnectorConfig) -> String {
@@ -57,13 +96,16 @@ impl Driver {
             extra,
         } = config;
 
+        let user = utf8_percent_encode(&user, USERINFO);
+        let pswd = utf8_percent_encode(&pswd, USERINFO);
         let conn_str = format!("{user}:{pswd}@{host}:{port}/{db}");
 
         let mut cs = match self {
             Driver::MYSQL => format!("mysql://{conn_str}"),
             Driver::POSTGRES => format!("postgresql://{conn_str}"),
             Driver::MSSQL => format!("mssql://{conn_str}"),
-            Driver::SQLITE => format!("sqlite://{conn_str}"),
+            Driver::SQLITE if db.is_empty() || db == ":memory:" => String::from("sqlite::memory:"),
+            Driver::SQLITE => format!("sqlite://{db}"),
         };
         if let Some(ref e) = extra {
             cs.push_str(e);
@@ -0,0 +1,444 @@
+//! file: bulk.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 19:14:52 Saturday
+//! brief:
+
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use chrono::{NaiveDate, NaiveDateTime, Timelike};
+use rust_decimal::Decimal;
+use tiberius::numeric::Numeric;
+use tiberius::{ColumnData, IntoSql, TokenRow};
+
+use super::dynm::{dyn_insert_statement, dyn_upsert_statement};
+use super::param::{bind_sqlite, QuerySqlite};
+use crate::adt::dat::arrow::{decimal_to_i128, DECIMAL_SCALE};
+use crate::adt::*;
+use crate::error::{FqxError, FqxResult};
+use crate::lazy::sql::{_quote, quote_ident};
+use crate::sources::sql::*;
+use crate::sources::SaveMode;
+
+// ================================================================================================
+// Const
+// ================================================================================================
+
+/// MsSql accepts at most 1000 rows in a single `VALUES` clause
+pub const DEFAULT_INSERT_BATCH_SIZE: usize = 1000;
+
+/// Sqlite's default `SQLITE_MAX_VARIABLE_NUMBER` (since 3.32.0)
+const SQLITE_MAX_VARIABLES: usize = 32766;
+
+/// flush size of the buffer sent by Postgres' `COPY`
+const PG_COPY_BUFFER_SIZE: usize = 1 << 20;
+
+// ================================================================================================
+// InsertStrategy
+// ================================================================================================
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InsertStrategy {
+    /// `Bulk` for Postgres and Sqlite, and for MsSql when the table is always created by the save
+    /// (`SaveMode::Override` or `SaveMode::ErrorIfExists`, bulk loading requires exact column types);
+    /// `Batch(DEFAULT_INSERT_BATCH_SIZE)` otherwise
+    #[default]
+    Auto,
+    /// plain multi-row `INSERT` statements, each one has at most `n` rows
+    Batch(usize),
+    /// Postgres `COPY FROM STDIN`, MsSql bulk load and multi-row prepared statements for Sqlite;
+    /// MySql has no such path, neither does `SaveMode::Upsert`, both fall back to
+    /// `Batch(DEFAULT_INSERT_BATCH_SIZE)`
+    Bulk,
+}
+
+impl InsertStrategy {
+    pub(crate) fn resolve(self, driver: &Driver, mode: &SaveMode) -> FqxResult<Self> {
+        let res = match (self, driver) {
+            (InsertStrategy::Batch(0), _) => {
+                return Err(FqxError::invalid("batch size must be greater than 0"))
+            }
+            (InsertStrategy::Batch(n), _) => InsertStrategy::Batch(n),
+            // neither `COPY` nor bulk load can update existing rows
+            _ if matches!(mode, SaveMode::Upsert { .. }) => {
+                InsertStrategy::Batch(DEFAULT_INSERT_BATCH_SIZE)
+            }
+            (_, Driver::MYSQL) => InsertStrategy::Batch(DEFAULT_INSERT_BATCH_SIZE),
+            (InsertStrategy::Auto, Driver::MSSQL)
+                if !matches!(mode, SaveMode::Override | SaveMode::ErrorIfExists) =>
+            {
+                InsertStrategy::Batch(DEFAULT_INSERT_BATCH_SIZE)
+            }
+            _ => InsertStrategy::Bulk,
+        };
+
+        Ok(res)
+    }
+}
+
+// ================================================================================================
+// Postgres: COPY FROM STDIN (csv)
+// ================================================================================================
+
+fn _pg_copy_statement(data: &FqxData, table_name: &str) -> String {
+    let columns = data
+        .columns
+        .iter()
+        .map(|c| _quote(&Driver::POSTGRES, c))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        "COPY {} ({}) FROM STDIN WITH (FORMAT csv)",
+        quote_ident(&Driver::POSTGRES, table_name),
+        columns
+    )
+}
+
+fn _pg_csv_value(buf: &mut String, value: &FqxValue) -> FqxResult<()> {
+    match value {
+        // an unquoted empty field is `NULL`, a quoted one is an empty string
+        FqxValue::Null => {}
+        FqxValue::String(v) => write!(buf, "\"{}\"", v.replace('"', "\"\""))?,
+        FqxValue::Blob(v) => {
+            buf.push_str("\\x");
+            for b in v.iter() {
+                write!(buf, "{:02x}", b)?;
+            }
+        }
+        FqxValue::Timestamp(v) => buf.push_str(&v.to_rfc3339()),
+        FqxValue::DateTime(v) => write!(buf, "{}", v.format("%Y-%m-%d %H:%M:%S%.f"))?,
+        v => buf.push_str(&v.to_string()),
+    }
+
+    Ok(())
+}
+
+fn _pg_csv_row(buf: &mut String, row: &FqxRow) -> FqxResult<()> {
+    for (idx, v) in row.0.iter().enumerate() {
+        if idx > 0 {
+            buf.push(',');
+        }
+        _pg_csv_value(buf, v)?;
+    }
+    buf.push('\n');
+
+    Ok(())
+}
+
+// ================================================================================================
+// MsSql: bulk load
+//
+// Values are converted into the column types generated by `create_tiberius_table`.
+// ================================================================================================
+
+fn _tiberius_datetime(v: NaiveDateTime) -> tiberius::time::DateTime {
+    // days since 1900-01-01, and 1/300 of a second
+    let epoch = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
+    let days = (v.date() - epoch).num_days() as i32;
+    let time = v.time();
+    let fragments = time.num_seconds_from_midnight() as u64 * 300
+        + (time.nanosecond() as u64 * 300).div_ceil(1_000_000_000);
+
+    tiberius::time::DateTime::new(days, fragments as u32)
+}
+
+fn _tiberius_null(typ: &FqxValueType) -> ColumnData<'static> {
+    match typ {
+        FqxValueType::Bool => ColumnData::Bit(None),
+        FqxValueType::U8 => ColumnData::U8(None),
+        FqxValueType::U16 | FqxValueType::I8 | FqxValueType::I16 => ColumnData::I16(None),
+        FqxValueType::U32 | FqxValueType::I32 => ColumnData::I32(None),
+        FqxValueType::U64 | FqxValueType::I64 => ColumnData::I64(None),
+        FqxValueType::F32 => ColumnData::F32(None),
+        FqxValueType::F64 => ColumnData::F64(None),
+        FqxValueType::Decimal => ColumnData::Numeric(None),
+        FqxValueType::String | FqxValueType::Null => ColumnData::String(None),
+        FqxValueType::Blob => ColumnData::Binary(None),
+        FqxValueType::Timestamp => ColumnData::DateTimeOffset(None),
+        FqxValueType::DateTime => ColumnData::DateTime(None),
+        FqxValueType::Date => ColumnData::Date(None),
+        FqxValueType::Time => ColumnData::Time(None),
+    }
+}
+
+fn _tiberius_column(value: FqxValue, typ: &FqxValueType) -> FqxResult<ColumnData<'static>> {
+    if value.is_null() {
+        return Ok(_tiberius_null(typ));
+    }
+
+    let res = match typ {
+        FqxValueType::Bool => ColumnData::Bit(Some(bool::try_from(value)?)),
+        FqxValueType::U8 => ColumnData::U8(Some(u8::try_from(value)?)),
+        FqxValueType::U16 => ColumnData::I16(Some(i16::try_from(u16::try_from(value)?)?)),
+        FqxValueType::U32 => ColumnData::I32(Some(i32::try_from(u32::try_from(value)?)?)),
+        FqxValueType::U64 => ColumnData::I64(Some(i64::try_from(u64::try_from(value)?)?)),
+        FqxValueType::I8 => ColumnData::I16(Some(i8::try_from(value)?.into())),
+        FqxValueType::I16 => ColumnData::I16(Some(i16::try_from(value)?)),
+        FqxValueType::I32 => ColumnData::I32(Some(i32::try_from(value)?)),
+        FqxValueType::I64 => ColumnData::I64(Some(i64::try_from(value)?)),
+        FqxValueType::F32 => ColumnData::F32(Some(f32::try_from(value)?)),
+        FqxValueType::F64 => ColumnData::F64(Some(f64::try_from(value)?)),
+        FqxValueType::Decimal => {
+            let m = decimal_to_i128(Decimal::try_from(value)?)?;
+            ColumnData::Numeric(Some(Numeric::new_with_scale(m, DECIMAL_SCALE as u8)))
+        }
+        FqxValueType::String | FqxValueType::Null => {
+            ColumnData::String(Some(Cow::Owned(String::try_from(value)?)))
+        }
+        FqxValueType::Blob => ColumnData::Binary(Some(Cow::Owned(Vec::<u8>::try_from(value)?))),
+        FqxValueType::Timestamp => {
+            let v: chrono::DateTime<chrono::Local> = value.try_into()?;
+            v.fixed_offset().into_sql()
+        }
+        FqxValueType::DateTime => ColumnData::DateTime(Some(_tiberius_datetime(value.try_into()?))),
+        FqxValueType::Date => NaiveDate::try_from(value)?.into_sql(),
+        FqxValueType::Time => chrono::NaiveTime::try_from(value)?.into_sql(),
+    };
+
+    Ok(res)
+}
+
+// ================================================================================================
+// Sqlite: multi-row prepared statements
+// ================================================================================================
+
+fn _sqlite_insert_statement(data: &FqxData, table_name: &str, rows: usize) -> String {
+    let columns = data
+        .columns
+        .iter()
+        .map(|c| _quote(&Driver::SQLITE, c))
+        .collect::<Vec<_>>()
+        .join(",");
+    let row = format!("({})", vec!["?"; data.columns.len()].join(","));
+    let values = vec![row; rows].join(",");
+
+    format!(
+        "INSERT INTO {} ({}) VALUES {}",
+        quote_ident(&Driver::SQLITE, table_name),
+        columns,
+        values
+    )
+}
+
+// ================================================================================================
+// FqxTransaction
+// ================================================================================================
+
+impl FqxTransaction {
+    pub(crate) async fn dyn_insert(
+        &mut self,
+        data: FqxData,
+        table_name: &str,
+        mode: &SaveMode,
+        strategy: InsertStrategy,
+    ) -> FqxResult<()> {
+        if data.data.is_empty() {
+            return Ok(());
+        }
+
+        match (strategy, self) {
+            (InsertStrategy::Batch(n), tx) => {
+                let driver = tx.driver();
+                let FqxData {
+                    columns,
+                    types,
+                    data,
+                } = data;
+
+                for chunk in data.chunks(n) {
+                    let d = FqxData {
+                        columns: columns.clone(),
+                        types: types.clone(),
+                        data: chunk.to_vec(),
+                    };
+                    let sql = match mode {
+                        SaveMode::Upsert { keys } => {
+                            dyn_upsert_statement(&driver, d, table_name, keys)?
+                        }
+                        _ => dyn_insert_statement(&driver, d, table_name)?,
+                    };
+                    tx.execute(&sql).await?;
+                }
+            }
+            (_, FqxTransaction::P(tx)) => {
+                let mut copy = tx
+                    .copy_in_raw(&_pg_copy_statement(&data, table_name))
+                    .await?;
+                let mut buf = String::with_capacity(PG_COPY_BUFFER_SIZE);
+
+                for row in data.data.iter() {
+                    _pg_csv_row(&mut buf, row)?;
+                    if buf.len() >= PG_COPY_BUFFER_SIZE {
+                        copy.send(std::mem::take(&mut buf).into_bytes()).await?;
+                    }
+                }
+                if !buf.is_empty() {
+                    copy.send(buf.into_bytes()).await?;
+                }
+
+                copy.finish().await?;
+            }
+            (_, FqxTransaction::Q(tx)) => {
+                let mut req = tx.conn().bulk_insert(table_name).await?;
+
+                let FqxData { types, data, .. } = data;
+                for row in data.into_iter() {
+                    let mut tr = TokenRow::new();
+                    for (v, t) in row.0.into_iter().zip(types.iter()) {
+                        tr.push(_tiberius_column(v, t)?);
+                    }
+                    req.send(tr).await?;
+                }
+
+                req.finalize().await?;
+            }
+            (_, FqxTransaction::S(tx)) => {
+                let width = data.columns.len().max(1);
+                let rows_per_sttm =
+                    (SQLITE_MAX_VARIABLES / width).clamp(1, DEFAULT_INSERT_BATCH_SIZE);
+
+                for chunk in data.data.chunks(rows_per_sttm) {
+                    // statements of the same size are prepared once and cached by sqlx
+                    let sql = _sqlite_insert_statement(&data, table_name, chunk.len());
+                    let query: QuerySqlite<'_> = chunk
+                        .iter()
+                        .flat_map(|r| r.0.iter())
+                        .try_fold(sqlx::query(&sql), bind_sqlite)?;
+
+                    query.execute(&mut **tx).await?;
+                }
+            }
+            (_, FqxTransaction::M(_)) => unreachable!("MySql is resolved to `Batch`"),
+        }
+
+        Ok(())
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_bulk {
+    use super::*;
+    use crate::fqx;
+    use crate::sources::sql::mock::sqlite_conn;
+
+    #[test]
+    fn pg_csv_success() {
+        let mut buf = String::new();
+        let row = FqxRow(vec![
+            fqx!(1),
+            fqx!("a \"quoted\", text"),
+            fqx!(),
+            FqxValue::Blob(vec![0xde, 0xad]),
+            fqx!(""),
+        ]);
+        _pg_csv_row(&mut buf, &row).unwrap();
+
+        assert_eq!(buf, "1,\"a \"\"quoted\"\", text\",,\\xdead,\"\"\n");
+    }
+
+    #[test]
+    fn pg_copy_statement_success() {
+        let data = FqxData::new(
+            ["id", "a.b"],
+            [FqxValueType::I32, FqxValueType::I32],
+            vec![vec![fqx!(1), fqx!(2)]],
+        )
+        .unwrap();
+
+        assert_eq!(
+            _pg_copy_statement(&data, "public.MyTable"),
+            r#"COPY "public"."MyTable" ("id","a.b") FROM STDIN WITH (FORMAT csv)"#
+        );
+    }
+
+    #[test]
+    fn resolve_strategy_success() {
+        let s = InsertStrategy::Auto;
+        assert_eq!(
+            s.resolve(&Driver::MYSQL, &SaveMode::Override).unwrap(),
+            InsertStrategy::Batch(DEFAULT_INSERT_BATCH_SIZE)
+        );
+        assert_eq!(
+            s.resolve(&Driver::MSSQL, &SaveMode::Append).unwrap(),
+            InsertStrategy::Batch(DEFAULT_INSERT_BATCH_SIZE)
+        );
+        assert_eq!(
+            s.resolve(&Driver::MSSQL, &SaveMode::Override).unwrap(),
+            InsertStrategy::Bulk
+        );
+        let upsert = SaveMode::Upsert {
+            keys: vec!["id".to_string()],
+        };
+        assert_eq!(
+            InsertStrategy::Bulk
+                .resolve(&Driver::POSTGRES, &upsert)
+                .unwrap(),
+            InsertStrategy::Batch(DEFAULT_INSERT_BATCH_SIZE)
+        );
+        assert!(InsertStrategy::Batch(0)
+            .resolve(&Driver::SQLITE, &SaveMode::Append)
+            .is_err());
+    }
+
+    #[test]
+    fn tiberius_column_success() {
+        let typ = FqxValueType::I8;
+        for v in [i8::MIN, -1, 0, i8::MAX] {
+            assert_eq!(
+                _tiberius_column(FqxValue::I8(v), &typ).unwrap(),
+                ColumnData::I16(Some(v as i16))
+            );
+        }
+        assert_eq!(
+            _tiberius_column(FqxValue::Null, &typ).unwrap(),
+            ColumnData::I16(None)
+        );
+        assert_eq!(
+            _tiberius_column(FqxValue::U8(255), &FqxValueType::U8).unwrap(),
+            ColumnData::U8(Some(255))
+        );
+
+        let typ = FqxValueType::Decimal;
+        assert_eq!(
+            _tiberius_column(FqxValue::Decimal(Decimal::new(-12345, 2)), &typ).unwrap(),
+            ColumnData::Numeric(Some(Numeric::new_with_scale(-1_234_500_000_000, 10)))
+        );
+        // more than 96 bits at scale 10
+        let too_large = FqxValue::Decimal(Decimal::from(9_000_000_000_000_000_000u64));
+        assert!(matches!(
+            _tiberius_column(too_large, &typ),
+            Err(FqxError::Cast { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn dyn_save_strategy_success() {
+        let conn = sqlite_conn("fqx_bulk.db").await;
+        let data = FqxData::new(
+            ["id", "name", "v"],
+            [FqxValueType::I32, FqxValueType::String, FqxValueType::F64],
+            (0..2500)
+                .map(|i| vec![fqx!(i), fqx!(format!("n{i}")), fqx!(i as f64 / 2.0)])
+                .collect(),
+        )
+        .unwrap();
+
+        for strategy in [
+            InsertStrategy::Auto,
+            InsertStrategy::Batch(7),
+            InsertStrategy::Bulk,
+        ] {
+            conn.dyn_save_with(data.clone(), "t", SaveMode::Override, false, strategy)
+                .await
+                .unwrap();
+
+            let res = conn.dyn_fetch("select * from t").await.unwrap();
+            assert_eq!(res.data(), data.data());
+        }
+    }
+}
@@ -3,13 +3,16 @@
 //! date: 2023/09/09 18:51:43 Saturday
 //! brief:
 
-use anyhow::{bail, Result};
+use std::path::Path;
+
 use pyo3::pyclass;
 
 use super::ab::{gen_sqlx_str, FqxSqlPool, FqxSqlRow};
 use super::adt::*;
+use super::options::ConnectorOptions;
 use super::sqx::*;
 use super::tbr::*;
+use crate::error::{FqxError, FqxResult};
 use crate::sources::SaveMode;
 
 // ================================================================================================
@@ -112,7 +115,7 @@ impl SqlConnector {
         user: &str,
         pass: &str,
         db: &str,
-    ) -> Result<Self> {
+    ) -> FqxResult<Self> {
         let (p, driver_str, driver, pt) = match driver {
             Driver::MYSQL => (
                 FqxPool::M(PoolMySql::new(host, port, user, pass, db).await?),
@@ -132,7 +135,7 @@ impl SqlConnector {
                 Driver::MSSQL,
                 port.unwrap_or(1433),
             ),
-            Driver::SQLITE => panic!("PoolSqlite does not have `new` method!"),
+            Driver::SQLITE => return Self::sqlite_file(db, false).await,
         };
 
         let conn_str = gen_sqlx_str(driver_str, host, pt, user, pass, db);
@@ -144,27 +147,36 @@ impl SqlConnector {
         })
     }
 
-    pub async fn new_by_str<S: Into<String>>(conn_str: S) -> Result<Self> {
+    pub async fn new_by_str<S: Into<String>>(conn_str: S) -> FqxResult<Self> {
+        Self::new_with_options(conn_str, &ConnectorOptions::default()).await
+    }
+
+    pub async fn new_with_options<S: Into<String>>(
+        conn_str: S,
+        options: &ConnectorOptions,
+    ) -> FqxResult<Self> {
         let conn_str = conn_str.into();
-        let (db, driver) = match &conn_str.split_once("://") {
+        // `sqlite::memory:` has no `//`
+        let (db, driver) = match &conn_str.split_once(':') {
             Some((MYSQL, _)) => (
-                FqxPool::M(PoolMySql::new_by_str(&conn_str).await?),
+                FqxPool::M(PoolMySql::new_with_options(&conn_str, options).await?),
                 Driver::MYSQL,
             ),
             Some((POSTGRES, _)) => (
-                FqxPool::P(PoolPostgres::new_by_str(&conn_str).await?),
+                FqxPool::P(PoolPostgres::new_with_options(&conn_str, options).await?),
                 Driver::POSTGRES,
             ),
             Some((SQLITE, _)) => (
-                FqxPool::S(PoolSqlite::new_by_str(&conn_str).await?),
+                FqxPool::S(PoolSqlite::new_with_options(&conn_str, options).await?),
                 Driver::SQLITE,
             ),
             Some((MSSQL, _)) => (
-                FqxPool::Q(PoolMsSql::new_by_str(&conn_str).await?),
+                FqxPool::Q(PoolMsSql::new_with_options(&conn_str, options).await?),
                 Driver::MSSQL,
             ),
             _ => {
-                bail!("driver not found, check your connect string: {}", &conn_str)
+                let msg = format!("driver not found, check your connect string: {conn_str}");
+                return Err(FqxError::invalid(msg));
             }
         };
 
@@ -175,6 +187,31 @@ impl SqlConnector {
         })
     }
 
+    /// `SqlConnector::new` with `Driver::SQLITE` is `sqlite_file(db, false)`, the others are ignored.
+    pub async fn sqlite_file<P: AsRef<Path>>(path: P, create_if_missing: bool) -> FqxResult<Self> {
+        let path = path.as_ref();
+        let mut conn_str = format!("sqlite://{}", path.display());
+        if create_if_missing {
+            conn_str.push_str("?mode=rwc");
+        }
+
+        Ok(Self {
+            driver: Driver::SQLITE,
+            conn_str,
+            db: FqxPool::S(PoolSqlite::new_file(path, create_if_missing).await?),
+        })
+    }
+
+    /// A private in-memory database, dropped along with the last clone of the connector. Its pool
+    /// holds a single connection, so don't fetch from the connector while holding a transaction.
+    pub async fn sqlite_memory() -> FqxResult<Self> {
+        Ok(Self {
+            driver: Driver::SQLITE,
+            conn_str: String::from("sqlite::memory:"),
+            db: FqxPool::S(PoolSqlite::new_memory().await?),
+        })
+    }
+
     pub fn driver(&self) -> &Driver {
         &self.driver
     }
@@ -187,7 +224,7 @@ impl SqlConnector {
         &self.db
     }
 
-    pub async fn acquire(&self) -> Result<FqxPoolConnection> {
+    pub async fn acquire(&self) -> FqxResult<FqxPoolConnection> {
         match self.db() {
             FqxPool::M(p) => {
                 let c = p.acquire().await?;
@@ -208,7 +245,7 @@ impl SqlConnector {
         }
     }
 
-    pub async fn close(&self) -> Result<&Self> {
+    pub async fn close(&self) -> FqxResult<&Self> {
         match self.db() {
             FqxPool::M(p) => p.close().await?,
             FqxPool::P(p) => p.close().await?,
@@ -228,7 +265,7 @@ impl SqlConnector {
         }
     }
 
-    pub async fn execute(&self, sql: &str) -> Result<()> {
+    pub async fn execute(&self, sql: &str) -> FqxResult<()> {
         match self.db() {
             FqxPool::M(p) => {
                 p.execute(sql).await?;
@@ -247,7 +284,7 @@ impl SqlConnector {
         Ok(())
     }
 
-    pub async fn fetch<R>(&self, sql: &str) -> Result<Vec<R>>
+    pub async fn fetch<R>(&self, sql: &str) -> FqxResult<Vec<R>>
     where
         R: FqxSqlRow,
     {
@@ -261,15 +298,17 @@ impl SqlConnector {
         Ok(res)
     }
 
-    pub async fn save<R>(&self, data: Vec<R>, mode: SaveMode) -> Result<()>
+    pub async fn save<R>(&self, data: Vec<R>, mode: SaveMode) -> FqxResult<()>
     where
         R: FqxSqlRow,
     {
+        let exists = mode.check_existence() && self.table_exists(R::table_name()).await?;
+
         match &self.db {
-            FqxPool::M(p) => p.save(data, mode).await,
-            FqxPool::P(p) => p.save(data, mode).await,
-            FqxPool::S(p) => p.save(data, mode).await,
-            FqxPool::Q(p) => p.save(data, mode).await,
+            FqxPool::M(p) => p.save(data, mode, exists).await,
+            FqxPool::P(p) => p.save(data, mode, exists).await,
+            FqxPool::S(p) => p.save(data, mode, exists).await,
+            FqxPool::Q(p) => p.save(data, mode, exists).await,
         }
     }
 }
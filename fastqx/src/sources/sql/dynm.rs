@@ -3,12 +3,22 @@
 //! date: 2023/09/13 15:23:06 Wednesday
 //! brief:
 
-use anyhow::Result;
-use futures::TryStreamExt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::channel::{mpsc, oneshot};
+use futures::stream::BoxStream;
+use futures::{SinkExt, Stream, StreamExt, TryStreamExt};
 use sea_query::*;
+use tiberius::QueryStream;
 
+use super::bulk::InsertStrategy;
+use super::param::*;
 use super::rowprocess::FqxSqlRowProcessor;
 use crate::adt::*;
+use crate::constant::DECIMAL_COLUMN_DEF;
+use crate::error::{FqxError, FqxResult};
+use crate::lazy::sql::quote_ident;
 use crate::sources::sql::tbr::sqlbuild as mssql_sqlbuild;
 use crate::sources::sql::*;
 use crate::sources::SaveMode;
@@ -58,6 +68,10 @@ impl FqxData {
                 FqxValueType::F64 => {
                     table.col(cd.double());
                 }
+                FqxValueType::Decimal => {
+                    // sea-query's `decimal` is rendered as `real` in Sqlite, use an explicit definition
+                    table.col(cd.custom(Alias::new(DECIMAL_COLUMN_DEF)));
+                }
                 FqxValueType::String => {
                     table.col(cd.string());
                 }
@@ -85,19 +99,22 @@ impl FqxData {
         table.to_owned()
     }
 
-    fn create_tiberius_table(&self, table_name: &str) -> Result<String> {
+    fn create_tiberius_table(&self, table_name: &str) -> FqxResult<String> {
         mssql_sqlbuild::create_table(&self, table_name)
     }
 
     fn drop_sqlx_table(&self, table_name: &str) -> TableDropStatement {
-        Table::drop().table(Alias::new(table_name)).to_owned()
+        Table::drop()
+            .table(Alias::new(table_name))
+            .if_exists()
+            .to_owned()
     }
 
     fn drop_tiberius_table(&self, table_name: &str) -> String {
         mssql_sqlbuild::drop_table(table_name)
     }
 
-    fn sqlx_insert(self, table_name: &str) -> Result<InsertStatement> {
+    fn sqlx_insert(self, table_name: &str) -> FqxResult<InsertStatement> {
         let mut query = Query::insert();
         let columns = self
             .columns
@@ -122,6 +139,7 @@ impl FqxData {
                         FqxValue::I64(v) => v.into(),
                         FqxValue::F32(v) => v.into(),
                         FqxValue::F64(v) => v.into(),
+                        FqxValue::Decimal(v) => v.into(),
                         FqxValue::String(v) => v.into(),
                         FqxValue::Blob(v) => v.into(),
                         FqxValue::Timestamp(v) => v.into(),
@@ -137,9 +155,197 @@ impl FqxData {
         Ok(query.to_owned())
     }
 
-    fn tiberuis_insert(self, table_name: &str) -> Result<String> {
+    fn tiberuis_insert(self, table_name: &str) -> FqxResult<String> {
         Ok(mssql_sqlbuild::insert(self, table_name))
     }
+
+    fn sqlx_upsert(self, table_name: &str, keys: &[String]) -> FqxResult<InsertStatement> {
+        let on_conflict = on_conflict(&self.columns, keys)?;
+        let mut query = self.sqlx_insert(table_name)?;
+        query.on_conflict(on_conflict);
+
+        Ok(query)
+    }
+
+    fn tiberius_upsert(self, table_name: &str, keys: &[String]) -> FqxResult<String> {
+        mssql_sqlbuild::upsert(self, table_name, keys)
+    }
+}
+
+// ================================================================================================
+// FqxRowStream
+//
+// A stream of `FqxRow` whose columns and types are resolved by the first row, before any row is
+// polled. An empty result set has no columns.
+// ================================================================================================
+
+/// capacity of the channel which buffers rows from the MsSql fetching task
+const MSSQL_STREAM_BUFFER: usize = 1024;
+
+type FqxSqlHeader = (Vec<String>, Vec<FqxValueType>);
+
+pub struct FqxRowStream<'a> {
+    pub(crate) columns: Vec<String>,
+    pub(crate) types: Vec<FqxValueType>,
+    pub(crate) stream: BoxStream<'a, FqxResult<FqxRow>>,
+}
+
+impl<'a> FqxRowStream<'a> {
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    pub fn types(&self) -> &[FqxValueType] {
+        &self.types
+    }
+
+    /// Group rows into `FqxData` of at most `chunk_size` rows, all sharing the same columns and
+    /// types.
+    pub fn into_chunks(self, chunk_size: usize) -> FqxResult<BoxStream<'a, FqxResult<FqxData>>> {
+        if chunk_size == 0 {
+            return Err(FqxError::invalid("chunk_size must be greater than 0"));
+        }
+
+        let FqxRowStream {
+            columns,
+            types,
+            stream,
+        } = self;
+
+        let chunks = stream
+            .try_chunks(chunk_size)
+            .map_err(|e| e.1)
+            .map_ok(move |data| FqxData {
+                columns: columns.clone(),
+                types: types.clone(),
+                data,
+            });
+
+        Ok(chunks.boxed())
+    }
+
+    pub async fn collect_data(self) -> FqxResult<FqxData> {
+        let FqxRowStream {
+            columns,
+            types,
+            stream,
+        } = self;
+
+        Ok(FqxData {
+            columns,
+            types,
+            data: stream.try_collect().await?,
+        })
+    }
+}
+
+impl<'a> Stream for FqxRowStream<'a> {
+    type Item = FqxResult<FqxRow>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.poll_next_unpin(cx)
+    }
+}
+
+/// the first row is processed eagerly, so that the processor has cached columns and types
+macro_rules! sqlx_row_stream {
+    ($executor:expr, $query:expr) => {{
+        use futures::{StreamExt, TryStreamExt};
+
+        let mut proc = $crate::sources::sql::rowprocess::FqxSqlRowProcessor::new();
+        let mut rows = $query.fetch($executor);
+
+        let first = match rows.try_next().await? {
+            Some(r) => Some(proc.process_sqlx_row(r)?),
+            None => None,
+        };
+        let columns = proc.columns().unwrap_or_default();
+        let types = proc.types().unwrap_or_default();
+
+        let rest = rows.map(move |r| Ok(proc.process_sqlx_row(r?)?));
+
+        $crate::sources::sql::dynm::FqxRowStream {
+            columns,
+            types,
+            stream: futures::stream::iter(first.map(Ok)).chain(rest).boxed(),
+        }
+    }};
+}
+
+pub(crate) use sqlx_row_stream;
+
+pub(crate) async fn collect_tiberius_rows(stream: QueryStream<'_>) -> FqxResult<FqxData> {
+    let mut rows = stream.into_row_stream();
+    let mut proc = FqxSqlRowProcessor::new();
+    let mut data = vec![];
+
+    while let Some(row) = rows.try_next().await? {
+        data.push(proc.process_tiberius_row(row)?);
+    }
+
+    Ok(FqxData {
+        columns: proc.columns().unwrap_or_default(),
+        types: proc.types().unwrap_or_default(),
+        data,
+    })
+}
+
+fn _tiberius_row_stream(
+    pool: PoolMsSql,
+    sql: String,
+) -> (
+    oneshot::Receiver<FqxResult<FqxSqlHeader>>,
+    mpsc::Receiver<FqxResult<FqxRow>>,
+) {
+    // `tiberius`' row stream borrows the connection, hence rows are pulled by a spawned task and
+    // passed through a bounded channel, which keeps the memory usage bounded as well
+    let (header_tx, header_rx) = oneshot::channel();
+    let (mut tx, rx) = mpsc::channel(MSSQL_STREAM_BUFFER);
+
+    tokio::spawn(async move {
+        let mut header_tx = Some(header_tx);
+
+        let res: FqxResult<()> = async {
+            let mut conn = pool.0.get_owned().await?;
+            let mut rows = conn.simple_query(sql).await?.into_row_stream();
+            let mut proc = FqxSqlRowProcessor::new();
+
+            while let Some(row) = rows.try_next().await? {
+                let row = proc.process_tiberius_row(row)?;
+
+                if let Some(h) = header_tx.take() {
+                    let header = (
+                        proc.columns().unwrap_or_default(),
+                        proc.types().unwrap_or_default(),
+                    );
+                    let _ = h.send(Ok(header));
+                }
+
+                // the receiver has been dropped
+                if tx.send(Ok(row)).await.is_err() {
+                    return Ok(());
+                }
+            }
+
+            Ok(())
+        }
+        .await;
+
+        match (header_tx.take(), res) {
+            (Some(h), Ok(())) => {
+                let _ = h.send(Ok((vec![], vec![])));
+            }
+            (Some(h), Err(e)) => {
+                let _ = h.send(Err(e));
+            }
+            (None, Err(e)) => {
+                let _ = tx.send(Err(e)).await;
+            }
+            (None, Ok(())) => {}
+        }
+    });
+
+    (header_rx, rx)
 }
 
 // ================================================================================================
@@ -147,122 +353,519 @@ impl FqxData {
 // ================================================================================================
 
 impl SqlConnector {
-    pub async fn dyn_fetch(&self, sql: &str) -> Result<FqxData> {
-        let mut proc = FqxSqlRowProcessor::new();
+    pub async fn dyn_fetch(&self, sql: &str) -> FqxResult<FqxData> {
+        self.dyn_fetch_stream(sql).await?.collect_data().await
+    }
 
-        let data = match self.db() {
-            FqxPool::M(p) => {
-                let stream = sqlx::query(sql)
-                    .try_map(|r| proc.process_sqlx_row(r))
-                    .fetch(p.as_ref());
+    /// Fetch rows lazily, the result set is never held in memory as a whole.
+    pub async fn dyn_fetch_stream<'a>(&'a self, sql: &'a str) -> FqxResult<FqxRowStream<'a>> {
+        let res = match self.db() {
+            FqxPool::M(p) => sqlx_row_stream!(p.as_ref(), sqlx::query(sql)),
+            FqxPool::P(p) => sqlx_row_stream!(p.as_ref(), sqlx::query(sql)),
+            FqxPool::S(p) => sqlx_row_stream!(p.as_ref(), sqlx::query(sql)),
+            FqxPool::Q(p) => {
+                let (header, rows) = _tiberius_row_stream(p.clone(), sql.to_string());
+                let (columns, types) = header.await??;
 
-                stream.try_collect::<Vec<_>>().await?
+                FqxRowStream {
+                    columns,
+                    types,
+                    stream: rows.boxed(),
+                }
             }
-            FqxPool::P(p) => {
-                let stream = sqlx::query(sql)
-                    .try_map(|r| proc.process_sqlx_row(r))
-                    .fetch(p.as_ref());
+        };
 
-                stream.try_collect::<Vec<_>>().await?
+        Ok(res)
+    }
+
+    /// Fetch rows lazily as `FqxData` batches of at most `chunk_size` rows.
+    pub async fn dyn_fetch_chunks<'a>(
+        &'a self,
+        sql: &'a str,
+        chunk_size: usize,
+    ) -> FqxResult<BoxStream<'a, FqxResult<FqxData>>> {
+        if chunk_size == 0 {
+            return Err(FqxError::invalid("chunk_size must be greater than 0"));
+        }
+
+        self.dyn_fetch_stream(sql).await?.into_chunks(chunk_size)
+    }
+
+    /// Fetch with bound parameters, `?` placeholders are adapted to the driver.
+    pub async fn dyn_fetch_with(&self, sql: &str, params: &[FqxValue]) -> FqxResult<FqxData> {
+        let sql = rewrite_placeholders(sql, self.driver(), params);
+
+        match self.db() {
+            FqxPool::M(p) => {
+                let query = params.iter().try_fold(sqlx::query(&sql), bind_mysql)?;
+                sqlx_row_stream!(p.as_ref(), query).collect_data().await
+            }
+            FqxPool::P(p) => {
+                let query = params.iter().try_fold(sqlx::query(&sql), bind_postgres)?;
+                sqlx_row_stream!(p.as_ref(), query).collect_data().await
             }
             FqxPool::S(p) => {
-                let stream = sqlx::query(sql)
-                    .try_map(|r| proc.process_sqlx_row(r))
-                    .fetch(p.as_ref());
-
-                stream.try_collect::<Vec<_>>().await?
+                let query = params.iter().try_fold(sqlx::query(&sql), bind_sqlite)?;
+                sqlx_row_stream!(p.as_ref(), query).collect_data().await
             }
             FqxPool::Q(p) => {
-                let mut pool = p.acquire().await?;
+                let mut query = tiberius::Query::new(sql.as_ref());
+                for param in params.iter() {
+                    bind_tiberius(&mut query, param)?;
+                }
 
-                let mut stream = pool.simple_query(sql).await?.into_row_stream();
-                let mut data = vec![];
+                let mut conn = p.0.get().await?;
+                let stream = query.query(&mut conn).await?;
 
-                while let Ok(Some(row)) = stream.try_next().await {
-                    data.push(proc.process_tiberius_row(row)?);
+                collect_tiberius_rows(stream).await
+            }
+        }
+    }
+
+    /// Execute with bound parameters, `?` placeholders are adapted to the driver.
+    pub async fn execute_with(&self, sql: &str, params: &[FqxValue]) -> FqxResult<()> {
+        let sql = rewrite_placeholders(sql, self.driver(), params);
+
+        match self.db() {
+            FqxPool::M(p) => {
+                let query = params.iter().try_fold(sqlx::query(&sql), bind_mysql)?;
+                query.execute(p.as_ref()).await?;
+            }
+            FqxPool::P(p) => {
+                let query = params.iter().try_fold(sqlx::query(&sql), bind_postgres)?;
+                query.execute(p.as_ref()).await?;
+            }
+            FqxPool::S(p) => {
+                let query = params.iter().try_fold(sqlx::query(&sql), bind_sqlite)?;
+                query.execute(p.as_ref()).await?;
+            }
+            FqxPool::Q(p) => {
+                let mut query = tiberius::Query::new(sql.as_ref());
+                for param in params.iter() {
+                    bind_tiberius(&mut query, param)?;
                 }
 
-                data
+                let mut conn = p.0.get().await?;
+                query.execute(&mut conn).await?;
             }
-        };
+        }
 
-        Ok(FqxData {
-            columns: proc.columns().unwrap_or_default(),
-            types: proc.types().unwrap_or_default(),
-            data,
-        })
+        Ok(())
     }
 
+    pub async fn table_exists(&self, table_name: &str) -> FqxResult<bool> {
+        let sql = table_exists_statement(self.driver());
+        let params = [FqxValue::String(table_name_param(
+            self.driver(),
+            table_name,
+        ))];
+
+        Ok(self.dyn_fetch_with(sql, &params).await?.height() > 0)
+    }
+
+    /// Save within a transaction, a failure leaves the table untouched. Note that MySql commits
+    /// implicitly on DDL statements, so a failed `SaveMode::Override` can still drop the table.
     pub async fn dyn_save(
         &self,
-        mut data: FqxData,
+        data: FqxData,
+        table_name: &str,
+        mode: SaveMode,
+        type_coercion: bool,
+    ) -> FqxResult<()> {
+        self.dyn_save_with(data, table_name, mode, type_coercion, InsertStrategy::Auto)
+            .await
+    }
+
+    /// Same as `dyn_save`, with an explicit `InsertStrategy`.
+    pub async fn dyn_save_with(
+        &self,
+        data: FqxData,
         table_name: &str,
         mode: SaveMode,
         type_coercion: bool,
-    ) -> Result<()> {
-        // make sure each row has the same type series
-        if type_coercion {
-            data.type_coercion()?;
+        strategy: InsertStrategy,
+    ) -> FqxResult<()> {
+        let mut tx = self.begin().await?;
+        tx.dyn_save_with(data, table_name, mode, type_coercion, strategy)
+            .await?;
+        tx.commit().await
+    }
+}
+
+// ================================================================================================
+// Save statements
+// ================================================================================================
+
+pub(crate) struct SaveStatements {
+    pub(crate) drop_table: Option<String>,
+    pub(crate) truncate_table: Option<String>,
+    pub(crate) create_table: Option<String>,
+}
+
+/// Existence query of a table, one row is returned if it exists. The table name is bound as the
+/// single (driver native) placeholder; Postgres and MsSql also accept a schema qualified name.
+pub(crate) fn table_exists_statement(driver: &Driver) -> &'static str {
+    match driver {
+        Driver::MYSQL => {
+            "SELECT 1 FROM information_schema.tables WHERE table_schema = DATABASE() AND table_name = ?"
         }
+        Driver::POSTGRES => "SELECT 1 WHERE to_regclass($1::text) IS NOT NULL",
+        Driver::SQLITE => "SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?",
+        Driver::MSSQL => "SELECT 1 WHERE OBJECT_ID(@P1, N'U') IS NOT NULL",
+    }
+}
 
-        match mode {
-            SaveMode::Override => {
-                let (dt, ct) = match self.db() {
-                    FqxPool::M(_) => {
-                        let drop_table = data.drop_sqlx_table(table_name);
-                        let create_table = data.create_sqlx_table(table_name);
-                        (
-                            drop_table.to_string(MysqlQueryBuilder),
-                            create_table.to_string(MysqlQueryBuilder),
-                        )
-                    }
-                    FqxPool::P(_) => {
-                        let drop_table = data.drop_sqlx_table(table_name);
-                        let create_table = data.create_sqlx_table(table_name);
-                        (
-                            drop_table.to_string(PostgresQueryBuilder),
-                            create_table.to_string(PostgresQueryBuilder),
-                        )
-                    }
-                    FqxPool::S(_) => {
-                        let drop_table = data.drop_sqlx_table(table_name);
-                        let create_table = data.create_sqlx_table(table_name);
-                        (
-                            drop_table.to_string(SqliteQueryBuilder),
-                            create_table.to_string(SqliteQueryBuilder),
-                        )
-                    }
-                    FqxPool::Q(_) => {
-                        let drop_table = data.drop_tiberius_table(table_name);
-                        let create_table = data.create_tiberius_table(table_name)?;
-                        (drop_table, create_table)
-                    }
-                };
-                let is = _dyn_insert_data(self.db(), data, table_name)?;
-
-                let _ = self.execute(&dt).await;
-                self.execute(&ct).await?;
-                self.execute(&is).await?;
-            }
-            SaveMode::Append => {
-                let is = _dyn_insert_data(self.db(), data, table_name)?;
-                self.execute(&is).await?;
+/// The table name bound to `table_exists_statement` and `describe_table`. Postgres' `to_regclass`
+/// folds unquoted names to lower case, so each part of the name is quoted to match it exactly.
+pub(crate) fn table_name_param(driver: &Driver, table_name: &str) -> String {
+    match driver {
+        Driver::POSTGRES => quote_ident(driver, table_name),
+        _ => table_name.to_string(),
+    }
+}
+
+/// Sqlite has no `TRUNCATE`, its `DELETE` without `WHERE` is optimized to the same effect.
+pub(crate) fn truncate_table_statement(driver: &Driver, table_name: &str) -> String {
+    let table_name = quote_ident(driver, table_name);
+    match driver {
+        Driver::SQLITE => format!("DELETE FROM {}", table_name),
+        _ => format!("TRUNCATE TABLE {}", table_name),
+    }
+}
+
+impl SaveMode {
+    /// whether `dyn_save_statements` depends on the table's existence
+    pub(crate) fn check_existence(&self) -> bool {
+        matches!(
+            self,
+            SaveMode::Truncate | SaveMode::CreateIfNotExists | SaveMode::ErrorIfExists
+        )
+    }
+}
+
+fn _create_table_statement(driver: &Driver, data: &FqxData, table_name: &str) -> FqxResult<String> {
+    let res = match driver {
+        Driver::MYSQL => data
+            .create_sqlx_table(table_name)
+            .to_string(MysqlQueryBuilder),
+        Driver::POSTGRES => data
+            .create_sqlx_table(table_name)
+            .to_string(PostgresQueryBuilder),
+        Driver::SQLITE => data
+            .create_sqlx_table(table_name)
+            .to_string(SqliteQueryBuilder),
+        Driver::MSSQL => data.create_tiberius_table(table_name)?,
+    };
+
+    Ok(res)
+}
+
+fn _drop_table_statement(driver: &Driver, data: &FqxData, table_name: &str) -> String {
+    match driver {
+        Driver::MYSQL => data
+            .drop_sqlx_table(table_name)
+            .to_string(MysqlQueryBuilder),
+        Driver::POSTGRES => data
+            .drop_sqlx_table(table_name)
+            .to_string(PostgresQueryBuilder),
+        Driver::SQLITE => data
+            .drop_sqlx_table(table_name)
+            .to_string(SqliteQueryBuilder),
+        Driver::MSSQL => data.drop_tiberius_table(table_name),
+    }
+}
+
+/// `exists` is only read by the modes whose `check_existence` is true.
+pub(crate) fn dyn_save_statements(
+    driver: &Driver,
+    data: &FqxData,
+    table_name: &str,
+    mode: &SaveMode,
+    exists: bool,
+) -> FqxResult<SaveStatements> {
+    let mut res = SaveStatements {
+        drop_table: None,
+        truncate_table: None,
+        create_table: None,
+    };
+
+    match mode {
+        SaveMode::Override => {
+            res.drop_table = Some(_drop_table_statement(driver, data, table_name));
+            res.create_table = Some(_create_table_statement(driver, data, table_name)?);
+        }
+        SaveMode::Truncate if exists => {
+            res.truncate_table = Some(truncate_table_statement(driver, table_name));
+        }
+        SaveMode::ErrorIfExists if exists => {
+            return Err(FqxError::invalid(format!(
+                "table {table_name} already exists"
+            )))
+        }
+        SaveMode::Truncate | SaveMode::CreateIfNotExists | SaveMode::ErrorIfExists => {
+            if !exists {
+                res.create_table = Some(_create_table_statement(driver, data, table_name)?);
             }
         }
-
-        Ok(())
+        SaveMode::Append | SaveMode::Upsert { .. } => {}
     }
+
+    Ok(res)
 }
 
-fn _dyn_insert_data(db: &FqxPool, data: FqxData, table_name: &str) -> Result<String> {
-    let res = match db {
-        FqxPool::M(_) => data.sqlx_insert(table_name)?.to_string(MysqlQueryBuilder),
-        FqxPool::P(_) => data
+pub(crate) fn dyn_insert_statement(
+    driver: &Driver,
+    data: FqxData,
+    table_name: &str,
+) -> FqxResult<String> {
+    let res = match driver {
+        Driver::MYSQL => data.sqlx_insert(table_name)?.to_string(MysqlQueryBuilder),
+        Driver::POSTGRES => data
             .sqlx_insert(table_name)?
             .to_string(PostgresQueryBuilder),
-        FqxPool::S(_) => data.sqlx_insert(table_name)?.to_string(SqliteQueryBuilder),
-        FqxPool::Q(_) => data.tiberuis_insert(table_name)?,
+        Driver::SQLITE => data.sqlx_insert(table_name)?.to_string(SqliteQueryBuilder),
+        Driver::MSSQL => data.tiberuis_insert(table_name)?,
+    };
+
+    Ok(res)
+}
+
+pub(crate) fn dyn_upsert_statement(
+    driver: &Driver,
+    data: FqxData,
+    table_name: &str,
+    keys: &[String],
+) -> FqxResult<String> {
+    let res = match driver {
+        Driver::MYSQL => data
+            .sqlx_upsert(table_name, keys)?
+            .to_string(MysqlQueryBuilder),
+        Driver::POSTGRES => data
+            .sqlx_upsert(table_name, keys)?
+            .to_string(PostgresQueryBuilder),
+        Driver::SQLITE => data
+            .sqlx_upsert(table_name, keys)?
+            .to_string(SqliteQueryBuilder),
+        Driver::MSSQL => data.tiberius_upsert(table_name, keys)?,
     };
 
     Ok(res)
 }
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_dynm {
+    use super::*;
+    use crate::fqx;
+    use crate::sources::sql::mock::sqlite_conn;
+
+    #[test]
+    fn table_name_param_success() {
+        assert_eq!(
+            table_name_param(&Driver::POSTGRES, "public.MyTable"),
+            "\"public\".\"MyTable\""
+        );
+        assert_eq!(table_name_param(&Driver::POSTGRES, "a\"b"), "\"a\"\"b\"");
+        assert_eq!(table_name_param(&Driver::SQLITE, "MyTable"), "MyTable");
+    }
+
+    #[test]
+    fn truncate_table_statement_success() {
+        assert_eq!(
+            truncate_table_statement(&Driver::POSTGRES, "public.MyTable"),
+            "TRUNCATE TABLE \"public\".\"MyTable\""
+        );
+        assert_eq!(
+            truncate_table_statement(&Driver::MSSQL, "dbo.t"),
+            "TRUNCATE TABLE [dbo].[t]"
+        );
+        assert_eq!(
+            truncate_table_statement(&Driver::SQLITE, "MyTable"),
+            "DELETE FROM \"MyTable\""
+        );
+    }
+
+    #[tokio::test]
+    async fn dyn_fetch_stream_success() {
+        let conn = sqlite_conn("fqx_dyn_fetch_stream.db").await;
+        let data = FqxData::new(
+            ["id", "name"],
+            [FqxValueType::I32, FqxValueType::String],
+            (0..10)
+                .map(|i| vec![fqx!(i), fqx!(format!("n{i}"))])
+                .collect(),
+        )
+        .unwrap();
+        conn.dyn_save(data, "t", SaveMode::Override, false)
+            .await
+            .unwrap();
+
+        let sql = "select id, name from t order by id";
+        let stream = conn.dyn_fetch_stream(sql).await.unwrap();
+        assert_eq!(stream.columns(), ["id", "name"]);
+        assert_eq!(stream.types(), [FqxValueType::I32, FqxValueType::String]);
+
+        let rows = stream.try_collect::<Vec<_>>().await.unwrap();
+        assert_eq!(rows.len(), 10);
+        assert_eq!(rows[3], FqxRow(vec![fqx!(3), fqx!("n3")]));
+
+        let chunks = conn
+            .dyn_fetch_chunks(sql, 4)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        println!("{:?}", chunks);
+        assert_eq!(
+            chunks.iter().map(|d| d.height()).collect::<Vec<_>>(),
+            [4, 4, 2]
+        );
+        assert_eq!(chunks[2].columns(), ["id", "name"]);
+
+        let empty = conn
+            .dyn_fetch("select * from t where id < 0")
+            .await
+            .unwrap();
+        assert_eq!(empty.height(), 0);
+
+        assert!(conn.dyn_fetch_chunks(sql, 0).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn dyn_fetch_with_success() {
+        let conn = sqlite_conn("fqx_dyn_fetch_with.db").await;
+        conn.execute("create table t (id integer, name text, v real)")
+            .await
+            .unwrap();
+
+        let sql = "insert into t values (?, ?, ?)";
+        conn.execute_with(sql, &[fqx!(1), fqx!("a'; drop table t; --"), fqx!(1.5)])
+            .await
+            .unwrap();
+        conn.execute_with(sql, &[fqx!(2), fqx!("b"), fqx!()])
+            .await
+            .unwrap();
+
+        let data = conn
+            .dyn_fetch_with(
+                "select * from t where id >= ? and name <> ?",
+                &[fqx!(1), fqx!("b")],
+            )
+            .await
+            .unwrap();
+        println!("{:?}", data);
+        assert_eq!(data.height(), 1);
+        assert_eq!(data.data()[0][1], fqx!("a'; drop table t; --"));
+
+        let data = conn
+            .dyn_fetch_with("select * from t where v is ?", &[fqx!()])
+            .await
+            .unwrap();
+        assert_eq!(data.data()[0][0], fqx!(2));
+    }
+
+    #[tokio::test]
+    async fn dyn_save_upsert_success() {
+        let conn = sqlite_conn("fqx_dyn_save_upsert.db").await;
+        conn.execute("create table t (id integer primary key, name text)")
+            .await
+            .unwrap();
+
+        let data = FqxData::new(
+            ["id", "name"],
+            [FqxValueType::I32, FqxValueType::String],
+            vec![vec![fqx!(1), fqx!("a")], vec![fqx!(2), fqx!("b")]],
+        )
+        .unwrap();
+        let mode = SaveMode::Upsert {
+            keys: vec!["id".to_string()],
+        };
+        conn.dyn_save(data, "t", mode.clone(), false).await.unwrap();
+
+        let data = FqxData::new(
+            ["id", "name"],
+            [FqxValueType::I32, FqxValueType::String],
+            vec![vec![fqx!(2), fqx!("c")], vec![fqx!(3), fqx!("d")]],
+        )
+        .unwrap();
+        conn.dyn_save(data.clone(), "t", mode, false).await.unwrap();
+
+        let res = conn.dyn_fetch("select * from t order by id").await.unwrap();
+        println!("{:?}", res);
+        assert_eq!(res.height(), 3);
+        assert_eq!(res.data()[1][1], fqx!("c"));
+
+        let mode = SaveMode::Upsert {
+            keys: vec!["x".to_string()],
+        };
+        assert!(conn.dyn_save(data, "t", mode, false).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn dyn_save_mode_success() {
+        let conn = sqlite_conn("fqx_dyn_save_mode.db").await;
+        let data = FqxData::new(
+            ["id", "name"],
+            [FqxValueType::I32, FqxValueType::String],
+            vec![vec![fqx!(1), fqx!("a")], vec![fqx!(2), fqx!("b")]],
+        )
+        .unwrap();
+        let count = |d: FqxData| d.height();
+
+        // create if not exists
+        assert!(!conn.table_exists("t").await.unwrap());
+        conn.dyn_save(data.clone(), "t", SaveMode::CreateIfNotExists, false)
+            .await
+            .unwrap();
+        conn.dyn_save(data.clone(), "t", SaveMode::CreateIfNotExists, false)
+            .await
+            .unwrap();
+        assert!(conn.table_exists("t").await.unwrap());
+        assert_eq!(count(conn.dyn_fetch("select * from t").await.unwrap()), 4);
+
+        // error if exists
+        let res = conn
+            .dyn_save(data.clone(), "t", SaveMode::ErrorIfExists, false)
+            .await;
+        assert!(res.is_err());
+        conn.dyn_save(data.clone(), "t2", SaveMode::ErrorIfExists, false)
+            .await
+            .unwrap();
+        assert_eq!(count(conn.dyn_fetch("select * from t2").await.unwrap()), 2);
+
+        // truncate keeps the hand-made DDL
+        conn.execute("create table t3 (id integer primary key, name text default 'x')")
+            .await
+            .unwrap();
+        conn.execute("insert into t3 (id) values (9)")
+            .await
+            .unwrap();
+        conn.dyn_save(data.clone(), "t3", SaveMode::Truncate, false)
+            .await
+            .unwrap();
+        let res = conn
+            .dyn_fetch("select * from t3 order by id")
+            .await
+            .unwrap();
+        assert_eq!(res.data(), data.data());
+        conn.execute("insert into t3 (id) values (3)")
+            .await
+            .unwrap();
+        let res = conn.dyn_fetch("select name from t3 where id = 3").await;
+        assert_eq!(res.unwrap().data()[0][0], fqx!("x"));
+        assert!(conn
+            .execute("insert into t3 (id) values (1)")
+            .await
+            .is_err());
+
+        // truncate creates a missing table
+        conn.dyn_save(data.clone(), "t4", SaveMode::Truncate, false)
+            .await
+            .unwrap();
+        assert_eq!(count(conn.dyn_fetch("select * from t4").await.unwrap()), 2);
+    }
+}
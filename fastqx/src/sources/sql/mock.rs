@@ -0,0 +1,15 @@
+//! file: mock.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 22:26:40 Saturday
+//! brief:
+
+use super::SqlConnector;
+
+/// A fresh Sqlite file `name` under the temp dir, replacing the one left by a previous run.
+pub(crate) async fn sqlite_conn(name: &str) -> SqlConnector {
+    let path = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_file(&path);
+    let conn_str = format!("sqlite://{}?mode=rwc", path.display());
+
+    SqlConnector::new_by_str(conn_str).await.unwrap()
+}
@@ -5,14 +5,25 @@
 
 pub mod ab;
 pub mod adt;
+pub mod bulk;
 pub mod conn;
 pub mod dynm;
+#[cfg(test)]
+pub(crate) mod mock;
+pub mod options;
+pub(crate) mod param;
 pub(crate) mod rowprocess;
+pub mod schema;
 pub mod sqx;
 pub mod tbr;
+pub mod trans;
 
 pub use ab::*;
 pub use adt::*;
+pub use bulk::*;
 pub use conn::*;
+pub use options::*;
+pub use schema::*;
 pub use sqx::*;
 pub use tbr::*;
+pub use trans::*;
@@ -0,0 +1,312 @@
+//! file: options.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 10:12:31 Saturday
+//! brief:
+
+use std::future::Future;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use sqlx::pool::PoolOptions;
+use sqlx::{Database, Executor};
+
+use super::adt::Driver;
+use crate::error::{FqxError, FqxResult};
+
+// ================================================================================================
+// ConnectorOptions
+// ================================================================================================
+
+/// Pool settings of a `SqlConnector`. `None` keeps the driver's default. Durations are
+/// (de)serialized as seconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConnectorOptions {
+    pub max_connections: Option<u32>,
+    pub min_connections: Option<u32>,
+    /// the longest to wait for a connection out of the pool
+    #[serde(with = "opt_secs")]
+    pub acquire_timeout: Option<Duration>,
+    /// an idle connection is closed after this long
+    #[serde(with = "opt_secs")]
+    pub idle_timeout: Option<Duration>,
+    /// set on each new connection, Postgres and MySql only (MySql only bounds `SELECT`)
+    #[serde(with = "opt_secs")]
+    pub statement_timeout: Option<Duration>,
+    /// extra attempts on a transient connect error (io error or pool timeout)
+    pub connect_retries: u32,
+    /// the wait before the first retry, doubled on each of the following ones
+    #[serde(with = "secs")]
+    pub retry_backoff: Duration,
+}
+
+mod secs {
+    use std::time::Duration;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_f64(d.as_secs_f64())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Duration, D::Error> {
+        Duration::try_from_secs_f64(f64::deserialize(d)?).map_err(D::Error::custom)
+    }
+}
+
+mod opt_secs {
+    use std::time::Duration;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(d: &Option<Duration>, s: S) -> Result<S::Ok, S::Error> {
+        match d {
+            Some(d) => s.serialize_some(&d.as_secs_f64()),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Duration>, D::Error> {
+        Option::<f64>::deserialize(d)?
+            .map(|f| Duration::try_from_secs_f64(f).map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+impl Default for ConnectorOptions {
+    fn default() -> Self {
+        Self {
+            max_connections: None,
+            min_connections: None,
+            acquire_timeout: None,
+            idle_timeout: None,
+            statement_timeout: None,
+            connect_retries: 0,
+            retry_backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+impl ConnectorOptions {
+    /// Rejects the options a driver cannot honor, rather than dropping them silently.
+    pub(crate) fn validate(&self, driver: &Driver) -> FqxResult<()> {
+        match driver {
+            Driver::MSSQL | Driver::SQLITE if self.statement_timeout.is_some() => Err(
+                FqxError::invalid(format!("statement_timeout is not supported by {driver:?}")),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn statement_timeout_sql(&self, driver: &Driver) -> Option<String> {
+        let ms = self.statement_timeout?.as_millis();
+
+        match driver {
+            Driver::MYSQL => Some(format!("SET SESSION max_execution_time = {ms}")),
+            Driver::POSTGRES => Some(format!("SET statement_timeout = {ms}")),
+            Driver::MSSQL | Driver::SQLITE => None,
+        }
+    }
+
+    pub(crate) fn sqlx_pool_options<DB>(&self, driver: &Driver) -> PoolOptions<DB>
+    where
+        DB: Database,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        let mut po = PoolOptions::<DB>::new();
+
+        if let Some(n) = self.max_connections {
+            po = po.max_connections(n);
+        }
+        if let Some(n) = self.min_connections {
+            po = po.min_connections(n);
+        }
+        if let Some(t) = self.acquire_timeout {
+            po = po.acquire_timeout(t);
+        }
+        if let Some(t) = self.idle_timeout {
+            po = po.idle_timeout(t);
+        }
+        if let Some(sql) = self.statement_timeout_sql(driver) {
+            po = po.after_connect(move |conn, _| {
+                let sql = sql.clone();
+                Box::pin(async move {
+                    conn.execute(sql.as_str()).await?;
+                    Ok(())
+                })
+            });
+        }
+
+        po
+    }
+
+    pub(crate) fn bb8_builder<M: bb8::ManageConnection>(&self) -> bb8::Builder<M> {
+        let mut b = bb8::Pool::builder();
+
+        if let Some(n) = self.max_connections {
+            b = b.max_size(n);
+        }
+        if let Some(n) = self.min_connections {
+            b = b.min_idle(n);
+        }
+        if let Some(t) = self.acquire_timeout {
+            b = b.connection_timeout(t);
+        }
+        if let Some(t) = self.idle_timeout {
+            b = b.idle_timeout(t);
+        }
+
+        b
+    }
+
+    /// Calls `f` until it succeeds, fails with a non-transient error, or runs out of retries.
+    pub(crate) async fn retry<T, F, Fut>(&self, f: F) -> FqxResult<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = FqxResult<T>>,
+    {
+        let mut backoff = self.retry_backoff;
+        let mut attempt = 0;
+
+        loop {
+            match f().await {
+                Err(e) if attempt < self.connect_retries && is_transient(&e) => {
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+/// Network failures and pool timeouts are worth a retry, wrong credentials or a bad connect
+/// string are not.
+pub(crate) fn is_transient(e: &FqxError) -> bool {
+    match e {
+        FqxError::Sql(e) => {
+            if let Some(e) = e.downcast_ref::<sqlx::Error>() {
+                return matches!(e, sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut);
+            }
+            if let Some(e) = e.downcast_ref::<tiberius::error::Error>() {
+                return matches!(e, tiberius::error::Error::Io { .. });
+            }
+            false
+        }
+        FqxError::Io(_) => true,
+        _ => false,
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_options {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    fn options(connect_retries: u32) -> ConnectorOptions {
+        ConnectorOptions {
+            connect_retries,
+            retry_backoff: Duration::from_millis(1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn statement_timeout_sql_success() {
+        let o = ConnectorOptions {
+            statement_timeout: Some(Duration::from_secs(5)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            o.statement_timeout_sql(&Driver::POSTGRES).unwrap(),
+            "SET statement_timeout = 5000"
+        );
+        assert_eq!(
+            o.statement_timeout_sql(&Driver::MYSQL).unwrap(),
+            "SET SESSION max_execution_time = 5000"
+        );
+        assert!(o.statement_timeout_sql(&Driver::SQLITE).is_none());
+        assert!(ConnectorOptions::default()
+            .statement_timeout_sql(&Driver::POSTGRES)
+            .is_none());
+    }
+
+    #[test]
+    fn validate_fail() {
+        let o = ConnectorOptions {
+            statement_timeout: Some(Duration::from_secs(5)),
+            ..Default::default()
+        };
+
+        assert!(o.validate(&Driver::POSTGRES).is_ok());
+        assert!(o.validate(&Driver::MYSQL).is_ok());
+        assert!(matches!(
+            o.validate(&Driver::MSSQL),
+            Err(FqxError::Invalid(_))
+        ));
+        assert!(matches!(
+            o.validate(&Driver::SQLITE),
+            Err(FqxError::Invalid(_))
+        ));
+        assert!(ConnectorOptions::default()
+            .validate(&Driver::SQLITE)
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn retry_transient_success() {
+        let calls = AtomicU32::new(0);
+
+        let res = options(3)
+            .retry(|| async {
+                if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(sqlx::Error::PoolTimedOut.into())
+                } else {
+                    Ok(42)
+                }
+            })
+            .await;
+
+        assert_eq!(res.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn retry_exhausted_fail() {
+        let calls = AtomicU32::new(0);
+
+        let res: FqxResult<()> = options(2)
+            .retry(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(std::io::Error::from(std::io::ErrorKind::ConnectionRefused).into())
+            })
+            .await;
+
+        assert!(res.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn retry_non_transient_fail() {
+        let calls = AtomicU32::new(0);
+
+        let res: FqxResult<()> = options(5)
+            .retry(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(FqxError::invalid("password authentication failed"))
+            })
+            .await;
+
+        assert!(res.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
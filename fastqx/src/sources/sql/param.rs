@@ -0,0 +1,240 @@
+//! file: param.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 17:05:21 Saturday
+//! brief:
+
+use std::borrow::Cow;
+
+use sqlx::mysql::{MySql, MySqlArguments};
+use sqlx::postgres::{PgArguments, Postgres};
+use sqlx::query::Query;
+use sqlx::sqlite::{Sqlite, SqliteArguments};
+use tiberius::numeric::Numeric;
+
+use crate::adt::FqxValue;
+use crate::error::FqxResult;
+use crate::sources::sql::Driver;
+
+// ================================================================================================
+// Placeholders
+//
+// `?` is accepted as a placeholder by every driver, and is rewritten into `$n` (Postgres) or `@Pn`
+// (MsSql). Question marks inside quoted literals or identifiers are kept as they are, and so is the
+// whole statement when `params` is empty (e.g. with Postgres' jsonb `?` operator).
+// ================================================================================================
+
+pub(crate) fn rewrite_placeholders<'a>(
+    sql: &'a str,
+    driver: &Driver,
+    params: &[FqxValue],
+) -> Cow<'a, str> {
+    let prefix = match driver {
+        Driver::POSTGRES => "$",
+        Driver::MSSQL => "@P",
+        Driver::MYSQL | Driver::SQLITE => return Cow::Borrowed(sql),
+    };
+    if params.is_empty() || !sql.contains('?') {
+        return Cow::Borrowed(sql);
+    }
+
+    let mut res = String::with_capacity(sql.len() + 8);
+    let mut quote: Option<char> = None;
+    let mut idx = 0;
+
+    for c in sql.chars() {
+        match (quote, c) {
+            (None, '\'' | '"' | '`') => quote = Some(c),
+            (None, '[') if matches!(driver, Driver::MSSQL) => quote = Some(']'),
+            (Some(q), c) if q == c => quote = None,
+            (None, '?') => {
+                idx += 1;
+                res.push_str(prefix);
+                res.push_str(&idx.to_string());
+                continue;
+            }
+            _ => {}
+        }
+        res.push(c);
+    }
+
+    Cow::Owned(res)
+}
+
+// ================================================================================================
+// Sqlx binding
+// ================================================================================================
+
+pub(crate) type QueryMySql<'q> = Query<'q, MySql, MySqlArguments>;
+pub(crate) type QueryPostgres<'q> = Query<'q, Postgres, PgArguments>;
+pub(crate) type QuerySqlite<'q> = Query<'q, Sqlite, SqliteArguments<'q>>;
+
+pub(crate) fn bind_mysql<'q>(query: QueryMySql<'q>, param: &FqxValue) -> FqxResult<QueryMySql<'q>> {
+    let q = match param.clone() {
+        FqxValue::Bool(v) => query.bind(v),
+        FqxValue::U8(v) => query.bind(v),
+        FqxValue::U16(v) => query.bind(v),
+        FqxValue::U32(v) => query.bind(v),
+        FqxValue::U64(v) => query.bind(v),
+        FqxValue::I8(v) => query.bind(v),
+        FqxValue::I16(v) => query.bind(v),
+        FqxValue::I32(v) => query.bind(v),
+        FqxValue::I64(v) => query.bind(v),
+        FqxValue::F32(v) => query.bind(v),
+        FqxValue::F64(v) => query.bind(v),
+        FqxValue::Decimal(v) => query.bind(v),
+        FqxValue::String(v) => query.bind(v),
+        FqxValue::Blob(v) => query.bind(v),
+        FqxValue::Timestamp(v) => query.bind(v),
+        FqxValue::DateTime(v) => query.bind(v),
+        FqxValue::Date(v) => query.bind(v),
+        FqxValue::Time(v) => query.bind(v),
+        FqxValue::Null => query.bind(Option::<String>::None),
+    };
+
+    Ok(q)
+}
+
+pub(crate) fn bind_postgres<'q>(
+    query: QueryPostgres<'q>,
+    param: &FqxValue,
+) -> FqxResult<QueryPostgres<'q>> {
+    // Postgres has neither unsigned integers nor a single byte integer, widen them
+    let q = match param.clone() {
+        FqxValue::Bool(v) => query.bind(v),
+        FqxValue::U8(v) => query.bind(i16::from(v)),
+        FqxValue::U16(v) => query.bind(i32::from(v)),
+        FqxValue::U32(v) => query.bind(i64::from(v)),
+        FqxValue::U64(v) => query.bind(i64::try_from(v)?),
+        FqxValue::I8(v) => query.bind(i16::from(v)),
+        FqxValue::I16(v) => query.bind(v),
+        FqxValue::I32(v) => query.bind(v),
+        FqxValue::I64(v) => query.bind(v),
+        FqxValue::F32(v) => query.bind(v),
+        FqxValue::F64(v) => query.bind(v),
+        FqxValue::Decimal(v) => query.bind(v),
+        FqxValue::String(v) => query.bind(v),
+        FqxValue::Blob(v) => query.bind(v),
+        FqxValue::Timestamp(v) => query.bind(v),
+        FqxValue::DateTime(v) => query.bind(v),
+        FqxValue::Date(v) => query.bind(v),
+        FqxValue::Time(v) => query.bind(v),
+        FqxValue::Null => query.bind(Option::<String>::None),
+    };
+
+    Ok(q)
+}
+
+pub(crate) fn bind_sqlite<'q>(
+    query: QuerySqlite<'q>,
+    param: &FqxValue,
+) -> FqxResult<QuerySqlite<'q>> {
+    let q = match param.clone() {
+        FqxValue::Bool(v) => query.bind(v),
+        FqxValue::U8(v) => query.bind(v),
+        FqxValue::U16(v) => query.bind(v),
+        FqxValue::U32(v) => query.bind(v),
+        FqxValue::U64(v) => query.bind(i64::try_from(v)?),
+        FqxValue::I8(v) => query.bind(v),
+        FqxValue::I16(v) => query.bind(v),
+        FqxValue::I32(v) => query.bind(v),
+        FqxValue::I64(v) => query.bind(v),
+        FqxValue::F32(v) => query.bind(v),
+        FqxValue::F64(v) => query.bind(v),
+        // `sqlx` doesn't support decimal in Sqlite, bound as text so that no digit is lost
+        FqxValue::Decimal(v) => query.bind(v.to_string()),
+        FqxValue::String(v) => query.bind(v),
+        FqxValue::Blob(v) => query.bind(v),
+        FqxValue::Timestamp(v) => query.bind(v),
+        FqxValue::DateTime(v) => query.bind(v),
+        FqxValue::Date(v) => query.bind(v),
+        FqxValue::Time(v) => query.bind(v),
+        FqxValue::Null => query.bind(Option::<String>::None),
+    };
+
+    Ok(q)
+}
+
+// ================================================================================================
+// Tiberius binding
+// ================================================================================================
+
+pub(crate) fn bind_tiberius(query: &mut tiberius::Query<'_>, param: &FqxValue) -> FqxResult<()> {
+    // ref: https://docs.rs/tiberius/latest/tiberius/trait.IntoSql.html
+    match param.clone() {
+        FqxValue::Bool(v) => query.bind(v),
+        FqxValue::U8(v) => query.bind(v),
+        FqxValue::U16(v) => query.bind(i32::from(v)),
+        FqxValue::U32(v) => query.bind(i64::from(v)),
+        FqxValue::U64(v) => query.bind(i64::try_from(v)?),
+        FqxValue::I8(v) => query.bind(i16::from(v)),
+        FqxValue::I16(v) => query.bind(v),
+        FqxValue::I32(v) => query.bind(v),
+        FqxValue::I64(v) => query.bind(v),
+        FqxValue::F32(v) => query.bind(v),
+        FqxValue::F64(v) => query.bind(v),
+        FqxValue::Decimal(v) => query.bind(Numeric::new_with_scale(v.mantissa(), v.scale() as u8)),
+        FqxValue::String(v) => query.bind(v),
+        FqxValue::Blob(v) => query.bind(v),
+        FqxValue::Timestamp(v) => query.bind(v.naive_utc().and_utc()),
+        FqxValue::DateTime(v) => query.bind(v),
+        FqxValue::Date(v) => query.bind(v),
+        FqxValue::Time(v) => query.bind(v),
+        FqxValue::Null => query.bind(Option::<String>::None),
+    };
+
+    Ok(())
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_param {
+    use std::str::FromStr;
+
+    use rust_decimal::Decimal;
+
+    use super::*;
+    use crate::adt::FqxD;
+    use crate::sources::sql::mock::sqlite_conn;
+
+    #[test]
+    fn rewrite_placeholders_success() {
+        let sql = "select * from t where a = ? and b = '?' and c = ?";
+        let params = [FqxValue::I32(1), FqxValue::I32(2)];
+
+        assert_eq!(rewrite_placeholders(sql, &Driver::MYSQL, &params), sql);
+        assert_eq!(
+            rewrite_placeholders(sql, &Driver::POSTGRES, &params),
+            "select * from t where a = $1 and b = '?' and c = $2"
+        );
+        assert_eq!(
+            rewrite_placeholders(sql, &Driver::MSSQL, &params),
+            "select * from t where a = @P1 and b = '?' and c = @P2"
+        );
+
+        let sql = "select [a?] from t where b = ?";
+        assert_eq!(
+            rewrite_placeholders(sql, &Driver::MSSQL, &params),
+            "select [a?] from t where b = @P1"
+        );
+
+        // no parameter, no placeholder: jsonb's `?` operator is left alone
+        let sql = "select * from t where doc ? 'k' and doc ?| array['a', 'b']";
+        assert_eq!(rewrite_placeholders(sql, &Driver::POSTGRES, &[]), sql);
+        assert_eq!(rewrite_placeholders(sql, &Driver::MSSQL, &[]), sql);
+    }
+
+    #[tokio::test]
+    async fn bind_sqlite_decimal_success() {
+        let conn = sqlite_conn("fqx_param.db").await;
+        let d = Decimal::from_str("1234567890.123456789012345678").unwrap();
+
+        let data = conn
+            .dyn_fetch_with("select ? as d", &[FqxValue::Decimal(d)])
+            .await
+            .unwrap();
+        assert_eq!(data.data()[0][0], FqxValue::String(d.to_string()));
+    }
+}
@@ -4,9 +4,10 @@
 //! brief:
 
 use std::borrow::Cow;
+use std::str::FromStr;
 
-use anyhow::Result;
 use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use rust_decimal::Decimal;
 use sqlx::mysql::MySqlRow;
 use sqlx::postgres::PgRow;
 use sqlx::sqlite::SqliteRow;
@@ -96,6 +97,7 @@ impl FqxSqlRow {
                 FqxValueType::I64 => get_value!(i64, I64, r, idx),
                 FqxValueType::F32 => get_value!(f32, F32, r, idx),
                 FqxValueType::F64 => get_value!(f64, F64, r, idx),
+                FqxValueType::Decimal => get_value!(Decimal, Decimal, r, idx),
                 FqxValueType::String => get_value!(String, String, r, idx),
                 FqxValueType::Blob => get_value!(Vec<u8>, Blob, r, idx),
                 FqxValueType::Timestamp => get_value!(DateTime<Local>, Timestamp, r, idx),
@@ -112,6 +114,7 @@ impl FqxSqlRow {
                 FqxValueType::I64 => get_value!(i64, I64, r, idx),
                 FqxValueType::F32 => get_value!(f32, F32, r, idx),
                 FqxValueType::F64 => get_value!(f64, F64, r, idx),
+                FqxValueType::Decimal => get_value!(Decimal, Decimal, r, idx),
                 FqxValueType::String => get_value!(String, String, r, idx),
                 FqxValueType::Blob => get_value!(Vec<u8>, Blob, r, idx),
                 FqxValueType::Timestamp => get_value!(DateTime<Local>, Timestamp, r, idx),
@@ -131,6 +134,18 @@ impl FqxSqlRow {
                 FqxValueType::I64 => get_value!(i64, I64, r, idx),
                 FqxValueType::F32 => get_value!(f32, F32, r, idx),
                 FqxValueType::F64 => get_value!(f64, F64, r, idx),
+                FqxValueType::Decimal => {
+                    // `sqlx` doesn't support decimal in Sqlite, and a numeric affinity column might be
+                    // stored as either integer, real or text; read its text form, which Sqlite renders
+                    // from any of them, instead of going through a lossy `f64`
+                    let v: Option<String> = r.try_get_unchecked(idx)?;
+                    v.map_or(Ok(FqxValue::Null), |s| {
+                        Decimal::from_str(&s)
+                            .or_else(|_| Decimal::from_scientific(&s))
+                            .map(FqxValue::Decimal)
+                            .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+                    })
+                }
                 FqxValueType::String => get_value!(String, String, r, idx),
                 FqxValueType::Blob => get_value!(Vec<u8>, Blob, r, idx),
                 FqxValueType::DateTime => get_value!(NaiveDateTime, DateTime, r, idx),
@@ -200,6 +215,7 @@ impl FqxSqlRow {
                         "Conversion of FqxValueType::F64 error",
                     )))
                 }
+                FqxValueType::Decimal => get_value!(Decimal, Decimal, row, idx),
                 FqxValueType::String => {
                     let v: Option<&str> = row.try_get(idx)?;
                     Ok(v.map_or(FqxValue::Null, |s| FqxValue::String(s.to_owned())))
@@ -333,3 +349,44 @@ impl FqxSqlRowProcessor {
         Ok(FqxRow(res))
     }
 }
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_rowprocess {
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn sqlite_decimal_success() {
+        let pool = SqlitePoolOptions::new()
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::query("create table t (v decimal(38, 10))")
+            .execute(&pool)
+            .await
+            .unwrap();
+        // the last integer is beyond the precision of `f64`
+        sqlx::query("insert into t values (7), (0.1), ('2.50'), (12345678901234567), (null)")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let mut processor = FqxSqlRowProcessor {
+            cache: Some(vec![(String::from("v"), FqxValueType::Decimal)]),
+        };
+        let res = sqlx::query("select v from t")
+            .fetch_all(&pool)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| processor.process_sqlx_row(r).map(|r| r[0].to_string()))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(res, ["7", "0.1", "2.5", "12345678901234567", ""]);
+    }
+}
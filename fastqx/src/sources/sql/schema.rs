@@ -0,0 +1,337 @@
+//! file: schema.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 20:05:13 Saturday
+//! brief:
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::adt::*;
+use crate::constant::{MYSQL_TMAP, POSTGRES_TMAP, SQLITE_TMAP};
+use crate::error::{FqxError, FqxResult};
+use crate::sources::sql::dynm::table_name_param;
+use crate::sources::sql::*;
+
+// ================================================================================================
+// ColumnInfo
+// ================================================================================================
+
+#[pyclass(get_all)]
+#[pyo3(name = "FqxSqlColumnInfo")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnInfo {
+    pub name: String,
+    /// type name reported by the database catalog
+    pub native_type: String,
+    /// type used by `dyn_fetch` when reading the column
+    pub value_type: FqxValueType,
+    pub nullable: bool,
+    pub primary_key: bool,
+}
+
+// ================================================================================================
+// Catalog queries
+//
+// Every query returns text columns only, so that they are read the same way by all the drivers.
+// `describe_table` returns: name, native type, nullable ('YES'/'NO') and primary key ('YES'/'NO').
+// ================================================================================================
+
+fn _list_schemas_statement(driver: &Driver) -> &'static str {
+    match driver {
+        Driver::MYSQL => {
+            "SELECT CAST(schema_name AS CHAR) FROM information_schema.schemata ORDER BY 1"
+        }
+        Driver::POSTGRES => "SELECT schema_name::text FROM information_schema.schemata ORDER BY 1",
+        Driver::SQLITE => "SELECT name FROM pragma_database_list ORDER BY seq",
+        Driver::MSSQL => "SELECT name FROM sys.schemas ORDER BY name",
+    }
+}
+
+fn _list_tables_statement(driver: &Driver) -> &'static str {
+    match driver {
+        Driver::MYSQL => {
+            "SELECT CAST(table_name AS CHAR) FROM information_schema.tables \
+            WHERE table_schema = DATABASE() AND table_type = 'BASE TABLE' ORDER BY 1"
+        }
+        Driver::POSTGRES => {
+            "SELECT table_name::text FROM information_schema.tables \
+            WHERE table_schema = current_schema() AND table_type = 'BASE TABLE' ORDER BY 1"
+        }
+        Driver::SQLITE => {
+            "SELECT name FROM sqlite_master \
+            WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name"
+        }
+        Driver::MSSQL => "SELECT name FROM sys.tables WHERE schema_id = SCHEMA_ID() ORDER BY name",
+    }
+}
+
+fn _describe_table_statement(driver: &Driver) -> &'static str {
+    match driver {
+        Driver::MYSQL => {
+            "SELECT CAST(column_name AS CHAR), CAST(column_type AS CHAR), \
+            CAST(is_nullable AS CHAR), CASE WHEN column_key = 'PRI' THEN 'YES' ELSE 'NO' END \
+            FROM information_schema.columns \
+            WHERE table_schema = DATABASE() AND table_name = ? ORDER BY ordinal_position"
+        }
+        Driver::POSTGRES => {
+            "SELECT a.attname::text, t.typname::text, \
+            CASE WHEN a.attnotnull THEN 'NO' ELSE 'YES' END, \
+            CASE WHEN EXISTS ( \
+                SELECT 1 FROM pg_index i \
+                WHERE i.indrelid = a.attrelid AND i.indisprimary AND a.attnum = ANY(i.indkey) \
+            ) THEN 'YES' ELSE 'NO' END \
+            FROM pg_attribute a JOIN pg_type t ON t.oid = a.atttypid \
+            WHERE a.attrelid = to_regclass($1::text) AND a.attnum > 0 AND NOT a.attisdropped \
+            ORDER BY a.attnum"
+        }
+        Driver::SQLITE => {
+            "SELECT name, type, \
+            CASE WHEN \"notnull\" = 0 THEN 'YES' ELSE 'NO' END, \
+            CASE WHEN pk > 0 THEN 'YES' ELSE 'NO' END \
+            FROM pragma_table_info(?) ORDER BY cid"
+        }
+        Driver::MSSQL => {
+            "SELECT c.name, t.name, \
+            CASE WHEN c.is_nullable = 1 THEN 'YES' ELSE 'NO' END, \
+            CASE WHEN ic.column_id IS NULL THEN 'NO' ELSE 'YES' END \
+            FROM sys.columns c \
+            JOIN sys.types t ON t.user_type_id = c.user_type_id \
+            LEFT JOIN sys.indexes i ON i.object_id = c.object_id AND i.is_primary_key = 1 \
+            LEFT JOIN sys.index_columns ic ON ic.object_id = i.object_id \
+                AND ic.index_id = i.index_id AND ic.column_id = c.column_id \
+            WHERE c.object_id = OBJECT_ID(@P1) ORDER BY c.column_id"
+        }
+    }
+}
+
+// ================================================================================================
+// Native type mapping
+// ================================================================================================
+
+/// strip arguments, e.g. `VARCHAR(100)` -> `VARCHAR`, `INT(11) UNSIGNED` -> `INT UNSIGNED`
+fn _strip_type_args(native_type: &str) -> String {
+    let mut res = String::new();
+    let mut depth = 0;
+    for c in native_type.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if depth == 0 => res.push(c),
+            _ => {}
+        }
+    }
+
+    res.split_whitespace()
+        .filter(|s| *s != "ZEROFILL")
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Sqlite's type affinity rules, ref: https://www.sqlite.org/datatype3.html
+fn _sqlite_affinity(native_type: &str) -> FqxValueType {
+    if native_type.contains("INT") {
+        FqxValueType::I32
+    } else if ["CHAR", "CLOB", "TEXT"]
+        .iter()
+        .any(|s| native_type.contains(s))
+    {
+        FqxValueType::String
+    } else if native_type.is_empty() || native_type.contains("BLOB") {
+        FqxValueType::Blob
+    } else if ["REAL", "FLOA", "DOUB"]
+        .iter()
+        .any(|s| native_type.contains(s))
+    {
+        FqxValueType::F64
+    } else {
+        FqxValueType::Decimal
+    }
+}
+
+/// As decoded by `tiberius`: a nullable integer or float is sent as `Intn` or `Floatn`, hence read
+/// as `I64` or `F64`, and money is always read as `F64`.
+fn _mssql_type(native_type: &str, nullable: bool) -> FqxValueType {
+    match native_type {
+        "TINYINT" | "SMALLINT" | "INT" | "BIGINT" if nullable => FqxValueType::I64,
+        "REAL" if nullable => FqxValueType::F64,
+        "BIT" => FqxValueType::Bool,
+        "TINYINT" => FqxValueType::I8,
+        "SMALLINT" => FqxValueType::I16,
+        "INT" => FqxValueType::I32,
+        "BIGINT" => FqxValueType::I64,
+        "REAL" => FqxValueType::F32,
+        "FLOAT" | "MONEY" | "SMALLMONEY" => FqxValueType::F64,
+        "DECIMAL" | "NUMERIC" => FqxValueType::Decimal,
+        "BINARY" | "VARBINARY" | "IMAGE" => FqxValueType::Blob,
+        "DATETIMEOFFSET" => FqxValueType::Timestamp,
+        "DATETIME" | "DATETIME2" | "SMALLDATETIME" => FqxValueType::DateTime,
+        "DATE" => FqxValueType::Date,
+        "TIME" => FqxValueType::Time,
+        _ => FqxValueType::String,
+    }
+}
+
+pub(crate) fn native_value_type(
+    driver: &Driver,
+    native_type: &str,
+    nullable: bool,
+) -> FqxValueType {
+    let nt = native_type.trim().to_uppercase();
+
+    match driver {
+        // `TINYINT(1)` is the only one whose argument matters
+        Driver::MYSQL => MYSQL_TMAP
+            .get(nt.as_str())
+            .or_else(|| MYSQL_TMAP.get(_strip_type_args(&nt).as_str()))
+            .cloned()
+            .unwrap_or(FqxValueType::String),
+        Driver::POSTGRES => POSTGRES_TMAP
+            .get(nt.as_str())
+            .cloned()
+            .unwrap_or(FqxValueType::String),
+        Driver::SQLITE => {
+            let nt = _strip_type_args(&nt);
+            SQLITE_TMAP
+                .get(nt.as_str())
+                .cloned()
+                .unwrap_or_else(|| _sqlite_affinity(&nt))
+        }
+        Driver::MSSQL => _mssql_type(&nt, nullable),
+    }
+}
+
+// ================================================================================================
+// SqlConnector
+// ================================================================================================
+
+fn _first_column(data: FqxData) -> FqxResult<Vec<String>> {
+    data.data
+        .into_iter()
+        .map(|r| String::try_from(r.0.into_iter().next().unwrap_or(FqxValue::Null)))
+        .collect()
+}
+
+fn _yes(value: FqxValue) -> FqxResult<bool> {
+    Ok(String::try_from(value)?.eq_ignore_ascii_case("YES"))
+}
+
+impl SqlConnector {
+    /// schemas of a database server, or attached databases for Sqlite
+    pub async fn list_schemas(&self) -> FqxResult<Vec<String>> {
+        let data = self
+            .dyn_fetch(_list_schemas_statement(self.driver()))
+            .await?;
+
+        _first_column(data)
+    }
+
+    /// base tables of the current schema (database for MySql)
+    pub async fn list_tables(&self) -> FqxResult<Vec<String>> {
+        let data = self
+            .dyn_fetch(_list_tables_statement(self.driver()))
+            .await?;
+
+        _first_column(data)
+    }
+
+    pub async fn describe_table(&self, table_name: &str) -> FqxResult<Vec<ColumnInfo>> {
+        let driver = self.driver();
+        let params = [FqxValue::String(table_name_param(driver, table_name))];
+        let data = self
+            .dyn_fetch_with(_describe_table_statement(driver), &params)
+            .await?;
+        if data.data.is_empty() {
+            return Err(FqxError::invalid(format!("table {table_name} not found")));
+        }
+
+        let mut res = vec![];
+        for row in data.data.into_iter() {
+            let [name, native_type, nullable, primary_key]: [FqxValue; 4] = row
+                .0
+                .try_into()
+                .map_err(|_| FqxError::Sql("describe_table: unexpected row".into()))?;
+            let native_type = String::try_from(native_type)?;
+            let nullable = _yes(nullable)?;
+
+            res.push(ColumnInfo {
+                name: String::try_from(name)?,
+                value_type: native_value_type(driver, &native_type, nullable),
+                native_type,
+                nullable,
+                primary_key: _yes(primary_key)?,
+            });
+        }
+
+        Ok(res)
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_schema {
+    use super::*;
+    use crate::sources::sql::mock::sqlite_conn;
+
+    #[test]
+    fn native_value_type_success() {
+        let t = |d, s| native_value_type(&d, s, false);
+
+        assert_eq!(t(Driver::MYSQL, "tinyint(1)"), FqxValueType::Bool);
+        assert_eq!(t(Driver::MYSQL, "int(11) unsigned"), FqxValueType::U32);
+        assert_eq!(t(Driver::MYSQL, "varchar(100)"), FqxValueType::String);
+        assert_eq!(t(Driver::MYSQL, "decimal(38,10)"), FqxValueType::Decimal);
+        assert_eq!(t(Driver::POSTGRES, "int8"), FqxValueType::I64);
+        assert_eq!(t(Driver::POSTGRES, "timestamptz"), FqxValueType::Timestamp);
+        assert_eq!(t(Driver::SQLITE, "VARCHAR(100)"), FqxValueType::String);
+        assert_eq!(t(Driver::SQLITE, "unsigned big int"), FqxValueType::I32);
+        assert_eq!(t(Driver::SQLITE, "double"), FqxValueType::F64);
+        assert_eq!(t(Driver::MSSQL, "datetime2"), FqxValueType::DateTime);
+        assert_eq!(t(Driver::MSSQL, "nvarchar"), FqxValueType::String);
+        // as `dyn_fetch` reads them through `tiberius`
+        assert_eq!(t(Driver::MSSQL, "int"), FqxValueType::I32);
+        assert_eq!(t(Driver::MSSQL, "money"), FqxValueType::F64);
+        assert_eq!(
+            native_value_type(&Driver::MSSQL, "int", true),
+            FqxValueType::I64
+        );
+        assert_eq!(
+            native_value_type(&Driver::MSSQL, "real", true),
+            FqxValueType::F64
+        );
+    }
+
+    #[tokio::test]
+    async fn describe_table_success() {
+        let conn = sqlite_conn("fqx_schema.db").await;
+
+        conn.execute("create table t (id integer primary key, name varchar(10) not null, v real)")
+            .await
+            .unwrap();
+        conn.execute("create table s (id integer)").await.unwrap();
+
+        assert_eq!(conn.list_schemas().await.unwrap(), ["main"]);
+        assert_eq!(conn.list_tables().await.unwrap(), ["s", "t"]);
+
+        let columns = conn.describe_table("t").await.unwrap();
+        println!("{:?}", columns);
+        assert_eq!(
+            columns[0],
+            ColumnInfo {
+                name: String::from("id"),
+                native_type: String::from("INTEGER"),
+                value_type: FqxValueType::I32,
+                nullable: true,
+                primary_key: true,
+            }
+        );
+        assert_eq!(columns[1].native_type, "varchar(10)");
+        assert_eq!(columns[1].value_type, FqxValueType::String);
+        assert!(!columns[1].nullable);
+        assert_eq!(columns[2].value_type, FqxValueType::F64);
+
+        assert!(conn.describe_table("x").await.is_err());
+    }
+}
@@ -5,6 +5,8 @@
 
 pub mod spool;
 pub mod srow;
+pub mod upsert;
 
 pub use spool::*;
 pub use srow::{FromSqlxRow, TryGetFromSqlxRow};
+pub use upsert::*;
@@ -3,13 +3,20 @@
 //! date: 2023/12/18 23:46:59 Monday
 //! brief:
 
+use std::path::Path;
+use std::str::FromStr;
+
 use ref_cast::RefCast;
 use sqlx::mysql::MySql;
-use sqlx::pool::PoolConnection;
+use sqlx::pool::{PoolConnection, PoolOptions};
 use sqlx::postgres::Postgres;
-use sqlx::sqlite::Sqlite;
+use sqlx::sqlite::{Sqlite, SqliteConnectOptions};
 use sqlx::Pool;
 
+use crate::error::FqxResult;
+use crate::sources::sql::adt::Driver;
+use crate::sources::sql::options::ConnectorOptions;
+
 // ================================================================================================
 // Pool
 // ================================================================================================
@@ -63,3 +70,47 @@ impl AsRef<Pool<Sqlite>> for PoolSqlite {
         &self.0
     }
 }
+
+// ================================================================================================
+// Sqlite
+// ================================================================================================
+
+impl PoolSqlite {
+    pub async fn new_file<P: AsRef<Path>>(path: P, create_if_missing: bool) -> FqxResult<Self> {
+        let options = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(create_if_missing);
+
+        Ok(Self(Pool::connect_with(options).await?))
+    }
+
+    /// A private in-memory database, which lives as long as the pool.
+    pub async fn new_memory() -> FqxResult<Self> {
+        Self::connect_memory(SqliteConnectOptions::from_str("sqlite::memory:")?).await
+    }
+
+    /// Every connection opens its own in-memory database (unless its cache is shared, which is
+    /// prone to `SQLITE_LOCKED`), hence the pool keeps a single connection which never expires.
+    async fn connect_memory(options: SqliteConnectOptions) -> FqxResult<Self> {
+        let pool = PoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await?;
+
+        Ok(Self(pool))
+    }
+
+    /// Pool `options` are ignored by an in-memory database.
+    pub(crate) async fn connect(url: &str, options: &ConnectorOptions) -> FqxResult<Self> {
+        let co = SqliteConnectOptions::from_str(url)?;
+
+        if url.contains(":memory:") || url.contains("mode=memory") {
+            Self::connect_memory(co).await
+        } else {
+            let po = options.sqlx_pool_options::<Sqlite>(&Driver::SQLITE);
+            Ok(Self(po.connect_with(co).await?))
+        }
+    }
+}
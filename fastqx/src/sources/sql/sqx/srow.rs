@@ -3,8 +3,8 @@
 //! date: 2023/12/18 20:10:34 Monday
 //! brief:
 
-use anyhow::Result;
 use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use rust_decimal::Decimal;
 use sqlx::mysql::MySqlRow;
 use sqlx::postgres::PgRow;
 use sqlx::sqlite::SqliteRow;
@@ -187,6 +187,9 @@ impl_try_get_from_mysqlrow!(f64);
 impl_try_get_from_pgrow!(f64);
 impl_try_get_from_sqliterow!(f64);
 
+impl_try_get_from_mysqlrow!(Decimal);
+impl_try_get_from_pgrow!(Decimal);
+
 impl_try_get_from_mysqlrow!(String);
 impl_try_get_from_pgrow!(String);
 impl_try_get_from_sqliterow!(String);
@@ -210,3 +213,23 @@ impl_try_get_from_sqliterow!(NaiveDate);
 impl_try_get_from_mysqlrow!(NaiveTime);
 impl_try_get_from_pgrow!(NaiveTime);
 impl_try_get_from_sqliterow!(NaiveTime);
+
+// `sqlx` doesn't support decimal in Sqlite, numeric affinity columns are read as f64
+
+impl TryGetFromSqlxRow<SqliteRow> for Decimal {
+    fn try_get(row: &SqliteRow, col_name: &str) -> Result<Self, sqlx::Error> {
+        let val: f64 = row.try_get_unchecked(col_name)?;
+
+        Decimal::try_from(val).map_err(|e| sqlx::Error::Decode(Box::new(e)))
+    }
+}
+
+impl TryGetFromSqlxRow<SqliteRow> for Option<Decimal> {
+    fn try_get(row: &SqliteRow, col_name: &str) -> Result<Self, sqlx::Error> {
+        let val: Option<f64> = row.try_get_unchecked(col_name)?;
+
+        val.map(Decimal::try_from)
+            .transpose()
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+    }
+}
@@ -0,0 +1,82 @@
+//! file: upsert.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 19:12:40 Saturday
+//! brief:
+
+use sea_query::{Alias, OnConflict};
+
+use crate::error::{FqxError, FqxResult};
+
+// ================================================================================================
+// OnConflict
+//
+// Rendered as `ON CONFLICT (keys) DO UPDATE` by Postgres & Sqlite query builders, and as
+// `ON DUPLICATE KEY UPDATE` by MySql's (conflict target is implied by the table's unique keys).
+// ================================================================================================
+
+pub fn on_conflict<S: AsRef<str>>(columns: &[S], keys: &[String]) -> FqxResult<OnConflict> {
+    if keys.is_empty() {
+        return Err(FqxError::invalid("upsert requires at least one key column"));
+    }
+    for k in keys.iter() {
+        if !columns.iter().any(|c| c.as_ref() == k) {
+            return Err(FqxError::ColumnNotFound(k.to_string()));
+        }
+    }
+
+    let mut update_columns = columns
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|c| !keys.iter().any(|k| k == c))
+        .collect::<Vec<_>>();
+    // every column is a key, updating keys with themselves keeps the statement valid
+    if update_columns.is_empty() {
+        update_columns = keys.iter().map(String::as_str).collect();
+    }
+
+    let res = OnConflict::columns(keys.iter().map(Alias::new))
+        .update_columns(update_columns.into_iter().map(Alias::new))
+        .to_owned();
+
+    Ok(res)
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_upsert {
+    use sea_query::*;
+
+    use super::*;
+
+    fn upsert_statement() -> InsertStatement {
+        Query::insert()
+            .into_table(Alias::new("t"))
+            .columns([Alias::new("id"), Alias::new("name")])
+            .values_panic([1.into(), "a".into()])
+            .on_conflict(on_conflict(&["id", "name"], &["id".to_string()]).unwrap())
+            .to_owned()
+    }
+
+    #[test]
+    fn on_conflict_success() {
+        let s = upsert_statement();
+
+        assert_eq!(
+            s.to_string(PostgresQueryBuilder),
+            r#"INSERT INTO "t" ("id", "name") VALUES (1, 'a') ON CONFLICT ("id") DO UPDATE SET "name" = "excluded"."name""#
+        );
+        assert_eq!(
+            s.to_string(MysqlQueryBuilder),
+            "INSERT INTO `t` (`id`, `name`) VALUES (1, 'a') ON DUPLICATE KEY UPDATE `name` = VALUES(`name`)"
+        );
+    }
+
+    #[test]
+    fn on_conflict_fail() {
+        assert!(on_conflict(&["id"], &[]).is_err());
+        assert!(on_conflict(&["id"], &["name".to_string()]).is_err());
+    }
+}
@@ -3,10 +3,12 @@
 //! date: 2023/09/18 09:29:22 Monday
 //! brief:
 
-use anyhow::{bail, Result};
 use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use rust_decimal::Decimal;
 
 use crate::adt::*;
+use crate::constant::DECIMAL_COLUMN_DEF;
+use crate::error::{FqxError, FqxResult};
 
 // ================================================================================================
 // ToSqlString
@@ -42,6 +44,7 @@ impl_try_to_sql_string!(i32);
 impl_try_to_sql_string!(i64);
 impl_try_to_sql_string!(f32);
 impl_try_to_sql_string!(f64);
+impl_try_to_sql_string!(Decimal);
 
 impl ToSqlString for bool {
     fn to_sql(self) -> String {
@@ -150,7 +153,7 @@ impl ToSqlString for Option<NaiveTime> {
 // MsSql Sql Builder
 // ================================================================================================
 
-pub(crate) fn create_table(data: &FqxData, table_name: &str) -> Result<String> {
+pub(crate) fn create_table(data: &FqxData, table_name: &str) -> FqxResult<String> {
     let mut res = format!(
         "IF OBJECT_ID(N'{}', N'U') IS NULL CREATE TABLE {} ",
         table_name, table_name
@@ -164,19 +167,21 @@ pub(crate) fn create_table(data: &FqxData, table_name: &str) -> Result<String> {
             FqxValueType::U16 => cols.push(format!("{} {}", cn, "SMALLINT")),
             FqxValueType::U32 => cols.push(format!("{} {}", cn, "INT")),
             FqxValueType::U64 => cols.push(format!("{} {}", cn, "BIGINT")),
-            FqxValueType::I8 => cols.push(format!("{} {}", cn, "TINYINT")),
+            // `TINYINT` is unsigned in MsSql
+            FqxValueType::I8 => cols.push(format!("{} {}", cn, "SMALLINT")),
             FqxValueType::I16 => cols.push(format!("{} {}", cn, "SMALLINT")),
             FqxValueType::I32 => cols.push(format!("{} {}", cn, "INT")),
             FqxValueType::I64 => cols.push(format!("{} {}", cn, "BIGINT")),
             FqxValueType::F32 => cols.push(format!("{} {}", cn, "FLOAT(24)")),
             FqxValueType::F64 => cols.push(format!("{} {}", cn, "FLOAT(53)")),
+            FqxValueType::Decimal => cols.push(format!("{} {}", cn, DECIMAL_COLUMN_DEF)),
             FqxValueType::String => cols.push(format!("{} {}", cn, "VARCHAR(100)")), // TODO: size
             FqxValueType::Blob => cols.push(format!("{} {}", cn, "BINARY")),
             FqxValueType::Timestamp => cols.push(format!("{} {}", cn, "DATETIMEOFFSET(7)")),
             FqxValueType::DateTime => cols.push(format!("{} {}", cn, "DATETIME")),
             FqxValueType::Date => cols.push(format!("{} {}", cn, "DATE")),
             FqxValueType::Time => cols.push(format!("{} {}", cn, "TIME(7)")),
-            FqxValueType::Null => bail!("unsupport type: null"),
+            FqxValueType::Null => return Err(FqxError::invalid("unsupport type: null")),
         }
     }
 
@@ -192,16 +197,9 @@ pub(crate) fn drop_table(table_name: &str) -> String {
     format!("DROP TABLE IF EXISTS {};", table_name)
 }
 
-pub(crate) fn insert(data: FqxData, table_name: &str) -> String {
-    let mut res = format!("INSERT INTO {} ", table_name);
-
-    let cols = data.columns.join(",");
-    res.push('(');
-    res.push_str(&cols);
-    res.push_str(") VALUES ");
-
+fn _values(data: Vec<FqxRow>) -> Vec<String> {
     let mut vals = vec![];
-    for row in data.data.into_iter() {
+    for row in data.into_iter() {
         let mut r = vec![];
         for v in row.0.into_iter() {
             let s = match v {
@@ -216,6 +214,7 @@ pub(crate) fn insert(data: FqxData, table_name: &str) -> String {
                 FqxValue::I64(v) => ToSqlString::to_sql(v),
                 FqxValue::F32(v) => ToSqlString::to_sql(v),
                 FqxValue::F64(v) => ToSqlString::to_sql(v),
+                FqxValue::Decimal(v) => ToSqlString::to_sql(v),
                 FqxValue::String(v) => ToSqlString::to_sql(v),
                 FqxValue::Blob(v) => ToSqlString::to_sql(v),
                 FqxValue::Timestamp(v) => ToSqlString::to_sql(v),
@@ -230,8 +229,131 @@ pub(crate) fn insert(data: FqxData, table_name: &str) -> String {
         vals.push(r);
     }
 
+    vals
+}
+
+pub(crate) fn insert(data: FqxData, table_name: &str) -> String {
+    let mut res = format!("INSERT INTO {} ", table_name);
+
+    let cols = data.columns.join(",");
+    res.push('(');
+    res.push_str(&cols);
+    res.push_str(") VALUES ");
+
+    let vals = _values(data.data);
+
     res.push_str(&vals.join(", "));
     res.push(';');
 
     res
 }
+
+/// `MERGE` rows into a table, matched by `keys`. `values` are the rendered rows, e.g. `(1,'a')`.
+pub fn merge_into<S: AsRef<str>>(
+    table_name: &str,
+    columns: &[S],
+    values: &[String],
+    keys: &[String],
+) -> FqxResult<String> {
+    if keys.is_empty() {
+        return Err(FqxError::invalid("upsert requires at least one key column"));
+    }
+    for k in keys.iter() {
+        if !columns.iter().any(|c| c.as_ref() == k) {
+            return Err(FqxError::ColumnNotFound(k.to_string()));
+        }
+    }
+
+    let cols = columns.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+    let on = keys
+        .iter()
+        .map(|k| format!("tgt.{k} = src.{k}"))
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    let set = cols
+        .iter()
+        .filter(|c| !keys.iter().any(|k| k == *c))
+        .map(|c| format!("tgt.{c} = src.{c}"))
+        .collect::<Vec<_>>()
+        .join(",");
+    let src_cols = cols
+        .iter()
+        .map(|c| format!("src.{c}"))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let mut res = format!(
+        "MERGE INTO {} AS tgt USING (VALUES {}) AS src ({}) ON {}",
+        table_name,
+        values.join(", "),
+        cols.join(","),
+        on
+    );
+    // every column is a key, nothing to update
+    if !set.is_empty() {
+        res.push_str(" WHEN MATCHED THEN UPDATE SET ");
+        res.push_str(&set);
+    }
+    res.push_str(&format!(
+        " WHEN NOT MATCHED THEN INSERT ({}) VALUES ({});",
+        cols.join(","),
+        src_cols
+    ));
+
+    Ok(res)
+}
+
+pub(crate) fn upsert(data: FqxData, table_name: &str, keys: &[String]) -> FqxResult<String> {
+    let FqxData { columns, data, .. } = data;
+
+    merge_into(table_name, &columns, &_values(data), keys)
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_sqlbuild {
+    use super::*;
+    use crate::fqx;
+
+    #[test]
+    fn upsert_success() {
+        let data = FqxData::new(
+            ["id", "name"],
+            [FqxValueType::I32, FqxValueType::String],
+            vec![vec![fqx!(1), fqx!("a")], vec![fqx!(2), fqx!("b")]],
+        )
+        .unwrap();
+
+        let res = upsert(data.clone(), "t", &["id".to_string()]).unwrap();
+        assert_eq!(
+            res,
+            "MERGE INTO t AS tgt USING (VALUES (1,'a'), (2,'b')) AS src (id,name) ON tgt.id = src.id \
+            WHEN MATCHED THEN UPDATE SET tgt.name = src.name \
+            WHEN NOT MATCHED THEN INSERT (id,name) VALUES (src.id,src.name);"
+        );
+
+        let keys = ["id".to_string(), "name".to_string()];
+        let res = upsert(data.clone(), "t", &keys).unwrap();
+        assert!(!res.contains("WHEN MATCHED"));
+
+        assert!(upsert(data, "t", &["x".to_string()]).is_err());
+    }
+
+    #[test]
+    fn create_table_success() {
+        let data = FqxData::new(
+            ["u", "i"],
+            [FqxValueType::U8, FqxValueType::I8],
+            vec![vec![FqxValue::U8(255), FqxValue::I8(-128)]],
+        )
+        .unwrap();
+
+        assert_eq!(
+            create_table(&data, "t").unwrap(),
+            "IF OBJECT_ID(N't', N'U') IS NULL CREATE TABLE t (u TINYINT,i SMALLINT);"
+        );
+    }
+}
@@ -3,32 +3,34 @@
 //! date: 2023/09/17 08:32:33 Sunday
 //! brief:
 
-use anyhow::{anyhow, Result};
 use chrono::{DateTime, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use rust_decimal::Decimal;
 use tiberius::Row;
 
+use crate::error::{FqxError, FqxResult};
+
 pub trait FromTiberiusRow: Sized {
-    fn from_row(row: Row) -> Result<Self>;
+    fn from_row(row: Row) -> FqxResult<Self>;
 }
 
 pub trait TryGetFromTiberiusRow: Sized {
-    fn try_get(row: &Row, col_name: &str) -> Result<Self>;
+    fn try_get(row: &Row, col_name: &str) -> FqxResult<Self>;
 }
 
 macro_rules! impl_try_get_from_row {
     ($t:ty) => {
         impl TryGetFromTiberiusRow for $t {
-            fn try_get(row: &Row, col_name: &str) -> Result<Self> {
+            fn try_get(row: &Row, col_name: &str) -> FqxResult<Self> {
                 let val: $t = row
                     .try_get(col_name)?
-                    .ok_or(anyhow!(format!("{} is None", col_name)))?;
+                    .ok_or_else(|| FqxError::Sql(format!("{col_name} is None").into()))?;
 
                 Ok(val)
             }
         }
 
         impl TryGetFromTiberiusRow for Option<$t> {
-            fn try_get(row: &Row, col_name: &str) -> Result<Self> {
+            fn try_get(row: &Row, col_name: &str) -> FqxResult<Self> {
                 let val: Option<$t> = row.try_get(col_name)?;
 
                 Ok(val)
@@ -37,17 +39,17 @@ macro_rules! impl_try_get_from_row {
     };
     ($r:ty, $t:ty) => {
         impl TryGetFromTiberiusRow for $r {
-            fn try_get(row: &Row, col_name: &str) -> Result<Self> {
+            fn try_get(row: &Row, col_name: &str) -> FqxResult<Self> {
                 let val: $t = row
                     .try_get(col_name)?
-                    .ok_or(anyhow!(format!("{} is None", col_name)))?;
+                    .ok_or_else(|| FqxError::Sql(format!("{col_name} is None").into()))?;
 
                 Ok(val as $r)
             }
         }
 
         impl TryGetFromTiberiusRow for Option<$r> {
-            fn try_get(row: &Row, col_name: &str) -> Result<Self> {
+            fn try_get(row: &Row, col_name: &str) -> FqxResult<Self> {
                 let val: Option<$t> = row.try_get(col_name)?;
 
                 Ok(val.map(|e| e as $r))
@@ -67,19 +69,20 @@ impl_try_get_from_row!(i32);
 impl_try_get_from_row!(i64);
 impl_try_get_from_row!(f32);
 impl_try_get_from_row!(f64);
+impl_try_get_from_row!(Decimal);
 
 impl TryGetFromTiberiusRow for String {
-    fn try_get(row: &Row, col_name: &str) -> Result<Self> {
+    fn try_get(row: &Row, col_name: &str) -> FqxResult<Self> {
         let val: &str = row
             .try_get(col_name)?
-            .ok_or(anyhow!(format!("{} is None", col_name)))?;
+            .ok_or_else(|| FqxError::Sql(format!("{col_name} is None").into()))?;
 
         Ok(val.to_string())
     }
 }
 
 impl TryGetFromTiberiusRow for Option<String> {
-    fn try_get(row: &Row, col_name: &str) -> Result<Self> {
+    fn try_get(row: &Row, col_name: &str) -> FqxResult<Self> {
         let val: Option<&str> = row.try_get(col_name)?;
 
         Ok(val.map(str::to_string))
@@ -87,17 +90,17 @@ impl TryGetFromTiberiusRow for Option<String> {
 }
 
 impl TryGetFromTiberiusRow for Vec<u8> {
-    fn try_get(row: &Row, col_name: &str) -> Result<Self> {
+    fn try_get(row: &Row, col_name: &str) -> FqxResult<Self> {
         let val: &[u8] = row
             .try_get(col_name)?
-            .ok_or(anyhow!(format!("{} is None", col_name)))?;
+            .ok_or_else(|| FqxError::Sql(format!("{col_name} is None").into()))?;
 
         Ok(val.to_vec())
     }
 }
 
 impl TryGetFromTiberiusRow for Option<Vec<u8>> {
-    fn try_get(row: &Row, col_name: &str) -> Result<Self> {
+    fn try_get(row: &Row, col_name: &str) -> FqxResult<Self> {
         let val: Option<&[u8]> = row.try_get(col_name)?;
 
         Ok(val.map(Vec::from))
@@ -105,17 +108,17 @@ impl TryGetFromTiberiusRow for Option<Vec<u8>> {
 }
 
 impl TryGetFromTiberiusRow for NaiveDateTime {
-    fn try_get(row: &Row, col_name: &str) -> Result<Self> {
+    fn try_get(row: &Row, col_name: &str) -> FqxResult<Self> {
         let val = row
             .try_get(col_name)?
-            .ok_or(anyhow!(format!("{} is None", col_name)))?;
+            .ok_or_else(|| FqxError::Sql(format!("{col_name} is None").into()))?;
 
         Ok(val)
     }
 }
 
 impl TryGetFromTiberiusRow for Option<NaiveDateTime> {
-    fn try_get(row: &Row, col_name: &str) -> Result<Self> {
+    fn try_get(row: &Row, col_name: &str) -> FqxResult<Self> {
         let val = row.try_get(col_name)?;
 
         Ok(val)
@@ -123,21 +126,23 @@ impl TryGetFromTiberiusRow for Option<NaiveDateTime> {
 }
 
 impl TryGetFromTiberiusRow for DateTime<Local> {
-    fn try_get(row: &Row, col_name: &str) -> Result<Self> {
+    fn try_get(row: &Row, col_name: &str) -> FqxResult<Self> {
         let val: NaiveDateTime = row
             .try_get(col_name)?
-            .ok_or(anyhow!(format!("{} is None", col_name)))?;
+            .ok_or_else(|| FqxError::Sql(format!("{col_name} is None").into()))?;
 
         if let LocalResult::Single(dtl) = Local.from_local_datetime(&val) {
             return Ok(dtl);
         }
 
-        Err(anyhow!("convert err"))
+        Err(FqxError::Sql(
+            format!("{col_name}: ambiguous local time").into(),
+        ))
     }
 }
 
 impl TryGetFromTiberiusRow for Option<DateTime<Local>> {
-    fn try_get(row: &Row, col_name: &str) -> Result<Self> {
+    fn try_get(row: &Row, col_name: &str) -> FqxResult<Self> {
         let val: Option<NaiveDateTime> = row.try_get(col_name)?;
 
         let val = val.and_then(|e| {
@@ -153,17 +158,17 @@ impl TryGetFromTiberiusRow for Option<DateTime<Local>> {
 }
 
 impl TryGetFromTiberiusRow for NaiveDate {
-    fn try_get(row: &Row, col_name: &str) -> Result<Self> {
+    fn try_get(row: &Row, col_name: &str) -> FqxResult<Self> {
         let val = row
             .try_get(col_name)?
-            .ok_or(anyhow!(format!("{} is None", col_name)))?;
+            .ok_or_else(|| FqxError::Sql(format!("{col_name} is None").into()))?;
 
         Ok(val)
     }
 }
 
 impl TryGetFromTiberiusRow for Option<NaiveDate> {
-    fn try_get(row: &Row, col_name: &str) -> Result<Self> {
+    fn try_get(row: &Row, col_name: &str) -> FqxResult<Self> {
         let val = row.try_get(col_name)?;
 
         Ok(val)
@@ -171,17 +176,17 @@ impl TryGetFromTiberiusRow for Option<NaiveDate> {
 }
 
 impl TryGetFromTiberiusRow for NaiveTime {
-    fn try_get(row: &Row, col_name: &str) -> Result<Self> {
+    fn try_get(row: &Row, col_name: &str) -> FqxResult<Self> {
         let val = row
             .try_get(col_name)?
-            .ok_or(anyhow!(format!("{} is None", col_name)))?;
+            .ok_or_else(|| FqxError::Sql(format!("{col_name} is None").into()))?;
 
         Ok(val)
     }
 }
 
 impl TryGetFromTiberiusRow for Option<NaiveTime> {
-    fn try_get(row: &Row, col_name: &str) -> Result<Self> {
+    fn try_get(row: &Row, col_name: &str) -> FqxResult<Self> {
         let val = row.try_get(col_name)?;
 
         Ok(val)
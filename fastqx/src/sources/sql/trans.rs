@@ -0,0 +1,349 @@
+//! file: trans.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 18:02:37 Saturday
+//! brief:
+
+use sqlx::mysql::MySql;
+use sqlx::postgres::Postgres;
+use sqlx::sqlite::Sqlite;
+use sqlx::Transaction;
+
+use super::bulk::InsertStrategy;
+use super::dynm::{
+    collect_tiberius_rows, dyn_save_statements, sqlx_row_stream, table_exists_statement,
+    table_name_param,
+};
+use crate::adt::*;
+use crate::error::FqxResult;
+use crate::sources::sql::*;
+use crate::sources::SaveMode;
+
+// ================================================================================================
+// Transaction
+// ================================================================================================
+
+pub type TransactionMySql = Transaction<'static, MySql>;
+
+pub type TransactionPostgres = Transaction<'static, Postgres>;
+
+pub type TransactionSqlite = Transaction<'static, Sqlite>;
+
+/// A pooled MsSql connection on which `BEGIN TRAN` has been issued.
+///
+/// Like sqlx's `Transaction`, it is rolled back when dropped without `commit` or `rollback`; since
+/// dropping cannot wait, the rollback is spawned onto the current tokio runtime. Outside of a
+/// runtime, or if the rollback fails, the connection is discarded instead of returned to the pool.
+pub struct TransactionMsSql(Option<PoolConnectionMsSql>);
+
+impl TransactionMsSql {
+    async fn begin(pool: &PoolMsSql) -> FqxResult<Self> {
+        let mut conn = pool.0.get_owned().await?;
+
+        // a plain batch, `sp_executesql` would complain about the unbalanced transaction count
+        conn.simple_query("BEGIN TRAN")
+            .await?
+            .into_results()
+            .await?;
+
+        Ok(Self(Some(conn)))
+    }
+
+    pub(crate) fn conn(&mut self) -> &mut PoolConnectionMsSql {
+        self.0.as_mut().unwrap()
+    }
+
+    // the connection stays in the guard until `sql` has gone through, so that a cancelled or
+    // failed `COMMIT` still ends up rolled back
+    async fn finish(mut self, sql: &str) -> FqxResult<()> {
+        let res = match self.conn().simple_query(sql).await {
+            Ok(stream) => stream.into_results().await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        if res.is_err() {
+            _rollback_or_discard(self.conn()).await;
+        }
+        self.0.take();
+
+        Ok(res?)
+    }
+}
+
+const ROLLBACK: &str = "IF @@TRANCOUNT > 0 ROLLBACK TRAN";
+
+// a connection which could not be rolled back must never go back to the pool
+async fn _rollback_or_discard(conn: &mut PoolConnectionMsSql) {
+    let rolled_back = match conn.simple_query(ROLLBACK).await {
+        Ok(stream) => stream.into_results().await.is_ok(),
+        Err(_) => false,
+    };
+    if !rolled_back {
+        conn.mark_broken();
+    }
+}
+
+impl Drop for TransactionMsSql {
+    fn drop(&mut self) {
+        let Some(mut conn) = self.0.take() else {
+            return;
+        };
+
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move {
+                    _rollback_or_discard(&mut conn).await;
+                });
+            }
+            // without a runtime the rollback cannot be issued, so the connection is closed
+            Err(_) => conn.mark_broken(),
+        }
+    }
+}
+
+// ================================================================================================
+// FqxTransaction
+// ================================================================================================
+
+pub enum FqxTransaction {
+    M(TransactionMySql),
+    P(TransactionPostgres),
+    S(TransactionSqlite),
+    Q(TransactionMsSql),
+}
+
+impl FqxTransaction {
+    pub fn driver(&self) -> Driver {
+        match self {
+            FqxTransaction::M(_) => Driver::MYSQL,
+            FqxTransaction::P(_) => Driver::POSTGRES,
+            FqxTransaction::S(_) => Driver::SQLITE,
+            FqxTransaction::Q(_) => Driver::MSSQL,
+        }
+    }
+
+    pub async fn execute(&mut self, sql: &str) -> FqxResult<()> {
+        match self {
+            FqxTransaction::M(tx) => {
+                sqlx::query(sql).execute(&mut **tx).await?;
+            }
+            FqxTransaction::P(tx) => {
+                sqlx::query(sql).execute(&mut **tx).await?;
+            }
+            FqxTransaction::S(tx) => {
+                sqlx::query(sql).execute(&mut **tx).await?;
+            }
+            FqxTransaction::Q(tx) => {
+                tx.conn().execute(sql, &[]).await?;
+            }
+        };
+
+        Ok(())
+    }
+
+    pub async fn dyn_fetch(&mut self, sql: &str) -> FqxResult<FqxData> {
+        match self {
+            FqxTransaction::M(tx) => {
+                sqlx_row_stream!(&mut **tx, sqlx::query(sql))
+                    .collect_data()
+                    .await
+            }
+            FqxTransaction::P(tx) => {
+                sqlx_row_stream!(&mut **tx, sqlx::query(sql))
+                    .collect_data()
+                    .await
+            }
+            FqxTransaction::S(tx) => {
+                sqlx_row_stream!(&mut **tx, sqlx::query(sql))
+                    .collect_data()
+                    .await
+            }
+            FqxTransaction::Q(tx) => {
+                let stream = tx.conn().simple_query(sql).await?;
+
+                collect_tiberius_rows(stream).await
+            }
+        }
+    }
+
+    pub async fn table_exists(&mut self, table_name: &str) -> FqxResult<bool> {
+        let sql = table_exists_statement(&self.driver());
+        let table_name = table_name_param(&self.driver(), table_name);
+        let table_name = table_name.as_str();
+
+        let res = match self {
+            FqxTransaction::M(tx) => sqlx::query(sql)
+                .bind(table_name)
+                .fetch_optional(&mut **tx)
+                .await?
+                .is_some(),
+            FqxTransaction::P(tx) => sqlx::query(sql)
+                .bind(table_name)
+                .fetch_optional(&mut **tx)
+                .await?
+                .is_some(),
+            FqxTransaction::S(tx) => sqlx::query(sql)
+                .bind(table_name)
+                .fetch_optional(&mut **tx)
+                .await?
+                .is_some(),
+            FqxTransaction::Q(tx) => {
+                let mut query = tiberius::Query::new(sql);
+                query.bind(table_name);
+                query.query(tx.conn()).await?.into_row().await?.is_some()
+            }
+        };
+
+        Ok(res)
+    }
+
+    pub async fn dyn_save(
+        &mut self,
+        data: FqxData,
+        table_name: &str,
+        mode: SaveMode,
+        type_coercion: bool,
+    ) -> FqxResult<()> {
+        self.dyn_save_with(data, table_name, mode, type_coercion, InsertStrategy::Auto)
+            .await
+    }
+
+    pub async fn dyn_save_with(
+        &mut self,
+        mut data: FqxData,
+        table_name: &str,
+        mode: SaveMode,
+        type_coercion: bool,
+        strategy: InsertStrategy,
+    ) -> FqxResult<()> {
+        // make sure each row has the same type series
+        if type_coercion {
+            data.type_coercion()?;
+        }
+
+        let exists = mode.check_existence() && self.table_exists(table_name).await?;
+        let sttm = dyn_save_statements(&self.driver(), &data, table_name, &mode, exists)?;
+
+        if let Some(dt) = sttm.drop_table {
+            self.execute(&dt).await?;
+        }
+        if let Some(tt) = sttm.truncate_table {
+            self.execute(&tt).await?;
+        }
+        if let Some(ct) = sttm.create_table {
+            self.execute(&ct).await?;
+        }
+
+        let strategy = strategy.resolve(&self.driver(), &mode)?;
+        self.dyn_insert(data, table_name, &mode, strategy).await
+    }
+
+    pub async fn commit(self) -> FqxResult<()> {
+        match self {
+            FqxTransaction::M(tx) => tx.commit().await?,
+            FqxTransaction::P(tx) => tx.commit().await?,
+            FqxTransaction::S(tx) => tx.commit().await?,
+            FqxTransaction::Q(tx) => tx.finish("COMMIT TRAN").await?,
+        };
+
+        Ok(())
+    }
+
+    pub async fn rollback(self) -> FqxResult<()> {
+        match self {
+            FqxTransaction::M(tx) => tx.rollback().await?,
+            FqxTransaction::P(tx) => tx.rollback().await?,
+            FqxTransaction::S(tx) => tx.rollback().await?,
+            FqxTransaction::Q(tx) => tx.finish("ROLLBACK TRAN").await?,
+        };
+
+        Ok(())
+    }
+}
+
+// ================================================================================================
+// SqlConnector
+// ================================================================================================
+
+impl SqlConnector {
+    pub async fn begin(&self) -> FqxResult<FqxTransaction> {
+        let tx = match self.db() {
+            FqxPool::M(p) => FqxTransaction::M(p.as_ref().begin().await?),
+            FqxPool::P(p) => FqxTransaction::P(p.as_ref().begin().await?),
+            FqxPool::S(p) => FqxTransaction::S(p.as_ref().begin().await?),
+            FqxPool::Q(p) => FqxTransaction::Q(TransactionMsSql::begin(p).await?),
+        };
+
+        Ok(tx)
+    }
+}
+
+// ================================================================================================
+// Test
+// ================================================================================================
+
+#[cfg(test)]
+mod test_trans {
+    use super::*;
+    use crate::fqx;
+    use crate::sources::sql::mock::sqlite_conn;
+
+    #[tokio::test]
+    async fn transaction_success() {
+        let conn = sqlite_conn("fqx_transaction.db").await;
+        let data = FqxData::new(
+            ["id", "name"],
+            [FqxValueType::I32, FqxValueType::String],
+            vec![vec![fqx!(1), fqx!("a")], vec![fqx!(2), fqx!("b")]],
+        )
+        .unwrap();
+
+        conn.dyn_save(data.clone(), "t", SaveMode::Override, false)
+            .await
+            .unwrap();
+
+        // rollback
+        let mut tx = conn.begin().await.unwrap();
+        tx.execute("delete from t").await.unwrap();
+        assert_eq!(tx.dyn_fetch("select * from t").await.unwrap().height(), 0);
+        tx.rollback().await.unwrap();
+        assert_eq!(conn.dyn_fetch("select * from t").await.unwrap().height(), 2);
+
+        // commit
+        let mut tx = conn.begin().await.unwrap();
+        tx.dyn_save(data.clone(), "t", SaveMode::Append, false)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+        assert_eq!(conn.dyn_fetch("select * from t").await.unwrap().height(), 4);
+
+        // a failed override keeps the original table, duplicated column names fail the creation
+        let bad = FqxData::new(
+            ["id", "id"],
+            [FqxValueType::I32, FqxValueType::I32],
+            vec![vec![fqx!(3), fqx!(3)]],
+        )
+        .unwrap();
+        let res = conn.dyn_save(bad, "t", SaveMode::Override, false).await;
+        println!("{:?}", res);
+        assert!(res.is_err());
+        assert_eq!(conn.dyn_fetch("select * from t").await.unwrap().height(), 4);
+    }
+    #[tokio::test]
+    async fn transaction_table_exists_success() {
+        let conn = sqlite_conn("fqx_transaction_exists.db").await;
+        conn.execute("create table \"MyTable\" (id integer)")
+            .await
+            .unwrap();
+
+        let mut tx = conn.begin().await.unwrap();
+        assert!(tx.table_exists("MyTable").await.unwrap());
+        assert!(!tx.table_exists("Other").await.unwrap());
+
+        // the save path checks existence through the transaction
+        let data = FqxData::new(["id"], [FqxValueType::I32], vec![vec![fqx!(1)]]).unwrap();
+        let res = tx
+            .dyn_save(data, "MyTable", SaveMode::ErrorIfExists, false)
+            .await;
+        assert!(res.is_err());
+        tx.rollback().await.unwrap();
+    }
+}
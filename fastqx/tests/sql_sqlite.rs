@@ -0,0 +1,111 @@
+//! file: sql_sqlite.rs
+//! author: Jacob Xie
+//! date: 2026/10/17 20:48:31 Saturday
+//! brief:
+
+use fastqx::prelude::*;
+use once_cell::sync::Lazy;
+
+static DATA: Lazy<FqxData> = Lazy::new(|| {
+    FqxData::new(
+        vec![
+            String::from("c1"),
+            String::from("c2"),
+            String::from("c3"),
+            String::from("c4"),
+        ],
+        vec![
+            FqxValueType::I32,
+            FqxValueType::Bool,
+            FqxValueType::String,
+            FqxValueType::F64,
+        ],
+        vec![
+            vec![
+                FqxValue::I32(1),
+                FqxValue::Bool(true),
+                FqxValue::String(String::from("A")),
+                FqxValue::F64(20.1),
+            ],
+            vec![
+                FqxValue::I32(2),
+                FqxValue::Bool(false),
+                FqxValue::String(String::from("B")),
+                FqxValue::F64(10.3),
+            ],
+            vec![
+                FqxValue::I32(3),
+                FqxValue::Null,
+                FqxValue::Null,
+                FqxValue::Null,
+            ],
+        ],
+    )
+    .unwrap()
+});
+
+fn tmp_path(name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_file(&path);
+
+    path
+}
+
+#[tokio::test]
+async fn dyn_save_and_fetch_memory() {
+    let conn = SqlConnector::sqlite_memory().await.unwrap();
+    assert_eq!(conn.conn_str(), "sqlite::memory:");
+
+    conn.dyn_save(DATA.clone(), "tmp_table", SaveMode::Override, false)
+        .await
+        .unwrap();
+    conn.dyn_save(DATA.clone(), "tmp_table", SaveMode::Append, false)
+        .await
+        .unwrap();
+
+    let res = conn
+        .dyn_fetch("SELECT * FROM tmp_table WHERE c1 < 3 ORDER BY c1")
+        .await
+        .unwrap();
+    println!("{:?}", res);
+    assert_eq!(res.types(), DATA.types());
+    assert_eq!(res.height(), 4);
+    assert_eq!(res.data()[0], DATA.data()[0]);
+    assert_eq!(res.data()[3], DATA.data()[1]);
+
+    // the same database is seen by every clone of the connector, and within a transaction
+    let conn2 = conn.clone();
+    let mut tx = conn2.begin().await.unwrap();
+    tx.execute("DELETE FROM tmp_table").await.unwrap();
+    tx.rollback().await.unwrap();
+    let res = conn.dyn_fetch("SELECT * FROM tmp_table").await.unwrap();
+    assert_eq!(res.height(), 6);
+
+    // a new connector opens another database
+    let other = SqlConnector::new_by_str("sqlite::memory:").await.unwrap();
+    assert!(other.list_tables().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn dyn_save_and_fetch_file() {
+    let path = tmp_path("fqx_sql_sqlite.db");
+
+    assert!(SqlConnector::sqlite_file(&path, false).await.is_err());
+
+    let conn = SqlConnector::sqlite_file(&path, true).await.unwrap();
+    conn.dyn_save(DATA.clone(), "tmp_table", SaveMode::Override, false)
+        .await
+        .unwrap();
+    conn.close().await.unwrap();
+
+    let conn = SqlConnector::new(Driver::SQLITE, "", None, "", "", path.to_str().unwrap())
+        .await
+        .unwrap();
+    let res = conn.dyn_fetch("SELECT * FROM tmp_table").await.unwrap();
+    assert_eq!(res.data(), DATA.data());
+
+    let conn_str =
+        Driver::SQLITE.to_conn_str(ConnectorConfig::sqlite(path.to_str().unwrap().to_string()));
+    let conn = SqlConnector::new_by_str(conn_str).await.unwrap();
+    assert_eq!(conn.list_tables().await.unwrap(), ["tmp_table"]);
+}
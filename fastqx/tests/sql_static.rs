@@ -81,6 +81,17 @@ fn derive_success() {
 
     let insert = Users::insert(&driver, DATA.clone());
     println!("{:?}", insert);
+
+    let keys = [String::from("id")];
+    let upsert = Users::upsert(&driver, DATA.clone(), &keys).unwrap();
+    println!("{:?}", upsert);
+    assert!(upsert.contains(r#"ON CONFLICT ("id") DO UPDATE"#));
+
+    let upsert = Users::upsert(&Driver::MSSQL, DATA.clone(), &keys).unwrap();
+    println!("{:?}", upsert);
+    assert!(upsert.contains("MERGE INTO users AS tgt"));
+
+    assert!(Users::upsert(&driver, DATA.clone(), &[String::from("x")]).is_err());
 }
 
 #[tokio::test]